@@ -0,0 +1,57 @@
+//! Reference signer for [`RemoteAuthenticator`](miden_client::keystore::RemoteAuthenticator).
+//!
+//! Serves signing requests on a Unix domain socket using the keys stored in a
+//! [`FilesystemKeyStore`] directory, so that the client host never needs access to secret keys.
+//! Unix domain sockets are only available on Unix platforms, so the signer isn't supported
+//! elsewhere.
+
+#[cfg(unix)]
+use std::{fs, os::unix::net::UnixListener, path::PathBuf};
+
+#[cfg(unix)]
+use clap::Parser;
+#[cfg(unix)]
+use miden_client::keystore::{FilesystemKeyStore, remote_authenticator::serve_signer};
+#[cfg(unix)]
+use miette::{IntoDiagnostic, WrapErr};
+
+/// Signer process that answers signature requests from Miden clients over a Unix socket.
+#[cfg(unix)]
+#[derive(Parser, Debug)]
+#[clap(name = "miden-signer", version, rename_all = "kebab-case")]
+struct SignerCli {
+    /// Directory containing the secret keys, as written by the Miden client.
+    #[clap(long)]
+    keys_directory: PathBuf,
+
+    /// Path of the Unix socket to listen on. An existing socket file at this path is replaced.
+    #[clap(long)]
+    socket: PathBuf,
+}
+
+#[cfg(unix)]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+    let cli = SignerCli::parse();
+
+    let keystore = FilesystemKeyStore::new(cli.keys_directory)
+        .into_diagnostic()
+        .wrap_err("failed to open keystore")?;
+
+    if cli.socket.exists() {
+        fs::remove_file(&cli.socket)
+            .into_diagnostic()
+            .wrap_err("failed to remove existing socket file")?;
+    }
+    let listener = UnixListener::bind(&cli.socket)
+        .into_diagnostic()
+        .wrap_err("failed to bind signer socket")?;
+
+    tracing::info!("Listening for signing requests on {}", cli.socket.display());
+    serve_signer(&listener, &keystore).into_diagnostic()
+}
+
+#[cfg(not(unix))]
+fn main() -> miette::Result<()> {
+    Err(miette::miette!("miden-signer is only supported on Unix platforms"))
+}
@@ -2,16 +2,17 @@ use clap::Parser;
 use comfy_table::{Cell, ContentArrangement, presets};
 use miden_client::{
     Client, ZERO,
-    account::{Account, AccountId, AccountType, StorageSlot},
+    account::{Account, AccountId, AccountStateDiff, AccountType, StorageSlot, StorageSlotDiff},
     asset::Asset,
 };
-use miden_objects::PrettyPrint;
+use miden_objects::{Digest, PrettyPrint};
 
 use crate::{
     CLIENT_BINARY_NAME,
     config::CliConfig,
     create_dynamic_table,
     errors::CliError,
+    faucet_details_map::FaucetDetailsMap,
     utils::{load_config_file, load_faucet_details_map, parse_account_id, update_config},
 };
 
@@ -31,6 +32,10 @@ pub struct AccountCmd {
     /// When using --show, include the account code in the output.
     #[clap(long, requires = "show")]
     with_code: bool,
+    /// Show every stored state of the account for the specified ID or hex prefix, along with the
+    /// changes between consecutive states.
+    #[clap(long, group = "action", value_name = "ID")]
+    history: Option<String>,
     /// Manages default account for transaction execution.
     ///
     /// If no ID is provided it will display the current default account ID.
@@ -53,6 +58,15 @@ impl AccountCmd {
                 let account_id = parse_account_id(&client, id).await?;
                 show_account(client, account_id, &cli_config, self.with_code).await?;
             },
+            AccountCmd {
+                list: false,
+                history: Some(id),
+                default: None,
+                ..
+            } => {
+                let account_id = parse_account_id(&client, id).await?;
+                show_account_history(client, account_id).await?;
+            },
             AccountCmd {
                 list: false,
                 show: None,
@@ -212,6 +226,79 @@ pub async fn show_account(
     Ok(())
 }
 
+// SHOW ACCOUNT HISTORY
+// ================================================================================================
+
+async fn show_account_history(client: Client, account_id: AccountId) -> Result<(), CliError> {
+    let history = client.get_account_history(account_id).await?;
+    let faucet_details_map = load_faucet_details_map()?;
+
+    let mut table = create_dynamic_table(&["Nonce", "Account Commitment", "Status", "Changes"]);
+    let mut previous_state: Option<&Account> = None;
+    for record in &history {
+        let changes = match previous_state {
+            Some(previous_state) => {
+                let diff = AccountStateDiff::new(previous_state, record.account());
+                format_account_diff(&diff, &faucet_details_map)?
+            },
+            None => "Initial state".to_string(),
+        };
+
+        table.add_row(vec![
+            record.account().nonce().as_int().to_string(),
+            record.account().commitment().to_string(),
+            record.status().to_string(),
+            changes,
+        ]);
+        previous_state = Some(record.account());
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// Returns a human readable summary of the changes in the diff, one change per line.
+fn format_account_diff(
+    diff: &AccountStateDiff,
+    faucet_details_map: &FaucetDetailsMap,
+) -> Result<String, CliError> {
+    if diff.is_empty() {
+        return Ok("No vault or storage changes".to_string());
+    }
+
+    let mut changes = vec![];
+    for (sign, assets) in [("+", &diff.vault.added_assets), ("-", &diff.vault.removed_assets)] {
+        for asset in assets {
+            match asset {
+                Asset::Fungible(fungible_asset) => {
+                    let (faucet, amount) =
+                        faucet_details_map.format_fungible_asset(fungible_asset)?;
+                    changes.push(format!("{sign}{amount} {faucet}"));
+                },
+                Asset::NonFungible(non_fungible_asset) => {
+                    changes.push(format!(
+                        "{sign}1 non-fungible asset from {}",
+                        non_fungible_asset.faucet_id_prefix().to_hex()
+                    ));
+                },
+            }
+        }
+    }
+
+    for slot_diff in &diff.storage {
+        changes.push(match slot_diff {
+            StorageSlotDiff::Value { index, new_value, .. } => {
+                format!("Storage slot {index} set to {}", Digest::from(*new_value).to_hex())
+            },
+            StorageSlotDiff::Map { index, changed_entries } => {
+                format!("Storage map {index}: {} entries changed", changed_entries.len())
+            },
+        });
+    }
+
+    Ok(changes.join("\n"))
+}
+
 // HELPERS
 // ================================================================================================
 
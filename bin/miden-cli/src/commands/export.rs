@@ -3,6 +3,7 @@ use std::{fs::File, io::Write, path::PathBuf};
 use miden_client::{
     Client, ClientError, Word,
     account::{Account, AccountFile},
+    keystore::KeyStore,
     store::NoteExportType,
     transaction::AccountInterface,
     utils::Serializable,
@@ -11,9 +12,7 @@ use miden_lib::AuthScheme;
 use miden_objects::AccountError;
 use tracing::info;
 
-use crate::{
-    CliKeyStore, Parser, errors::CliError, get_output_note_with_id_prefix, utils::parse_account_id,
-};
+use crate::{Parser, errors::CliError, get_output_note_with_id_prefix, utils::parse_account_id};
 
 #[derive(Debug, Parser, Clone)]
 #[clap(about = "Export client output notes, or account data")]
@@ -57,7 +56,11 @@ impl From<&ExportType> for NoteExportType {
 }
 
 impl ExportCmd {
-    pub async fn execute(&self, mut client: Client, keystore: CliKeyStore) -> Result<(), CliError> {
+    pub async fn execute(
+        &self,
+        mut client: Client,
+        keystore: impl KeyStore,
+    ) -> Result<(), CliError> {
         if self.account {
             export_account(&client, &keystore, self.id.as_str(), self.filename.clone()).await?;
         } else if let Some(export_type) = &self.export_type {
@@ -76,7 +79,7 @@ impl ExportCmd {
 
 async fn export_account(
     client: &Client,
-    keystore: &CliKeyStore,
+    keystore: &impl KeyStore,
     account_id: &str,
     filename: Option<PathBuf>,
 ) -> Result<File, CliError> {
@@ -7,14 +7,14 @@ use std::{
 use miden_client::{
     Client, ClientError,
     account::{AccountFile, AccountId},
+    keystore::KeyStore,
     note::NoteFile,
     utils::Deserializable,
 };
 use tracing::info;
 
 use crate::{
-    CliKeyStore, Parser, commands::account::maybe_set_default_account, errors::CliError,
-    utils::load_config_file,
+    Parser, commands::account::maybe_set_default_account, errors::CliError, utils::load_config_file,
 };
 
 #[derive(Debug, Parser, Clone)]
@@ -29,7 +29,11 @@ pub struct ImportCmd {
 }
 
 impl ImportCmd {
-    pub async fn execute(&self, mut client: Client, keystore: CliKeyStore) -> Result<(), CliError> {
+    pub async fn execute(
+        &self,
+        mut client: Client,
+        keystore: impl KeyStore,
+    ) -> Result<(), CliError> {
         validate_paths(&self.filenames)?;
         let (mut current_config, _) = load_config_file()?;
         for filename in &self.filenames {
@@ -69,7 +73,7 @@ impl ImportCmd {
 
 async fn import_account(
     client: &mut Client,
-    keystore: &CliKeyStore,
+    keystore: &impl KeyStore,
     account_data_file_contents: &[u8],
     overwrite: bool,
 ) -> Result<AccountId, CliError> {
@@ -77,7 +81,10 @@ async fn import_account(
         .map_err(ClientError::DataDeserializationError)?;
     let account_id = account_data.account.id();
 
-    keystore.add_key(&account_data.auth_secret_key).map_err(CliError::KeyStore)?;
+    keystore
+        .add_key(&account_data.auth_secret_key)
+        .await
+        .map_err(CliError::KeyStore)?;
 
     client
         .add_account(&account_data.account, account_data.account_seed, overwrite)
@@ -52,6 +52,10 @@ pub struct InitCmd {
     /// Maximum number of blocks the client can be behind the network.
     #[clap(long)]
     block_delta: Option<u32>,
+
+    /// Number of blocks after which the transactions created by the client expire by default.
+    #[clap(long)]
+    expiration_delta: Option<u16>,
 }
 
 impl InitCmd {
@@ -80,6 +84,7 @@ impl InitCmd {
         };
 
         cli_config.max_block_number_delta = self.block_delta;
+        cli_config.default_expiration_delta = self.expiration_delta;
 
         let config_as_toml_string = toml::to_string_pretty(&cli_config).map_err(|err| {
             CliError::Config("failed to serialize config".to_string().into(), err.to_string())
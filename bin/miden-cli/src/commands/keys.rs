@@ -0,0 +1,312 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::{Parser, Subcommand};
+use miden_client::{
+    Client, Word,
+    auth::AuthSecretKey,
+    crypto::{Digest, SecretKey},
+    keystore::KeyStore,
+    store::TransactionFilter,
+    transaction::TransactionStatus,
+    utils::{Deserializable, Serializable},
+};
+
+use crate::{CLIENT_BINARY_NAME, create_dynamic_table, errors::CliError, utils::parse_account_id};
+
+/// Time to wait between syncs while waiting for a key rotation transaction to be committed.
+const ROTATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Default number of syncs after which the CLI stops waiting for a key rotation transaction to be
+/// committed.
+const DEFAULT_ROTATION_MAX_SYNCS: u32 = 60;
+
+// KEYS COMMAND
+// ================================================================================================
+
+#[derive(Debug, Parser, Clone)]
+#[clap(about = "View and manage the secret keys held in the keystore")]
+pub struct KeysCmd {
+    #[clap(subcommand)]
+    action: KeysAction,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+enum KeysAction {
+    /// List the public keys of the stored secret keys, along with the tracked accounts using them.
+    List,
+    /// Remove a secret key from the keystore.
+    Remove {
+        /// Public key of the secret key to remove, as a hex string.
+        pub_key: String,
+        /// Remove the key even if it's used by a tracked account.
+        #[clap(long, default_value_t = false)]
+        force: bool,
+    },
+    /// Export a secret key to a file.
+    Export {
+        /// Public key of the secret key to export, as a hex string.
+        pub_key: String,
+        /// Desired filename for the binary file. Defaults to the public key if not provided.
+        #[clap(short, long)]
+        filename: Option<PathBuf>,
+        /// Overwrite the file if it already exists.
+        #[clap(long, default_value_t = false)]
+        force: bool,
+    },
+    /// Import a secret key from a file created with `keys export`.
+    Import {
+        /// Path to the file that contains the secret key.
+        filename: PathBuf,
+    },
+    /// Replace the key of an account with a newly generated one.
+    ///
+    /// The account must have been created with a rotatable key. The old key is removed from the
+    /// keystore once the key rotation transaction is committed.
+    Rotate {
+        /// ID (or a valid prefix) of the account whose key should be rotated.
+        account_id: String,
+        /// Skip the confirmation prompt.
+        #[clap(long, default_value_t = false)]
+        force: bool,
+        /// Maximum number of syncs to wait for the key rotation transaction to be committed.
+        #[clap(long, default_value_t = DEFAULT_ROTATION_MAX_SYNCS)]
+        max_syncs: u32,
+    },
+}
+
+impl KeysCmd {
+    pub async fn execute(&self, client: Client, keystore: impl KeyStore) -> Result<(), CliError> {
+        match &self.action {
+            KeysAction::List => list_keys(&client, &keystore).await,
+            KeysAction::Remove { pub_key, force } => {
+                remove_key(&client, &keystore, parse_pub_key(pub_key)?, *force).await
+            },
+            KeysAction::Export { pub_key, filename, force } => {
+                export_key(&keystore, parse_pub_key(pub_key)?, filename.clone(), *force)
+            },
+            KeysAction::Import { filename } => import_key(&keystore, filename).await,
+            KeysAction::Rotate { account_id, force, max_syncs } => {
+                rotate_key(client, &keystore, account_id, *force, *max_syncs).await
+            },
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+async fn list_keys(client: &Client, keystore: &impl KeyStore) -> Result<(), CliError> {
+    let accounts_by_key = client.get_accounts_by_public_key().await?;
+    let pub_keys = keystore.get_pub_keys().await.map_err(CliError::KeyStore)?;
+
+    let mut table = create_dynamic_table(&["Public Key", "Accounts"]);
+    for pub_key in pub_keys {
+        let accounts = accounts_by_key
+            .get(&pub_key)
+            .map(|account_ids| {
+                account_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+            })
+            .unwrap_or_else(|| "-".to_string());
+
+        table.add_row(vec![Digest::from(pub_key).to_hex(), accounts]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+async fn remove_key(
+    client: &Client,
+    keystore: &impl KeyStore,
+    pub_key: Word,
+    force: bool,
+) -> Result<(), CliError> {
+    if !keystore.contains_key(pub_key).map_err(CliError::KeyStore)? {
+        return Err(CliError::InvalidArgument(format!(
+            "key {} is not in the keystore",
+            Digest::from(pub_key)
+        )));
+    }
+
+    if let Some(account_ids) = client.get_accounts_by_public_key().await?.get(&pub_key) {
+        if !force {
+            let account_ids =
+                account_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+            return Err(CliError::InvalidArgument(format!(
+                "key is used by tracked accounts ({account_ids}), use --force to remove it anyway"
+            )));
+        }
+    }
+
+    keystore.remove_key(pub_key).await.map_err(CliError::KeyStore)?;
+    println!("Key {} removed", Digest::from(pub_key));
+    Ok(())
+}
+
+fn export_key(
+    keystore: &impl KeyStore,
+    pub_key: Word,
+    filename: Option<PathBuf>,
+    force: bool,
+) -> Result<(), CliError> {
+    let secret_key = keystore
+        .get_key(pub_key)
+        .map_err(CliError::KeyStore)?
+        .ok_or(CliError::Export(format!("key {} not found", Digest::from(pub_key))))?;
+
+    let file_path = if let Some(filename) = filename {
+        filename
+    } else {
+        let current_dir = std::env::current_dir()?;
+        current_dir.join(format!("{}.key", Digest::from(pub_key).to_hex()))
+    };
+
+    if file_path.exists() && !force {
+        return Err(CliError::Export(format!(
+            "file {} already exists, use --force to overwrite it",
+            file_path.display()
+        )));
+    }
+
+    create_private_file(&file_path)?.write_all(&secret_key.to_bytes())?;
+
+    println!("Successfully exported key to {}", file_path.display());
+    Ok(())
+}
+
+/// Creates (or truncates) a file that only the current user can read and write.
+fn create_private_file(file_path: &Path) -> Result<File, io::Error> {
+    let mut options = File::options();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        options.mode(0o600);
+        let file = options.open(file_path)?;
+        // The mode is only applied to new files, so existing ones are restricted as well
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        Ok(file)
+    }
+
+    #[cfg(not(unix))]
+    options.open(file_path)
+}
+
+async fn import_key(keystore: &impl KeyStore, filename: &PathBuf) -> Result<(), CliError> {
+    let secret_key = AuthSecretKey::read_from_bytes(&fs::read(filename)?)
+        .map_err(|err| CliError::Import(format!("failed to read secret key: {err}")))?;
+
+    keystore.add_key(&secret_key).await.map_err(CliError::KeyStore)?;
+
+    let AuthSecretKey::RpoFalcon512(key) = &secret_key;
+    println!("Successfully imported key {}", Digest::from(Word::from(key.public_key())));
+    Ok(())
+}
+
+async fn rotate_key(
+    mut client: Client,
+    keystore: &impl KeyStore,
+    account_id: &str,
+    force: bool,
+    max_syncs: u32,
+) -> Result<(), CliError> {
+    let account_id = parse_account_id(&client, account_id).await?;
+    let account = client.try_get_account(account_id).await?;
+    let old_pub_key = miden_client::account::get_account_public_key(account.account())
+        .ok_or(CliError::InvalidArgument(format!("account {account_id} has no public key")))?;
+
+    let new_key = SecretKey::with_rng(client.rng());
+    let new_pub_key = new_key.public_key();
+    let transaction_request = client.build_key_rotation_request(account_id, new_pub_key).await?;
+
+    if !force {
+        println!(
+            "\nRotate the key of account {account_id}? The old key will be removed from the keystore once the rotation is committed (y/N)"
+        );
+        let mut proceed_str: String = String::new();
+        io::stdin().read_line(&mut proceed_str).expect("Should read line");
+
+        if proceed_str.trim().to_lowercase() != "y" {
+            println!("Key rotation was cancelled.");
+            return Ok(());
+        }
+    }
+
+    // The new key is stored before submitting the transaction so it can't be lost
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(new_key))
+        .await
+        .map_err(CliError::KeyStore)?;
+
+    println!("Executing key rotation transaction...");
+    let transaction_result = client.new_transaction(account_id, transaction_request).await?;
+    let transaction_id = transaction_result.executed_transaction().id();
+    client.submit_transaction(transaction_result).await?;
+
+    println!("Waiting for transaction {transaction_id} to be committed...");
+    let mut syncs = 0;
+    loop {
+        if syncs == max_syncs {
+            return Err(CliError::Transaction(
+                "timed out".to_string().into(),
+                format!(
+                    "key rotation transaction {transaction_id} wasn't committed after {max_syncs} syncs. Both keys are kept in the keystore; check the transaction with `{CLIENT_BINARY_NAME} tx list` and remove the unused key with `{CLIENT_BINARY_NAME} keys remove`"
+                ),
+            ));
+        }
+
+        client.sync_state().await?;
+        syncs += 1;
+
+        let transaction = client
+            .get_transactions(TransactionFilter::Ids(vec![transaction_id]))
+            .await?
+            .pop()
+            .ok_or(CliError::Transaction(
+                "transaction not found".to_string().into(),
+                format!("transaction {transaction_id} is not tracked by the client"),
+            ))?;
+
+        match transaction.status {
+            TransactionStatus::Committed(_) => break,
+            TransactionStatus::Pending => tokio::time::sleep(ROTATION_POLL_INTERVAL).await,
+            TransactionStatus::Discarded(cause) => {
+                keystore.remove_key(Word::from(new_pub_key)).await.map_err(CliError::KeyStore)?;
+                return Err(CliError::Transaction(
+                    format!("{cause:?}").into(),
+                    "key rotation transaction was discarded, the old key is still in use"
+                        .to_string(),
+                ));
+            },
+        }
+    }
+
+    // Other tracked accounts may still use the old key
+    if client.get_accounts_by_public_key().await?.contains_key(&old_pub_key) {
+        println!(
+            "Old key {} is still used by other accounts, keeping it in the keystore",
+            Digest::from(old_pub_key)
+        );
+    } else {
+        keystore.remove_key(old_pub_key).await.map_err(CliError::KeyStore)?;
+    }
+
+    println!(
+        "Key of account {account_id} rotated to {}. You can list the keys with `{CLIENT_BINARY_NAME} keys list`",
+        Digest::from(Word::from(new_pub_key))
+    );
+    Ok(())
+}
+
+fn parse_pub_key(pub_key: &str) -> Result<Word, CliError> {
+    Digest::try_from(pub_key)
+        .map(Word::from)
+        .map_err(|err| CliError::Parse(err.into(), format!("failed to parse public key {pub_key}")))
+}
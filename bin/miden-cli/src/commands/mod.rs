@@ -3,9 +3,11 @@ pub mod exec;
 pub mod export;
 pub mod import;
 pub mod init;
+pub mod keys;
 pub mod new_account;
 pub mod new_transactions;
 pub mod notes;
+pub mod store;
 pub mod sync;
 pub mod tags;
 pub mod transactions;
@@ -10,10 +10,11 @@ use miden_client::{
     Client, Word,
     account::{
         Account, AccountBuilder, AccountStorageMode, AccountType,
-        component::COMPONENT_TEMPLATE_EXTENSION,
+        component::{COMPONENT_TEMPLATE_EXTENSION, RpoFalcon512WithRotation},
     },
     auth::AuthSecretKey,
     crypto::SecretKey,
+    keystore::KeyStore,
     utils::Deserializable,
 };
 use miden_lib::account::{auth::RpoFalcon512, wallets::BasicWallet};
@@ -23,8 +24,8 @@ use miden_objects::account::{
 use rand::RngCore;
 
 use crate::{
-    CLIENT_BINARY_NAME, CliKeyStore, commands::account::maybe_set_default_account,
-    errors::CliError, utils::load_config_file,
+    CLIENT_BINARY_NAME, commands::account::maybe_set_default_account, errors::CliError,
+    utils::load_config_file,
 };
 
 // CLI TYPES
@@ -82,6 +83,9 @@ pub struct NewWalletCmd {
     /// Defines if the account code is mutable (by default it isn't mutable).
     #[clap(short, long)]
     pub mutable: bool,
+    /// Defines if the account key can be rotated with `miden keys rotate` (by default it can't).
+    #[clap(long)]
+    pub rotatable_key: bool,
     /// Optional list of files specifying additional components to add to the account.
     #[clap(short, long)]
     pub extra_components: Vec<PathBuf>,
@@ -94,7 +98,11 @@ pub struct NewWalletCmd {
 }
 
 impl NewWalletCmd {
-    pub async fn execute(&self, mut client: Client, keystore: CliKeyStore) -> Result<(), CliError> {
+    pub async fn execute(
+        &self,
+        mut client: Client,
+        keystore: impl KeyStore,
+    ) -> Result<(), CliError> {
         // Load extra component templates using the helper.
         let extra_components = load_component_templates(&self.extra_components)?;
 
@@ -109,11 +117,17 @@ impl NewWalletCmd {
             AccountType::RegularAccountImmutableCode
         };
 
+        let auth_component: AccountComponent = if self.rotatable_key {
+            RpoFalcon512WithRotation::new(key_pair.public_key()).into()
+        } else {
+            RpoFalcon512::new(key_pair.public_key()).into()
+        };
+
         let (new_account, seed) = build_account(
             &mut client,
             account_type,
             self.storage_mode.into(),
-            &[RpoFalcon512::new(key_pair.public_key()).into(), BasicWallet.into()],
+            &[auth_component, BasicWallet.into()],
             &extra_components,
             &init_storage_data,
         )
@@ -121,6 +135,7 @@ impl NewWalletCmd {
 
         keystore
             .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
+            .await
             .map_err(CliError::KeyStore)?;
 
         client.add_account(&new_account, Some(seed), false).await?;
@@ -168,7 +183,11 @@ pub struct NewAccountCmd {
 }
 
 impl NewAccountCmd {
-    pub async fn execute(&self, mut client: Client, keystore: CliKeyStore) -> Result<(), CliError> {
+    pub async fn execute(
+        &self,
+        mut client: Client,
+        keystore: impl KeyStore,
+    ) -> Result<(), CliError> {
         // Load component templates using the helper.
         let component_templates = load_component_templates(&self.component_templates)?;
 
@@ -195,6 +214,7 @@ impl NewAccountCmd {
 
         keystore
             .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
+            .await
             .map_err(CliError::KeyStore)?;
 
         client.add_account(&new_account, Some(seed), false).await?;
@@ -1,17 +1,23 @@
-use std::{io, sync::Arc};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use clap::{Parser, ValueEnum};
 use miden_client::{
-    Client, RemoteTransactionProver,
+    Client, ClientError, RemoteTransactionProver,
     account::AccountId,
     asset::{FungibleAsset, NonFungibleDeltaAction},
     crypto::Digest,
     note::{BlockNumber, NoteType as MidenNoteType, build_swap_tag, get_input_note_with_id_prefix},
     store::NoteRecordError,
     transaction::{
-        InputNote, OutputNote, PaymentTransactionData, SwapTransactionData, TransactionRequest,
-        TransactionRequestBuilder, TransactionResult,
+        InputNote, OutputNote, PaymentTransactionData, SimulatedTransaction, SwapTransactionData,
+        TransactionExecutorError, TransactionRequest, TransactionRequestBuilder,
+        TransactionSimulation,
     },
+    utils::Serializable,
 };
 use tracing::info;
 
@@ -59,6 +65,20 @@ pub struct MintCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[clap(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `miden tx prove` and submitted with `miden tx submit`.
+    #[clap(long, value_name = "FILENAME", conflicts_with = "delegate_proving")]
+    export: Option<PathBuf>,
+
+    /// Number of blocks after which the transaction expires if it wasn't committed. Overrides the
+    /// `default_expiration_delta` set in the config file.
+    #[clap(long, value_name = "BLOCKS")]
+    expiration_delta: Option<u16>,
+
+    /// Don't expire the transaction, even if the config file sets a `default_expiration_delta`.
+    #[clap(long, default_value_t = false, conflicts_with = "expiration_delta")]
+    no_expiration: bool,
 }
 
 impl MintCmd {
@@ -70,7 +90,7 @@ impl MintCmd {
 
         let target_account_id = parse_account_id(&client, self.target_account_id.as_str()).await?;
 
-        let transaction_request = TransactionRequestBuilder::new()
+        let transaction_request = request_builder(self.expiration_delta, self.no_expiration)
             .build_mint_fungible_asset(
                 fungible_asset,
                 target_account_id,
@@ -87,6 +107,7 @@ impl MintCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export.as_deref(),
         )
         .await
     }
@@ -122,6 +143,20 @@ pub struct SendCmd {
     /// Flag to delegate proving to the remote prover specified in the config file
     #[clap(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `miden tx prove` and submitted with `miden tx submit`.
+    #[clap(long, value_name = "FILENAME", conflicts_with = "delegate_proving")]
+    export: Option<PathBuf>,
+
+    /// Number of blocks after which the transaction expires if it wasn't committed. Overrides the
+    /// `default_expiration_delta` set in the config file.
+    #[clap(long, value_name = "BLOCKS")]
+    expiration_delta: Option<u16>,
+
+    /// Don't expire the transaction, even if the config file sets a `default_expiration_delta`.
+    #[clap(long, default_value_t = false, conflicts_with = "expiration_delta")]
+    no_expiration: bool,
 }
 
 impl SendCmd {
@@ -143,7 +178,7 @@ impl SendCmd {
             target_account_id,
         );
 
-        let transaction_request = TransactionRequestBuilder::new()
+        let transaction_request = request_builder(self.expiration_delta, self.no_expiration)
             .build_pay_to_id(
                 payment_transaction,
                 self.recall_height.map(BlockNumber::from),
@@ -160,6 +195,7 @@ impl SendCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export.as_deref(),
         )
         .await
     }
@@ -190,6 +226,20 @@ pub struct SwapCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[clap(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `miden tx prove` and submitted with `miden tx submit`.
+    #[clap(long, value_name = "FILENAME", conflicts_with = "delegate_proving")]
+    export: Option<PathBuf>,
+
+    /// Number of blocks after which the transaction expires if it wasn't committed. Overrides the
+    /// `default_expiration_delta` set in the config file.
+    #[clap(long, value_name = "BLOCKS")]
+    expiration_delta: Option<u16>,
+
+    /// Don't expire the transaction, even if the config file sets a `default_expiration_delta`.
+    #[clap(long, default_value_t = false, conflicts_with = "expiration_delta")]
+    no_expiration: bool,
 }
 
 impl SwapCmd {
@@ -213,7 +263,7 @@ impl SwapCmd {
             requested_fungible_asset.into(),
         );
 
-        let transaction_request = TransactionRequestBuilder::new()
+        let transaction_request = request_builder(self.expiration_delta, self.no_expiration)
             .build_swap(&swap_transaction, (&self.note_type).into(), client.rng())
             .map_err(|err| {
                 CliError::Transaction(err.into(), "Failed to build swap transaction".to_string())
@@ -225,6 +275,7 @@ impl SwapCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export.as_deref(),
         )
         .await?;
 
@@ -261,6 +312,11 @@ pub struct ConsumeNotesCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[clap(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `miden tx prove` and submitted with `miden tx submit`.
+    #[clap(long, value_name = "FILENAME", conflicts_with = "delegate_proving")]
+    export: Option<PathBuf>,
 }
 
 impl ConsumeNotesCmd {
@@ -318,12 +374,19 @@ impl ConsumeNotesCmd {
                 )
             })?;
 
+        if client.transaction_policy().expiration_delta().is_some() {
+            println!(
+                "The `default_expiration_delta` set in the config file doesn't apply to transactions that only consume notes, so this transaction won't expire."
+            );
+        }
+
         execute_transaction(
             &mut client,
             account_id,
             transaction_request,
             force,
             self.delegate_proving,
+            self.export.as_deref(),
         )
         .await
     }
@@ -332,23 +395,52 @@ impl ConsumeNotesCmd {
 // EXECUTE TRANSACTION
 // ================================================================================================
 
+/// Returns a transaction request builder with the expiration set through the CLI flags. If no
+/// flag is set, the transaction expires according to the config file.
+fn request_builder(
+    expiration_delta: Option<u16>,
+    no_expiration: bool,
+) -> TransactionRequestBuilder {
+    let builder = TransactionRequestBuilder::new();
+    match expiration_delta {
+        Some(expiration_delta) => builder.with_expiration_delta(expiration_delta),
+        None if no_expiration => builder.without_expiration(),
+        None => builder,
+    }
+}
+
 async fn execute_transaction(
     client: &mut Client,
     account_id: AccountId,
     transaction_request: TransactionRequest,
     force: bool,
     delegated_proving: bool,
+    export_path: Option<&Path>,
 ) -> Result<(), CliError> {
     println!("Executing transaction...");
-    let transaction_execution_result =
-        client.new_transaction(account_id, transaction_request).await?;
+    let transaction_execution_result = match client
+        .new_transaction(account_id, transaction_request.clone())
+        .await
+    {
+        Ok(transaction_execution_result) => transaction_execution_result,
+        Err(ClientError::TransactionExecutorError(error)) => {
+            return Err(transaction_failure(client, account_id, transaction_request, error).await);
+        },
+        Err(err) => return Err(err.into()),
+    };
 
-    // Show delta and ask for confirmation
-    print_transaction_details(&transaction_execution_result)?;
     if !force {
-        println!(
-            "\nContinue with proving and submission? Changes will be irreversible once the proof is finalized on the network (y/N)"
-        );
+        // Preview the effects of the executed transaction and ask for confirmation before the
+        // same result is exported or submitted
+        print_transaction_details(&client.preview_transaction(&transaction_execution_result))?;
+
+        if export_path.is_some() {
+            println!("\nContinue with the export of the transaction? (y/N)");
+        } else {
+            println!(
+                "\nContinue with proving and submission? Changes will be irreversible once the proof is finalized on the network (y/N)"
+            );
+        }
         let mut proceed_str: String = String::new();
         io::stdin().read_line(&mut proceed_str).expect("Should read line");
 
@@ -358,9 +450,20 @@ async fn execute_transaction(
         }
     }
 
+    let transaction_id = transaction_execution_result.executed_transaction().id();
+
+    if let Some(export_path) = export_path {
+        // The transaction is queued so the proof can be matched with it when it's submitted
+        client.enqueue_transaction(transaction_execution_result.clone()).await?;
+        fs::write(export_path, transaction_execution_result.to_bytes())?;
+
+        println!("Transaction {transaction_id} exported to {}", export_path.display());
+        println!("Prove it with `miden tx prove` and submit the proof with `miden tx submit`.");
+        return Ok(());
+    }
+
     println!("Proving transaction and then submitting it to node...");
 
-    let transaction_id = transaction_execution_result.executed_transaction().id();
     let output_notes = transaction_execution_result
         .created_notes()
         .iter()
@@ -368,17 +471,8 @@ async fn execute_transaction(
         .collect::<Vec<_>>();
 
     if delegated_proving {
-        let (cli_config, _) = load_config_file()?;
-        let remote_prover_endpoint =
-            cli_config.remote_prover_endpoint.as_ref().ok_or(CliError::Config(
-                "Remote prover endpoint".to_string().into(),
-                "remote prover endpoint is not set in the configuration file".to_string(),
-            ))?;
-
-        let remote_prover =
-            Arc::new(RemoteTransactionProver::new(remote_prover_endpoint.to_string()));
         client
-            .submit_transaction_with_prover(transaction_execution_result, remote_prover)
+            .submit_transaction_with_prover(transaction_execution_result, load_remote_prover()?)
             .await?;
     } else {
         client.submit_transaction(transaction_execution_result).await?;
@@ -399,13 +493,45 @@ async fn execute_transaction(
     Ok(())
 }
 
-fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(), CliError> {
+/// Returns the error for a transaction that failed to execute, pointing to the input note that
+/// made it fail, if any.
+async fn transaction_failure(
+    client: &mut Client,
+    account_id: AccountId,
+    transaction_request: TransactionRequest,
+    error: TransactionExecutorError,
+) -> CliError {
+    // The notes are checked one by one only to find out which one failed
+    let failed_note = match client.simulate_transaction(account_id, transaction_request).await {
+        Ok(TransactionSimulation::Failed { failed_note, .. }) => failed_note,
+        _ => None,
+    };
+
+    let message = match failed_note {
+        Some(note_id) => format!("Failed to consume note {note_id}"),
+        None => "Transaction failed".to_string(),
+    };
+    CliError::Transaction(error.into(), message)
+}
+
+/// Returns a prover for the remote prover endpoint specified in the config file.
+pub(crate) fn load_remote_prover() -> Result<Arc<RemoteTransactionProver>, CliError> {
+    let (cli_config, _) = load_config_file()?;
+    let remote_prover_endpoint =
+        cli_config.remote_prover_endpoint.as_ref().ok_or(CliError::Config(
+            "Remote prover endpoint".to_string().into(),
+            "remote prover endpoint is not set in the configuration file".to_string(),
+        ))?;
+
+    Ok(Arc::new(RemoteTransactionProver::new(remote_prover_endpoint.to_string())))
+}
+
+fn print_transaction_details(simulated_transaction: &SimulatedTransaction) -> Result<(), CliError> {
     println!("The transaction will have the following effects:\n");
 
     // INPUT NOTES
-    let input_note_ids = transaction_result
-        .executed_transaction()
-        .input_notes()
+    let input_note_ids = simulated_transaction
+        .consumed_notes()
         .iter()
         .map(InputNote::id)
         .collect::<Vec<_>>();
@@ -420,21 +546,36 @@ fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(
     println!();
 
     // OUTPUT NOTES
-    let output_note_count = transaction_result.executed_transaction().output_notes().iter().count();
-    if output_note_count == 0 {
+    let created_notes = simulated_transaction.created_notes();
+    if created_notes.is_empty() {
         println!("No notes will be created as a result of this transaction.");
     } else {
-        println!("{output_note_count} notes will be created as a result of this transaction.");
+        println!(
+            "{} notes will be created as a result of this transaction:",
+            created_notes.num_notes()
+        );
+        let mut table = create_dynamic_table(&["Note ID", "Type", "Recipient"]);
+        for note in created_notes.iter() {
+            let recipient = note
+                .recipient_digest()
+                .map_or_else(|| "-".to_string(), |recipient| recipient.to_hex());
+            table.add_row(vec![
+                note.id().to_hex(),
+                format!("{:?}", note.metadata().note_type()),
+                recipient,
+            ]);
+        }
+        println!("{table}");
     }
     println!();
 
     // ACCOUNT CHANGES
     println!(
         "The account with ID {} will be modified as follows:",
-        transaction_result.executed_transaction().account_id()
+        simulated_transaction.account_id()
     );
 
-    let account_delta = transaction_result.account_delta();
+    let account_delta = simulated_transaction.account_delta();
 
     let has_storage_changes = !account_delta.storage().is_empty();
     if has_storage_changes {
@@ -501,5 +642,20 @@ fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(
         println!("No nonce changes.");
     }
 
+    // FEE
+    match simulated_transaction.fee() {
+        Some(fee) => {
+            let (faucet_fmt, amount_fmt) =
+                load_faucet_details_map()?.format_fungible_asset(&fee)?;
+            println!("\nThe transaction pays a fee of {amount_fmt} {faucet_fmt}.");
+        },
+        None => println!("\nThe transaction doesn't pay a fee."),
+    }
+
+    println!(
+        "\nExecuting the transaction takes {} cycles.",
+        simulated_transaction.total_cycles()
+    );
+
     Ok(())
 }
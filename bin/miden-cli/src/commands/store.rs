@@ -0,0 +1,150 @@
+use std::{fs, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use miden_client::{
+    Client, ClientError,
+    store::{PruneSummary, RetentionPolicy, StoreBackup},
+    utils::{Deserializable, Serializable},
+};
+
+use crate::errors::CliError;
+
+// STORE COMMAND
+// ================================================================================================
+
+#[derive(Debug, Parser, Clone)]
+#[clap(about = "Manage the client's local store")]
+pub struct StoreCmd {
+    #[clap(subcommand)]
+    action: StoreAction,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+enum StoreAction {
+    /// Remove data that isn't needed anymore.
+    ///
+    /// Without options, only account code, storage, vaults and scripts that aren't referenced
+    /// anymore are removed.
+    Prune {
+        /// Number of states to keep for each account, counting from the latest one.
+        #[clap(long, value_name = "N")]
+        keep_account_states: Option<usize>,
+        /// Remove consumed notes that were nullified at least this many blocks ago.
+        #[clap(long, value_name = "BLOCKS")]
+        consumed_notes_age: Option<u32>,
+        /// Remove discarded transactions executed at least this many blocks ago.
+        #[clap(long, value_name = "BLOCKS")]
+        discarded_transactions_age: Option<u32>,
+        /// Keep account code, storage, vaults and scripts that aren't referenced anymore.
+        #[clap(long, default_value_t = false)]
+        keep_unreferenced: bool,
+        /// Compact the store after pruning to release the freed space. Can take a while on large
+        /// stores.
+        #[clap(long, default_value_t = false)]
+        compact: bool,
+    },
+    /// Write a backup of all the data tracked by the client to a file.
+    ///
+    /// The backup doesn't depend on the kind of store, so it can be restored on any client.
+    /// Account keys aren't part of the backup.
+    Backup {
+        /// Path of the backup file.
+        filename: PathBuf,
+    },
+    /// Replace all the data tracked by the client with the contents of a backup file.
+    Restore {
+        /// Path of the backup file.
+        filename: PathBuf,
+    },
+}
+
+impl StoreCmd {
+    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
+        match &self.action {
+            StoreAction::Prune {
+                keep_account_states,
+                consumed_notes_age,
+                discarded_transactions_age,
+                keep_unreferenced,
+                compact,
+            } => {
+                let policy = RetentionPolicy {
+                    account_states_to_keep: *keep_account_states,
+                    consumed_notes_max_age: *consumed_notes_age,
+                    discarded_transactions_max_age: *discarded_transactions_age,
+                    remove_unreferenced_data: !keep_unreferenced,
+                    compact: *compact,
+                };
+
+                let summary = client.prune_store(&policy).await?;
+                print_prune_summary(&summary);
+                Ok(())
+            },
+            StoreAction::Backup { filename } => {
+                let backup = client.backup_store().await?;
+                fs::write(filename, backup.to_bytes())?;
+
+                println!(
+                    "Backed up {} account states, {} input notes, {} output notes and {} \
+                     transactions to {}",
+                    backup.accounts.len(),
+                    backup.input_notes.len(),
+                    backup.output_notes.len(),
+                    backup.transactions.len(),
+                    filename.display()
+                );
+                Ok(())
+            },
+            StoreAction::Restore { filename } => {
+                let contents = fs::read(filename)?;
+                let backup = StoreBackup::read_from_bytes(&contents)
+                    .map_err(ClientError::DataDeserializationError)?;
+                let sync_height = backup.sync_height;
+
+                client.restore_store(backup).await?;
+                println!(
+                    "Restored store from {} (synced up to block {sync_height})",
+                    filename.display()
+                );
+                Ok(())
+            },
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+fn print_prune_summary(summary: &PruneSummary) {
+    println!("Removed account states: {}", summary.removed_account_states);
+    println!("Removed input notes: {}", summary.removed_input_notes);
+    println!("Removed output notes: {}", summary.removed_output_notes);
+    println!("Removed transactions: {}", summary.removed_transactions);
+    println!("Removed unreferenced entries: {}", summary.removed_unreferenced_entries);
+    match summary.reclaimed_bytes {
+        Some(reclaimed_bytes) => println!("Reclaimed space: {}", format_bytes(reclaimed_bytes)),
+        None => println!("Reclaimed space: unknown (use --compact to release the freed space)"),
+    }
+}
+
+/// Formats a number of bytes using the largest binary unit that keeps the value above 1.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss, reason = "the value is only displayed")]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.2} {unit}")
+}
@@ -1,23 +1,152 @@
+use std::{collections::BTreeSet, time::Duration};
+
 use clap::Parser;
-use miden_client::Client;
+use miden_client::{
+    Client, ClientError,
+    note::{BlockNumber, NoteTag},
+    sync::{RescanScope, SyncScheduler, SyncSummary},
+};
 
-use crate::errors::CliError;
+use crate::{errors::CliError, utils::parse_account_id};
 
 #[derive(Debug, Parser, Clone)]
 #[clap(about = "Sync this client with the latest state of the Miden network")]
-pub struct SyncCmd {}
+pub struct SyncCmd {
+    /// Persist the sync progress every this many blocks, so an interrupted sync resumes from the
+    /// last checkpoint instead of starting over.
+    #[clap(long, value_name = "BLOCKS", conflicts_with = "watch")]
+    checkpoint_interval: Option<u32>,
+
+    /// Keep syncing periodically in the foreground until the command is interrupted.
+    #[clap(long, default_value_t = false)]
+    watch: bool,
+
+    /// Roll back to the last block shared with the node before syncing. Used to recover when
+    /// the node's chain diverges from the client's, for example after a chain reorganization.
+    #[clap(long, default_value_t = false)]
+    rollback: bool,
+
+    /// Seconds between two syncs when watching.
+    #[clap(long, value_name = "SECONDS", default_value_t = 5, requires = "watch")]
+    interval: u64,
+
+    /// After syncing, look again for notes committed since this block. Used to find the notes of
+    /// accounts or tags added after the client synced past them.
+    #[clap(long, value_name = "BLOCK", conflicts_with = "watch")]
+    rescan_from: Option<u32>,
+
+    /// Last block to rescan. Defaults to the last synced block.
+    #[clap(long, value_name = "BLOCK", requires = "rescan_from")]
+    rescan_to: Option<u32>,
+
+    /// Only rescan the notes of this account. Can be repeated.
+    #[clap(long = "rescan-account", value_name = "ACCOUNT_ID", requires = "rescan_from")]
+    rescan_accounts: Vec<String>,
+
+    /// Only rescan the notes matching this tag. Can be repeated.
+    #[clap(
+        long = "rescan-tag",
+        value_name = "TAG",
+        requires = "rescan_from",
+        conflicts_with = "rescan_accounts"
+    )]
+    rescan_tags: Vec<u32>,
+}
 
 impl SyncCmd {
     pub async fn execute(&self, mut client: Client) -> Result<(), CliError> {
-        let new_details = client.sync_state().await?;
-
-        println!("State synced to block {}", new_details.block_num);
-        println!("New public notes: {}", new_details.new_public_notes.len());
-        println!("Committed notes: {}", new_details.committed_notes.len());
-        println!("Tracked notes consumed: {}", new_details.consumed_notes.len());
-        println!("Tracked accounts updated: {}", new_details.updated_accounts.len());
-        println!("Locked accounts: {}", new_details.locked_accounts.len());
-        println!("Commited transactions: {}", new_details.committed_transactions.len());
+        if self.rollback {
+            let block_num = client.rollback_to_common_ancestor().await?;
+            println!("Rolled back to block {block_num}");
+        }
+
+        if self.watch {
+            return self.watch(client).await;
+        }
+
+        let new_details = match self.checkpoint_interval {
+            Some(checkpoint_interval) => {
+                client.sync_state_checkpointed(checkpoint_interval).await?
+            },
+            None => client.sync_state().await?,
+        };
+
+        print_summary(&new_details);
+
+        if let Some(rescan_from) = self.rescan_from {
+            self.rescan(&mut client, rescan_from.into()).await?;
+        }
         Ok(())
     }
+
+    /// Looks for notes in the blocks that were already synced, within the selected scope.
+    async fn rescan(&self, client: &mut Client, from_block: BlockNumber) -> Result<(), CliError> {
+        let scope = self.rescan_scope(client).await?;
+        let to_block = match self.rescan_to {
+            Some(to_block) => to_block.into(),
+            None => client.get_sync_height().await?,
+        };
+        let rescan_details = client.rescan(from_block, to_block, scope).await?;
+
+        println!("Rescanned blocks {from_block} to {}", to_block.min(rescan_details.block_num));
+        println!("New public notes: {}", rescan_details.new_public_notes.len());
+        println!("Committed notes: {}", rescan_details.committed_notes.len());
+        println!("Tracked notes consumed: {}", rescan_details.consumed_notes.len());
+        Ok(())
+    }
+
+    /// Returns the scope of the rescan. Every tracked tag is rescanned unless some accounts or
+    /// tags are selected.
+    async fn rescan_scope(&self, client: &Client) -> Result<RescanScope, CliError> {
+        if !self.rescan_tags.is_empty() {
+            return Ok(RescanScope::NoteTags(
+                self.rescan_tags.iter().copied().map(NoteTag::from).collect(),
+            ));
+        }
+        if self.rescan_accounts.is_empty() {
+            return Ok(RescanScope::All);
+        }
+
+        let mut account_ids = BTreeSet::new();
+        for account_id in &self.rescan_accounts {
+            account_ids.insert(parse_account_id(client, account_id).await?);
+        }
+        Ok(RescanScope::Accounts(account_ids))
+    }
+
+    /// Syncs the client on an interval until the process is interrupted. Errors reaching the node
+    /// are reported and retried with an increasing delay.
+    async fn watch(&self, mut client: Client) -> Result<(), CliError> {
+        let mut scheduler = SyncScheduler::new().with_interval(Duration::from_secs(self.interval));
+
+        loop {
+            match scheduler.sync(&mut client).await {
+                Ok(Some(new_details)) if !new_details.is_empty() => print_summary(&new_details),
+                Ok(Some(new_details)) => {
+                    println!("State synced to block {}", new_details.block_num);
+                },
+                Ok(None) => {},
+                Err(ClientError::RpcError(err)) => {
+                    println!(
+                        "Failed to reach the node ({err}), retrying in {}s",
+                        scheduler.next_sync_delay().as_secs()
+                    );
+                },
+                Err(err) => return Err(err.into()),
+            }
+
+            tokio::time::sleep(scheduler.next_sync_delay()).await;
+        }
+    }
+}
+
+fn print_summary(new_details: &SyncSummary) {
+    println!("State synced to block {}", new_details.block_num);
+    println!("New public notes: {}", new_details.new_public_notes.len());
+    println!("Committed notes: {}", new_details.committed_notes.len());
+    println!("Tracked notes consumed: {}", new_details.consumed_notes.len());
+    println!("Tracked accounts updated: {}", new_details.updated_accounts.len());
+    println!("Locked accounts: {}", new_details.locked_accounts.len());
+    println!("Commited transactions: {}", new_details.committed_transactions.len());
+    println!("Expired transactions: {}", new_details.expired_transactions.len());
 }
@@ -1,15 +1,17 @@
 use miden_client::{
     Client,
-    note::{NoteExecutionMode, NoteTag},
+    crypto::Digest,
+    note::{BlockNumber, NoteExecutionMode, NoteTag},
+    sync::NoteDiscoveryRule,
 };
 use tracing::info;
 
-use crate::{Parser, errors::CliError};
+use crate::{Parser, errors::CliError, utils::parse_account_id};
 
 #[derive(Default, Debug, Parser, Clone)]
-#[clap(about = "View and manage tags. Defaults to `list` command")]
+#[clap(about = "View and manage tags and note discovery rules. Defaults to `list` command")]
 pub struct TagsCmd {
-    /// List all tags monitored by this client.
+    /// List all tags and note discovery rules monitored by this client.
     #[clap(short, long, group = "action")]
     list: bool,
 
@@ -20,6 +22,31 @@ pub struct TagsCmd {
     /// Removes a tag from the list of tags monitored by this client.
     #[clap(short, long, group = "action", value_name = "tag")]
     remove: Option<u32>,
+
+    /// Discover public notes created by the account, even if they don't match any tag.
+    #[clap(long, group = "action", value_name = "account_id")]
+    add_sender: Option<String>,
+
+    /// Stop discovering notes created by the account.
+    #[clap(long, group = "action", value_name = "account_id")]
+    remove_sender: Option<String>,
+
+    /// Discover public notes with the script root, even if they don't match any tag.
+    #[clap(long, group = "action", value_name = "script_root")]
+    add_script_root: Option<String>,
+
+    /// Stop discovering notes with the script root.
+    #[clap(long, group = "action", value_name = "script_root")]
+    remove_script_root: Option<String>,
+
+    /// Look for notes matching the discovery rules in the blocks since this one, which were synced
+    /// before the rules were added.
+    #[clap(long, group = "action", value_name = "block_num")]
+    scan_from: Option<u32>,
+
+    /// Last block to scan. Defaults to the last synced block.
+    #[clap(long, requires = "scan_from", value_name = "block_num")]
+    scan_to: Option<u32>,
 }
 
 impl TagsCmd {
@@ -31,6 +58,25 @@ impl TagsCmd {
             TagsCmd { remove: Some(tag), .. } => {
                 remove_tag(client, *tag).await?;
             },
+            TagsCmd { add_sender: Some(account_id), .. } => {
+                let rule = NoteDiscoveryRule::Sender(parse_account_id(&client, account_id).await?);
+                add_discovery_rule(client, rule).await?;
+            },
+            TagsCmd { remove_sender: Some(account_id), .. } => {
+                let rule = NoteDiscoveryRule::Sender(parse_account_id(&client, account_id).await?);
+                remove_discovery_rule(client, rule).await?;
+            },
+            TagsCmd { add_script_root: Some(script_root), .. } => {
+                add_discovery_rule(client, parse_script_root_rule(script_root)?).await?;
+            },
+            TagsCmd {
+                remove_script_root: Some(script_root), ..
+            } => {
+                remove_discovery_rule(client, parse_script_root_rule(script_root)?).await?;
+            },
+            TagsCmd { scan_from: Some(from), scan_to, .. } => {
+                scan_blocks(client, *from, *scan_to).await?;
+            },
             _ => {
                 list_tags(client).await?;
             },
@@ -44,6 +90,14 @@ impl TagsCmd {
 async fn list_tags(client: Client) -> Result<(), CliError> {
     let tags = client.get_note_tags().await?;
     println!("Tags: {tags:?}");
+
+    let rules = client.get_note_discovery_rules().await?;
+    if !rules.is_empty() {
+        println!("Discovery rules:");
+        for rule in rules {
+            println!("  - {rule}");
+        }
+    }
     Ok(())
 }
 
@@ -68,3 +122,49 @@ async fn remove_tag(mut client: Client, tag: u32) -> Result<(), CliError> {
     println!("Tag {tag} removed");
     Ok(())
 }
+
+async fn add_discovery_rule(mut client: Client, rule: NoteDiscoveryRule) -> Result<(), CliError> {
+    client.add_note_discovery_rule(rule).await?;
+    println!("Discovery rule for {rule} added");
+    println!(
+        "Notes matching the rule are discovered from the next sync on. Use `--scan-from` to look \
+         for them in blocks that were already synced."
+    );
+    Ok(())
+}
+
+async fn remove_discovery_rule(
+    mut client: Client,
+    rule: NoteDiscoveryRule,
+) -> Result<(), CliError> {
+    client.remove_note_discovery_rule(rule).await?;
+    println!("Discovery rule for {rule} removed");
+    Ok(())
+}
+
+async fn scan_blocks(mut client: Client, from: u32, to: Option<u32>) -> Result<(), CliError> {
+    let rules = client.get_note_discovery_rules().await?;
+    if rules.is_empty() {
+        return Err(CliError::Input(
+            "There are no discovery rules to scan the blocks with".to_string(),
+        ));
+    }
+
+    let to = match to {
+        Some(to) => BlockNumber::from(to),
+        None => client.get_sync_height().await?,
+    };
+    let imported_notes = client.scan_blocks_for_notes(&rules, BlockNumber::from(from)..=to).await?;
+
+    println!("Scanned blocks {from} to {to}, {} new notes imported", imported_notes.len());
+    for note_id in imported_notes {
+        println!("  - {note_id}");
+    }
+    Ok(())
+}
+
+fn parse_script_root_rule(script_root: &str) -> Result<NoteDiscoveryRule, CliError> {
+    Digest::try_from(script_root).map(NoteDiscoveryRule::ScriptRoot).map_err(|err| {
+        CliError::Parse(err.into(), format!("failed to parse script root {script_root}"))
+    })
+}
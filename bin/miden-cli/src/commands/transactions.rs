@@ -1,5 +1,20 @@
-use miden_client::{Client, store::TransactionFilter, transaction::TransactionRecord};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
+use clap::Subcommand;
+use miden_client::{
+    Client,
+    crypto::Digest,
+    store::TransactionFilter,
+    transaction::{
+        ProvenTransaction, QueuedTransaction, TransactionId, TransactionRecord, TransactionResult,
+    },
+    utils::{Deserializable, Serializable},
+};
+
+use super::new_transactions::load_remote_prover;
 use crate::{Parser, create_dynamic_table, errors::CliError};
 
 #[derive(Default, Debug, Parser, Clone)]
@@ -8,11 +23,96 @@ pub struct TransactionCmd {
     /// List currently tracked transactions.
     #[clap(short, long, group = "action")]
     list: bool,
+
+    #[clap(subcommand)]
+    action: Option<TransactionAction>,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+enum TransactionAction {
+    /// View and manage the transactions that are being proven and submitted.
+    ///
+    /// Transactions stay in the queue until they are committed or discarded, so the ones that
+    /// failed to be proven or submitted can be retried. Defaults to listing the queue.
+    Queue {
+        /// Retry the queued transaction, even if it isn't due according to the retry policy.
+        #[clap(long, group = "queue_action", value_name = "transaction_id")]
+        retry: Option<String>,
+
+        /// Retry all the queued transactions that are due according to the retry policy.
+        #[clap(long, group = "queue_action", default_value_t = false)]
+        retry_all: bool,
+
+        /// Remove the transaction from the queue. Transactions waiting to be committed can't be
+        /// cancelled.
+        #[clap(long, group = "queue_action", value_name = "transaction_id")]
+        cancel: Option<String>,
+    },
+    /// Prove a transaction exported with the `--export` flag of the transaction commands.
+    ///
+    /// Proving doesn't need the keys of the account, so it can be done on another machine.
+    Prove {
+        /// Path of the exported transaction.
+        executed_transaction: PathBuf,
+        /// Path of the file to write the proven transaction to.
+        proven_transaction: PathBuf,
+        /// Flag to delegate proving to the remote prover specified in the config file.
+        #[clap(long, default_value_t = false)]
+        delegate_proving: bool,
+    },
+    /// Submit a proven transaction to the network.
+    ///
+    /// If the transaction was exported by this client, it's also saved into the local store.
+    Submit {
+        /// Path of the proven transaction.
+        proven_transaction: PathBuf,
+    },
+    /// Save a transaction exported by this client into the local store, after it was proven and
+    /// submitted elsewhere.
+    Apply {
+        /// Path of the proven transaction.
+        proven_transaction: PathBuf,
+    },
 }
 
 impl TransactionCmd {
     pub async fn execute(&self, client: Client) -> Result<(), CliError> {
-        list_transactions(client).await?;
+        match &self.action {
+            Some(TransactionAction::Queue { retry: Some(transaction_id), .. }) => {
+                retry_queued_transaction(client, parse_transaction_id(transaction_id)?).await?;
+            },
+            Some(TransactionAction::Queue { retry_all: true, .. }) => {
+                retry_queued_transactions(client).await?;
+            },
+            Some(TransactionAction::Queue { cancel: Some(transaction_id), .. }) => {
+                cancel_queued_transaction(client, parse_transaction_id(transaction_id)?).await?;
+            },
+            Some(TransactionAction::Queue { .. }) => {
+                list_queued_transactions(client).await?;
+            },
+            Some(TransactionAction::Prove {
+                executed_transaction,
+                proven_transaction,
+                delegate_proving,
+            }) => {
+                prove_transaction(
+                    client,
+                    executed_transaction,
+                    proven_transaction,
+                    *delegate_proving,
+                )
+                .await?;
+            },
+            Some(TransactionAction::Submit { proven_transaction }) => {
+                submit_proven_transaction(client, proven_transaction).await?;
+            },
+            Some(TransactionAction::Apply { proven_transaction }) => {
+                apply_proven_transaction(client, proven_transaction).await?;
+            },
+            None => {
+                list_transactions(client).await?;
+            },
+        }
         Ok(())
     }
 }
@@ -25,6 +125,103 @@ async fn list_transactions(client: Client) -> Result<(), CliError> {
     Ok(())
 }
 
+// TRANSACTION QUEUE
+// ================================================================================================
+async fn list_queued_transactions(client: Client) -> Result<(), CliError> {
+    let queued_transactions = client.get_queued_transactions().await?;
+    print_queued_transactions_summary(&queued_transactions);
+    Ok(())
+}
+
+async fn retry_queued_transaction(
+    mut client: Client,
+    transaction_id: TransactionId,
+) -> Result<(), CliError> {
+    client.retry_queued_transaction(transaction_id).await?;
+    println!("Transaction {transaction_id} submitted");
+    Ok(())
+}
+
+async fn retry_queued_transactions(mut client: Client) -> Result<(), CliError> {
+    let submitted = client.retry_queued_transactions().await?;
+    println!("Submitted {} transactions", submitted.len());
+    for transaction_id in submitted {
+        println!("  - {transaction_id}");
+    }
+
+    let remaining = client
+        .get_queued_transactions()
+        .await?
+        .into_iter()
+        .filter(|queued_transaction| !queued_transaction.state().is_submitted())
+        .count();
+    if remaining > 0 {
+        println!("{remaining} transactions are still waiting to be submitted");
+    }
+    Ok(())
+}
+
+async fn cancel_queued_transaction(
+    mut client: Client,
+    transaction_id: TransactionId,
+) -> Result<(), CliError> {
+    client.cancel_queued_transaction(transaction_id).await?;
+    println!("Transaction {transaction_id} removed from the queue");
+    Ok(())
+}
+
+// EXTERNAL PROVING
+// ================================================================================================
+async fn prove_transaction(
+    client: Client,
+    executed_transaction: &Path,
+    proven_transaction: &Path,
+    delegate_proving: bool,
+) -> Result<(), CliError> {
+    let tx_result =
+        TransactionResult::read_from_bytes(&fs::read(executed_transaction)?).map_err(|err| {
+            CliError::Import(format!(
+                "failed to read transaction from {}: {err}",
+                executed_transaction.display()
+            ))
+        })?;
+
+    println!("Proving transaction {}...", tx_result.executed_transaction().id());
+    let proven = if delegate_proving {
+        client.prove_transaction_with_prover(&tx_result, load_remote_prover()?).await?
+    } else {
+        client.prove_transaction(&tx_result).await?
+    };
+    fs::write(proven_transaction, proven.to_bytes())?;
+
+    println!("Proven transaction written to {}", proven_transaction.display());
+    Ok(())
+}
+
+async fn submit_proven_transaction(
+    mut client: Client,
+    proven_transaction: &Path,
+) -> Result<(), CliError> {
+    let proven_transaction = read_proven_transaction(proven_transaction)?;
+    let transaction_id = proven_transaction.id();
+
+    client.submit_proven_transaction(proven_transaction).await?;
+    println!("Transaction {transaction_id} submitted");
+    Ok(())
+}
+
+async fn apply_proven_transaction(
+    mut client: Client,
+    proven_transaction: &Path,
+) -> Result<(), CliError> {
+    let proven_transaction = read_proven_transaction(proven_transaction)?;
+    let transaction_id = proven_transaction.id();
+
+    client.apply_proven_transaction(proven_transaction).await?;
+    println!("Transaction {transaction_id} applied to the local store");
+    Ok(())
+}
+
 // HELPERS
 // ================================================================================================
 fn print_transactions_summary<'a, I>(executed_transactions: I)
@@ -53,3 +250,37 @@ where
 
     println!("{table}");
 }
+
+fn print_queued_transactions_summary<'a, I>(queued_transactions: I)
+where
+    I: IntoIterator<Item = &'a QueuedTransaction>,
+{
+    let mut table = create_dynamic_table(&["ID", "Account ID", "State", "Attempts", "Last Error"]);
+
+    for queued_transaction in queued_transactions {
+        table.add_row(vec![
+            queued_transaction.id().to_string(),
+            queued_transaction.account_id().to_string(),
+            queued_transaction.state().to_string(),
+            queued_transaction.attempts().to_string(),
+            queued_transaction.last_error().unwrap_or("-").to_string(),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+fn parse_transaction_id(transaction_id: &str) -> Result<TransactionId, CliError> {
+    Digest::try_from(transaction_id).map(TransactionId::from).map_err(|err| {
+        CliError::Parse(err.into(), format!("failed to parse transaction ID {transaction_id}"))
+    })
+}
+
+fn read_proven_transaction(filename: &Path) -> Result<ProvenTransaction, CliError> {
+    ProvenTransaction::read_from_bytes(&fs::read(filename)?).map_err(|err| {
+        CliError::Import(format!(
+            "failed to read proven transaction from {}: {err}",
+            filename.display()
+        ))
+    })
+}
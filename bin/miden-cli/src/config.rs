@@ -28,6 +28,9 @@ pub struct CliConfig {
     pub store_filepath: PathBuf,
     /// Path to the directory that contains the secret key files.
     pub secret_keys_directory: PathBuf,
+    /// Keystore used to store the secret key files. Defaults to plaintext key files.
+    #[serde(default)]
+    pub keystore_type: KeyStoreType,
     /// Address of the Miden node to connect to.
     pub default_account_id: Option<String>,
     /// Path to the file containing the token symbol map.
@@ -39,6 +42,9 @@ pub struct CliConfig {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     pub max_block_number_delta: Option<u32>,
+    /// Number of blocks after which the transactions created by the CLI expire, unless a
+    /// transaction command overrides it. If this isn't present, transactions don't expire.
+    pub default_expiration_delta: Option<u16>,
 }
 
 // Make `ClientConfig` a provider itself for composability.
@@ -69,15 +75,32 @@ impl Default for CliConfig {
             rpc: RpcConfig::default(),
             store_filepath: exec_dir.join(STORE_FILENAME),
             secret_keys_directory: exec_dir.join(KEYSTORE_DIRECTORY),
+            keystore_type: KeyStoreType::default(),
             default_account_id: None,
             token_symbol_map_filepath: Path::new(TOKEN_SYMBOL_MAP_FILEPATH).to_path_buf(),
             remote_prover_endpoint: None,
             component_template_directory: Path::new(DEFAULT_COMPONENT_TEMPLATE_DIR).to_path_buf(),
             max_block_number_delta: None,
+            default_expiration_delta: None,
         }
     }
 }
 
+// KEYSTORE TYPE
+// ================================================================================================
+
+/// Keystore used by the CLI to store secret keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStoreType {
+    /// Keys are stored in plaintext files.
+    #[default]
+    Filesystem,
+    /// Keys are stored in files encrypted with the passphrase set in the
+    /// `MIDEN_KEYSTORE_PASSPHRASE` environment variable.
+    Encrypted,
+}
+
 // RPC CONFIG
 // ================================================================================================
 
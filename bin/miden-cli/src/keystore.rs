@@ -0,0 +1,111 @@
+use std::{env, path::PathBuf};
+
+use miden_client::{
+    AuthenticationError, Felt, Word,
+    account::AccountDelta,
+    auth::{AuthSecretKey, TransactionAuthenticator},
+    keystore::{EncryptedFilesystemKeyStore, FilesystemKeyStore, KeyStore, KeyStoreError},
+};
+use rand::rngs::StdRng;
+
+use crate::{
+    config::{CliConfig, KeyStoreType},
+    errors::CliError,
+};
+
+/// Environment variable holding the passphrase of the encrypted keystore.
+const KEYSTORE_PASSPHRASE_ENV_VAR: &str = "MIDEN_KEYSTORE_PASSPHRASE";
+
+// CLI KEYSTORE
+// ================================================================================================
+
+/// Keystore used by the CLI, selected with the `keystore_type` field of the config file.
+#[derive(Debug, Clone)]
+pub enum CliKeyStore {
+    /// Keys are stored in plaintext files.
+    Filesystem(FilesystemKeyStore<StdRng>),
+    /// Keys are stored in encrypted files. The keystore is unlocked when it's created.
+    Encrypted(EncryptedFilesystemKeyStore<StdRng>),
+}
+
+impl CliKeyStore {
+    /// Creates a plaintext keystore in the given directory.
+    pub fn new(keys_directory: PathBuf) -> Result<Self, KeyStoreError> {
+        FilesystemKeyStore::new(keys_directory).map(Self::Filesystem)
+    }
+
+    /// Creates the keystore set in the config file. The encrypted keystore is unlocked with the
+    /// passphrase set in the `MIDEN_KEYSTORE_PASSPHRASE` environment variable.
+    pub(crate) fn from_config(cli_config: &CliConfig) -> Result<Self, CliError> {
+        let keys_directory = cli_config.secret_keys_directory.clone();
+
+        match cli_config.keystore_type {
+            KeyStoreType::Filesystem => Self::new(keys_directory).map_err(CliError::KeyStore),
+            KeyStoreType::Encrypted => {
+                let passphrase = env::var(KEYSTORE_PASSPHRASE_ENV_VAR).map_err(|_| {
+                    CliError::Config(
+                        "Keystore passphrase".to_string().into(),
+                        format!(
+                            "the encrypted keystore requires the {KEYSTORE_PASSPHRASE_ENV_VAR} environment variable to be set"
+                        ),
+                    )
+                })?;
+
+                let keystore =
+                    EncryptedFilesystemKeyStore::new(keys_directory).map_err(CliError::KeyStore)?;
+                keystore.unlock(&passphrase).map_err(CliError::KeyStore)?;
+
+                Ok(Self::Encrypted(keystore))
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyStore for CliKeyStore {
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        match self {
+            CliKeyStore::Filesystem(keystore) => keystore.add_key(key).await,
+            CliKeyStore::Encrypted(keystore) => keystore.add_key(key).await,
+        }
+    }
+
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        match self {
+            CliKeyStore::Filesystem(keystore) => keystore.get_key(pub_key),
+            CliKeyStore::Encrypted(keystore) => keystore.get_key(pub_key),
+        }
+    }
+
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        match self {
+            CliKeyStore::Filesystem(keystore) => keystore.remove_key(pub_key).await,
+            CliKeyStore::Encrypted(keystore) => keystore.remove_key(pub_key).await,
+        }
+    }
+
+    async fn get_pub_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        match self {
+            CliKeyStore::Filesystem(keystore) => keystore.get_pub_keys().await,
+            CliKeyStore::Encrypted(keystore) => keystore.get_pub_keys().await,
+        }
+    }
+}
+
+impl TransactionAuthenticator for CliKeyStore {
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        match self {
+            CliKeyStore::Filesystem(keystore) => {
+                keystore.get_signature(pub_key, message, account_delta)
+            },
+            CliKeyStore::Encrypted(keystore) => {
+                keystore.get_signature(pub_key, message, account_delta)
+            },
+        }
+    }
+}
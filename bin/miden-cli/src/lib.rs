@@ -7,11 +7,11 @@ use miden_client::{
     Client, ClientError, Felt, IdPrefixFetchError,
     account::AccountHeader,
     crypto::RpoRandomCoin,
-    keystore::FilesystemKeyStore,
     rpc::TonicRpcClient,
     store::{NoteFilter as ClientNoteFilter, OutputNoteRecord, Store, sqlite_store::SqliteStore},
+    transaction::TransactionPolicy,
 };
-use rand::{Rng, rngs::StdRng};
+use rand::Rng;
 mod commands;
 use commands::{
     account::AccountCmd,
@@ -19,22 +19,24 @@ use commands::{
     export::ExportCmd,
     import::ImportCmd,
     init::InitCmd,
+    keys::KeysCmd,
     new_account::{NewAccountCmd, NewWalletCmd},
     new_transactions::{ConsumeNotesCmd, MintCmd, SendCmd, SwapCmd},
     notes::NotesCmd,
+    store::StoreCmd,
     sync::SyncCmd,
     tags::TagsCmd,
     transactions::TransactionCmd,
 };
 
+pub use self::keystore::CliKeyStore;
 use self::utils::load_config_file;
 
-pub type CliKeyStore = FilesystemKeyStore<StdRng>;
-
 mod config;
 mod errors;
 mod faucet_details_map;
 mod info;
+mod keystore;
 mod utils;
 
 /// Config file name.
@@ -69,7 +71,9 @@ pub enum Command {
     Import(ImportCmd),
     Export(ExportCmd),
     Init(InitCmd),
+    Keys(KeysCmd),
     Notes(NotesCmd),
+    Store(StoreCmd),
     Sync(SyncCmd),
     /// View a summary of the current client state.
     Info,
@@ -115,10 +119,9 @@ impl Cli {
         let coin_seed: [u64; 4] = rng.random();
 
         let rng = RpoRandomCoin::new(coin_seed.map(Felt::new));
-        let keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())
-            .map_err(CliError::KeyStore)?;
+        let keystore = CliKeyStore::from_config(&cli_config)?;
 
-        let client = Client::new(
+        let mut client = Client::new(
             Arc::new(TonicRpcClient::new(
                 &cli_config.rpc.endpoint.clone().into(),
                 cli_config.rpc.timeout_ms,
@@ -131,6 +134,12 @@ impl Cli {
             cli_config.max_block_number_delta,
         );
 
+        if let Some(expiration_delta) = cli_config.default_expiration_delta {
+            client.set_transaction_policy(
+                TransactionPolicy::new().with_expiration_delta(expiration_delta),
+            );
+        }
+
         // Execute CLI command
         match &self.action {
             Command::Account(account) => account.execute(client).await,
@@ -138,8 +147,10 @@ impl Cli {
             Command::NewAccount(new_account) => new_account.execute(client, keystore).await,
             Command::Import(import) => import.execute(client, keystore).await,
             Command::Init(_) => Ok(()),
+            Command::Keys(keys) => keys.execute(client, keystore).await,
             Command::Info => info::print_client_info(&client).await,
             Command::Notes(notes) => notes.execute(client).await,
+            Command::Store(store) => store.execute(client).await,
             Command::Sync(sync) => sync.execute(client).await,
             Command::Tags(tags) => tags.execute(client).await,
             Command::Transaction(transaction) => transaction.execute(client).await,
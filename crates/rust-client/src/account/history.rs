@@ -0,0 +1,412 @@
+//! Provides client APIs to inspect the past states of tracked accounts, along with the
+//! [`AccountStateDiff`] type used to compare two states of the same account.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest, EMPTY_WORD, Felt, Word,
+    account::{Account, AccountId, StorageSlot},
+    asset::{Asset, FungibleAsset},
+    block::BlockNumber,
+};
+
+use crate::{
+    Client, ClientError,
+    store::{AccountRecord, TransactionFilter},
+    transaction::TransactionStatus,
+};
+
+// ACCOUNT STATE DIFF
+// ================================================================================================
+
+/// Describes the changes between two states of the same account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountStateDiff {
+    /// ID of the account.
+    pub account_id: AccountId,
+    /// Nonce of the initial state.
+    pub from_nonce: Felt,
+    /// Nonce of the final state.
+    pub to_nonce: Felt,
+    /// Changes to the account's vault.
+    pub vault: AccountVaultDiff,
+    /// Storage slots whose contents changed, ordered by index.
+    pub storage: Vec<StorageSlotDiff>,
+}
+
+impl AccountStateDiff {
+    /// Returns the changes needed to go from the `from` state to the `to` state. Both states are
+    /// expected to belong to the same account.
+    pub fn new(from: &Account, to: &Account) -> Self {
+        Self {
+            account_id: to.id(),
+            from_nonce: from.nonce(),
+            to_nonce: to.nonce(),
+            vault: AccountVaultDiff::new(from, to),
+            storage: storage_diff(from, to),
+        }
+    }
+
+    /// Returns `true` if neither the vault nor the storage changed between both states.
+    pub fn is_empty(&self) -> bool {
+        self.vault.is_empty() && self.storage.is_empty()
+    }
+}
+
+/// Describes the changes to the assets held by an account.
+///
+/// Fungible assets are reported as the net amount added or removed for each faucet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountVaultDiff {
+    /// Assets present in the final state and not in the initial one.
+    pub added_assets: Vec<Asset>,
+    /// Assets present in the initial state and not in the final one.
+    pub removed_assets: Vec<Asset>,
+}
+
+impl AccountVaultDiff {
+    fn new(from: &Account, to: &Account) -> Self {
+        let mut diff = Self::default();
+
+        let from_balances = fungible_balances(from);
+        let to_balances = fungible_balances(to);
+        let faucet_ids = from_balances.keys().chain(to_balances.keys()).copied();
+        for faucet_id in faucet_ids.collect::<BTreeSet<_>>() {
+            let from_amount = from_balances.get(&faucet_id).copied().unwrap_or_default();
+            let to_amount = to_balances.get(&faucet_id).copied().unwrap_or_default();
+
+            if to_amount > from_amount {
+                diff.added_assets.push(fungible_asset(faucet_id, to_amount - from_amount));
+            } else if from_amount > to_amount {
+                diff.removed_assets.push(fungible_asset(faucet_id, from_amount - to_amount));
+            }
+        }
+
+        let from_non_fungible = non_fungible_assets(from);
+        let to_non_fungible = non_fungible_assets(to);
+        diff.added_assets.extend(
+            to_non_fungible
+                .iter()
+                .filter(|asset| !from_non_fungible.contains(asset))
+                .copied(),
+        );
+        diff.removed_assets.extend(
+            from_non_fungible
+                .iter()
+                .filter(|asset| !to_non_fungible.contains(asset))
+                .copied(),
+        );
+
+        diff
+    }
+
+    /// Returns `true` if no assets were added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added_assets.is_empty() && self.removed_assets.is_empty()
+    }
+}
+
+/// Describes the change of a single storage slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageSlotDiff {
+    /// A value slot changed from `old_value` to `new_value`.
+    Value {
+        index: u8,
+        old_value: Word,
+        new_value: Word,
+    },
+    /// Entries of a map slot changed. Each changed key maps to its old and new values, where
+    /// missing entries are represented by [`EMPTY_WORD`].
+    Map {
+        index: u8,
+        changed_entries: BTreeMap<Digest, (Word, Word)>,
+    },
+}
+
+impl StorageSlotDiff {
+    /// Returns the index of the changed slot.
+    pub fn index(&self) -> u8 {
+        match self {
+            StorageSlotDiff::Value { index, .. } | StorageSlotDiff::Map { index, .. } => *index,
+        }
+    }
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+impl Client {
+    /// Returns every state of the account stored by the client, ordered by ascending nonce.
+    ///
+    /// States are only stored for the nonces the client has seen, so the history may have gaps
+    /// (for example, for public accounts updated by other clients).
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not tracked by the client.
+    pub async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, ClientError> {
+        let history = self.store.get_account_history(account_id).await?;
+        if history.is_empty() {
+            return Err(ClientError::AccountDataNotFound(account_id));
+        }
+
+        Ok(history)
+    }
+
+    /// Returns the stored state of the account with the provided nonce, or `None` if the client
+    /// doesn't have that state.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not tracked by the client.
+    pub async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: u64,
+    ) -> Result<Option<AccountRecord>, ClientError> {
+        Ok(self
+            .get_account_history(account_id)
+            .await?
+            .into_iter()
+            .find(|record| record.account().nonce().as_int() == nonce))
+    }
+
+    /// Returns the state the account had at the provided block, or `None` if the account wasn't
+    /// known to be in any stored state at that block.
+    ///
+    /// The block at which a state was reached is taken from the commit height of the tracked
+    /// transaction that produced it. Accounts created by the client don't exist on chain until
+    /// their first transaction is committed, so their initial state is never returned. The initial
+    /// state of imported accounts is returned for any block from the one their ID is anchored to,
+    /// as the block at which it was reached isn't known. Other states that weren't produced by a
+    /// committed transaction of this client (such as updates to public accounts received through
+    /// sync) are skipped for the same reason.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not tracked by the client.
+    pub async fn get_account_at_block(
+        &self,
+        account_id: AccountId,
+        block_num: BlockNumber,
+    ) -> Result<Option<AccountRecord>, ClientError> {
+        let mut history = self.get_account_history(account_id).await?.into_iter();
+
+        // No account exists before the block its ID is anchored to
+        if block_num < BlockNumber::from_epoch(account_id.anchor_epoch()) {
+            return Ok(None);
+        }
+
+        let commit_heights: BTreeMap<Digest, BlockNumber> = self
+            .store
+            .get_transactions(TransactionFilter::All)
+            .await?
+            .into_iter()
+            .filter(|tx| tx.details.account_id == account_id)
+            .filter_map(|tx| match tx.status {
+                TransactionStatus::Committed(commit_height) => {
+                    Some((tx.details.final_account_state, commit_height))
+                },
+                _ => None,
+            })
+            .collect();
+
+        let mut state_at_block = history.next().filter(|initial_state| {
+            match commit_heights.get(&initial_state.account().commitment()) {
+                Some(commit_height) => *commit_height <= block_num,
+                None => !initial_state.account().is_new(),
+            }
+        });
+
+        for record in history {
+            if commit_heights
+                .get(&record.account().commitment())
+                .is_some_and(|commit_height| *commit_height <= block_num)
+            {
+                state_at_block = Some(record);
+            }
+        }
+
+        Ok(state_at_block)
+    }
+
+    /// Returns the changes to the account between the states with the provided nonces.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not tracked by the client.
+    /// - If the client doesn't have the state of the account at any of the nonces.
+    pub async fn get_account_state_diff(
+        &self,
+        account_id: AccountId,
+        from_nonce: u64,
+        to_nonce: u64,
+    ) -> Result<AccountStateDiff, ClientError> {
+        let history = self.get_account_history(account_id).await?;
+        let find_state = |nonce: u64| {
+            history
+                .iter()
+                .find(|record| record.account().nonce().as_int() == nonce)
+                .map(AccountRecord::account)
+                .ok_or(ClientError::AccountStateNotFound { account_id, nonce })
+        };
+
+        Ok(AccountStateDiff::new(find_state(from_nonce)?, find_state(to_nonce)?))
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the fungible balances of the account, by faucet.
+fn fungible_balances(account: &Account) -> BTreeMap<AccountId, u64> {
+    account
+        .vault()
+        .assets()
+        .filter_map(|asset| match asset {
+            Asset::Fungible(asset) => Some((asset.faucet_id(), asset.amount())),
+            Asset::NonFungible(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the non-fungible assets held by the account.
+fn non_fungible_assets(account: &Account) -> Vec<Asset> {
+    account.vault().assets().filter(|asset| !asset.is_fungible()).collect()
+}
+
+fn fungible_asset(faucet_id: AccountId, amount: u64) -> Asset {
+    FungibleAsset::new(faucet_id, amount)
+        .expect("difference between valid amounts should be a valid amount")
+        .into()
+}
+
+/// Returns the storage slots that changed between both states.
+fn storage_diff(from: &Account, to: &Account) -> Vec<StorageSlotDiff> {
+    let from_slots = from.storage().slots();
+    let to_slots = to.storage().slots();
+
+    (0..from_slots.len().max(to_slots.len()))
+        .filter_map(|index| {
+            let index = u8::try_from(index).expect("there are no more than 256 slots");
+            slot_diff(index, from_slots.get(usize::from(index)), to_slots.get(usize::from(index)))
+        })
+        .collect()
+}
+
+/// Returns the change of a single slot, or `None` if it didn't change. Missing slots are treated
+/// as empty slots of the same type as the existing one.
+fn slot_diff(
+    index: u8,
+    from: Option<&StorageSlot>,
+    to: Option<&StorageSlot>,
+) -> Option<StorageSlotDiff> {
+    let is_map =
+        matches!(from, Some(StorageSlot::Map(_))) || matches!(to, Some(StorageSlot::Map(_)));
+
+    if is_map {
+        let map_entries = |slot: Option<&StorageSlot>| -> BTreeMap<Digest, Word> {
+            match slot {
+                Some(StorageSlot::Map(map)) => {
+                    map.entries().map(|(key, value)| (*key, *value)).collect()
+                },
+                _ => BTreeMap::new(),
+            }
+        };
+        let old_entries = map_entries(from);
+        let new_entries = map_entries(to);
+
+        let changed_entries: BTreeMap<Digest, (Word, Word)> = old_entries
+            .keys()
+            .chain(new_entries.keys())
+            .filter_map(|key| {
+                let old_value = old_entries.get(key).copied().unwrap_or(EMPTY_WORD);
+                let new_value = new_entries.get(key).copied().unwrap_or(EMPTY_WORD);
+                (old_value != new_value).then_some((*key, (old_value, new_value)))
+            })
+            .collect();
+
+        (!changed_entries.is_empty()).then_some(StorageSlotDiff::Map { index, changed_entries })
+    } else {
+        let slot_value = |slot: Option<&StorageSlot>| match slot {
+            Some(StorageSlot::Value(value)) => *value,
+            _ => EMPTY_WORD,
+        };
+        let old_value = slot_value(from);
+        let new_value = slot_value(to);
+
+        (old_value != new_value).then_some(StorageSlotDiff::Value { index, old_value, new_value })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        Felt,
+        account::{Account, AccountBuilder, AccountComponent, AccountStorageMode, StorageSlot},
+        asset::{Asset, FungibleAsset},
+        testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+    };
+
+    use super::{AccountStateDiff, StorageSlotDiff};
+
+    fn build_account(balance: u64, slot_value: u64) -> Account {
+        let component = AccountComponent::compile(
+            "export.foo push.1 drop end",
+            miden_lib::transaction::TransactionKernel::assembler(),
+            vec![StorageSlot::Value([
+                Felt::new(slot_value),
+                Felt::new(0),
+                Felt::new(0),
+                Felt::new(0),
+            ])],
+        )
+        .unwrap()
+        .with_supports_all_types();
+
+        let asset: Asset =
+            FungibleAsset::new(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET.try_into().unwrap(), balance)
+                .unwrap()
+                .into();
+
+        AccountBuilder::new([0; 32])
+            .storage_mode(AccountStorageMode::Private)
+            .with_component(component)
+            .with_assets([asset])
+            .build_existing()
+            .unwrap()
+    }
+
+    #[test]
+    fn diff_reports_vault_and_storage_changes() {
+        let from = build_account(100, 1);
+        let to = build_account(60, 2);
+
+        let diff = AccountStateDiff::new(&from, &to);
+        assert!(diff.vault.added_assets.is_empty());
+        assert_eq!(
+            diff.vault.removed_assets,
+            vec![Asset::from(
+                FungibleAsset::new(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET.try_into().unwrap(), 40)
+                    .unwrap()
+            )]
+        );
+        assert_eq!(diff.storage.len(), 1);
+        assert!(matches!(
+            diff.storage[0],
+            StorageSlotDiff::Value { index: 0, old_value, new_value }
+                if old_value[0] == Felt::new(1) && new_value[0] == Felt::new(2)
+        ));
+
+        assert!(AccountStateDiff::new(&from, &from).is_empty());
+    }
+}
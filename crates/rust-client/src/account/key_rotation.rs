@@ -0,0 +1,201 @@
+//! Provides an authentication component whose public key can be rotated, along with the client
+//! APIs used to inspect which keys are used by tracked accounts and to build key rotation
+//! transactions.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use miden_lib::{
+    AuthScheme, account::interface::AccountInterface, transaction::TransactionKernel,
+    utils::word_to_masm_push_string,
+};
+use miden_objects::{
+    Digest, Word,
+    account::{Account, AccountCode, AccountComponent, AccountId, AccountStorage, StorageSlot},
+    crypto::dsa::rpo_falcon512::PublicKey,
+};
+use miden_tx::utils::sync::LazyLock;
+
+use super::procedure_roots::RPO_FALCON_512_AUTH;
+use crate::{
+    Client, ClientError,
+    transaction::{TransactionRequest, TransactionRequestBuilder},
+};
+
+// ROTATABLE AUTH COMPONENT
+// ================================================================================================
+
+/// Source code of the [`RpoFalcon512WithRotation`] component.
+///
+/// `rotate_pub_key` authenticates the transaction with the current key before replacing it, so
+/// only the holder of the current key can rotate it.
+const RPO_FALCON_512_WITH_ROTATION_CODE: &str = "
+    use.miden::account
+    use.miden::contracts::auth::basic
+
+    export.basic::auth_tx_rpo_falcon512
+
+    #! Replaces the public key used to authenticate transactions.
+    #!
+    #! Inputs:  [NEW_PUB_KEY, pad(12)]
+    #! Outputs: [pad(16)]
+    export.rotate_pub_key
+        exec.basic::auth_tx_rpo_falcon512
+        # => [NEW_PUB_KEY, pad(12)]
+
+        push.0 exec.account::set_item
+        # => [OLD_STORAGE_ROOT, OLD_PUB_KEY, pad(12)]
+
+        dropw dropw
+    end
+";
+
+/// Root of the `rotate_pub_key` procedure, computed once since it requires compiling the
+/// component.
+static ROTATE_PUB_KEY_PROCEDURE_ROOT: LazyLock<Digest> = LazyLock::new(|| {
+    AccountComponent::from(RpoFalcon512WithRotation::new(PublicKey::new(Word::default())))
+        .mast_forest()
+        .procedure_digests()
+        .find(|root| *root != rpo_falcon_512_auth_root())
+        .expect("component should export the key rotation procedure")
+});
+
+/// An [`AccountComponent`] implementing the same authentication scheme as
+/// [`RpoFalcon512`](miden_lib::account::auth::RpoFalcon512), which additionally exposes a
+/// `rotate_pub_key` procedure to replace the stored public key.
+///
+/// Transactions against accounts using this component are authenticated exactly like with the
+/// standard component, so the rest of the client handles them transparently. Use
+/// [`Client::build_key_rotation_request`] to build a transaction that rotates the key.
+pub struct RpoFalcon512WithRotation {
+    public_key: PublicKey,
+}
+
+impl RpoFalcon512WithRotation {
+    /// Creates a new [`RpoFalcon512WithRotation`] component with the given initial public key.
+    pub fn new(public_key: PublicKey) -> Self {
+        Self { public_key }
+    }
+
+    /// Returns the root of the `rotate_pub_key` procedure.
+    pub fn rotate_pub_key_procedure_root() -> Digest {
+        *ROTATE_PUB_KEY_PROCEDURE_ROOT
+    }
+}
+
+impl From<RpoFalcon512WithRotation> for AccountComponent {
+    fn from(auth: RpoFalcon512WithRotation) -> Self {
+        AccountComponent::compile(
+            RPO_FALCON_512_WITH_ROTATION_CODE,
+            TransactionKernel::assembler(),
+            vec![StorageSlot::Value(auth.public_key.into())],
+        )
+        .expect(
+            "rotatable RpoFalcon512 component should satisfy the requirements of a valid account \
+             component",
+        )
+        .with_supports_all_types()
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the public key stored by the authentication component of the account, if the account
+/// uses the `RpoFalcon512` authentication scheme.
+pub fn get_account_public_key(account: &Account) -> Option<Word> {
+    let interface = AccountInterface::from(account);
+    interface.auth().iter().find_map(|auth| match auth {
+        AuthScheme::RpoFalcon512 { pub_key } => Some(Word::from(*pub_key)),
+    })
+}
+
+/// Returns the public key stored by the `RpoFalcon512` authentication procedure of the account
+/// code, if any.
+///
+/// Unlike [`get_account_public_key`], this only needs the code and storage of the account.
+fn get_public_key(code: &AccountCode, storage: &AccountStorage) -> Option<Word> {
+    let auth_root = rpo_falcon_512_auth_root();
+    let procedure =
+        code.procedures().iter().find(|procedure| *procedure.mast_root() == auth_root)?;
+
+    storage.get_item(procedure.storage_offset()).ok().map(Word::from)
+}
+
+/// Returns the root of the `RpoFalcon512` authentication procedure.
+fn rpo_falcon_512_auth_root() -> Digest {
+    Digest::try_from(RPO_FALCON_512_AUTH).expect("procedure root should be valid hex")
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// This section of the [Client] contains methods for:
+///
+/// - **Key usage:** Map the public keys used for authentication to the tracked accounts.
+/// - **Key rotation:** Build transactions that replace the public key of an account using the
+///   [`RpoFalcon512WithRotation`] component.
+impl Client {
+    /// Returns the public keys used to authenticate the tracked accounts, each one mapped to the
+    /// IDs of the accounts using it.
+    ///
+    /// Accounts that don't use the `RpoFalcon512` authentication scheme are skipped. Only the code
+    /// and storage of the accounts are loaded from the store.
+    pub async fn get_accounts_by_public_key(
+        &self,
+    ) -> Result<BTreeMap<Word, Vec<AccountId>>, ClientError> {
+        let mut accounts_by_key = BTreeMap::<Word, Vec<AccountId>>::new();
+
+        for (account_id, code, storage) in self.store.get_accounts_code_and_storage().await? {
+            if let Some(pub_key) = get_public_key(&code, &storage) {
+                accounts_by_key.entry(pub_key).or_default().push(account_id);
+            }
+        }
+
+        Ok(accounts_by_key)
+    }
+
+    /// Builds a [`TransactionRequest`] that replaces the public key of the account with
+    /// `new_pub_key`.
+    ///
+    /// The transaction is authenticated with the current key of the account, so the keystore must
+    /// still hold it when executing the transaction. The current key should only be removed from
+    /// the keystore once the transaction has been committed.
+    ///
+    /// # Errors
+    ///
+    /// - If the account isn't tracked by the client.
+    /// - If the account doesn't use the [`RpoFalcon512WithRotation`] component.
+    pub async fn build_key_rotation_request(
+        &self,
+        account_id: AccountId,
+        new_pub_key: PublicKey,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account = self.try_get_account(account_id).await?;
+        let rotation_root = RpoFalcon512WithRotation::rotate_pub_key_procedure_root();
+
+        if !account
+            .account()
+            .code()
+            .procedures()
+            .iter()
+            .any(|procedure| *procedure.mast_root() == rotation_root)
+        {
+            return Err(ClientError::AccountKeyNotRotatable(account_id));
+        }
+
+        let code = format!(
+            "
+            begin
+                push.{new_pub_key}
+                call.{rotation_root}
+                dropw
+            end
+            ",
+            new_pub_key = word_to_masm_push_string(&new_pub_key.into()),
+            rotation_root = rotation_root.to_hex(),
+        );
+        let tx_script = self.compile_tx_script(vec![], &code)?;
+
+        Ok(TransactionRequestBuilder::new().with_custom_script(tx_script).build()?)
+    }
+}
@@ -49,11 +49,15 @@ use crate::{
     store::{AccountRecord, AccountStatus},
 };
 
+mod history;
+mod key_rotation;
 pub mod procedure_roots;
 
 // RE-EXPORTS
 // ================================================================================================
 
+pub use history::{AccountStateDiff, AccountVaultDiff, StorageSlotDiff};
+pub use key_rotation::get_account_public_key;
 pub use miden_objects::account::{
     Account, AccountBuilder, AccountCode, AccountDelta, AccountFile, AccountHeader, AccountId,
     AccountStorage, AccountStorageMode, AccountType, StorageMap, StorageSlot,
@@ -70,6 +74,8 @@ pub mod component {
         InitStorageData, StorageEntry, StorageSlotType, StorageValueName, TemplateType,
         WordRepresentation,
     };
+
+    pub use super::key_rotation::RpoFalcon512WithRotation;
 }
 
 // CLIENT METHODS
@@ -177,6 +183,8 @@ impl Client {
     /// - If the account is not found on the network.
     /// - If the account is private.
     /// - There was an error sending the request to the network.
+    /// - In verified mode, if the account doesn't match its account proof, or if the proof's block
+    ///   is ahead of the client's sync height (see [`Client::set_verified_mode`]).
     pub async fn import_account_by_id(&mut self, account_id: AccountId) -> Result<(), ClientError> {
         let fetched_account = self.rpc_api.get_account_details(account_id).await?;
 
@@ -187,6 +195,10 @@ impl Client {
             FetchedAccount::Public(account, ..) => account,
         };
 
+        if self.verified_mode {
+            self.verify_public_account(&account).await?;
+        }
+
         self.add_account(&account, None, true).await
     }
 
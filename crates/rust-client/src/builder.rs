@@ -15,7 +15,14 @@ use rand::Rng;
 use crate::rpc::{Endpoint, TonicRpcClient};
 #[cfg(feature = "sqlite")]
 use crate::store::sqlite_store::SqliteStore;
-use crate::{Client, ClientError, keystore::FilesystemKeyStore, rpc::NodeRpcClient, store::Store};
+use crate::{
+    Client, ClientError,
+    keystore::{FilesystemKeyStore, KeyStore},
+    rpc::NodeRpcClient,
+    store::Store,
+    sync::BlockRetentionPolicy,
+    transaction::{TransactionPolicy, TransactionRetryPolicy},
+};
 
 // CONSTANTS
 // ================================================================================================
@@ -31,7 +38,7 @@ const TX_GRACEFUL_BLOCKS: u32 = 20;
 /// This enum defers authenticator instantiation until the build phase. The builder can accept
 /// either:
 ///
-/// - A direct instance of an authenticator, or
+/// - A direct instance of an authenticator (which can be any [`KeyStore`]), or
 /// - A keystore path as a string which is then used as an authenticator.
 enum AuthenticatorConfig {
     Path(String),
@@ -44,8 +51,9 @@ enum AuthenticatorConfig {
 /// A builder for constructing a Miden client.
 ///
 /// This builder allows you to configure the various components required by the client, such as the
-/// RPC endpoint, store, RNG, and keystore. It is generic over the keystore type. By default, it
-/// uses `FilesystemKeyStore<rand::rngs::StdRng>`.
+/// RPC endpoint, store, RNG, and keystore. Any [`KeyStore`] implementation can be provided through
+/// [`ClientBuilder::with_keystore`], or a `FilesystemKeyStore<rand::rngs::StdRng>` can be created
+/// from a path with [`ClientBuilder::with_filesystem_keystore`].
 pub struct ClientBuilder {
     /// An optional custom RPC client. If provided, this takes precedence over `rpc_endpoint`.
     rpc_api: Option<Arc<dyn NodeRpcClient + Send>>,
@@ -66,6 +74,14 @@ pub struct ClientBuilder {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// Whether the data received from the node is verified before being stored.
+    verified_mode: bool,
+    /// Policy deciding which block headers are kept in the store.
+    block_retention_policy: BlockRetentionPolicy,
+    /// Policy deciding when failed queued transactions are retried.
+    transaction_retry_policy: TransactionRetryPolicy,
+    /// Defaults applied to the transactions created by the client.
+    transaction_policy: TransactionPolicy,
 }
 
 impl Default for ClientBuilder {
@@ -80,6 +96,10 @@ impl Default for ClientBuilder {
             in_debug_mode: false,
             tx_graceful_blocks: Some(TX_GRACEFUL_BLOCKS),
             max_block_number_delta: None,
+            verified_mode: false,
+            block_retention_policy: BlockRetentionPolicy::default(),
+            transaction_retry_policy: TransactionRetryPolicy::default(),
+            transaction_policy: TransactionPolicy::default(),
         }
     }
 }
@@ -142,6 +162,16 @@ impl ClientBuilder {
         self
     }
 
+    /// Provide a keystore instance to authenticate transactions with.
+    ///
+    /// This accepts any [`KeyStore`] implementation, which allows using a custom keystore backend
+    /// instead of the default [`FilesystemKeyStore`].
+    #[must_use]
+    pub fn with_keystore<K: KeyStore + 'static>(mut self, keystore: Arc<K>) -> Self {
+        self.keystore = Some(AuthenticatorConfig::Instance(keystore));
+        self
+    }
+
     /// Optionally set a maximum number of blocks that the client can be behind the network.
     /// By default, there's no maximum.
     #[must_use]
@@ -150,6 +180,40 @@ impl ClientBuilder {
         self
     }
 
+    /// Enable or disable verified mode, in which account and note data received from the node is
+    /// verified against the chain tracked by the client before being stored. See
+    /// [`Client::set_verified_mode`].
+    #[must_use]
+    pub fn in_verified_mode(mut self, verified_mode: bool) -> Self {
+        self.verified_mode = verified_mode;
+        self
+    }
+
+    /// Sets the policy deciding which block headers the client keeps in its store. By default,
+    /// the blocks with notes relevant to the client are kept. See
+    /// [`Client::set_block_retention_policy`].
+    #[must_use]
+    pub fn with_block_retention_policy(mut self, policy: BlockRetentionPolicy) -> Self {
+        self.block_retention_policy = policy;
+        self
+    }
+
+    /// Sets the policy deciding when failed queued transactions are retried. See
+    /// [`Client::set_transaction_retry_policy`].
+    #[must_use]
+    pub fn with_transaction_retry_policy(mut self, policy: TransactionRetryPolicy) -> Self {
+        self.transaction_retry_policy = policy;
+        self
+    }
+
+    /// Sets the defaults applied to the transactions created by the client, such as their
+    /// expiration delta. See [`Client::set_transaction_policy`].
+    #[must_use]
+    pub fn with_transaction_policy(mut self, policy: TransactionPolicy) -> Self {
+        self.transaction_policy = policy;
+        self
+    }
+
     /// Optionally set a maximum number of blocks to wait for a transaction to be confirmed. If
     /// `None`, there is no limit and transactions will be kept indefinitely.
     /// By default, the maximum is set to `TX_GRACEFUL_BLOCKS`.
@@ -231,7 +295,7 @@ impl ClientBuilder {
             }
         };
 
-        Ok(Client::new(
+        let mut client = Client::new(
             rpc_api,
             rng,
             arc_store,
@@ -239,6 +303,12 @@ impl ClientBuilder {
             self.in_debug_mode,
             self.tx_graceful_blocks,
             self.max_block_number_delta,
-        ))
+        );
+        client.set_verified_mode(self.verified_mode);
+        client.set_block_retention_policy(self.block_retention_policy);
+        client.set_transaction_retry_policy(self.transaction_retry_policy);
+        client.set_transaction_policy(self.transaction_policy);
+
+        Ok(client)
     }
 }
@@ -1,4 +1,5 @@
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
@@ -6,7 +7,8 @@ use alloc::{
 use miden_lib::account::interface::AccountInterfaceError;
 use miden_objects::{
     AccountError, AssetError, Digest, NoteError, PartialBlockchainError, TransactionInputError,
-    TransactionScriptError, account::AccountId, crypto::merkle::MerkleError, note::NoteId,
+    TransactionScriptError, account::AccountId, block::BlockNumber, crypto::merkle::MerkleError,
+    note::NoteId, transaction::TransactionId,
 };
 // RE-EXPORTS
 // ================================================================================================
@@ -40,14 +42,20 @@ pub enum ClientError {
     AccountCommitmentMismatch(Digest),
     #[error("account with id {0} is private")]
     AccountIsPrivate(AccountId),
+    #[error("account with id {0} doesn't support key rotation")]
+    AccountKeyNotRotatable(AccountId),
     #[error("account nonce is too low to import")]
     AccountNonceTooLow,
     #[error("asset error")]
     AssetError(#[from] AssetError),
     #[error("account data wasn't found for account id {0}")]
     AccountDataNotFound(AccountId),
+    #[error("state with nonce {nonce} wasn't found for account id {account_id}")]
+    AccountStateNotFound { account_id: AccountId, nonce: u64 },
     #[error("error creating the partial blockchain")]
     PartialBlockchainError(#[from] PartialBlockchainError),
+    #[error("the node's chain is not consistent with the client's chain")]
+    ChainDivergence(#[from] ChainDivergenceError),
     #[error("data deserialization error")]
     DataDeserializationError(#[from] DeserializationError),
     #[error("note with id {0} not found on chain")]
@@ -70,6 +78,14 @@ pub enum ClientError {
     NoConsumableNoteForAccount(AccountId),
     #[error("rpc api error")]
     RpcError(#[from] RpcError),
+    #[error("block {0} isn't stored, so the client can't roll back to it")]
+    RollbackBlockNotFound(BlockNumber),
+    #[error("block {0} wasn't stored by a sync, so the client can't roll back to it")]
+    RollbackBlockNotAnchor(BlockNumber),
+    #[error("transaction {0} isn't in the outbound transaction queue")]
+    QueuedTransactionNotFound(TransactionId),
+    #[error("queued transaction {0} was already submitted")]
+    QueuedTransactionAlreadySubmitted(TransactionId),
     #[error("recency condition error: {0}")]
     RecencyConditionError(String),
     #[error("note screener error")]
@@ -78,6 +94,16 @@ pub enum ClientError {
     StoreError(#[from] StoreError),
     #[error("transaction executor error: {0}")]
     TransactionExecutorError(#[from] TransactionExecutorError),
+    #[error(
+        "transaction {index} of the batch failed after {} transactions were submitted",
+        .submitted.len()
+    )]
+    TransactionBatchError {
+        index: usize,
+        submitted: Vec<TransactionId>,
+        #[source]
+        source: Box<ClientError>,
+    },
     #[error("transaction input error")]
     TransactionInputError(#[source] TransactionInputError),
     #[error("transaction prover error")]
@@ -88,6 +114,8 @@ pub enum ClientError {
     AccountInterfaceError(#[from] AccountInterfaceError),
     #[error("transaction script error")]
     TransactionScriptError(#[source] TransactionScriptError),
+    #[error("data received from the node failed verification")]
+    VerificationError(#[from] VerificationError),
     #[error("client initialization error: {0}")]
     ClientInitializationError(String),
 }
@@ -101,6 +129,65 @@ impl From<ClientError> for String {
     }
 }
 
+// CHAIN DIVERGENCE ERROR
+// ================================================================================================
+
+/// Error when the chain data received from the node doesn't extend the chain tracked by the
+/// client.
+///
+/// The client can recover from it by rolling back to the last block it shares with the node (see
+/// [`crate::Client::rollback_to_common_ancestor`]) and syncing again.
+#[derive(Debug, Error)]
+pub enum ChainDivergenceError {
+    /// The node's chain is shorter than the chain already synced by the client.
+    #[error("the node's chain tip {chain_tip} is behind the client's sync height {sync_height}")]
+    NodeBehind {
+        sync_height: BlockNumber,
+        chain_tip: BlockNumber,
+    },
+    /// The block received from the node doesn't commit to the blocks tracked by the client.
+    #[error("block {0} doesn't extend the chain tracked by the client")]
+    ChainMismatch(BlockNumber),
+    /// None of the blocks stored by the client, including the genesis block, match the node's.
+    #[error("the client doesn't share any block with the node's chain")]
+    NoCommonAncestor,
+}
+
+// VERIFICATION ERROR
+// ================================================================================================
+
+/// Error when data received from the node can't be verified against the chain tracked by the
+/// client. Only returned when the client is in verified mode (see
+/// [`crate::Client::set_verified_mode`]).
+#[derive(Debug, Error)]
+pub enum VerificationError {
+    /// The block header received from the node isn't part of the client's partial blockchain.
+    #[error("block header {0} isn't part of the chain tracked by the client")]
+    BlockHeaderNotInChain(BlockNumber),
+    /// The data is anchored in a block the client couldn't sync to.
+    #[error("block {0} is ahead of the chain tracked by the client")]
+    BlockNotSynced(BlockNumber),
+    /// The node didn't return a proof for the account.
+    #[error("the node didn't return a proof for account {0}")]
+    MissingAccountProof(AccountId),
+    /// The account's witness doesn't match the account root of the block it's anchored in.
+    #[error(
+        "the witness for account {account_id} doesn't match the account root of block {block_num}"
+    )]
+    AccountWitnessMismatch {
+        account_id: AccountId,
+        block_num: BlockNumber,
+    },
+    /// The account state received from the node doesn't match the proven account commitment.
+    #[error("the state of account {0} doesn't match its proven commitment")]
+    AccountStateMismatch(AccountId),
+    /// The note's inclusion proof doesn't match the note root of the block it's anchored in.
+    #[error(
+        "the inclusion proof for note {note_id} doesn't match the note root of block {block_num}"
+    )]
+    NoteInclusionMismatch { note_id: NoteId, block_num: BlockNumber },
+}
+
 // ID PREFIX FETCH ERROR
 // ================================================================================================
 
@@ -0,0 +1,543 @@
+use alloc::collections::BTreeMap;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    string::ToString,
+    sync::Arc,
+    vec::Vec,
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use miden_objects::{
+    Digest, Felt, Word,
+    account::{AccountDelta, AuthSecretKey},
+};
+use miden_tx::{
+    AuthenticationError,
+    auth::TransactionAuthenticator,
+    utils::{Deserializable, Serializable, sync::RwLock},
+};
+use rand::{Rng, SeedableRng};
+use zeroize::Zeroizing;
+
+use super::{
+    KeyStore, KeyStoreError,
+    fs_keystore::{decode_key_file, hash_pub_key, read_file},
+    public_key,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Magic bytes at the start of every encrypted key file.
+const MAGIC: &[u8; 4] = b"MKEY";
+
+/// Version of the encrypted key file format.
+const FORMAT_VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// Length of the file header: magic, version, the three KDF parameters, salt and nonce.
+const HEADER_LEN: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+/// Name of the file holding the passphrase check value. It starts with a dot so it's never taken
+/// for a key file.
+const PASSPHRASE_CHECK_FILE: &str = ".passphrase-check";
+
+/// Value encrypted in the passphrase check file.
+const PASSPHRASE_CHECK_VALUE: &[u8] = b"miden encrypted keystore";
+
+/// Upper bounds for the key derivation parameters, so a tampered file header can't make unlocking
+/// allocate unbounded memory or run for an unbounded time.
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+// KEY DERIVATION PARAMETERS
+// ================================================================================================
+
+/// Argon2id parameters used to derive the file encryption key from the passphrase.
+///
+/// The parameters are written in the header of every key file, so files encrypted with different
+/// parameters can coexist in the same keystore. Parameters above 1 GiB of memory, 64 iterations or
+/// a parallelism of 16 are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyDerivationParams {
+    /// Memory cost in KiB.
+    pub memory_kib: u32,
+    /// Number of iterations.
+    pub iterations: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+impl Default for KeyDerivationParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KeyDerivationParams {
+    /// Derives a 32-byte key from the passphrase and salt.
+    fn derive_key(
+        &self,
+        passphrase: &[u8],
+        salt: &[u8],
+    ) -> Result<Zeroizing<[u8; KEY_LEN]>, KeyStoreError> {
+        if self.memory_kib > MAX_MEMORY_KIB
+            || self.iterations > MAX_ITERATIONS
+            || self.parallelism > MAX_PARALLELISM
+        {
+            return Err(KeyStoreError::EncryptionError(format!(
+                "key derivation params exceed the supported limits: {self:?}"
+            )));
+        }
+
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(KEY_LEN))
+            .map_err(|err| {
+                KeyStoreError::EncryptionError(format!("invalid key derivation params: {err}"))
+            })?;
+
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, salt, key.as_mut())
+            .map_err(|err| {
+                KeyStoreError::EncryptionError(format!("error deriving encryption key: {err}"))
+            })?;
+
+        Ok(key)
+    }
+}
+
+// ENCRYPTED FILESYSTEM KEYSTORE
+// ================================================================================================
+
+/// A filesystem-based keystore that keeps every secret key encrypted at rest.
+///
+/// Keys are stored one per file, using the same file naming scheme as
+/// [`FilesystemKeyStore`](super::FilesystemKeyStore). Each file starts with a versioned header
+/// holding the key derivation parameters and a random salt and nonce, followed by the secret key
+/// encrypted with ChaCha20-Poly1305 under a key derived from the passphrase with Argon2id. The
+/// header is authenticated along with the ciphertext.
+///
+/// The keystore starts locked. [`Self::unlock`] decrypts the stored keys into memory and keeps
+/// them there until [`Self::lock`] is called, so decrypted keys only exist while a session is
+/// open. The first successful unlock also writes a value encrypted with the passphrase next to
+/// the keys, so later unlocks are checked against it even when no key is stored. Any operation that
+/// needs a secret key fails with [`KeyStoreError::Locked`] while the keystore is locked.
+#[derive(Debug, Clone)]
+pub struct EncryptedFilesystemKeyStore<R: Rng> {
+    /// The random number generator used to generate signatures.
+    rng: Arc<RwLock<R>>,
+    /// The directory where the keys are stored and read from.
+    keys_directory: PathBuf,
+    /// Parameters used to derive the encryption key of newly written files.
+    kdf_params: KeyDerivationParams,
+    /// The unlocked session, if any.
+    session: Arc<RwLock<Option<Session>>>,
+}
+
+/// Decrypted state held in memory while the keystore is unlocked.
+struct Session {
+    passphrase: Zeroizing<Vec<u8>>,
+    keys: BTreeMap<Digest, AuthSecretKey>,
+}
+
+impl core::fmt::Debug for Session {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Session")
+            .field("num_keys", &self.keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Rng> EncryptedFilesystemKeyStore<R> {
+    pub fn with_rng(keys_directory: PathBuf, rng: R) -> Result<Self, KeyStoreError> {
+        if !keys_directory.exists() {
+            fs::create_dir_all(&keys_directory).map_err(|err| {
+                KeyStoreError::StorageError(format!("error creating keys directory: {err:?}"))
+            })?;
+        }
+
+        Ok(EncryptedFilesystemKeyStore {
+            rng: Arc::new(RwLock::new(rng)),
+            keys_directory,
+            kdf_params: KeyDerivationParams::default(),
+            session: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Sets the key derivation parameters used when writing new key files.
+    ///
+    /// Existing files keep the parameters they were written with.
+    #[must_use]
+    pub fn with_kdf_params(mut self, kdf_params: KeyDerivationParams) -> Self {
+        self.kdf_params = kdf_params;
+        self
+    }
+
+    // SESSION LIFECYCLE
+    // --------------------------------------------------------------------------------------------
+
+    /// Unlocks the keystore with the given passphrase, decrypting every encrypted key file in the
+    /// keys directory into memory.
+    ///
+    /// Plaintext key files are left untouched; use [`Self::migrate_plaintext_keys`] to encrypt
+    /// them.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::EncryptionError`] if the passphrase doesn't match the one the
+    /// keystore was first unlocked with, or if any of the key files can't be decrypted with it.
+    pub fn unlock(&self, passphrase: &str) -> Result<(), KeyStoreError> {
+        let check_path = self.keys_directory.join(PASSPHRASE_CHECK_FILE);
+        let has_check_value = check_path.exists();
+        if has_check_value {
+            let check_value = decrypt(&read_file(&check_path)?, passphrase.as_bytes())?;
+            if check_value.as_slice() != PASSPHRASE_CHECK_VALUE {
+                return Err(KeyStoreError::EncryptionError(
+                    "error unlocking keystore: wrong passphrase".to_string(),
+                ));
+            }
+        }
+
+        let mut keys = BTreeMap::new();
+
+        for path in self.key_files()? {
+            let contents = read_file(&path)?;
+            if !is_encrypted(&contents) {
+                continue;
+            }
+
+            let key = decrypt_key(&contents, passphrase.as_bytes())?;
+            keys.insert(Digest::from(public_key(&key)), key);
+        }
+
+        // Keystores created before the check value existed get it on their next unlock
+        if !has_check_value {
+            let check_value =
+                encrypt(PASSPHRASE_CHECK_VALUE, passphrase.as_bytes(), self.kdf_params)?;
+            write_file_atomically(&check_path, &check_value)?;
+        }
+
+        *self.session.write() = Some(Session {
+            passphrase: Zeroizing::new(passphrase.as_bytes().to_vec()),
+            keys,
+        });
+
+        Ok(())
+    }
+
+    /// Locks the keystore, dropping the passphrase and all decrypted keys from memory.
+    pub fn lock(&self) {
+        *self.session.write() = None;
+    }
+
+    /// Returns `true` if the keystore is locked.
+    pub fn is_locked(&self) -> bool {
+        self.session.read().is_none()
+    }
+
+    // KEY MANAGEMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Encrypts every plaintext key file in the keys directory with the session passphrase,
+    /// replacing the original file. Returns the number of migrated keys.
+    ///
+    /// Files written by [`FilesystemKeyStore`](super::FilesystemKeyStore) can be migrated this
+    /// way by pointing this keystore to the same directory.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    pub fn migrate_plaintext_keys(&self) -> Result<usize, KeyStoreError> {
+        let mut session = self.session.write();
+        let session = session.as_mut().ok_or(KeyStoreError::Locked)?;
+
+        let mut migrated = 0;
+        for path in self.key_files()? {
+            let contents = read_file(&path)?;
+            if is_encrypted(&contents) {
+                continue;
+            }
+
+            let key = decode_key_file(&contents)?;
+            let pub_key = public_key(&key);
+            let encrypted = encrypt_key(&key, &session.passphrase, self.kdf_params)?;
+
+            let target_path = self.keys_directory.join(hash_pub_key(pub_key));
+            write_file_atomically(&target_path, &encrypted)?;
+            if target_path != path {
+                fs::remove_file(&path).map_err(|err| {
+                    KeyStoreError::StorageError(format!(
+                        "error removing plaintext key file: {err:?}"
+                    ))
+                })?;
+            }
+
+            session.keys.insert(Digest::from(pub_key), key);
+            migrated += 1;
+        }
+
+        Ok(migrated)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Retrieves a secret key from the unlocked session given its public key.
+    fn read_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        let session = self.session.read();
+        let session = session.as_ref().ok_or(KeyStoreError::Locked)?;
+
+        Ok(session.keys.get(&Digest::from(pub_key)).cloned())
+    }
+
+    /// Returns the paths of all key files in the keys directory, skipping temporary files and the
+    /// passphrase check file.
+    fn key_files(&self) -> Result<Vec<PathBuf>, KeyStoreError> {
+        let entries = fs::read_dir(&self.keys_directory).map_err(|err| {
+            KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+        })?;
+
+        let mut paths = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| {
+                KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+            })?;
+            let path = entry.path();
+            let is_temp_file = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with('.'));
+
+            if path.is_file() && !is_temp_file {
+                paths.push(path);
+            }
+        }
+
+        Ok(paths)
+    }
+}
+
+// Provide a default implementation for `StdRng` so you can call EncryptedFilesystemKeyStore::new()
+// without type annotations.
+impl EncryptedFilesystemKeyStore<rand::rngs::StdRng> {
+    /// Creates a new locked [`EncryptedFilesystemKeyStore`] using [`rand::rngs::StdRng`] as the
+    /// RNG.
+    pub fn new(keys_directory: PathBuf) -> Result<Self, KeyStoreError> {
+        use rand::rngs::StdRng;
+        let rng = StdRng::from_os_rng();
+
+        EncryptedFilesystemKeyStore::with_rng(keys_directory, rng)
+    }
+}
+
+/// All key operations fail with [`KeyStoreError::Locked`] while the keystore is locked.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<R: Rng + Send + Sync> KeyStore for EncryptedFilesystemKeyStore<R> {
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let mut session = self.session.write();
+        let session = session.as_mut().ok_or(KeyStoreError::Locked)?;
+
+        let pub_key = public_key(key);
+        let contents = encrypt_key(key, &session.passphrase, self.kdf_params)?;
+        write_file_atomically(&self.keys_directory.join(hash_pub_key(pub_key)), &contents)?;
+
+        session.keys.insert(Digest::from(pub_key), key.clone());
+
+        Ok(())
+    }
+
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        self.read_key(pub_key)
+    }
+
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        let mut session = self.session.write();
+        let session = session.as_mut().ok_or(KeyStoreError::Locked)?;
+
+        let file_path = self.keys_directory.join(hash_pub_key(pub_key));
+        if file_path.exists() {
+            fs::remove_file(file_path).map_err(|err| {
+                KeyStoreError::StorageError(format!("error removing secret key file: {err:?}"))
+            })?;
+        }
+
+        session.keys.remove(&Digest::from(pub_key));
+
+        Ok(())
+    }
+
+    async fn get_pub_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        let session = self.session.read();
+        let session = session.as_ref().ok_or(KeyStoreError::Locked)?;
+
+        Ok(session.keys.keys().map(|pub_key| Word::from(*pub_key)).collect())
+    }
+}
+
+impl<R: Rng> TransactionAuthenticator for EncryptedFilesystemKeyStore<R> {
+    /// Gets a signature over a message, given a public key.
+    ///
+    /// The public key should correspond to one of the keys tracked by the keystore.
+    ///
+    /// # Errors
+    /// If the keystore is locked, [`AuthenticationError::Other`] is returned. If the public key
+    /// isn't found in the store, [`AuthenticationError::UnknownPublicKey`] is returned.
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        _account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let mut rng = self.rng.write();
+
+        let secret_key = self
+            .read_key(pub_key)
+            .map_err(|err| AuthenticationError::other(err.to_string()))?;
+
+        let AuthSecretKey::RpoFalcon512(k) = secret_key
+            .ok_or(AuthenticationError::UnknownPublicKey(Digest::from(pub_key).into()))?;
+
+        miden_tx::auth::signatures::get_falcon_signature(&k, message, &mut *rng)
+    }
+}
+
+// ENCRYPTION HELPERS
+// ================================================================================================
+
+/// Returns `true` if the file contents start with the encrypted key file magic bytes.
+pub(super) fn is_encrypted(contents: &[u8]) -> bool {
+    contents.starts_with(MAGIC)
+}
+
+/// Serializes and encrypts a secret key, returning the full contents of the key file.
+fn encrypt_key(
+    key: &AuthSecretKey,
+    passphrase: &[u8],
+    kdf_params: KeyDerivationParams,
+) -> Result<Vec<u8>, KeyStoreError> {
+    encrypt(&Zeroizing::new(key.to_bytes()), passphrase, kdf_params)
+}
+
+/// Decrypts and deserializes the secret key of an encrypted key file.
+fn decrypt_key(contents: &[u8], passphrase: &[u8]) -> Result<AuthSecretKey, KeyStoreError> {
+    let plaintext = decrypt(contents, passphrase)?;
+
+    AuthSecretKey::read_from_bytes(&plaintext).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error reading secret key from bytes: {err:?}"))
+    })
+}
+
+/// Encrypts the plaintext, returning the full contents of the encrypted file.
+///
+/// The file layout is:
+/// `magic (4) | version (1) | memory_kib (4) | iterations (4) | parallelism (4) | salt (16) |
+/// nonce (12) | ciphertext`, with all integers in little-endian order. The header is used as
+/// associated data.
+fn encrypt(
+    plaintext: &[u8],
+    passphrase: &[u8],
+    kdf_params: KeyDerivationParams,
+) -> Result<Vec<u8>, KeyStoreError> {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let nonce: [u8; NONCE_LEN] = rand::random();
+
+    let mut contents = Vec::with_capacity(HEADER_LEN);
+    contents.extend_from_slice(MAGIC);
+    contents.push(FORMAT_VERSION);
+    contents.extend_from_slice(&kdf_params.memory_kib.to_le_bytes());
+    contents.extend_from_slice(&kdf_params.iterations.to_le_bytes());
+    contents.extend_from_slice(&kdf_params.parallelism.to_le_bytes());
+    contents.extend_from_slice(&salt);
+    contents.extend_from_slice(&nonce);
+
+    let encryption_key = kdf_params.derive_key(passphrase, &salt)?;
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(encryption_key.as_ref()))
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &contents })
+        .map_err(|err| {
+            KeyStoreError::EncryptionError(format!("error encrypting key file: {err}"))
+        })?;
+
+    contents.extend_from_slice(&ciphertext);
+    Ok(contents)
+}
+
+/// Parses the header of an encrypted file and decrypts its contents. The key derivation
+/// parameters read from the header are rejected if they exceed the supported limits.
+fn decrypt(contents: &[u8], passphrase: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyStoreError> {
+    if contents.len() < HEADER_LEN {
+        return Err(KeyStoreError::DecodingError("encrypted key file is too short".to_string()));
+    }
+
+    let (header, ciphertext) = contents.split_at(HEADER_LEN);
+    let version = header[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(KeyStoreError::DecodingError(format!(
+            "unsupported encrypted key file version: {version}"
+        )));
+    }
+
+    let mut fields = header[MAGIC.len() + 1..]
+        .chunks_exact(4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("chunks should be 4 bytes long")));
+    let kdf_params = KeyDerivationParams {
+        memory_kib: fields.next().expect("header should contain memory cost"),
+        iterations: fields.next().expect("header should contain iteration count"),
+        parallelism: fields.next().expect("header should contain parallelism"),
+    };
+    let salt_start = MAGIC.len() + 1 + 3 * 4;
+    let salt = &header[salt_start..salt_start + SALT_LEN];
+    let nonce = &header[salt_start + SALT_LEN..];
+
+    let encryption_key = kdf_params.derive_key(passphrase, salt)?;
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(encryption_key.as_ref()))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+        .map_err(|_| {
+            KeyStoreError::EncryptionError(
+                "error decrypting key file: wrong passphrase or corrupted file".to_string(),
+            )
+        })?;
+
+    Ok(Zeroizing::new(plaintext))
+}
+
+// FILE HELPERS
+// ================================================================================================
+
+/// Writes the contents to a temporary file next to `path` and renames it over `path`, so a key
+/// file is never left partially written.
+fn write_file_atomically(path: &Path, contents: &[u8]) -> Result<(), KeyStoreError> {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{file_name}.tmp"));
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&temp_path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
+    })?;
+    file.write_all(contents).and_then(|()| file.sync_all()).map_err(|err| {
+        KeyStoreError::StorageError(format!("error writing secret key file: {err:?}"))
+    })?;
+
+    fs::rename(&temp_path, path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error replacing secret key file: {err:?}"))
+    })
+}
@@ -2,8 +2,8 @@ use alloc::string::String;
 use std::{
     fs::OpenOptions,
     hash::{DefaultHasher, Hash, Hasher},
-    io::{BufRead, BufReader, BufWriter, Write},
-    path::PathBuf,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
     string::ToString,
     sync::Arc,
     vec::Vec,
@@ -19,8 +19,9 @@ use miden_tx::{
     utils::{Deserializable, Serializable, sync::RwLock},
 };
 use rand::{Rng, SeedableRng};
+use zeroize::Zeroizing;
 
-use super::KeyStoreError;
+use super::{KeyStore, KeyStoreError, encrypted_fs_keystore::is_encrypted, public_key};
 
 /// A filesystem-based keystore that stores keys in separate files and provides transaction
 /// authentication functionality. The public key is hashed and the result is used as the filename
@@ -50,14 +51,42 @@ impl<R: Rng> FilesystemKeyStore<R> {
         })
     }
 
-    /// Adds a secret key to the keystore.
-    pub fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
-        let pub_key = match key {
-            AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
-        };
-
+    /// Retrieves a secret key from the keys directory given its public key.
+    fn read_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
         let filename = hash_pub_key(pub_key);
 
+        let file_path = self.keys_directory.join(filename);
+        if !file_path.exists() {
+            return Ok(None);
+        }
+
+        Self::read_key_file(&file_path).map(Some)
+    }
+
+    /// Reads and decodes the secret key stored in the given file.
+    fn read_key_file(file_path: &Path) -> Result<AuthSecretKey, KeyStoreError> {
+        decode_key_file(&read_file(file_path)?)
+    }
+}
+
+// Provide a default implementation for `StdRng` so you can call FilesystemKeyStore::new() without
+// type annotations.
+impl FilesystemKeyStore<rand::rngs::StdRng> {
+    /// Creates a new [`FilesystemKeyStore`] using [`rand::rngs::StdRng`] as the RNG.
+    pub fn new(keys_directory: PathBuf) -> Result<Self, KeyStoreError> {
+        use rand::rngs::StdRng;
+        let rng = StdRng::from_os_rng();
+
+        FilesystemKeyStore::with_rng(keys_directory, rng)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<R: Rng + Send + Sync> KeyStore for FilesystemKeyStore<R> {
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let filename = hash_pub_key(public_key(key));
+
         let file_path = self.keys_directory.join(filename);
         let file = OpenOptions::new()
             .write(true)
@@ -77,47 +106,45 @@ impl<R: Rng> FilesystemKeyStore<R> {
         Ok(())
     }
 
-    /// Retrieves a secret key from the keystore given its public key.
-    pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
-        let filename = hash_pub_key(pub_key);
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        self.read_key(pub_key)
+    }
 
-        let file_path = self.keys_directory.join(filename);
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        let file_path = self.keys_directory.join(hash_pub_key(pub_key));
         if !file_path.exists() {
-            return Ok(None);
+            return Ok(());
         }
 
-        let file = OpenOptions::new().read(true).open(file_path).map_err(|err| {
-            KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
-        })?;
-        let mut reader = BufReader::new(file);
-        let mut key_pair_hex = String::new();
-        reader.read_line(&mut key_pair_hex).map_err(|err| {
-            KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
-        })?;
+        std::fs::remove_file(file_path).map_err(|err| {
+            KeyStoreError::StorageError(format!("error removing secret key file: {err:?}"))
+        })
+    }
 
-        let secret_key_bytes = hex::decode(key_pair_hex.trim()).map_err(|err| {
-            KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+    async fn get_pub_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        let entries = std::fs::read_dir(&self.keys_directory).map_err(|err| {
+            KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
         })?;
-        let secret_key =
-            AuthSecretKey::read_from_bytes(secret_key_bytes.as_slice()).map_err(|err| {
-                KeyStoreError::DecodingError(format!(
-                    "error reading secret key from bytes: {err:?}"
-                ))
-            })?;
 
-        Ok(Some(secret_key))
-    }
-}
-
-// Provide a default implementation for `StdRng` so you can call FilesystemKeyStore::new() without
-// type annotations.
-impl FilesystemKeyStore<rand::rngs::StdRng> {
-    /// Creates a new [`FilesystemKeyStore`] using [`rand::rngs::StdRng`] as the RNG.
-    pub fn new(keys_directory: PathBuf) -> Result<Self, KeyStoreError> {
-        use rand::rngs::StdRng;
-        let rng = StdRng::from_os_rng();
+        let mut pub_keys = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|err| {
+                    KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+                })?
+                .path();
+            if !path.is_file() || !is_key_file_name(&path) {
+                continue;
+            }
+
+            // Keys encrypted by `EncryptedFilesystemKeyStore` can't be read without the passphrase
+            let contents = read_file(&path)?;
+            if !is_encrypted(&contents) {
+                pub_keys.push(public_key(&decode_key_file(&contents)?));
+            }
+        }
 
-        FilesystemKeyStore::with_rng(keys_directory, rng)
+        Ok(pub_keys)
     }
 }
 
@@ -138,7 +165,7 @@ impl<R: Rng> TransactionAuthenticator for FilesystemKeyStore<R> {
         let mut rng = self.rng.write();
 
         let secret_key = self
-            .get_key(pub_key)
+            .read_key(pub_key)
             .map_err(|err| AuthenticationError::other(err.to_string()))?;
 
         let AuthSecretKey::RpoFalcon512(k) = secret_key
@@ -149,9 +176,37 @@ impl<R: Rng> TransactionAuthenticator for FilesystemKeyStore<R> {
 }
 
 /// Hashes a public key to a string representation.
-fn hash_pub_key(pub_key: Word) -> String {
+pub(super) fn hash_pub_key(pub_key: Word) -> String {
     let pub_key = Digest::from(pub_key).to_hex();
     let mut hasher = DefaultHasher::new();
     pub_key.hash(&mut hasher);
     hasher.finish().to_string()
 }
+
+/// Returns `true` if the file name is one generated by [`hash_pub_key`], so files that don't hold
+/// keys, such as temporary files, are ignored.
+pub(super) fn is_key_file_name(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.parse::<u64>().is_ok())
+}
+
+/// Decodes the contents of a plaintext key file: the hex-encoded serialized secret key.
+pub(super) fn decode_key_file(contents: &[u8]) -> Result<AuthSecretKey, KeyStoreError> {
+    let key_pair_hex = core::str::from_utf8(contents).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error decoding secret key file: {err:?}"))
+    })?;
+    let secret_key_bytes = Zeroizing::new(hex::decode(key_pair_hex.trim()).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+    })?);
+
+    AuthSecretKey::read_from_bytes(&secret_key_bytes).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error reading secret key from bytes: {err:?}"))
+    })
+}
+
+pub(super) fn read_file(path: &Path) -> Result<Vec<u8>, KeyStoreError> {
+    std::fs::read(path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
+    })
+}
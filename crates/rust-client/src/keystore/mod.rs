@@ -1,5 +1,10 @@
-use alloc::string::String;
+//! Provides the [`KeyStore`] trait, which abstracts over the storage of the secret keys used to
+//! authenticate transactions, along with the keystore implementations shipped with the client.
 
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use miden_objects::{Word, account::AuthSecretKey};
+use miden_tx::auth::TransactionAuthenticator;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,6 +13,53 @@ pub enum KeyStoreError {
     StorageError(String),
     #[error("decoding error: {0}")]
     DecodingError(String),
+    #[error("encryption error: {0}")]
+    EncryptionError(String),
+    #[error("keystore is locked")]
+    Locked,
+}
+
+// KEYSTORE TRAIT
+// ================================================================================================
+
+/// The [`KeyStore`] trait exposes the methods a secret key storage backend needs to implement in
+/// order to be used by the client.
+///
+/// Keys are indexed by their public key, represented as a [`Word`]. Every keystore is also a
+/// [`TransactionAuthenticator`], so it can be used to sign transactions with the keys it holds.
+///
+/// Methods that write to the underlying storage are async, as some backends (such as the browser's
+/// IndexedDB) can only be accessed asynchronously. Reading a key is synchronous because it's
+/// needed from within [`TransactionAuthenticator::get_signature`].
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait KeyStore: TransactionAuthenticator + Send + Sync {
+    /// Adds a secret key to the keystore. If a key with the same public key is already stored, it
+    /// gets overwritten.
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError>;
+
+    /// Retrieves a secret key from the keystore given its public key. Returns `None` if the key
+    /// isn't stored.
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError>;
+
+    /// Removes the secret key corresponding to the given public key from the keystore. Removing a
+    /// key that isn't stored is a no-op.
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError>;
+
+    /// Returns the public keys of all the secret keys stored in the keystore.
+    async fn get_pub_keys(&self) -> Result<Vec<Word>, KeyStoreError>;
+
+    /// Returns `true` if the keystore holds the secret key corresponding to the given public key.
+    fn contains_key(&self, pub_key: Word) -> Result<bool, KeyStoreError> {
+        Ok(self.get_key(pub_key)?.is_some())
+    }
+}
+
+/// Returns the public key corresponding to a secret key.
+pub(crate) fn public_key(key: &AuthSecretKey) -> Word {
+    match key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    }
 }
 
 #[cfg(feature = "std")]
@@ -15,6 +67,16 @@ mod fs_keystore;
 #[cfg(feature = "std")]
 pub use fs_keystore::FilesystemKeyStore;
 
+#[cfg(feature = "std")]
+mod encrypted_fs_keystore;
+#[cfg(feature = "std")]
+pub use encrypted_fs_keystore::{EncryptedFilesystemKeyStore, KeyDerivationParams};
+
+#[cfg(all(feature = "std", unix))]
+pub mod remote_authenticator;
+#[cfg(all(feature = "std", unix))]
+pub use remote_authenticator::RemoteAuthenticator;
+
 #[cfg(feature = "idxdb")]
 mod web_keystore;
 #[cfg(feature = "idxdb")]
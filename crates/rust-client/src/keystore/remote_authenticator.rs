@@ -0,0 +1,311 @@
+//! Provides a [`TransactionAuthenticator`] that forwards signing requests to an external signer
+//! process over a Unix domain socket, so that secret keys never need to be loaded into the client
+//! host.
+//!
+//! ## Wire protocol
+//!
+//! Every connection carries a single request followed by a single response. Both are sent as a
+//! frame consisting of the body length as a little-endian `u32`, followed by the body itself.
+//! Bodies are encoded with the [`Serializable`] implementations of the Miden types:
+//!
+//! - Request: `version: u8 | kind: u8 | payload`. The only request kind is `1` (sign), whose
+//!   payload is `pub_key: Word | message: Word | account_delta: AccountDelta`.
+//! - Response: `version: u8 | status: u8 | payload`. The status is `0` for a signature (payload is
+//!   a `Vec<Felt>`), `1` if the signer doesn't hold the key (payload is the requested `Word`) and
+//!   `2` for any other error (payload is a UTF-8 message prefixed by its length as a `usize`).
+//!
+//! The current protocol version is [`PROTOCOL_VERSION`]. Frames bigger than [`MAX_FRAME_SIZE`]
+//! bytes are rejected.
+
+use alloc::string::{String, ToString};
+use std::{
+    io::{self, Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+    time::Duration,
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest, Felt, Word,
+    account::AccountDelta,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+use miden_tx::{AuthenticationError, auth::TransactionAuthenticator};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the signer wire protocol.
+pub const PROTOCOL_VERSION: u8 = 1;
+
+/// Maximum size in bytes of a request or response body.
+pub const MAX_FRAME_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Default timeout for reading from and writing to the signer socket.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const REQUEST_SIGN: u8 = 1;
+
+const RESPONSE_SIGNATURE: u8 = 0;
+const RESPONSE_UNKNOWN_PUBLIC_KEY: u8 = 1;
+const RESPONSE_ERROR: u8 = 2;
+
+// SIGNER MESSAGES
+// ================================================================================================
+
+/// A request sent from a [`RemoteAuthenticator`] to the signer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignerRequest {
+    /// Requests a signature over `message` with the secret key corresponding to `pub_key`.
+    Sign {
+        pub_key: Word,
+        message: Word,
+        account_delta: AccountDelta,
+    },
+}
+
+/// A response sent from the signer to a [`RemoteAuthenticator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerResponse {
+    /// The requested signature.
+    Signature(Vec<Felt>),
+    /// The signer doesn't hold the secret key for the requested public key.
+    UnknownPublicKey(Word),
+    /// The signer failed to produce the signature.
+    Error(String),
+}
+
+impl Serializable for SignerRequest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(PROTOCOL_VERSION);
+        match self {
+            SignerRequest::Sign { pub_key, message, account_delta } => {
+                target.write_u8(REQUEST_SIGN);
+                pub_key.write_into(target);
+                message.write_into(target);
+                account_delta.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for SignerRequest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        read_version(source)?;
+        match source.read_u8()? {
+            REQUEST_SIGN => Ok(SignerRequest::Sign {
+                pub_key: Word::read_from(source)?,
+                message: Word::read_from(source)?,
+                account_delta: AccountDelta::read_from(source)?,
+            }),
+            kind => Err(DeserializationError::InvalidValue(format!(
+                "unknown signer request kind: {kind}"
+            ))),
+        }
+    }
+}
+
+impl Serializable for SignerResponse {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(PROTOCOL_VERSION);
+        match self {
+            SignerResponse::Signature(signature) => {
+                target.write_u8(RESPONSE_SIGNATURE);
+                signature.write_into(target);
+            },
+            SignerResponse::UnknownPublicKey(pub_key) => {
+                target.write_u8(RESPONSE_UNKNOWN_PUBLIC_KEY);
+                pub_key.write_into(target);
+            },
+            SignerResponse::Error(message) => {
+                target.write_u8(RESPONSE_ERROR);
+                target.write_usize(message.len());
+                target.write_bytes(message.as_bytes());
+            },
+        }
+    }
+}
+
+impl Deserializable for SignerResponse {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        read_version(source)?;
+        match source.read_u8()? {
+            RESPONSE_SIGNATURE => Ok(SignerResponse::Signature(Vec::<Felt>::read_from(source)?)),
+            RESPONSE_UNKNOWN_PUBLIC_KEY => {
+                Ok(SignerResponse::UnknownPublicKey(Word::read_from(source)?))
+            },
+            RESPONSE_ERROR => {
+                let len = source.read_usize()?;
+                let message = String::from_utf8(source.read_vec(len)?)
+                    .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+                Ok(SignerResponse::Error(message))
+            },
+            status => Err(DeserializationError::InvalidValue(format!(
+                "unknown signer response status: {status}"
+            ))),
+        }
+    }
+}
+
+fn read_version<R: ByteReader>(source: &mut R) -> Result<(), DeserializationError> {
+    let version = source.read_u8()?;
+    if version != PROTOCOL_VERSION {
+        return Err(DeserializationError::InvalidValue(format!(
+            "unsupported signer protocol version: {version}"
+        )));
+    }
+
+    Ok(())
+}
+
+// REMOTE AUTHENTICATOR
+// ================================================================================================
+
+/// A [`TransactionAuthenticator`] that requests signatures from a signer process listening on a
+/// Unix domain socket, using the protocol described in the [module docs](self).
+///
+/// A new connection is opened for every signature, so the signer can be restarted while the client
+/// is running.
+#[derive(Debug, Clone)]
+pub struct RemoteAuthenticator {
+    /// Path of the socket the signer listens on.
+    socket_path: PathBuf,
+    /// Timeout applied to every read from and write to the socket.
+    timeout: Duration,
+}
+
+impl RemoteAuthenticator {
+    /// Creates a new [`RemoteAuthenticator`] that connects to the signer listening on
+    /// `socket_path`.
+    pub fn new(socket_path: PathBuf) -> Self {
+        Self { socket_path, timeout: DEFAULT_TIMEOUT }
+    }
+
+    /// Sets the timeout used when reading from and writing to the signer socket.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends a request to the signer and waits for its response.
+    fn send_request(&self, request: &SignerRequest) -> io::Result<SignerResponse> {
+        let mut stream = UnixStream::connect(&self.socket_path)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        write_frame(&mut stream, &request.to_bytes())?;
+        let response = read_frame(&mut stream)?;
+
+        SignerResponse::read_from_bytes(&response)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+impl TransactionAuthenticator for RemoteAuthenticator {
+    /// Gets a signature over a message from the remote signer, given a public key.
+    ///
+    /// # Errors
+    /// If the signer doesn't hold the key, [`AuthenticationError::UnknownPublicKey`] is returned.
+    /// Connection failures and signer errors are returned as [`AuthenticationError::Other`].
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let request = SignerRequest::Sign {
+            pub_key,
+            message,
+            account_delta: account_delta.clone(),
+        };
+
+        let response = self.send_request(&request).map_err(|err| {
+            AuthenticationError::other(format!("error communicating with remote signer: {err}"))
+        })?;
+
+        match response {
+            SignerResponse::Signature(signature) => Ok(signature),
+            SignerResponse::UnknownPublicKey(pub_key) => {
+                Err(AuthenticationError::UnknownPublicKey(Digest::from(pub_key).into()))
+            },
+            SignerResponse::Error(message) => {
+                Err(AuthenticationError::other(format!("remote signer error: {message}")))
+            },
+        }
+    }
+}
+
+// SIGNER SERVER
+// ================================================================================================
+
+/// Accepts connections on `listener` and answers their signing requests with `authenticator`,
+/// one connection at a time.
+///
+/// Errors in individual connections are logged and don't stop the signer. This only returns if
+/// accepting a connection fails.
+pub fn serve_signer<A: TransactionAuthenticator>(
+    listener: &UnixListener,
+    authenticator: &A,
+) -> io::Result<()> {
+    loop {
+        let (mut stream, _) = listener.accept()?;
+        stream.set_read_timeout(Some(DEFAULT_TIMEOUT))?;
+        stream.set_write_timeout(Some(DEFAULT_TIMEOUT))?;
+
+        if let Err(err) = handle_signer_connection(&mut stream, authenticator) {
+            tracing::warn!("Error handling signer connection: {err}");
+        }
+    }
+}
+
+/// Reads a single request from `stream`, answers it with `authenticator` and writes the response
+/// back.
+pub fn handle_signer_connection<A: TransactionAuthenticator>(
+    stream: &mut UnixStream,
+    authenticator: &A,
+) -> io::Result<()> {
+    let request = read_frame(stream)?;
+    let response = match SignerRequest::read_from_bytes(&request) {
+        Ok(SignerRequest::Sign { pub_key, message, account_delta }) => {
+            match authenticator.get_signature(pub_key, message, &account_delta) {
+                Ok(signature) => SignerResponse::Signature(signature),
+                Err(AuthenticationError::UnknownPublicKey(_)) => {
+                    SignerResponse::UnknownPublicKey(pub_key)
+                },
+                Err(err) => SignerResponse::Error(err.to_string()),
+            }
+        },
+        Err(err) => SignerResponse::Error(format!("invalid request: {err}")),
+    };
+
+    write_frame(stream, &response.to_bytes())
+}
+
+// FRAMING HELPERS
+// ================================================================================================
+
+fn write_frame<W: Write>(writer: &mut W, body: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(body.len())
+        .ok()
+        .filter(|len| *len <= MAX_FRAME_SIZE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "frame is too big"))?;
+
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(body)?;
+    writer.flush()
+}
+
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len);
+    if len > MAX_FRAME_SIZE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame is too big"));
+    }
+
+    let mut body = vec![0u8; len as usize];
+    reader.read_exact(&mut body)?;
+    Ok(body)
+}
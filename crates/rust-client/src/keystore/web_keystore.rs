@@ -1,15 +1,18 @@
-use alloc::{string::ToString, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
 
 use miden_lib::utils::{Deserializable, Serializable};
 use rand::Rng;
 
-use super::KeyStoreError;
+use super::{KeyStore, KeyStoreError, public_key};
 use crate::{
     AuthenticationError, Felt, Word,
     account::AccountDelta,
     auth::{AuthSecretKey, TransactionAuthenticator},
     crypto::Digest,
-    store::web_store::account::utils::{get_account_auth_by_pub_key, insert_account_auth},
+    store::web_store::account::utils::{
+        get_account_auth_by_pub_key, get_account_auth_pub_keys, insert_account_auth,
+        remove_account_auth,
+    },
     utils::RwLock,
 };
 
@@ -27,20 +30,8 @@ impl<R: Rng> WebKeyStore<R> {
         WebKeyStore { rng: Arc::new(RwLock::new(rng)) }
     }
 
-    pub async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
-        let pub_key = match &key {
-            AuthSecretKey::RpoFalcon512(k) => Digest::from(Word::from(k.public_key())).to_hex(),
-        };
-        let secret_key_hex = hex::encode(key.to_bytes());
-
-        insert_account_auth(pub_key, secret_key_hex).await.map_err(|_| {
-            KeyStoreError::StorageError("Failed to insert item into local storage".to_string())
-        })?;
-
-        Ok(())
-    }
-
-    pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+    /// Retrieves a secret key from the local storage cache given its public key.
+    fn read_key(pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
         let pub_key_str = Digest::from(pub_key).to_hex();
         let secret_key_hex = get_account_auth_by_pub_key(pub_key_str).map_err(|_| {
             KeyStoreError::StorageError("Failed to get item from local storage".to_string())
@@ -58,6 +49,45 @@ impl<R: Rng> WebKeyStore<R> {
     }
 }
 
+#[async_trait::async_trait(?Send)]
+impl<R: Rng + Send + Sync> KeyStore for WebKeyStore<R> {
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let pub_key = Digest::from(public_key(key)).to_hex();
+        let secret_key_hex = hex::encode(key.to_bytes());
+
+        insert_account_auth(pub_key, secret_key_hex).await.map_err(|_| {
+            KeyStoreError::StorageError("Failed to insert item into local storage".to_string())
+        })?;
+
+        Ok(())
+    }
+
+    fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        Self::read_key(pub_key)
+    }
+
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        remove_account_auth(Digest::from(pub_key).to_hex()).await.map_err(|_| {
+            KeyStoreError::StorageError("Failed to remove item from local storage".to_string())
+        })
+    }
+
+    async fn get_pub_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        let pub_keys = get_account_auth_pub_keys().await.map_err(|_| {
+            KeyStoreError::StorageError("Failed to get items from local storage".to_string())
+        })?;
+
+        pub_keys
+            .iter()
+            .map(|pub_key| {
+                Digest::try_from(pub_key.as_str()).map(Word::from).map_err(|err| {
+                    KeyStoreError::DecodingError(format!("error decoding public key: {err:?}"))
+                })
+            })
+            .collect()
+    }
+}
+
 impl<R: Rng> TransactionAuthenticator for WebKeyStore<R> {
     /// Gets a signature over a message, given a public key.
     ///
@@ -73,9 +103,8 @@ impl<R: Rng> TransactionAuthenticator for WebKeyStore<R> {
         _account_delta: &AccountDelta,
     ) -> Result<Vec<Felt>, AuthenticationError> {
         let mut rng = self.rng.write();
-        let secret_key = self
-            .get_key(pub_key)
-            .map_err(|err| AuthenticationError::other(err.to_string()))?;
+        let secret_key =
+            Self::read_key(pub_key).map_err(|err| AuthenticationError::other(err.to_string()))?;
         let AuthSecretKey::RpoFalcon512(k) = secret_key
             .ok_or(AuthenticationError::UnknownPublicKey(Digest::from(pub_key).into()))?;
         miden_tx::auth::signatures::get_falcon_signature(&k, message, &mut *rng)
@@ -129,6 +129,7 @@ mod test_utils;
 pub mod tests;
 
 mod errors;
+mod verification;
 
 // RE-EXPORTS
 // ================================================================================================
@@ -173,7 +174,9 @@ pub mod crypto {
     };
 }
 
-pub use errors::{AuthenticationError, ClientError, IdPrefixFetchError};
+pub use errors::{
+    AuthenticationError, ChainDivergenceError, ClientError, IdPrefixFetchError, VerificationError,
+};
 pub use miden_objects::{Felt, ONE, StarkField, Word, ZERO};
 pub use miden_proving_service_client::proving_service::tx_prover::RemoteTransactionProver;
 
@@ -197,7 +200,7 @@ pub mod testing {
     pub use crate::test_utils::*;
 }
 
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 use miden_objects::crypto::rand::FeltRng;
 use miden_tx::{
@@ -206,8 +209,10 @@ use miden_tx::{
 };
 use rand::RngCore;
 use rpc::NodeRpcClient;
-use store::{Store, data_store::ClientDataStore};
+use store::{PruneSummary, RetentionPolicy, Store, StoreBackup, data_store::ClientDataStore};
+use sync::{BlockRetentionPolicy, FetchLimits, SyncListener};
 use tracing::info;
+use transaction::{TransactionPolicy, TransactionRetryPolicy};
 
 // MIDEN CLIENT
 // ================================================================================================
@@ -233,6 +238,9 @@ pub struct Client {
     tx_prover: Arc<LocalTransactionProver>,
     /// An instance of a [`TransactionExecutor`] that will be used to execute transactions.
     tx_executor: TransactionExecutor,
+    /// The data store used by the transaction executor, which holds the pending account states of
+    /// transaction batches.
+    data_store: Arc<ClientDataStore>,
     /// A MAST store, used to provide code inputs to the VM.
     mast_store: Arc<TransactionMastStore>,
     /// Flag to enable the debug mode for scripts compilation and execution.
@@ -242,6 +250,18 @@ pub struct Client {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// Listeners notified of the changes applied during syncs.
+    sync_listeners: Vec<Arc<dyn SyncListener>>,
+    /// Limits for the concurrent requests used to fetch public notes and accounts during syncs.
+    sync_fetch_limits: FetchLimits,
+    /// Whether the data received from the node is verified before being stored.
+    verified_mode: bool,
+    /// Policy deciding which block headers are kept in the store.
+    block_retention_policy: BlockRetentionPolicy,
+    /// Policy deciding when failed queued transactions are retried.
+    transaction_retry_policy: TransactionRetryPolicy,
+    /// Defaults applied to the transactions created by the client.
+    transaction_policy: TransactionPolicy,
 }
 
 /// Construction and access methods.
@@ -286,7 +306,7 @@ impl Client {
         let mast_store = client_data_store.mast_store();
 
         let authenticator = Some(authenticator);
-        let mut tx_executor = TransactionExecutor::new(client_data_store, authenticator);
+        let mut tx_executor = TransactionExecutor::new(client_data_store.clone(), authenticator);
         let tx_prover = Arc::new(LocalTransactionProver::default());
 
         if in_debug_mode {
@@ -300,10 +320,17 @@ impl Client {
             rpc_api,
             tx_prover,
             tx_executor,
+            data_store: client_data_store,
             in_debug_mode,
             tx_graceful_blocks,
             max_block_number_delta,
             mast_store,
+            sync_listeners: Vec::new(),
+            sync_fetch_limits: FetchLimits::default(),
+            verified_mode: false,
+            block_retention_policy: BlockRetentionPolicy::default(),
+            transaction_retry_policy: TransactionRetryPolicy::default(),
+            transaction_policy: TransactionPolicy::default(),
         }
     }
 
@@ -318,6 +345,42 @@ impl Client {
         &mut self.rng
     }
 
+    // STORE MAINTENANCE
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes the data that the client doesn't need anymore from the store, according to the
+    /// provided [`RetentionPolicy`], and returns a summary of the removed data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store doesn't support pruning or if the data couldn't be removed.
+    pub async fn prune_store(&self, policy: &RetentionPolicy) -> Result<PruneSummary, ClientError> {
+        Ok(self.store.prune(policy).await?)
+    }
+
+    /// Returns a [`StoreBackup`] with all the data tracked by the client.
+    ///
+    /// The backup can be serialized and later restored with [`Client::restore_store`], on a
+    /// client using the same or a different kind of store. Keys held by the client's
+    /// authenticator aren't part of the backup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data couldn't be read from the store.
+    pub async fn backup_store(&self) -> Result<StoreBackup, ClientError> {
+        Ok(self.store.export_backup().await?)
+    }
+
+    /// Replaces all the data tracked by the client with the contents of the provided
+    /// [`StoreBackup`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data couldn't be written to the store.
+    pub async fn restore_store(&self, backup: StoreBackup) -> Result<(), ClientError> {
+        Ok(self.store.import_backup(backup).await?)
+    }
+
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -42,6 +42,8 @@ impl Client {
     /// # Errors
     ///
     /// - If an attempt is made to overwrite a note that is currently processing.
+    /// - In verified mode, if the note's inclusion proof doesn't match its block, or if the block
+    ///   is ahead of the client's sync height (see [`Client::set_verified_mode`]).
     pub async fn import_note(&mut self, note_file: NoteFile) -> Result<NoteId, ClientError> {
         let id = match &note_file {
             NoteFile::NoteId(id) => *id,
@@ -65,6 +67,11 @@ impl Client {
                     .await?
             },
             NoteFile::NoteWithProof(note, inclusion_proof) => {
+                if self.verified_mode {
+                    self.verify_note_inclusion(note.id(), note.metadata(), &inclusion_proof)
+                        .await?;
+                }
+
                 self.import_note_record_by_proof(previous_note, note, inclusion_proof).await?
             },
         };
@@ -93,7 +100,7 @@ impl Client {
     /// - If the note doesn't exist on the node.
     /// - If the note exists but is private.
     async fn import_note_record_by_id(
-        &self,
+        &mut self,
         previous_note: Option<InputNoteRecord>,
         id: NoteId,
     ) -> Result<Option<InputNoteRecord>, ClientError> {
@@ -103,6 +110,10 @@ impl Client {
         })?;
 
         let inclusion_proof = fetched_note.inclusion_proof().clone();
+        if self.verified_mode {
+            self.verify_note_inclusion(id, fetched_note.metadata(), &inclusion_proof)
+                .await?;
+        }
 
         if let Some(mut previous_note) = previous_note {
             if previous_note.inclusion_proof_received(inclusion_proof, *fetched_note.metadata())? {
@@ -127,8 +127,11 @@ impl<'a> NoteScreener<'a> {
         let transaction_request =
             TransactionRequestBuilder::new().build_consume_notes(vec![note.id()])?;
 
-        let tx_script =
-            transaction_request.build_transaction_script(&AccountInterface::from(account), true)?;
+        let tx_script = transaction_request.build_transaction_script(
+            &AccountInterface::from(account),
+            None,
+            true,
+        )?;
 
         let tx_args = transaction_request.clone().into_transaction_args(tx_script, vec![]);
         let input_notes = InputNotes::new(vec![InputNote::unauthenticated(note.clone())])
@@ -1,7 +1,7 @@
 use alloc::collections::BTreeMap;
 
 use miden_objects::{
-    block::BlockHeader,
+    block::{BlockHeader, BlockNumber},
     note::{NoteId, NoteInclusionProof, Nullifier},
 };
 
@@ -262,6 +262,40 @@ impl NoteUpdateTracker {
         Ok(())
     }
 
+    /// Applies the necessary state transitions to the [`NoteUpdateTracker`] when a note is
+    /// committed in a block whose header isn't tracked by the client yet.
+    ///
+    /// The note's inclusion proof is stored, but input notes stay unverified until the block
+    /// header is received in a later sync.
+    pub(crate) fn apply_unverified_note_state_transitions(
+        &mut self,
+        committed_note: &CommittedNote,
+        public_note_data: Option<InputNoteRecord>,
+        block_num: BlockNumber,
+    ) -> Result<(), ClientError> {
+        let inclusion_proof = NoteInclusionProof::new(
+            block_num,
+            committed_note.note_index(),
+            committed_note.merkle_path().clone(),
+        )?;
+
+        if let Some(input_note_record) = public_note_data {
+            self.input_notes
+                .insert(input_note_record.id(), InputNoteUpdate::new_insert(input_note_record));
+        }
+
+        if let Some(input_note_record) = self.get_input_note_by_id(*committed_note.note_id()) {
+            input_note_record
+                .inclusion_proof_received(inclusion_proof.clone(), committed_note.metadata())?;
+        }
+
+        if let Some(output_note_record) = self.get_output_note_by_id(*committed_note.note_id()) {
+            output_note_record.inclusion_proof_received(inclusion_proof)?;
+        }
+
+        Ok(())
+    }
+
     /// Applies the necessary state transitions to the [`NoteUpdateTracker`] when a note is
     /// nullified in a block.
     ///
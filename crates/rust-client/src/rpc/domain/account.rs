@@ -51,6 +51,13 @@ impl FetchedAccount {
         }
     }
 
+    /// Returns the number of the block in which the account was last updated.
+    pub fn last_block_num(&self) -> BlockNumber {
+        match self {
+            Self::Private(_, summary) | Self::Public(_, summary) => summary.last_block_num.into(),
+        }
+    }
+
     // Returns the associated account if the account is public, otherwise none
     pub fn account(&self) -> Option<&Account> {
         match self {
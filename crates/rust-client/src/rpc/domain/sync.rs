@@ -16,6 +16,7 @@ use crate::rpc::{RpcError, generated::responses::SyncStateResponse};
 // ================================================================================================
 
 /// Represents a `SyncStateResponse` with fields converted into domain types.
+#[derive(Clone)]
 pub struct StateSyncInfo {
     /// The block number of the chain tip at the moment of the response.
     pub chain_tip: BlockNumber,
@@ -0,0 +1,386 @@
+// STORE BACKUP
+// ================================================================================================
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::num::NonZeroUsize;
+
+use miden_objects::{
+    Digest, Word,
+    account::Account,
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{InOrderIndex, MmrPeaks},
+};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{
+    InputNoteRecord, NoteFilter, OutputNoteRecord, PartialBlockchainFilter, Store, StoreError,
+    TransactionFilter,
+};
+use crate::{
+    sync::{NoteDiscoveryRule, NoteTagRecord},
+    transaction::{QueuedTransaction, TransactionRecord},
+};
+
+/// Bytes at the start of every serialized [`StoreBackup`], used to tell backups apart from other
+/// files.
+const BACKUP_MAGIC: [u8; 4] = *b"MCSB";
+
+/// Latest version of the [`StoreBackup`] serialization format.
+///
+/// The version is bumped whenever the format changes. Backups written with previous versions can
+/// still be read.
+pub const STORE_BACKUP_VERSION: u16 = 3;
+
+/// A portable snapshot of all the data tracked by a [`Store`].
+///
+/// Backups don't depend on the store they were exported from, so they can be used to move the
+/// client's data between different store implementations (for example, from a `SqliteStore` to a
+/// `WebStore`) or between client versions. They're created with [`Store::export_backup`] and
+/// restored with [`Store::import_backup`].
+///
+/// Cached foreign account code isn't part of the backup, as it's fetched again from the node when
+/// needed. Keys used to sign transactions aren't kept in the store and have to be backed up
+/// separately.
+#[derive(Debug, Clone)]
+pub struct StoreBackup {
+    /// Every stored state of every tracked account.
+    pub accounts: Vec<AccountStateBackup>,
+    /// Tracked input notes.
+    pub input_notes: Vec<InputNoteRecord>,
+    /// Tracked output notes.
+    pub output_notes: Vec<OutputNoteRecord>,
+    /// Note tags used when syncing with the node.
+    pub tags: Vec<NoteTagRecord>,
+    /// Note discovery rules used when syncing with the node. Empty for backups written with
+    /// version 1 of the format.
+    pub note_discovery_rules: Vec<NoteDiscoveryRule>,
+    /// Tracked transactions.
+    pub transactions: Vec<TransactionRecord>,
+    /// Outbound queue of transactions being proven and submitted. Empty for backups written with
+    /// versions 1 and 2 of the format.
+    pub queued_transactions: Vec<QueuedTransaction>,
+    /// Stored block headers along with the partial blockchain peaks at each of them.
+    pub block_headers: Vec<BlockHeaderBackup>,
+    /// Authentication nodes of the partial blockchain.
+    pub partial_blockchain_nodes: Vec<(InOrderIndex, Digest)>,
+    /// Block number of the last sync.
+    pub sync_height: BlockNumber,
+}
+
+impl StoreBackup {
+    /// Builds a backup from the data returned by the store.
+    pub(crate) async fn from_store<S: Store + ?Sized>(store: &S) -> Result<Self, StoreError> {
+        let mut accounts = Vec::new();
+        for account_id in store.get_account_ids().await? {
+            for record in store.get_account_history(account_id).await? {
+                accounts.push(AccountStateBackup {
+                    seed: record.seed().copied(),
+                    locked: record.is_locked(),
+                    account: record.account().clone(),
+                });
+            }
+        }
+
+        // Besides the headers of blocks with relevant notes, the store always keeps the genesis
+        // block and the block of the last sync
+        let sync_height = store.get_sync_height().await?;
+        let mut block_numbers: BTreeSet<BlockNumber> = store
+            .get_tracked_block_headers()
+            .await?
+            .iter()
+            .map(BlockHeader::block_num)
+            .collect();
+        block_numbers.insert(BlockNumber::GENESIS);
+        block_numbers.insert(sync_height);
+
+        let mut block_headers = Vec::new();
+        for (header, has_client_notes) in store.get_block_headers(&block_numbers).await? {
+            let partial_blockchain_peaks =
+                store.get_partial_blockchain_peaks_by_block_num(header.block_num()).await?;
+            block_headers.push(BlockHeaderBackup {
+                header,
+                partial_blockchain_peaks,
+                has_client_notes,
+            });
+        }
+
+        Ok(Self {
+            accounts,
+            input_notes: store.get_input_notes(NoteFilter::All).await?,
+            output_notes: store.get_output_notes(NoteFilter::All).await?,
+            tags: store.get_note_tags().await?,
+            note_discovery_rules: store.get_note_discovery_rules().await?,
+            transactions: store.get_transactions(TransactionFilter::All).await?,
+            queued_transactions: store.get_queued_transactions().await?,
+            block_headers,
+            partial_blockchain_nodes: store
+                .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+                .await?
+                .into_iter()
+                .collect(),
+            sync_height,
+        })
+    }
+}
+
+impl Serializable for StoreBackup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&BACKUP_MAGIC);
+        target.write_u16(STORE_BACKUP_VERSION);
+
+        self.accounts.write_into(target);
+        self.input_notes.write_into(target);
+        self.output_notes.write_into(target);
+        self.tags.write_into(target);
+        self.transactions.write_into(target);
+        self.block_headers.write_into(target);
+
+        target.write_usize(self.partial_blockchain_nodes.len());
+        for (index, node) in &self.partial_blockchain_nodes {
+            target.write_u64(index.inner());
+            node.write_into(target);
+        }
+
+        self.sync_height.write_into(target);
+
+        // Added in version 2
+        self.note_discovery_rules.write_into(target);
+
+        // Added in version 3
+        self.queued_transactions.write_into(target);
+    }
+}
+
+impl Deserializable for StoreBackup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic: [u8; 4] = source.read_array()?;
+        if magic != BACKUP_MAGIC {
+            return Err(DeserializationError::InvalidValue(
+                "the data is not a store backup".to_string(),
+            ));
+        }
+
+        let version = source.read_u16()?;
+        if version == 0 || version > STORE_BACKUP_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported store backup version {version}, the latest supported version is \
+                 {STORE_BACKUP_VERSION}"
+            )));
+        }
+
+        let accounts = Vec::<AccountStateBackup>::read_from(source)?;
+        let input_notes = Vec::<InputNoteRecord>::read_from(source)?;
+        let output_notes = Vec::<OutputNoteRecord>::read_from(source)?;
+        let tags = Vec::<NoteTagRecord>::read_from(source)?;
+        let transactions = Vec::<TransactionRecord>::read_from(source)?;
+        let block_headers = Vec::<BlockHeaderBackup>::read_from(source)?;
+
+        let num_nodes = source.read_usize()?;
+        let mut partial_blockchain_nodes = Vec::with_capacity(num_nodes);
+        for _ in 0..num_nodes {
+            let index = read_in_order_index(source)?;
+            let node = Digest::read_from(source)?;
+            partial_blockchain_nodes.push((index, node));
+        }
+
+        let sync_height = BlockNumber::read_from(source)?;
+
+        let note_discovery_rules = if version >= 2 {
+            Vec::<NoteDiscoveryRule>::read_from(source)?
+        } else {
+            Vec::new()
+        };
+
+        let queued_transactions = if version >= 3 {
+            Vec::<QueuedTransaction>::read_from(source)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            accounts,
+            input_notes,
+            output_notes,
+            tags,
+            note_discovery_rules,
+            transactions,
+            queued_transactions,
+            block_headers,
+            partial_blockchain_nodes,
+            sync_height,
+        })
+    }
+}
+
+// ACCOUNT STATE BACKUP
+// ================================================================================================
+
+/// A single stored state of an account.
+#[derive(Debug, Clone)]
+pub struct AccountStateBackup {
+    /// The account at this state.
+    pub account: Account,
+    /// Seed used to create the account, if the account is new.
+    pub seed: Option<Word>,
+    /// Whether the account is locked because of a mismatch with the network state.
+    pub locked: bool,
+}
+
+impl Serializable for AccountStateBackup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account.write_into(target);
+        self.seed.write_into(target);
+        self.locked.write_into(target);
+    }
+}
+
+impl Deserializable for AccountStateBackup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account = Account::read_from(source)?;
+        let seed = Option::<Word>::read_from(source)?;
+        let locked = bool::read_from(source)?;
+
+        Ok(Self { account, seed, locked })
+    }
+}
+
+// BLOCK HEADER BACKUP
+// ================================================================================================
+
+/// A stored block header along with the partial blockchain peaks at its height.
+#[derive(Debug, Clone)]
+pub struct BlockHeaderBackup {
+    /// The block header.
+    pub header: BlockHeader,
+    /// Peaks of the partial blockchain at the block's height.
+    pub partial_blockchain_peaks: MmrPeaks,
+    /// Whether the block contains notes relevant to the client.
+    pub has_client_notes: bool,
+}
+
+impl Serializable for BlockHeaderBackup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.header.write_into(target);
+        target.write_usize(self.partial_blockchain_peaks.num_leaves());
+        self.partial_blockchain_peaks.peaks().to_vec().write_into(target);
+        self.has_client_notes.write_into(target);
+    }
+}
+
+impl Deserializable for BlockHeaderBackup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let header = BlockHeader::read_from(source)?;
+        let num_leaves = source.read_usize()?;
+        let peaks = Vec::<Digest>::read_from(source)?;
+        let partial_blockchain_peaks = MmrPeaks::new(num_leaves, peaks)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        let has_client_notes = bool::read_from(source)?;
+
+        Ok(Self {
+            header,
+            partial_blockchain_peaks,
+            has_client_notes,
+        })
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+fn read_in_order_index<R: ByteReader>(
+    source: &mut R,
+) -> Result<InOrderIndex, DeserializationError> {
+    let index = source.read_u64()?;
+    usize::try_from(index)
+        .ok()
+        .and_then(NonZeroUsize::new)
+        .map(InOrderIndex::new)
+        .ok_or_else(|| {
+            DeserializationError::InvalidValue(String::from(
+                "invalid partial blockchain node index",
+            ))
+        })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use miden_objects::block::BlockNumber;
+    use miden_tx::utils::{Deserializable, DeserializationError, Serializable};
+
+    use super::{BACKUP_MAGIC, STORE_BACKUP_VERSION, StoreBackup};
+    use crate::{sync::NoteDiscoveryRule, transaction::QueuedTransaction};
+
+    fn empty_backup() -> StoreBackup {
+        StoreBackup {
+            accounts: vec![],
+            input_notes: vec![],
+            output_notes: vec![],
+            tags: vec![],
+            note_discovery_rules: vec![],
+            transactions: vec![],
+            queued_transactions: vec![],
+            block_headers: vec![],
+            partial_blockchain_nodes: vec![],
+            sync_height: BlockNumber::from(7),
+        }
+    }
+
+    #[test]
+    fn backup_starts_with_magic_and_version() {
+        let bytes = empty_backup().to_bytes();
+        assert_eq!(bytes[..4], BACKUP_MAGIC);
+        assert_eq!(u16::from_le_bytes([bytes[4], bytes[5]]), STORE_BACKUP_VERSION);
+
+        let backup = StoreBackup::read_from_bytes(&bytes).unwrap();
+        assert_eq!(backup.sync_height, BlockNumber::from(7));
+    }
+
+    #[test]
+    fn version_1_backups_are_read_without_discovery_rules() {
+        // Version 1 backups end right after the sync height
+        let mut bytes = empty_backup().to_bytes();
+        bytes.truncate(
+            bytes.len()
+                - Vec::<NoteDiscoveryRule>::new().to_bytes().len()
+                - Vec::<QueuedTransaction>::new().to_bytes().len(),
+        );
+        bytes[4..6].copy_from_slice(&1u16.to_le_bytes());
+
+        let backup = StoreBackup::read_from_bytes(&bytes).unwrap();
+        assert_eq!(backup.sync_height, BlockNumber::from(7));
+        assert!(backup.note_discovery_rules.is_empty());
+    }
+
+    #[test]
+    fn version_2_backups_are_read_without_queued_transactions() {
+        // Version 2 backups end right after the note discovery rules
+        let mut bytes = empty_backup().to_bytes();
+        bytes.truncate(bytes.len() - Vec::<QueuedTransaction>::new().to_bytes().len());
+        bytes[4..6].copy_from_slice(&2u16.to_le_bytes());
+
+        let backup = StoreBackup::read_from_bytes(&bytes).unwrap();
+        assert_eq!(backup.sync_height, BlockNumber::from(7));
+        assert!(backup.queued_transactions.is_empty());
+    }
+
+    #[test]
+    fn newer_backup_versions_are_rejected() {
+        let mut bytes = empty_backup().to_bytes();
+        bytes[4..6].copy_from_slice(&(STORE_BACKUP_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            StoreBackup::read_from_bytes(&bytes),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+
+        bytes[0] = 0;
+        assert!(StoreBackup::read_from_bytes(&bytes).is_err());
+    }
+}
@@ -1,4 +1,9 @@
-use alloc::{boxed::Box, collections::BTreeSet, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
 
 use miden_objects::{
     Digest, MastForest, Word,
@@ -7,7 +12,9 @@ use miden_objects::{
     crypto::merkle::{InOrderIndex, MerklePath, PartialMmr},
     transaction::PartialBlockchain,
 };
-use miden_tx::{DataStore, DataStoreError, MastForestStore, TransactionMastStore};
+use miden_tx::{
+    DataStore, DataStoreError, MastForestStore, TransactionMastStore, utils::sync::RwLock,
+};
 
 use super::{PartialBlockchainFilter, Store};
 use crate::store::StoreError;
@@ -21,6 +28,10 @@ pub(crate) struct ClientDataStore {
     store: alloc::sync::Arc<dyn Store>,
     /// Store used to provide MAST nodes to the transaction executor.
     transaction_mast_store: Arc<TransactionMastStore>,
+    /// Account states resulting from executed transactions that weren't applied to the store yet.
+    /// Transactions are executed against these states instead of the stored ones, which allows
+    /// chaining transactions in a batch.
+    pending_accounts: RwLock<BTreeMap<AccountId, Account>>,
 }
 
 impl ClientDataStore {
@@ -28,12 +39,49 @@ impl ClientDataStore {
         Self {
             store,
             transaction_mast_store: Arc::new(TransactionMastStore::new()),
+            pending_accounts: RwLock::new(BTreeMap::new()),
         }
     }
 
     pub fn mast_store(&self) -> Arc<TransactionMastStore> {
         self.transaction_mast_store.clone()
     }
+
+    /// Returns the pending state of the account, if a transaction against it was executed but not
+    /// applied to the store yet.
+    pub fn pending_account(&self, account_id: AccountId) -> Option<Account> {
+        self.pending_accounts.read().get(&account_id).cloned()
+    }
+
+    /// Returns a guard that sets pending account states, and removes all of them when it's
+    /// dropped, so transactions are executed against the stored states again.
+    pub fn pending_accounts_guard(self: &Arc<Self>) -> PendingAccountsGuard {
+        PendingAccountsGuard { data_store: self.clone() }
+    }
+}
+
+// PENDING ACCOUNTS GUARD
+// ================================================================================================
+
+/// Sets the pending account states of a [`ClientDataStore`] and clears them when dropped.
+///
+/// Dropping the guard clears the states even if the future that set them is dropped or returns
+/// early, so they never outlive the batch they belong to.
+pub(crate) struct PendingAccountsGuard {
+    data_store: Arc<ClientDataStore>,
+}
+
+impl PendingAccountsGuard {
+    /// Sets the state that the next transactions against the account are executed against.
+    pub fn set_pending_account(&self, account: Account) {
+        self.data_store.pending_accounts.write().insert(account.id(), account);
+    }
+}
+
+impl Drop for PendingAccountsGuard {
+    fn drop(&mut self) {
+        self.data_store.pending_accounts.write().clear();
+    }
 }
 
 #[async_trait::async_trait(?Send)]
@@ -46,15 +94,20 @@ impl DataStore for ClientDataStore {
         // Pop last block, used as reference (it does not need to be authenticated manually)
         let ref_block = block_refs.pop_last().ok_or(DataStoreError::other("Block set is empty"))?;
 
-        // Construct Account
-        let account_record = self
-            .store
-            .get_account(account_id)
-            .await?
-            .ok_or(DataStoreError::AccountNotFound(account_id))?;
-
-        let seed = account_record.seed().copied();
-        let account: Account = account_record.into();
+        // Construct Account. Pending states belong to accounts that already executed a
+        // transaction, so they aren't new and don't need a seed
+        let (account, seed) = if let Some(account) = self.pending_account(account_id) {
+            (account, None)
+        } else {
+            let account_record = self
+                .store
+                .get_account(account_id)
+                .await?
+                .ok_or(DataStoreError::AccountNotFound(account_id))?;
+
+            let seed = account_record.seed().copied();
+            (account_record.into(), seed)
+        };
 
         // If the account is new, add its anchor block to partial MMR
         if seed.is_some() {
@@ -3,6 +3,7 @@ use alloc::string::String;
 use miden_objects::{
     AccountError, AccountIdError, AssetVaultError, Digest, NoteError, TransactionScriptError,
     account::AccountId,
+    block::BlockNumber,
     crypto::merkle::MmrError,
     utils::{DeserializationError, HexParseError},
 };
@@ -36,6 +37,8 @@ pub enum StoreError {
     AccountKeyNotFound(String),
     #[error("account storage data with root {0} not found")]
     AccountStorageNotFound(Digest),
+    #[error("block header for block {0} not found")]
+    BlockHeaderNotFound(BlockNumber),
     #[error("partial blockchain node at index {0} not found")]
     PartialBlockchainNodeNotFound(u64),
     #[error("error deserializing data from the store")]
@@ -54,8 +57,12 @@ pub enum StoreError {
     NoteTagAlreadyTracked(u64),
     #[error("failed to parse data retrieved from the database: {0}")]
     ParsingError(String),
+    #[error("the store doesn't support pruning")]
+    PruningNotSupported,
     #[error("failed to retrieve data from the database: {0}")]
     QueryError(String),
+    #[error("the store doesn't support tenants")]
+    TenantsNotSupported,
     #[error("error instantiating transaction script")]
     TransactionScriptError(#[from] TransactionScriptError),
     #[error("account vault data for root {0} not found")]
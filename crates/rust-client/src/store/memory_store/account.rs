@@ -0,0 +1,220 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use miden_objects::{
+    Digest, Word,
+    account::{Account, AccountCode, AccountHeader, AccountId},
+};
+
+use super::MemoryStoreState;
+use crate::store::{AccountRecord, AccountStateBackup, AccountStatus, StoreError};
+
+// ACCOUNT STATE
+// ================================================================================================
+
+/// A single stored state of an account.
+pub(super) struct AccountState {
+    account: Account,
+    /// Seed used to create the account. Only set for the state the account was inserted with.
+    seed: Option<Word>,
+    locked: bool,
+}
+
+impl AccountState {
+    fn status(&self) -> AccountStatus {
+        match (self.seed, self.locked) {
+            (_, true) => AccountStatus::Locked,
+            (Some(seed), _) => AccountStatus::New { seed },
+            _ => AccountStatus::Tracked,
+        }
+    }
+}
+
+impl MemoryStoreState {
+    pub(super) fn get_account_ids(&self) -> Vec<AccountId> {
+        let mut account_ids = Vec::new();
+        for state in &self.accounts {
+            if !account_ids.contains(&state.account.id()) {
+                account_ids.push(state.account.id());
+            }
+        }
+        account_ids
+    }
+
+    pub(super) fn get_account_headers(&self) -> Vec<(AccountHeader, AccountStatus)> {
+        self.get_account_ids()
+            .into_iter()
+            .filter_map(|account_id| self.get_account_header(account_id))
+            .collect()
+    }
+
+    pub(super) fn get_account_header(
+        &self,
+        account_id: AccountId,
+    ) -> Option<(AccountHeader, AccountStatus)> {
+        self.latest_account_state(account_id)
+            .map(|state| (AccountHeader::from(&state.account), state.status()))
+    }
+
+    pub(super) fn get_account_header_by_commitment(
+        &self,
+        account_commitment: Digest,
+    ) -> Option<AccountHeader> {
+        self.accounts
+            .iter()
+            .find(|state| state.account.commitment() == account_commitment)
+            .map(|state| AccountHeader::from(&state.account))
+    }
+
+    pub(super) fn get_account(&self, account_id: AccountId) -> Option<AccountRecord> {
+        self.latest_account_state(account_id)
+            .map(|state| AccountRecord::new(state.account.clone(), state.status()))
+    }
+
+    pub(super) fn get_account_history(&self, account_id: AccountId) -> Vec<AccountRecord> {
+        let mut states: Vec<&AccountState> =
+            self.accounts.iter().filter(|state| state.account.id() == account_id).collect();
+        states.sort_by_key(|state| state.account.nonce().as_int());
+
+        states
+            .into_iter()
+            .map(|state| AccountRecord::new(state.account.clone(), state.status()))
+            .collect()
+    }
+
+    pub(super) fn insert_account(&mut self, account: &Account, account_seed: Option<Word>) {
+        self.insert_account_state(account, account_seed);
+    }
+
+    /// Updates an existing account with a new state.
+    ///
+    /// Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
+    pub(super) fn update_existing_account(
+        &mut self,
+        new_account_state: &Account,
+    ) -> Result<(), StoreError> {
+        if self.latest_account_state(new_account_state.id()).is_none() {
+            return Err(StoreError::AccountDataNotFound(new_account_state.id()));
+        }
+
+        self.update_account(new_account_state);
+        Ok(())
+    }
+
+    pub(super) fn get_foreign_account_code(
+        &self,
+        account_ids: &[AccountId],
+    ) -> BTreeMap<AccountId, AccountCode> {
+        account_ids
+            .iter()
+            .filter_map(|account_id| {
+                self.foreign_account_code
+                    .get(account_id)
+                    .map(|code| (*account_id, code.clone()))
+            })
+            .collect()
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Stores a new state for the account without checking whether the account is tracked.
+    pub(super) fn update_account(&mut self, new_account_state: &Account) {
+        self.insert_account_state(new_account_state, None);
+    }
+
+    /// Locks the account if the mismatched digest doesn't belong to a previous account state
+    /// (stale data).
+    pub(super) fn lock_account_on_unexpected_commitment(
+        &mut self,
+        account_id: &AccountId,
+        mismatched_digest: &Digest,
+    ) {
+        // Mismatched digests may be due to stale network data. If the mismatched digest is
+        // tracked and corresponds to the mismatched account, it means we got a past update and
+        // shouldn't lock the account.
+        let is_known_state = self.accounts.iter().any(|state| {
+            state.account.id() == *account_id && state.account.commitment() == *mismatched_digest
+        });
+
+        if !is_known_state {
+            for state in self.accounts.iter_mut().filter(|state| state.account.id() == *account_id)
+            {
+                state.locked = true;
+            }
+        }
+    }
+
+    /// Removes the account states that match the provided commitments.
+    pub(super) fn undo_account_state(&mut self, account_commitments: &[Digest]) {
+        self.accounts
+            .retain(|state| !account_commitments.contains(&state.account.commitment()));
+    }
+
+    /// Removes the states of the account except for the `states_to_keep` ones with the highest
+    /// nonce. Returns the number of removed states.
+    pub(super) fn remove_old_account_states(
+        &mut self,
+        account_id: AccountId,
+        states_to_keep: usize,
+    ) -> usize {
+        let mut nonces: Vec<u64> = self
+            .accounts
+            .iter()
+            .filter(|state| state.account.id() == account_id)
+            .map(|state| state.account.nonce().as_int())
+            .collect();
+        if nonces.len() <= states_to_keep {
+            return 0;
+        }
+
+        nonces.sort_unstable();
+        let min_kept_nonce = nonces[nonces.len() - states_to_keep];
+
+        let previous_len = self.accounts.len();
+        self.accounts.retain(|state| {
+            state.account.id() != account_id || state.account.nonce().as_int() >= min_kept_nonce
+        });
+        previous_len - self.accounts.len()
+    }
+
+    /// Stores an account state restored from a backup, keeping its seed and lock status.
+    pub(super) fn restore_account_state(&mut self, account_state: AccountStateBackup) {
+        let AccountStateBackup { account, seed, locked } = account_state;
+        self.insert_account_state(&account, seed);
+        if locked {
+            for state in self
+                .accounts
+                .iter_mut()
+                .filter(|state| state.account.commitment() == account.commitment())
+            {
+                state.locked = true;
+            }
+        }
+    }
+
+    /// Returns the state with the highest nonce for the provided account.
+    fn latest_account_state(&self, account_id: AccountId) -> Option<&AccountState> {
+        self.accounts
+            .iter()
+            .filter(|state| state.account.id() == account_id)
+            .max_by_key(|state| state.account.nonce().as_int())
+    }
+
+    /// Stores the provided account state, replacing any previous state with the same commitment.
+    fn insert_account_state(&mut self, account: &Account, account_seed: Option<Word>) {
+        let new_state = AccountState {
+            account: account.clone(),
+            seed: account_seed,
+            locked: false,
+        };
+
+        match self
+            .accounts
+            .iter_mut()
+            .find(|state| state.account.commitment() == account.commitment())
+        {
+            Some(state) => *state = new_state,
+            None => self.accounts.push(new_state),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use super::MemoryStoreState;
+use crate::store::StoreBackup;
+
+impl MemoryStoreState {
+    /// Returns a state holding the contents of the backup.
+    pub(super) fn from_backup(backup: StoreBackup) -> Self {
+        let StoreBackup {
+            accounts,
+            input_notes,
+            output_notes,
+            tags,
+            note_discovery_rules,
+            transactions,
+            queued_transactions,
+            block_headers,
+            partial_blockchain_nodes,
+            sync_height,
+        } = backup;
+
+        let mut state = Self {
+            transactions,
+            queued_transactions,
+            input_notes,
+            output_notes,
+            block_headers: block_headers
+                .into_iter()
+                .map(|block| {
+                    (
+                        block.header.block_num(),
+                        (block.header, block.partial_blockchain_peaks, block.has_client_notes),
+                    )
+                })
+                .collect(),
+            partial_blockchain_nodes: partial_blockchain_nodes.into_iter().collect(),
+            tags,
+            note_discovery_rules: note_discovery_rules.into_iter().collect(),
+            sync_height,
+            ..Default::default()
+        };
+
+        for account_state in accounts {
+            state.restore_account_state(account_state);
+        }
+
+        state
+    }
+}
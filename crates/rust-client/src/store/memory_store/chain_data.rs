@@ -0,0 +1,118 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest,
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{InOrderIndex, MmrPeaks},
+};
+
+use super::MemoryStoreState;
+use crate::{
+    store::{PartialBlockchainFilter, StoreError},
+    sync::{BlockRetentionPolicy, stale_authentication_nodes},
+};
+
+impl MemoryStoreState {
+    /// Inserts a block header alongside the partial blockchain peaks at its height.
+    ///
+    /// If the block header exists and `has_client_notes` is `true` then the `has_client_notes`
+    /// flag is updated to `true` to signify that the block now contains a relevant note.
+    pub(super) fn insert_block_header(
+        &mut self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: MmrPeaks,
+        has_client_notes: bool,
+    ) {
+        self.block_headers
+            .entry(block_header.block_num())
+            .or_insert_with(|| (block_header.clone(), partial_blockchain_peaks, has_client_notes));
+
+        self.set_block_header_has_client_notes(block_header.block_num(), has_client_notes);
+    }
+
+    pub(super) fn get_block_headers(
+        &self,
+        block_numbers: &BTreeSet<BlockNumber>,
+    ) -> Vec<(BlockHeader, bool)> {
+        block_numbers
+            .iter()
+            .filter_map(|block_num| self.block_headers.get(block_num))
+            .map(|(header, _, has_client_notes)| (header.clone(), *has_client_notes))
+            .collect()
+    }
+
+    pub(super) fn get_tracked_block_headers(&self) -> Vec<BlockHeader> {
+        self.block_headers
+            .values()
+            .filter(|(_, _, has_client_notes)| *has_client_notes)
+            .map(|(header, ..)| header.clone())
+            .collect()
+    }
+
+    pub(super) fn get_partial_blockchain_nodes(
+        &self,
+        filter: &PartialBlockchainFilter,
+    ) -> BTreeMap<InOrderIndex, Digest> {
+        match filter {
+            PartialBlockchainFilter::All => self.partial_blockchain_nodes.clone(),
+            PartialBlockchainFilter::List(ids) => ids
+                .iter()
+                .filter_map(|id| self.partial_blockchain_nodes.get(id).map(|node| (*id, *node)))
+                .collect(),
+        }
+    }
+
+    pub(super) fn get_partial_blockchain_peaks_by_block_num(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<MmrPeaks, StoreError> {
+        match self.block_headers.get(&block_num) {
+            Some((_, partial_blockchain_peaks, _)) => Ok(partial_blockchain_peaks.clone()),
+            None => Ok(MmrPeaks::new(0, vec![])?),
+        }
+    }
+
+    /// Inserts a list of MMR authentication nodes. Nodes that are already stored are ignored.
+    pub(super) fn insert_partial_blockchain_nodes(&mut self, nodes: &[(InOrderIndex, Digest)]) {
+        for (index, node) in nodes {
+            self.partial_blockchain_nodes.entry(*index).or_insert(*node);
+        }
+    }
+
+    /// Removes the block headers that the policy doesn't keep at the current sync height, along
+    /// with the partial blockchain nodes that were only needed to authenticate them.
+    pub(super) fn prune_block_headers(&mut self, policy: &BlockRetentionPolicy) {
+        let sync_height = self.sync_height;
+        let mut pruned_blocks = Vec::new();
+        self.block_headers.retain(|block_num, (_, _, has_client_notes)| {
+            let retained = policy.retains(*block_num, *has_client_notes, sync_height);
+            if !retained {
+                pruned_blocks.push(*block_num);
+            }
+            retained
+        });
+
+        let stored_nodes = self.partial_blockchain_nodes.keys().copied().collect();
+        for index in stale_authentication_nodes(&pruned_blocks, stored_nodes) {
+            self.partial_blockchain_nodes.remove(&index);
+        }
+    }
+
+    /// Marks the block as containing client notes. Blocks that are already marked, or that aren't
+    /// stored, are left untouched.
+    pub(super) fn set_block_header_has_client_notes(
+        &mut self,
+        block_num: BlockNumber,
+        has_client_notes: bool,
+    ) {
+        if let Some((_, _, stored_has_client_notes)) = self.block_headers.get_mut(&block_num) {
+            // Only update to change has_client_notes to true if it was false previously
+            if !*stored_has_client_notes {
+                *stored_has_client_notes = has_client_notes;
+            }
+        }
+    }
+}
@@ -0,0 +1,485 @@
+//! This module provides an in-memory implementation of the [Store] trait.
+//!
+//! [`MemoryStore`] keeps accounts, transactions, notes, block headers, and MMR nodes in memory, so
+//! its contents are lost once it's dropped. It doesn't require a filesystem or a database, which
+//! makes it suitable for tests and for ephemeral or embedded clients.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest, Word,
+    account::{Account, AccountCode, AccountHeader, AccountId},
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{InOrderIndex, MmrPeaks},
+    note::{NoteId, NoteTag, Nullifier},
+    transaction::TransactionId,
+};
+use miden_tx::utils::sync::RwLock;
+
+use super::{
+    AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, OutputNoteRecord,
+    PartialBlockchainFilter, PruneSummary, RetentionPolicy, Store, StoreBackup, TenantId,
+    TransactionFilter,
+};
+use crate::{
+    store::StoreError,
+    sync::{
+        BlockRetentionPolicy, ChainRollback, NoteDiscoveryRule, NoteTagRecord, StateSyncUpdate,
+    },
+    transaction::{QueuedTransaction, TransactionRecord, TransactionStoreUpdate},
+};
+
+mod account;
+mod backup;
+mod chain_data;
+mod note;
+mod prune;
+mod sync;
+mod transaction;
+
+use account::AccountState;
+
+// MEMORY STORE
+// ================================================================================================
+
+/// A [Store] that keeps all of the client's data in memory.
+///
+/// All data is guarded by a single lock, so every update is applied atomically with respect to
+/// other readers and writers.
+///
+/// Tenants (see [`Store::with_tenant`]) are kept in separate states that share the same store
+/// instance.
+///
+/// The store doesn't read the system time on its own, so it can be used on targets without a
+/// clock. Timestamps are only tracked if a clock is provided with [`MemoryStore::with_clock`].
+#[derive(Default)]
+pub struct MemoryStore {
+    state: Arc<RwLock<MemoryStoreState>>,
+    /// States of the tenants created from this store or any of its tenant handles.
+    tenants: Arc<RwLock<BTreeMap<TenantId, Arc<RwLock<MemoryStoreState>>>>>,
+    /// Source of the timestamps returned by [`Store::get_current_timestamp`], shared with the
+    /// tenant handles.
+    clock: Option<Arc<StoreClock>>,
+}
+
+/// A function returning the current time, measured in non-leap seconds since Unix epoch.
+pub type StoreClock = dyn Fn() -> u64 + Send + Sync;
+
+impl MemoryStore {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new, empty instance of [`MemoryStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the clock used to timestamp the store's data and returns the updated store.
+    ///
+    /// Without a clock, [`Store::get_current_timestamp`] returns `None`.
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+}
+
+/// Data tracked by a [`MemoryStore`].
+///
+/// The collections mirror the tables used by the database-backed stores. Entity specific methods
+/// are grouped by entity types in their own sub-modules.
+#[derive(Default)]
+struct MemoryStoreState {
+    /// Every stored account state, in insertion order.
+    accounts: Vec<AccountState>,
+    /// Cached code of foreign accounts.
+    foreign_account_code: BTreeMap<AccountId, AccountCode>,
+    /// Tracked transactions, in insertion order.
+    transactions: Vec<TransactionRecord>,
+    /// Outbound transaction queue, in insertion order.
+    queued_transactions: Vec<QueuedTransaction>,
+    /// Tracked input notes, in insertion order.
+    input_notes: Vec<InputNoteRecord>,
+    /// Tracked output notes, in insertion order.
+    output_notes: Vec<OutputNoteRecord>,
+    /// Stored block headers along with the partial blockchain peaks at each block and whether the
+    /// block contains notes relevant to the client.
+    block_headers: BTreeMap<BlockNumber, (BlockHeader, MmrPeaks, bool)>,
+    /// Partial blockchain authentication nodes.
+    partial_blockchain_nodes: BTreeMap<InOrderIndex, Digest>,
+    /// Note tags the client is interested in.
+    tags: Vec<NoteTagRecord>,
+    /// Note discovery rules the client is interested in.
+    note_discovery_rules: BTreeSet<NoteDiscoveryRule>,
+    /// Notes found by the note discovery rules that weren't imported yet.
+    discovered_notes: BTreeSet<NoteId>,
+    /// Block number of the last state sync.
+    sync_height: BlockNumber,
+}
+
+// In-memory implementation of the Store trait
+//
+// All implementations rely on inner MemoryStoreState functions that map 1:1 by name, grouped by
+// entity types in their own sub-modules.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Store for MemoryStore {
+    fn get_current_timestamp(&self) -> Option<u64> {
+        self.clock.as_ref().map(|clock| clock())
+    }
+
+    async fn get_note_tags(&self) -> Result<Vec<NoteTagRecord>, StoreError> {
+        Ok(self.state.read().get_note_tags())
+    }
+
+    async fn get_unique_note_tags(&self) -> Result<BTreeSet<NoteTag>, StoreError> {
+        Ok(self.state.read().get_unique_note_tags())
+    }
+
+    async fn add_note_tag(&self, tag: NoteTagRecord) -> Result<bool, StoreError> {
+        Ok(self.state.write().add_note_tag(tag))
+    }
+
+    async fn remove_note_tag(&self, tag: NoteTagRecord) -> Result<usize, StoreError> {
+        Ok(self.state.write().remove_note_tag(tag))
+    }
+
+    async fn get_note_discovery_rules(&self) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        Ok(self.state.read().get_note_discovery_rules())
+    }
+
+    async fn add_note_discovery_rule(&self, rule: NoteDiscoveryRule) -> Result<bool, StoreError> {
+        Ok(self.state.write().add_note_discovery_rule(rule))
+    }
+
+    async fn remove_note_discovery_rule(
+        &self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        Ok(self.state.write().remove_note_discovery_rule(rule))
+    }
+
+    async fn get_discovered_notes(&self) -> Result<Vec<NoteId>, StoreError> {
+        Ok(self.state.read().get_discovered_notes())
+    }
+
+    async fn remove_discovered_notes(&self, note_ids: &[NoteId]) -> Result<(), StoreError> {
+        self.state.write().remove_discovered_notes(note_ids);
+        Ok(())
+    }
+
+    async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
+        Ok(self.state.read().sync_height)
+    }
+
+    async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError> {
+        self.state.write().apply_state_sync(state_sync_update);
+        Ok(())
+    }
+
+    async fn rollback_to(&self, rollback: ChainRollback) -> Result<(), StoreError> {
+        self.state.write().rollback_to(rollback);
+        Ok(())
+    }
+
+    async fn get_transactions(
+        &self,
+        filter: TransactionFilter,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        Ok(self.state.read().get_transactions(&filter))
+    }
+
+    async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError> {
+        self.state.write().apply_transaction(&tx_update);
+        Ok(())
+    }
+
+    async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, StoreError> {
+        Ok(self.state.read().get_queued_transactions())
+    }
+
+    async fn upsert_queued_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        self.state.write().upsert_queued_transaction(queued_transaction);
+        Ok(())
+    }
+
+    async fn remove_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        Ok(self.state.write().remove_queued_transaction(transaction_id))
+    }
+
+    async fn get_input_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        Ok(self.state.read().get_input_notes(&filter))
+    }
+
+    async fn get_output_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        Ok(self.state.read().get_output_notes(&filter))
+    }
+
+    async fn upsert_input_notes(&self, notes: &[InputNoteRecord]) -> Result<(), StoreError> {
+        self.state.write().upsert_input_notes(notes);
+        Ok(())
+    }
+
+    async fn insert_block_header(
+        &self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: MmrPeaks,
+        has_client_notes: bool,
+    ) -> Result<(), StoreError> {
+        self.state.write().insert_block_header(
+            block_header,
+            partial_blockchain_peaks,
+            has_client_notes,
+        );
+        Ok(())
+    }
+
+    async fn prune_block_headers(&self, policy: &BlockRetentionPolicy) -> Result<(), StoreError> {
+        self.state.write().prune_block_headers(policy);
+        Ok(())
+    }
+
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<PruneSummary, StoreError> {
+        Ok(self.state.write().prune(policy))
+    }
+
+    async fn import_backup(&self, backup: StoreBackup) -> Result<(), StoreError> {
+        *self.state.write() = MemoryStoreState::from_backup(backup);
+        Ok(())
+    }
+
+    async fn get_block_headers(
+        &self,
+        block_numbers: &BTreeSet<BlockNumber>,
+    ) -> Result<Vec<(BlockHeader, bool)>, StoreError> {
+        Ok(self.state.read().get_block_headers(block_numbers))
+    }
+
+    async fn get_tracked_block_headers(&self) -> Result<Vec<BlockHeader>, StoreError> {
+        Ok(self.state.read().get_tracked_block_headers())
+    }
+
+    async fn get_partial_blockchain_nodes(
+        &self,
+        filter: PartialBlockchainFilter,
+    ) -> Result<BTreeMap<InOrderIndex, Digest>, StoreError> {
+        Ok(self.state.read().get_partial_blockchain_nodes(&filter))
+    }
+
+    async fn insert_partial_blockchain_nodes(
+        &self,
+        nodes: &[(InOrderIndex, Digest)],
+    ) -> Result<(), StoreError> {
+        self.state.write().insert_partial_blockchain_nodes(nodes);
+        Ok(())
+    }
+
+    async fn get_partial_blockchain_peaks_by_block_num(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<MmrPeaks, StoreError> {
+        self.state.read().get_partial_blockchain_peaks_by_block_num(block_num)
+    }
+
+    async fn insert_account(
+        &self,
+        account: &Account,
+        account_seed: Option<Word>,
+    ) -> Result<(), StoreError> {
+        self.state.write().insert_account(account, account_seed);
+        Ok(())
+    }
+
+    async fn update_account(&self, account: &Account) -> Result<(), StoreError> {
+        self.state.write().update_existing_account(account)
+    }
+
+    async fn get_account_ids(&self) -> Result<Vec<AccountId>, StoreError> {
+        Ok(self.state.read().get_account_ids())
+    }
+
+    async fn get_account_headers(&self) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
+        Ok(self.state.read().get_account_headers())
+    }
+
+    async fn get_account_header(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
+        Ok(self.state.read().get_account_header(account_id))
+    }
+
+    async fn get_account_header_by_commitment(
+        &self,
+        account_commitment: Digest,
+    ) -> Result<Option<AccountHeader>, StoreError> {
+        Ok(self.state.read().get_account_header_by_commitment(account_commitment))
+    }
+
+    async fn get_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        Ok(self.state.read().get_account(account_id))
+    }
+
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        Ok(self.state.read().get_account_history(account_id))
+    }
+
+    async fn upsert_foreign_account_code(
+        &self,
+        account_id: AccountId,
+        code: AccountCode,
+    ) -> Result<(), StoreError> {
+        self.state.write().foreign_account_code.insert(account_id, code);
+        Ok(())
+    }
+
+    async fn get_foreign_account_code(
+        &self,
+        account_ids: Vec<AccountId>,
+    ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError> {
+        Ok(self.state.read().get_foreign_account_code(&account_ids))
+    }
+
+    async fn get_unspent_input_note_nullifiers(&self) -> Result<Vec<Nullifier>, StoreError> {
+        Ok(self.state.read().get_unspent_input_note_nullifiers())
+    }
+
+    async fn with_tenant(&self, tenant_id: TenantId) -> Result<Arc<dyn Store>, StoreError> {
+        let state = self.tenants.write().entry(tenant_id).or_default().clone();
+
+        Ok(Arc::new(MemoryStore {
+            state,
+            tenants: self.tenants.clone(),
+            clock: self.clock.clone(),
+        }))
+    }
+
+    async fn get_tenants(&self) -> Result<Vec<TenantId>, StoreError> {
+        Ok(self.tenants.read().keys().cloned().collect())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::transaction::TransactionKernel;
+    use miden_objects::{
+        Felt, Word,
+        account::Account,
+        testing::account_id::{
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+        },
+    };
+
+    use super::MemoryStore;
+    use crate::store::{AccountStatus, Store, StoreError, TenantId};
+
+    fn is_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn is_send_sync_holds() {
+        is_send_sync::<MemoryStore>();
+    }
+
+    #[tokio::test]
+    async fn account_history_is_kept() {
+        let store = MemoryStore::new();
+
+        let account = Account::mock(
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+            Felt::new(0),
+            TransactionKernel::testing_assembler(),
+        );
+        store.insert_account(&account, Some(Word::default())).await.unwrap();
+        let (_, status) = store.get_account_header(account.id()).await.unwrap().unwrap();
+        assert!(matches!(status, AccountStatus::New { .. }));
+
+        let updated_account = Account::mock(
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+            Felt::new(1),
+            TransactionKernel::testing_assembler(),
+        );
+        store.update_account(&updated_account).await.unwrap();
+
+        let record = store.get_account(account.id()).await.unwrap().unwrap();
+        assert_eq!(record.account().commitment(), updated_account.commitment());
+        assert!(matches!(record.status(), AccountStatus::Tracked));
+
+        // The previous state can still be retrieved by its commitment
+        let old_header = store
+            .get_account_header_by_commitment(account.commitment())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(old_header.nonce(), account.nonce());
+
+        assert_eq!(store.get_account_ids().await.unwrap(), vec![account.id()]);
+    }
+
+    #[tokio::test]
+    async fn update_unknown_account_fails() {
+        let store = MemoryStore::new();
+
+        let account = Account::mock(
+            ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+            Felt::new(1),
+            TransactionKernel::testing_assembler(),
+        );
+
+        assert!(matches!(
+            store.update_account(&account).await,
+            Err(StoreError::AccountDataNotFound(id)) if id == account.id()
+        ));
+    }
+
+    #[tokio::test]
+    async fn tenants_are_isolated() {
+        let store = MemoryStore::new();
+        let tenant_a = store.with_tenant(TenantId::new("a")).await.unwrap();
+        let tenant_b = store.with_tenant(TenantId::new("b")).await.unwrap();
+
+        let account = Account::mock(
+            ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+            Felt::new(0),
+            TransactionKernel::testing_assembler(),
+        );
+        tenant_a.insert_account(&account, Some(Word::default())).await.unwrap();
+
+        assert_eq!(tenant_a.get_account_ids().await.unwrap(), vec![account.id()]);
+        assert!(tenant_b.get_account_ids().await.unwrap().is_empty());
+        assert!(store.get_account_ids().await.unwrap().is_empty());
+
+        // Handles for the same tenant share their data, and tenants are visible from any handle
+        let tenant_a_again = tenant_b.with_tenant(TenantId::new("a")).await.unwrap();
+        assert_eq!(tenant_a_again.get_account_ids().await.unwrap(), vec![account.id()]);
+        assert_eq!(
+            store.get_tenants().await.unwrap(),
+            vec![TenantId::new("a"), TenantId::new("b")]
+        );
+    }
+}
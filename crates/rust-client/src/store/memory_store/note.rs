@@ -0,0 +1,144 @@
+use alloc::vec::Vec;
+
+use miden_objects::note::Nullifier;
+
+use super::MemoryStoreState;
+use crate::{
+    note::NoteUpdateTracker,
+    store::{
+        NoteFilter,
+        note_record::{InputNoteRecord, InputNoteState, OutputNoteRecord, OutputNoteState},
+    },
+};
+
+// NOTE FILTER
+// ================================================================================================
+
+/// Returns whether the input note matches the provided filter.
+fn input_note_matches(filter: &NoteFilter, note: &InputNoteRecord) -> bool {
+    let discriminant = note.state().discriminant();
+    match filter {
+        NoteFilter::All => true,
+        NoteFilter::Committed => discriminant == InputNoteState::STATE_COMMITTED,
+        NoteFilter::Consumed => [
+            InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL,
+            InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL,
+            InputNoteState::STATE_CONSUMED_EXTERNAL,
+        ]
+        .contains(&discriminant),
+        NoteFilter::Expected => discriminant == InputNoteState::STATE_EXPECTED,
+        NoteFilter::Processing => [
+            InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+            InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+        ]
+        .contains(&discriminant),
+        NoteFilter::Unverified => discriminant == InputNoteState::STATE_UNVERIFIED,
+        NoteFilter::Unspent => [
+            InputNoteState::STATE_EXPECTED,
+            InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+            InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+            InputNoteState::STATE_UNVERIFIED,
+            InputNoteState::STATE_COMMITTED,
+        ]
+        .contains(&discriminant),
+        NoteFilter::Unique(note_id) => note.id() == *note_id,
+        NoteFilter::List(note_ids) => note_ids.contains(&note.id()),
+        NoteFilter::Nullifiers(nullifiers) => nullifiers.contains(&note.nullifier()),
+    }
+}
+
+/// Returns whether the output note matches the provided filter.
+fn output_note_matches(filter: &NoteFilter, note: &OutputNoteRecord) -> bool {
+    let discriminant = note.state().discriminant();
+    match filter {
+        NoteFilter::All => true,
+        NoteFilter::Committed => {
+            [OutputNoteState::STATE_COMMITTED_PARTIAL, OutputNoteState::STATE_COMMITTED_FULL]
+                .contains(&discriminant)
+        },
+        NoteFilter::Consumed => discriminant == OutputNoteState::STATE_CONSUMED,
+        NoteFilter::Expected => {
+            [OutputNoteState::STATE_EXPECTED_PARTIAL, OutputNoteState::STATE_EXPECTED_FULL]
+                .contains(&discriminant)
+        },
+        // There are no processing or unverified output notes
+        NoteFilter::Processing | NoteFilter::Unverified => false,
+        NoteFilter::Unspent => {
+            [OutputNoteState::STATE_EXPECTED_FULL, OutputNoteState::STATE_COMMITTED_FULL]
+                .contains(&discriminant)
+        },
+        NoteFilter::Unique(note_id) => note.id() == *note_id,
+        NoteFilter::List(note_ids) => note_ids.contains(&note.id()),
+        NoteFilter::Nullifiers(nullifiers) => {
+            note.nullifier().is_some_and(|nullifier| nullifiers.contains(&nullifier))
+        },
+    }
+}
+
+// NOTES
+// ================================================================================================
+
+impl MemoryStoreState {
+    pub(super) fn get_input_notes(&self, filter: &NoteFilter) -> Vec<InputNoteRecord> {
+        self.input_notes
+            .iter()
+            .filter(|note| input_note_matches(filter, note))
+            .cloned()
+            .collect()
+    }
+
+    pub(super) fn get_output_notes(&self, filter: &NoteFilter) -> Vec<OutputNoteRecord> {
+        self.output_notes
+            .iter()
+            .filter(|note| output_note_matches(filter, note))
+            .cloned()
+            .collect()
+    }
+
+    pub(super) fn upsert_input_notes(&mut self, notes: &[InputNoteRecord]) {
+        for note in notes {
+            self.upsert_input_note(note.clone());
+
+            // Whenever we insert a note, we also update block relevance
+            if let Some(inclusion_proof) = note.inclusion_proof() {
+                self.set_block_header_has_client_notes(
+                    inclusion_proof.location().block_num(),
+                    true,
+                );
+            }
+        }
+    }
+
+    pub(super) fn get_unspent_input_note_nullifiers(&self) -> Vec<Nullifier> {
+        self.input_notes
+            .iter()
+            .filter(|note| input_note_matches(&NoteFilter::Unspent, note))
+            .map(InputNoteRecord::nullifier)
+            .collect()
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    pub(super) fn apply_note_updates(&mut self, note_updates: &NoteUpdateTracker) {
+        for input_note in note_updates.updated_input_notes() {
+            self.upsert_input_note(input_note.inner().clone());
+        }
+
+        for output_note in note_updates.updated_output_notes() {
+            self.upsert_output_note(output_note.inner().clone());
+        }
+    }
+
+    /// Inserts the provided input note, if the note already exists, it will be replaced.
+    pub(super) fn upsert_input_note(&mut self, note: InputNoteRecord) {
+        self.input_notes.retain(|stored_note| stored_note.id() != note.id());
+        self.input_notes.push(note);
+    }
+
+    /// Inserts the provided output note, if the note already exists, it will be replaced.
+    pub(super) fn upsert_output_note(&mut self, note: OutputNoteRecord) {
+        self.output_notes.retain(|stored_note| stored_note.id() != note.id());
+        self.output_notes.push(note);
+    }
+}
@@ -0,0 +1,56 @@
+use alloc::collections::BTreeSet;
+
+use miden_objects::account::AccountId;
+
+use super::MemoryStoreState;
+use crate::{
+    store::{PruneSummary, RetentionPolicy},
+    transaction::TransactionStatus,
+};
+
+impl MemoryStoreState {
+    /// Removes the data that isn't needed anymore according to the policy.
+    ///
+    /// Account code, storage, vaults and scripts aren't stored separately from the records that
+    /// use them, so there's never unreferenced data to remove.
+    pub(super) fn prune(&mut self, policy: &RetentionPolicy) -> PruneSummary {
+        let mut summary = PruneSummary::default();
+
+        if let Some(states_to_keep) = policy.states_to_keep_per_account() {
+            // Previous states of accounts with pending transactions are needed to roll them back
+            // if the transactions get discarded
+            let accounts_with_pending_transactions: BTreeSet<AccountId> = self
+                .transactions
+                .iter()
+                .filter(|tx| matches!(tx.status, TransactionStatus::Pending))
+                .map(|tx| tx.details.account_id)
+                .collect();
+
+            for account_id in self.get_account_ids() {
+                if !accounts_with_pending_transactions.contains(&account_id) {
+                    summary.removed_account_states +=
+                        self.remove_old_account_states(account_id, states_to_keep);
+                }
+            }
+        }
+
+        let expired_input_notes = policy.expired_input_notes(&self.input_notes, self.sync_height);
+        self.input_notes.retain(|note| !expired_input_notes.contains(&note.id()));
+        summary.removed_input_notes = expired_input_notes.len();
+
+        let expired_output_notes =
+            policy.expired_output_notes(&self.output_notes, self.sync_height);
+        self.output_notes.retain(|note| !expired_output_notes.contains(&note.id()));
+        summary.removed_output_notes = expired_output_notes.len();
+
+        let previous_len = self.transactions.len();
+        let sync_height = self.sync_height;
+        self.transactions.retain(|tx| {
+            !matches!(tx.status, TransactionStatus::Discarded(_))
+                || !policy.is_discarded_transaction_expired(tx.details.block_num, sync_height)
+        });
+        summary.removed_transactions = previous_len - self.transactions.len();
+
+        summary
+    }
+}
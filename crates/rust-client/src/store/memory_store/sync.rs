@@ -0,0 +1,172 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use miden_objects::{
+    Digest,
+    note::{NoteId, NoteTag},
+};
+
+use super::MemoryStoreState;
+use crate::sync::{
+    ChainRollback, NoteDiscoveryRule, NoteTagRecord, NoteTagSource, StateSyncUpdate,
+};
+
+impl MemoryStoreState {
+    pub(super) fn get_note_tags(&self) -> Vec<NoteTagRecord> {
+        self.tags.clone()
+    }
+
+    pub(super) fn get_unique_note_tags(&self) -> BTreeSet<NoteTag> {
+        self.tags.iter().map(|record| record.tag).collect()
+    }
+
+    pub(super) fn add_note_tag(&mut self, tag: NoteTagRecord) -> bool {
+        if self.tags.contains(&tag) {
+            return false;
+        }
+
+        self.tags.push(tag);
+        true
+    }
+
+    pub(super) fn remove_note_tag(&mut self, tag: NoteTagRecord) -> usize {
+        let initial_len = self.tags.len();
+        self.tags.retain(|record| *record != tag);
+
+        initial_len - self.tags.len()
+    }
+
+    pub(super) fn get_note_discovery_rules(&self) -> Vec<NoteDiscoveryRule> {
+        self.note_discovery_rules.iter().copied().collect()
+    }
+
+    pub(super) fn add_note_discovery_rule(&mut self, rule: NoteDiscoveryRule) -> bool {
+        self.note_discovery_rules.insert(rule)
+    }
+
+    pub(super) fn remove_note_discovery_rule(&mut self, rule: NoteDiscoveryRule) -> bool {
+        self.note_discovery_rules.remove(&rule)
+    }
+
+    pub(super) fn get_discovered_notes(&self) -> Vec<NoteId> {
+        self.discovered_notes.iter().copied().collect()
+    }
+
+    pub(super) fn remove_discovered_notes(&mut self, note_ids: &[NoteId]) {
+        for note_id in note_ids {
+            self.discovered_notes.remove(note_id);
+        }
+    }
+
+    pub(super) fn apply_state_sync(&mut self, state_sync_update: StateSyncUpdate) {
+        let StateSyncUpdate {
+            block_num,
+            block_updates,
+            note_updates,
+            transaction_updates,
+            account_updates,
+            mut discovered_notes,
+        } = state_sync_update;
+
+        // Update state sync block number
+        self.sync_height = block_num;
+
+        for (block_header, block_has_relevant_notes, new_mmr_peaks) in block_updates.block_headers()
+        {
+            self.insert_block_header(
+                block_header,
+                new_mmr_peaks.clone(),
+                *block_has_relevant_notes,
+            );
+        }
+
+        // Insert new authentication nodes (inner nodes of the PartialBlockchain)
+        self.insert_partial_blockchain_nodes(block_updates.new_authentication_nodes());
+
+        // Update notes
+        self.apply_note_updates(&note_updates);
+
+        // Remove tags
+        let tags_to_remove = note_updates
+            .updated_input_notes()
+            .filter_map(|note_update| {
+                let note = note_update.inner();
+                if note.is_committed() {
+                    Some(NoteTagRecord {
+                        tag: note.metadata().expect("Committed notes should have metadata").tag(),
+                        source: NoteTagSource::Note(note.id()),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for tag in tags_to_remove {
+            self.remove_note_tag(tag);
+        }
+
+        for transaction_record in transaction_updates
+            .committed_transactions()
+            .chain(transaction_updates.discarded_transactions())
+        {
+            self.upsert_transaction_record(transaction_record.clone());
+        }
+
+        // Remove the accounts that are originated from the discarded transactions
+        let account_hashes_to_delete: Vec<Digest> = transaction_updates
+            .discarded_transactions()
+            .map(|tx| tx.details.final_account_state)
+            .collect();
+
+        self.undo_account_state(&account_hashes_to_delete);
+
+        // Update public accounts that have been updated onchain
+        for account in account_updates.updated_public_accounts() {
+            self.update_account(account);
+        }
+
+        for (account_id, digest) in account_updates.mismatched_private_accounts() {
+            self.lock_account_on_unexpected_commitment(account_id, digest);
+        }
+
+        // Keep the discovered notes until they are imported
+        self.discovered_notes.append(&mut discovered_notes);
+    }
+
+    pub(super) fn rollback_to(&mut self, rollback: ChainRollback) {
+        let ChainRollback {
+            block_num,
+            partial_blockchain_nodes,
+            transactions,
+            input_notes,
+            output_notes,
+            tags,
+        } = rollback;
+
+        self.sync_height = block_num;
+        self.block_headers.retain(|stored_block_num, _| *stored_block_num <= block_num);
+
+        // The authentication nodes of the rollback replace the stored ones
+        self.partial_blockchain_nodes.clear();
+        self.insert_partial_blockchain_nodes(&partial_blockchain_nodes);
+
+        for transaction_record in transactions {
+            self.upsert_transaction_record(transaction_record);
+        }
+
+        for note in input_notes {
+            self.upsert_input_note(note);
+        }
+
+        for note in output_notes {
+            self.upsert_output_note(note);
+        }
+
+        for tag in tags {
+            self.add_note_tag(tag);
+        }
+
+        // The notes found in the removed blocks are found again by the next sync
+        self.discovered_notes.clear();
+    }
+}
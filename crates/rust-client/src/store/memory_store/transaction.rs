@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use miden_objects::{
+    Digest,
+    transaction::{ToInputNoteCommitments, TransactionId},
+};
+
+use super::MemoryStoreState;
+use crate::{
+    store::TransactionFilter,
+    transaction::{
+        QueuedTransaction, TransactionDetails, TransactionRecord, TransactionStatus,
+        TransactionStoreUpdate,
+    },
+};
+
+// TRANSACTIONS FILTERS
+// ================================================================================================
+
+/// Returns whether the transaction matches the provided [`TransactionFilter`].
+fn transaction_matches(filter: &TransactionFilter, transaction: &TransactionRecord) -> bool {
+    match filter {
+        TransactionFilter::All => true,
+        TransactionFilter::Uncommitted => {
+            !matches!(transaction.status, TransactionStatus::Committed(_))
+        },
+        TransactionFilter::Ids(ids) => ids.contains(&transaction.id),
+        TransactionFilter::ExpiredBefore(block_num) => {
+            transaction.details.block_num < *block_num
+                && matches!(transaction.status, TransactionStatus::Pending)
+        },
+    }
+}
+
+// TRANSACTIONS
+// ================================================================================================
+
+impl MemoryStoreState {
+    /// Retrieves tracked transactions, filtered by [`TransactionFilter`].
+    pub(super) fn get_transactions(&self, filter: &TransactionFilter) -> Vec<TransactionRecord> {
+        self.transactions
+            .iter()
+            .filter(|transaction| transaction_matches(filter, transaction))
+            .cloned()
+            .collect()
+    }
+
+    /// Inserts a transaction and updates the current state based on the `tx_result` changes.
+    pub(super) fn apply_transaction(&mut self, tx_update: &TransactionStoreUpdate) {
+        // Build transaction record
+        let executed_transaction = tx_update.executed_transaction();
+
+        let nullifiers: Vec<Digest> = executed_transaction
+            .input_notes()
+            .iter()
+            .map(|x| x.nullifier().inner())
+            .collect();
+
+        let output_notes = executed_transaction.output_notes();
+
+        let details = TransactionDetails {
+            account_id: executed_transaction.account_id(),
+            init_account_state: executed_transaction.initial_account().commitment(),
+            final_account_state: executed_transaction.final_account().commitment(),
+            input_note_nullifiers: nullifiers,
+            output_notes: output_notes.clone(),
+            block_num: executed_transaction.block_header().block_num(),
+            submission_height: tx_update.submission_height(),
+            expiration_block_num: executed_transaction.expiration_block_num(),
+        };
+
+        let transaction_record = TransactionRecord::new(
+            executed_transaction.id(),
+            details,
+            executed_transaction.tx_args().tx_script().cloned(),
+            TransactionStatus::Pending,
+        );
+
+        // Insert transaction data
+        self.upsert_transaction_record(transaction_record);
+
+        // Account Data
+        self.update_account(tx_update.updated_account());
+
+        // Note Updates
+        self.apply_note_updates(tx_update.note_updates());
+
+        for tag_record in tx_update.new_tags() {
+            self.tags.push(*tag_record);
+        }
+    }
+
+    /// Updates the transaction record, inserting it if it doesn't exist.
+    pub(super) fn upsert_transaction_record(&mut self, transaction: TransactionRecord) {
+        match self.transactions.iter_mut().find(|stored| stored.id == transaction.id) {
+            Some(stored) => *stored = transaction,
+            None => self.transactions.push(transaction),
+        }
+    }
+
+    /// Returns the transactions in the outbound queue.
+    pub(super) fn get_queued_transactions(&self) -> Vec<QueuedTransaction> {
+        self.queued_transactions.clone()
+    }
+
+    /// Inserts the transaction into the outbound queue, or replaces its entry if it's already
+    /// queued.
+    pub(super) fn upsert_queued_transaction(&mut self, queued_transaction: &QueuedTransaction) {
+        let transaction_id = queued_transaction.id();
+        match self.queued_transactions.iter_mut().find(|entry| entry.id() == transaction_id) {
+            Some(entry) => *entry = queued_transaction.clone(),
+            None => self.queued_transactions.push(queued_transaction.clone()),
+        }
+    }
+
+    /// Removes the transaction from the outbound queue, returning whether it was queued.
+    pub(super) fn remove_queued_transaction(&mut self, transaction_id: TransactionId) -> bool {
+        let initial_len = self.queued_transactions.len();
+        self.queued_transactions.retain(|entry| entry.id() != transaction_id);
+        initial_len != self.queued_transactions.len()
+    }
+}
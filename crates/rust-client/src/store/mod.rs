@@ -10,8 +10,9 @@
 //! methods to (among others):
 //!
 //! - Retrieve and update transactions, notes, and accounts.
+//! - Keep the outbound queue of transactions being proven and submitted.
 //! - Store and query block headers along with MMR peaks and authentication nodes.
-//! - Manage note tags for synchronizing with the node.
+//! - Manage note tags and note discovery rules for synchronizing with the node.
 //!
 //! These are all used by the Miden client to provide transaction execution in the correct contexts.
 //!
@@ -23,13 +24,15 @@
 use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::String,
+    sync::Arc,
     vec::Vec,
 };
-use core::fmt::Debug;
+use core::fmt::{self, Debug};
 
 use miden_objects::{
     Digest, Word,
-    account::{Account, AccountCode, AccountHeader, AccountId},
+    account::{Account, AccountCode, AccountHeader, AccountId, AccountStorage},
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
     note::{NoteId, NoteTag, Nullifier},
@@ -37,8 +40,10 @@ use miden_objects::{
 };
 
 use crate::{
-    sync::{NoteTagRecord, StateSyncUpdate},
-    transaction::{TransactionRecord, TransactionStoreUpdate},
+    sync::{
+        BlockRetentionPolicy, ChainRollback, NoteDiscoveryRule, NoteTagRecord, StateSyncUpdate,
+    },
+    transaction::{QueuedTransaction, TransactionRecord, TransactionStoreUpdate},
 };
 
 /// Contains [`ClientDataStore`] to automatically implement [`DataStore`] for anything that
@@ -55,14 +60,26 @@ pub use errors::*;
 #[cfg(all(feature = "sqlite", feature = "idxdb"))]
 compile_error!("features `sqlite` and `idxdb` are mutually exclusive");
 
+#[cfg(all(feature = "postgres", feature = "idxdb"))]
+compile_error!("features `postgres` and `idxdb` are mutually exclusive");
+
+pub mod memory_store;
+
 #[cfg(feature = "sqlite")]
 pub mod sqlite_store;
 
+#[cfg(feature = "postgres")]
+pub mod postgres_store;
+
 #[cfg(feature = "idxdb")]
 pub mod web_store;
 
 mod account;
 pub use account::{AccountRecord, AccountStatus, AccountUpdates};
+mod backup;
+pub use backup::{AccountStateBackup, BlockHeaderBackup, STORE_BACKUP_VERSION, StoreBackup};
+mod retention;
+pub use retention::{PruneSummary, RetentionPolicy};
 mod note_record;
 pub use note_record::{
     InputNoteRecord, InputNoteState, NoteExportType, NoteRecordError, OutputNoteRecord,
@@ -115,6 +132,25 @@ pub trait Store: Send + Sync {
     /// - Inserting the transaction into the store to track.
     async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError>;
 
+    /// Returns the transactions in the outbound transaction queue, in no particular order.
+    async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, StoreError>;
+
+    /// Inserts the transaction into the outbound transaction queue, or replaces its entry if it's
+    /// already queued.
+    async fn upsert_queued_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError>;
+
+    /// Removes the transaction from the outbound transaction queue.
+    ///
+    /// If the transaction wasn't queued returns false since no entry was actually removed.
+    /// Otherwise returns true.
+    async fn remove_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError>;
+
     // NOTES
     // --------------------------------------------------------------------------------------------
 
@@ -212,9 +248,22 @@ pub trait Store: Send + Sync {
         has_client_notes: bool,
     ) -> Result<(), StoreError>;
 
+    /// Removes the block headers that the provided policy doesn't keep at the current sync
+    /// height. The genesis block and the block at the sync height are always kept.
+    ///
+    /// The partial blockchain nodes that were only needed to authenticate the removed blocks are
+    /// removed along with them.
+    async fn prune_block_headers(&self, policy: &BlockRetentionPolicy) -> Result<(), StoreError>;
+
     /// Removes block headers that do not contain any client notes and aren't the genesis or last
     /// block.
-    async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError>;
+    #[deprecated(
+        since = "0.9.1",
+        note = "use `Store::prune_block_headers` with `BlockRetentionPolicy::relevant_blocks` instead"
+    )]
+    async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
+        self.prune_block_headers(&BlockRetentionPolicy::relevant_blocks()).await
+    }
 
     // ACCOUNT
     // --------------------------------------------------------------------------------------------
@@ -249,6 +298,32 @@ pub trait Store: Send + Sync {
     async fn get_account(&self, account_id: AccountId)
     -> Result<Option<AccountRecord>, StoreError>;
 
+    /// Retrieves every stored state of the account as full [`AccountRecord`] objects, ordered by
+    /// ascending nonce. Returns an empty vector if the account is not found.
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError>;
+
+    /// Retrieves the code and storage of the latest state of every stored account, without loading
+    /// their vaults.
+    ///
+    /// The default implementation loads every full account, so stores that can query the code and
+    /// storage directly should override it.
+    async fn get_accounts_code_and_storage(
+        &self,
+    ) -> Result<Vec<(AccountId, AccountCode, AccountStorage)>, StoreError> {
+        let mut accounts = Vec::new();
+        for account_id in self.get_account_ids().await? {
+            if let Some(record) = self.get_account(account_id).await? {
+                let account = record.account();
+                accounts.push((account_id, account.code().clone(), account.storage().clone()));
+            }
+        }
+
+        Ok(accounts)
+    }
+
     /// Inserts an [`Account`] along with the seed used to create it.
     async fn insert_account(
         &self,
@@ -300,6 +375,29 @@ pub trait Store: Send + Sync {
     /// Otherwise returns true.
     async fn remove_note_tag(&self, tag: NoteTagRecord) -> Result<usize, StoreError>;
 
+    /// Returns the note discovery rules that the client is interested in.
+    async fn get_note_discovery_rules(&self) -> Result<Vec<NoteDiscoveryRule>, StoreError>;
+
+    /// Adds a note discovery rule to the rules that the client is interested in.
+    ///
+    /// If the rule was already being tracked, returns false since no new rule was actually added.
+    /// Otherwise true.
+    async fn add_note_discovery_rule(&self, rule: NoteDiscoveryRule) -> Result<bool, StoreError>;
+
+    /// Removes a note discovery rule from the rules that the client is interested in.
+    ///
+    /// If the rule wasn't present in the store returns false since no rule was actually removed.
+    /// Otherwise returns true.
+    async fn remove_note_discovery_rule(&self, rule: NoteDiscoveryRule)
+    -> Result<bool, StoreError>;
+
+    /// Returns the IDs of the public notes found by the note discovery rules during state syncs
+    /// that haven't been imported yet.
+    async fn get_discovered_notes(&self) -> Result<Vec<NoteId>, StoreError>;
+
+    /// Removes the notes from the discovered notes pending to be imported.
+    async fn remove_discovered_notes(&self, note_ids: &[NoteId]) -> Result<(), StoreError>;
+
     /// Returns the block number of the last state sync block.
     async fn get_sync_height(&self) -> Result<BlockNumber, StoreError>;
 
@@ -314,7 +412,115 @@ pub trait Store: Send + Sync {
     ///     locked.
     /// - Storing new MMR authentication nodes.
     /// - Updating the tracked public accounts.
+    /// - Storing the IDs of the notes found by the note discovery rules, so they can be imported
+    ///   afterwards.
     async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError>;
+
+    /// Rolls the store back to the rollback's block. A rollback involves:
+    ///
+    /// - Setting the sync height to the rollback block.
+    /// - Removing the block headers after the rollback block.
+    /// - Replacing the MMR authentication nodes with the ones of the rollback.
+    /// - Upserting the reverted transactions and input/output notes.
+    /// - Tracking the tags of the reverted notes again.
+    /// - Removing the discovered notes that weren't imported yet, as they may belong to removed
+    ///   blocks.
+    ///
+    /// Stores that support transactions apply the rollback atomically.
+    async fn rollback_to(&self, rollback: ChainRollback) -> Result<(), StoreError>;
+
+    // MAINTENANCE
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes the data that isn't needed anymore according to the provided [`RetentionPolicy`]
+    /// and returns a summary of the removed data.
+    ///
+    /// Pruning is atomic: if an error is returned, no data is removed.
+    ///
+    /// The default implementation returns [`StoreError::PruningNotSupported`].
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<PruneSummary, StoreError> {
+        let _ = policy;
+        Err(StoreError::PruningNotSupported)
+    }
+
+    // BACKUP
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a [`StoreBackup`] with all the data tracked by the store.
+    ///
+    /// The default implementation builds the backup from the data returned by the other methods
+    /// of the trait.
+    async fn export_backup(&self) -> Result<StoreBackup, StoreError> {
+        StoreBackup::from_store(self).await
+    }
+
+    /// Replaces all the data tracked by the store with the contents of the provided
+    /// [`StoreBackup`].
+    ///
+    /// Stores that support transactions apply the import atomically: if an error is returned, the
+    /// store is left unchanged.
+    async fn import_backup(&self, backup: StoreBackup) -> Result<(), StoreError>;
+
+    // TENANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a handle to the same underlying storage whose data is scoped to the provided
+    /// tenant. Accounts, notes, tags, transactions and chain data stored through the returned
+    /// handle are only visible to handles of the same tenant, so a [`crate::Client`] built on top
+    /// of it only tracks the tenant's data.
+    ///
+    /// The tenant is created if it doesn't exist yet.
+    ///
+    /// The default implementation returns [`StoreError::TenantsNotSupported`].
+    async fn with_tenant(&self, tenant_id: TenantId) -> Result<Arc<dyn Store>, StoreError> {
+        let _ = tenant_id;
+        Err(StoreError::TenantsNotSupported)
+    }
+
+    /// Returns the tenants that have been created in the underlying storage.
+    ///
+    /// The default implementation returns [`StoreError::TenantsNotSupported`].
+    async fn get_tenants(&self) -> Result<Vec<TenantId>, StoreError> {
+        Err(StoreError::TenantsNotSupported)
+    }
+}
+
+// TENANT ID
+// ================================================================================================
+
+/// Identifies a tenant, an independent set of accounts and related data hosted in a store shared
+/// with other tenants. See [`Store::with_tenant`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Returns a new [`TenantId`] from the provided identifier.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Returns the identifier as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for TenantId {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for TenantId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
 }
 
 // PARTIAL BLOCKCHAIN NODE FILTER
@@ -97,6 +97,18 @@ impl InputNoteState {
         self.inner().consumer_transaction_id()
     }
 
+    /// Returns the block height at which the note was nullified, if it was consumed.
+    pub(crate) fn nullifier_block_height(&self) -> Option<u32> {
+        match self {
+            InputNoteState::ConsumedAuthenticatedLocal(state) => Some(state.nullifier_block_height),
+            InputNoteState::ConsumedUnauthenticatedLocal(state) => {
+                Some(state.nullifier_block_height)
+            },
+            InputNoteState::ConsumedExternal(state) => Some(state.nullifier_block_height),
+            _ => None,
+        }
+    }
+
     /// Returns a unique identifier for each note state.
     pub(crate) fn discriminant(&self) -> u8 {
         match self {
@@ -270,6 +270,37 @@ impl OutputNoteRecord {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for OutputNoteRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.assets.write_into(target);
+        self.metadata.write_into(target);
+        self.recipient_digest.write_into(target);
+        self.state.write_into(target);
+        self.expected_height.write_into(target);
+    }
+}
+
+impl Deserializable for OutputNoteRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let assets = NoteAssets::read_from(source)?;
+        let metadata = NoteMetadata::read_from(source)?;
+        let recipient_digest = Digest::read_from(source)?;
+        let state = OutputNoteState::read_from(source)?;
+        let expected_height = BlockNumber::read_from(source)?;
+
+        Ok(OutputNoteRecord {
+            assets,
+            metadata,
+            recipient_digest,
+            state,
+            expected_height,
+        })
+    }
+}
+
 // OUTPUT NOTE STATE
 // ================================================================================================
 
@@ -334,6 +365,14 @@ impl OutputNoteState {
         }
     }
 
+    /// Returns the block height at which the note was nullified, if it was consumed.
+    pub(crate) fn nullifier_block_height(&self) -> Option<u32> {
+        match self {
+            OutputNoteState::Consumed { block_height, .. } => Some(*block_height),
+            _ => None,
+        }
+    }
+
     pub fn inclusion_proof(&self) -> Option<&NoteInclusionProof> {
         match self {
             OutputNoteState::CommittedPartial { inclusion_proof, .. }
@@ -0,0 +1,390 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use std::collections::BTreeMap;
+
+use miden_objects::{
+    Digest, Felt, Word,
+    account::{Account, AccountCode, AccountHeader, AccountId, AccountStorage},
+    asset::{Asset, AssetVault},
+};
+use miden_tx::utils::{Deserializable, Serializable};
+use tokio_postgres::{Client, Row, Transaction};
+
+use super::{PostgresStore, i64_to_u64, u64_to_i64};
+use crate::store::{AccountRecord, AccountStatus, StoreError};
+
+impl PostgresStore {
+    // ACCOUNTS
+    // --------------------------------------------------------------------------------------------
+
+    pub(super) async fn get_account_ids(conn: &mut Client) -> Result<Vec<AccountId>, StoreError> {
+        const QUERY: &str = "SELECT DISTINCT id FROM accounts";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let id: String = row.try_get(0)?;
+                Ok(AccountId::from_hex(&id)?)
+            })
+            .collect::<Result<Vec<AccountId>, StoreError>>()
+    }
+
+    pub(super) async fn get_account_headers(
+        conn: &mut Client,
+    ) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
+        const QUERY: &str = "SELECT a.id, a.nonce, a.vault_root, a.storage_root, a.code_root, a.account_seed, a.locked \
+            FROM accounts a \
+            WHERE a.nonce = (SELECT MAX(b.nonce) FROM accounts b WHERE b.id = a.id)";
+
+        conn.query(QUERY, &[]).await?.iter().map(parse_accounts).collect()
+    }
+
+    pub(crate) async fn get_account_header(
+        conn: &mut Client,
+        account_id: AccountId,
+    ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
+        const QUERY: &str = "SELECT id, nonce, vault_root, storage_root, code_root, account_seed, locked \
+            FROM accounts WHERE id = $1 \
+            ORDER BY nonce DESC \
+            LIMIT 1";
+
+        conn.query_opt(QUERY, &[&account_id.to_hex()])
+            .await?
+            .as_ref()
+            .map(parse_accounts)
+            .transpose()
+    }
+
+    pub(crate) async fn get_account_header_by_commitment(
+        conn: &mut Client,
+        account_commitment: Digest,
+    ) -> Result<Option<AccountHeader>, StoreError> {
+        const QUERY: &str = "SELECT id, nonce, vault_root, storage_root, code_root, account_seed, locked \
+            FROM accounts WHERE account_commitment = $1";
+
+        conn.query_opt(QUERY, &[&account_commitment.to_string()])
+            .await?
+            .as_ref()
+            .map(|row| Ok(parse_accounts(row)?.0))
+            .transpose()
+    }
+
+    pub(crate) async fn get_account(
+        conn: &mut Client,
+        account_id: AccountId,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, account_storage.slots, account_vaults.assets, accounts.locked \
+                            FROM accounts \
+                            JOIN account_code ON accounts.code_root = account_code.root \
+                            JOIN account_storage ON accounts.storage_root = account_storage.root \
+                            JOIN account_vaults ON accounts.vault_root = account_vaults.root \
+                            WHERE accounts.id = $1 \
+                            ORDER BY accounts.nonce DESC \
+                            LIMIT 1";
+
+        conn.query_opt(QUERY, &[&account_id.to_hex()])
+            .await?
+            .as_ref()
+            .map(parse_account)
+            .transpose()
+    }
+
+    pub(crate) async fn get_accounts_code_and_storage(
+        conn: &mut Client,
+    ) -> Result<Vec<(AccountId, AccountCode, AccountStorage)>, StoreError> {
+        const QUERY: &str = "SELECT a.id, account_code.code, account_storage.slots \
+                            FROM accounts a \
+                            JOIN account_code ON a.code_root = account_code.root \
+                            JOIN account_storage ON a.storage_root = account_storage.root \
+                            WHERE a.nonce = (SELECT MAX(b.nonce) FROM accounts b WHERE b.id = a.id)";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let id: String = row.try_get(0)?;
+                let code: Vec<u8> = row.try_get(1)?;
+                let storage: Vec<u8> = row.try_get(2)?;
+                Ok((
+                    AccountId::from_hex(&id)
+                        .expect("Conversion from stored AccountID should not panic"),
+                    AccountCode::from_bytes(&code)?,
+                    AccountStorage::read_from_bytes(&storage)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub(crate) async fn get_account_history(
+        conn: &mut Client,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, account_storage.slots, account_vaults.assets, accounts.locked \
+                            FROM accounts \
+                            JOIN account_code ON accounts.code_root = account_code.root \
+                            JOIN account_storage ON accounts.storage_root = account_storage.root \
+                            JOIN account_vaults ON accounts.vault_root = account_vaults.root \
+                            WHERE accounts.id = $1 \
+                            ORDER BY accounts.nonce ASC";
+
+        conn.query(QUERY, &[&account_id.to_hex()])
+            .await?
+            .iter()
+            .map(parse_account)
+            .collect()
+    }
+
+    pub(crate) async fn insert_account(
+        conn: &mut Client,
+        account: &Account,
+        account_seed: Option<Word>,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        insert_account_code(&tx, account.code()).await?;
+        insert_account_storage(&tx, account.storage()).await?;
+        insert_account_asset_vault(&tx, account.vault()).await?;
+        insert_account_record(&tx, account, account_seed).await?;
+
+        Ok(tx.commit().await?)
+    }
+
+    pub(crate) async fn update_account(
+        conn: &mut Client,
+        new_account_state: &Account,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        const QUERY: &str = "SELECT 1 FROM accounts WHERE id = $1";
+        if tx.query_opt(QUERY, &[&new_account_state.id().to_hex()]).await?.is_none() {
+            return Err(StoreError::AccountDataNotFound(new_account_state.id()));
+        }
+
+        update_account(&tx, new_account_state).await?;
+        Ok(tx.commit().await?)
+    }
+
+    pub async fn upsert_foreign_account_code(
+        conn: &mut Client,
+        account_id: AccountId,
+        code: &AccountCode,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        insert_account_code(&tx, code).await?;
+
+        const QUERY: &str = "INSERT INTO foreign_account_code (account_id, code_root) VALUES ($1, $2) \
+            ON CONFLICT (account_id) DO UPDATE SET code_root = EXCLUDED.code_root";
+        tx.execute(QUERY, &[&account_id.to_hex(), &code.commitment().to_string()])
+            .await?;
+
+        Ok(tx.commit().await?)
+    }
+
+    pub async fn get_foreign_account_code(
+        conn: &mut Client,
+        account_ids: Vec<AccountId>,
+    ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError> {
+        let account_ids: Vec<String> = account_ids.into_iter().map(|id| id.to_hex()).collect();
+        const QUERY: &str = "
+            SELECT foreign_account_code.account_id, account_code.code
+            FROM foreign_account_code
+            JOIN account_code ON foreign_account_code.code_root = account_code.root
+            WHERE foreign_account_code.account_id = ANY($1)";
+
+        conn.query(QUERY, &[&account_ids])
+            .await?
+            .iter()
+            .map(|row| {
+                let id: String = row.try_get(0)?;
+                let code: Vec<u8> = row.try_get(1)?;
+                Ok((AccountId::from_hex(&id)?, AccountCode::from_bytes(&code)?))
+            })
+            .collect::<Result<BTreeMap<AccountId, AccountCode>, _>>()
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Update previously-existing account after a transaction execution.
+///
+/// Because the Client retrieves the account by account ID before applying the delta, we don't
+/// need to check that it exists here. This inserts a new row into the accounts table.
+/// We can later identify the proper account state by looking at the nonce.
+pub(crate) async fn update_account(
+    tx: &Transaction<'_>,
+    new_account_state: &Account,
+) -> Result<(), StoreError> {
+    insert_account_storage(tx, new_account_state.storage()).await?;
+    insert_account_asset_vault(tx, new_account_state.vault()).await?;
+    insert_account_record(tx, new_account_state, None).await
+}
+
+pub(super) async fn insert_account_record(
+    tx: &Transaction<'_>,
+    account: &Account,
+    account_seed: Option<Word>,
+) -> Result<(), StoreError> {
+    let account_seed = account_seed.map(|seed| seed.to_bytes());
+
+    const QUERY: &str = "\
+        INSERT INTO accounts (id, code_root, storage_root, vault_root, nonce, committed, account_seed, account_commitment, locked) \
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+        ON CONFLICT (account_commitment) DO UPDATE SET \
+            id = EXCLUDED.id, \
+            code_root = EXCLUDED.code_root, \
+            storage_root = EXCLUDED.storage_root, \
+            vault_root = EXCLUDED.vault_root, \
+            nonce = EXCLUDED.nonce, \
+            committed = EXCLUDED.committed, \
+            account_seed = EXCLUDED.account_seed, \
+            locked = EXCLUDED.locked";
+
+    tx.execute(
+        QUERY,
+        &[
+            &account.id().to_hex(),
+            &account.code().commitment().to_string(),
+            &account.storage().commitment().to_string(),
+            &account.vault().root().to_string(),
+            &u64_to_i64(account.nonce().as_int()),
+            &account.is_public(),
+            &account_seed,
+            &account.commitment().to_string(),
+            &false,
+        ],
+    )
+    .await?;
+    Ok(())
+}
+
+/// Inserts an [`AccountCode`].
+pub(super) async fn insert_account_code(
+    tx: &Transaction<'_>,
+    account_code: &AccountCode,
+) -> Result<(), StoreError> {
+    const QUERY: &str =
+        "INSERT INTO account_code (root, code) VALUES ($1, $2) ON CONFLICT DO NOTHING";
+    tx.execute(QUERY, &[&account_code.commitment().to_string(), &account_code.to_bytes()])
+        .await?;
+    Ok(())
+}
+
+/// Inserts an [`AccountStorage`].
+pub(super) async fn insert_account_storage(
+    tx: &Transaction<'_>,
+    account_storage: &AccountStorage,
+) -> Result<(), StoreError> {
+    const QUERY: &str =
+        "INSERT INTO account_storage (root, slots) VALUES ($1, $2) ON CONFLICT DO NOTHING";
+    tx.execute(QUERY, &[&account_storage.commitment().to_string(), &account_storage.to_bytes()])
+        .await?;
+    Ok(())
+}
+
+/// Inserts an [`AssetVault`].
+pub(super) async fn insert_account_asset_vault(
+    tx: &Transaction<'_>,
+    asset_vault: &AssetVault,
+) -> Result<(), StoreError> {
+    let assets = asset_vault.assets().collect::<Vec<Asset>>().to_bytes();
+    const QUERY: &str =
+        "INSERT INTO account_vaults (root, assets) VALUES ($1, $2) ON CONFLICT DO NOTHING";
+    tx.execute(QUERY, &[&asset_vault.root().to_string(), &assets]).await?;
+    Ok(())
+}
+
+/// Locks the account if the mismatched digest doesn't belong to a previous account state (stale
+/// data).
+pub(super) async fn lock_account_on_unexpected_commitment(
+    tx: &Transaction<'_>,
+    account_id: &AccountId,
+    mismatched_digest: &Digest,
+) -> Result<(), StoreError> {
+    // Mismatched digests may be due to stale network data. If the mismatched digest is
+    // tracked in the db and corresponds to the mismatched account, it means we
+    // got a past update and shouldn't lock the account.
+    const QUERY: &str = "UPDATE accounts SET locked = true WHERE id = $1 AND NOT EXISTS (SELECT 1 FROM accounts WHERE id = $1 AND account_commitment = $2)";
+    tx.execute(QUERY, &[&account_id.to_hex(), &mismatched_digest.to_string()])
+        .await?;
+    Ok(())
+}
+
+/// Parse an account header from the provided row.
+fn parse_accounts(row: &Row) -> Result<(AccountHeader, AccountStatus), StoreError> {
+    let id: String = row.try_get(0)?;
+    let nonce: i64 = row.try_get(1)?;
+    let vault_root: String = row.try_get(2)?;
+    let storage_root: String = row.try_get(3)?;
+    let code_root: String = row.try_get(4)?;
+    let account_seed: Option<Vec<u8>> = row.try_get(5)?;
+    let locked: bool = row.try_get(6)?;
+
+    let account_seed = account_seed.map(|seed| Word::read_from_bytes(&seed)).transpose()?;
+
+    Ok((
+        AccountHeader::new(
+            AccountId::from_hex(&id).expect("Conversion from stored AccountID should not panic"),
+            Felt::new(i64_to_u64(nonce)),
+            Digest::try_from(&vault_root)?,
+            Digest::try_from(&storage_root)?,
+            Digest::try_from(&code_root)?,
+        ),
+        account_status(account_seed, locked),
+    ))
+}
+
+/// Parse a full account from the provided row.
+fn parse_account(row: &Row) -> Result<AccountRecord, StoreError> {
+    let id: String = row.try_get(0)?;
+    let nonce: i64 = row.try_get(1)?;
+    let account_seed: Option<Vec<u8>> = row.try_get(2)?;
+    let code: Vec<u8> = row.try_get(3)?;
+    let storage: Vec<u8> = row.try_get(4)?;
+    let assets: Vec<u8> = row.try_get(5)?;
+    let locked: bool = row.try_get(6)?;
+
+    let account_seed = account_seed.map(|seed| Word::read_from_bytes(&seed)).transpose()?;
+    let account_id: AccountId =
+        AccountId::from_hex(&id).expect("Conversion from stored AccountID should not panic");
+    let account_code = AccountCode::from_bytes(&code)?;
+    let account_storage = AccountStorage::read_from_bytes(&storage)?;
+    let account_assets: Vec<Asset> = Vec::<Asset>::read_from_bytes(&assets)?;
+    let account = Account::from_parts(
+        account_id,
+        AssetVault::new(&account_assets)?,
+        account_storage,
+        account_code,
+        Felt::new(i64_to_u64(nonce)),
+    );
+
+    Ok(AccountRecord::new(account, account_status(account_seed, locked)))
+}
+
+fn account_status(account_seed: Option<Word>, locked: bool) -> AccountStatus {
+    match (account_seed, locked) {
+        (_, true) => AccountStatus::Locked,
+        (Some(seed), _) => AccountStatus::New { seed },
+        _ => AccountStatus::Tracked,
+    }
+}
+
+/// Removes account states with the specified hashes from the database.
+///
+/// This is used to rollback account changes when a transaction is discarded,
+/// effectively undoing the account state changes that were applied by the transaction.
+pub(crate) async fn undo_account_state(
+    tx: &Transaction<'_>,
+    account_hashes: &[Digest],
+) -> Result<(), StoreError> {
+    let account_hashes: Vec<String> = account_hashes.iter().map(Digest::to_hex).collect();
+    const QUERY: &str = "DELETE FROM accounts WHERE account_commitment = ANY($1)";
+    tx.execute(QUERY, &[&account_hashes]).await?;
+    Ok(())
+}
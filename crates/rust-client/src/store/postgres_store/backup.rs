@@ -0,0 +1,118 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::string::ToString;
+
+use tokio_postgres::{Client, Transaction};
+
+use super::{
+    PostgresStore,
+    account::{
+        insert_account_asset_vault, insert_account_code, insert_account_record,
+        insert_account_storage,
+    },
+    note::{upsert_input_note_tx, upsert_output_note_tx},
+    sync::{add_note_discovery_rule_tx, add_note_tag_tx},
+    transaction::{upsert_queued_transaction_tx, upsert_transaction_record},
+};
+use crate::store::{AccountStateBackup, StoreBackup, StoreError};
+
+impl PostgresStore {
+    pub(crate) async fn import_backup(
+        conn: &mut Client,
+        backup: &StoreBackup,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        clear_store(&tx).await?;
+
+        for account_state in &backup.accounts {
+            insert_account_state(&tx, account_state).await?;
+        }
+        for note in &backup.input_notes {
+            upsert_input_note_tx(&tx, note).await?;
+        }
+        for note in &backup.output_notes {
+            upsert_output_note_tx(&tx, note).await?;
+        }
+        for tag in &backup.tags {
+            add_note_tag_tx(&tx, tag).await?;
+        }
+        for rule in &backup.note_discovery_rules {
+            add_note_discovery_rule_tx(&tx, rule).await?;
+        }
+        for transaction in &backup.transactions {
+            upsert_transaction_record(&tx, transaction).await?;
+        }
+        for queued_transaction in &backup.queued_transactions {
+            upsert_queued_transaction_tx(&tx, queued_transaction).await?;
+        }
+        for block in &backup.block_headers {
+            Self::insert_block_header_tx(
+                &tx,
+                &block.header,
+                &block.partial_blockchain_peaks,
+                block.has_client_notes,
+            )
+            .await?;
+        }
+        Self::insert_partial_blockchain_nodes_tx(&tx, &backup.partial_blockchain_nodes).await?;
+
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = $1";
+        tx.execute(BLOCK_NUMBER_QUERY, &[&i64::from(backup.sync_height.as_u32())])
+            .await?;
+
+        Ok(tx.commit().await?)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Removes all the data from the store, except for the settings.
+async fn clear_store(tx: &Transaction<'_>) -> Result<(), StoreError> {
+    // Tables are cleared before the tables they reference
+    const QUERIES: [&str; 16] = [
+        "DELETE FROM accounts",
+        "DELETE FROM foreign_account_code",
+        "DELETE FROM account_code",
+        "DELETE FROM account_storage",
+        "DELETE FROM account_vaults",
+        "DELETE FROM transaction_queue",
+        "DELETE FROM transactions",
+        "DELETE FROM transaction_scripts",
+        "DELETE FROM input_notes",
+        "DELETE FROM output_notes",
+        "DELETE FROM notes_scripts",
+        "DELETE FROM tags",
+        "DELETE FROM note_discovery_rules",
+        "DELETE FROM discovered_notes",
+        "DELETE FROM block_headers",
+        "DELETE FROM partial_blockchain_nodes",
+    ];
+
+    for query in QUERIES {
+        tx.execute(query, &[]).await?;
+    }
+
+    Ok(())
+}
+
+/// Inserts the account state along with its code, storage and vault.
+async fn insert_account_state(
+    tx: &Transaction<'_>,
+    account_state: &AccountStateBackup,
+) -> Result<(), StoreError> {
+    let account = &account_state.account;
+
+    insert_account_code(tx, account.code()).await?;
+    insert_account_storage(tx, account.storage()).await?;
+    insert_account_asset_vault(tx, account.vault()).await?;
+    insert_account_record(tx, account, account_state.seed).await?;
+
+    if account_state.locked {
+        const QUERY: &str = "UPDATE accounts SET locked = true WHERE account_commitment = $1";
+        tx.execute(QUERY, &[&account.commitment().to_string()]).await?;
+    }
+
+    Ok(())
+}
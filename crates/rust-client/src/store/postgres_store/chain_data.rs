@@ -0,0 +1,290 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use std::{collections::BTreeSet, num::NonZeroUsize};
+
+use miden_objects::{
+    Digest,
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{InOrderIndex, MmrPeaks},
+};
+use miden_tx::utils::{Deserializable, Serializable};
+use tokio_postgres::{Client, Row, Transaction};
+
+use super::{PostgresStore, i64_to_block_number, i64_to_u64};
+use crate::{
+    store::{PartialBlockchainFilter, StoreError},
+    sync::{BlockRetentionPolicy, authentication_node_candidates, stale_authentication_nodes},
+};
+
+impl PostgresStore {
+    pub(crate) async fn insert_block_header(
+        conn: &mut Client,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: &MmrPeaks,
+        has_client_notes: bool,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        Self::insert_block_header_tx(&tx, block_header, partial_blockchain_peaks, has_client_notes)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub(crate) async fn get_block_headers(
+        conn: &mut Client,
+        block_numbers: &BTreeSet<BlockNumber>,
+    ) -> Result<Vec<(BlockHeader, bool)>, StoreError> {
+        let block_number_list = block_numbers
+            .iter()
+            .map(|block_number| i64::from(block_number.as_u32()))
+            .collect::<Vec<i64>>();
+
+        const QUERY: &str =
+            "SELECT header, has_client_notes FROM block_headers WHERE block_num = ANY($1)";
+
+        conn.query(QUERY, &[&block_number_list])
+            .await?
+            .iter()
+            .map(parse_block_header)
+            .collect()
+    }
+
+    pub(crate) async fn get_tracked_block_headers(
+        conn: &mut Client,
+    ) -> Result<Vec<BlockHeader>, StoreError> {
+        const QUERY: &str =
+            "SELECT header, has_client_notes FROM block_headers WHERE has_client_notes = true";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| parse_block_header(row).map(|(block, _)| block))
+            .collect()
+    }
+
+    pub(crate) async fn get_partial_blockchain_nodes(
+        conn: &mut Client,
+        filter: &PartialBlockchainFilter,
+    ) -> Result<BTreeMap<InOrderIndex, Digest>, StoreError> {
+        const BASE_QUERY: &str = "SELECT id, node FROM partial_blockchain_nodes";
+
+        let rows = match filter {
+            PartialBlockchainFilter::All => conn.query(BASE_QUERY, &[]).await?,
+            PartialBlockchainFilter::List(ids) => {
+                let id_values = ids
+                    .iter()
+                    // SAFETY: d.inner() is a usize casted to u64, should not fail.
+                    .map(|id| i64::try_from(id.inner()).expect("id is a valid i64"))
+                    .collect::<Vec<_>>();
+
+                conn.query(&format!("{BASE_QUERY} WHERE id = ANY($1)"), &[&id_values]).await?
+            },
+        };
+
+        rows.iter().map(parse_partial_blockchain_node).collect()
+    }
+
+    pub(crate) async fn get_partial_blockchain_peaks_by_block_num(
+        conn: &mut Client,
+        block_num: BlockNumber,
+    ) -> Result<MmrPeaks, StoreError> {
+        const QUERY: &str =
+            "SELECT partial_blockchain_peaks FROM block_headers WHERE block_num = $1";
+
+        let partial_blockchain_peaks = conn
+            .query_opt(QUERY, &[&i64::from(block_num.as_u32())])
+            .await?
+            .map(|row| row.try_get::<_, Vec<u8>>(0))
+            .transpose()?;
+
+        if let Some(partial_blockchain_peaks) = partial_blockchain_peaks {
+            return parse_partial_blockchain_peaks(block_num.as_u32(), &partial_blockchain_peaks);
+        }
+
+        Ok(MmrPeaks::new(0, vec![])?)
+    }
+
+    pub async fn insert_partial_blockchain_nodes(
+        conn: &mut Client,
+        nodes: &[(InOrderIndex, Digest)],
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        Self::insert_partial_blockchain_nodes_tx(&tx, nodes).await?;
+
+        Ok(tx.commit().await?)
+    }
+
+    /// Inserts a list of MMR authentication nodes to the Partial Blockchain nodes table.
+    pub(crate) async fn insert_partial_blockchain_nodes_tx(
+        tx: &Transaction<'_>,
+        nodes: &[(InOrderIndex, Digest)],
+    ) -> Result<(), StoreError> {
+        const QUERY: &str = "INSERT INTO partial_blockchain_nodes (id, node) VALUES ($1, $2) \
+            ON CONFLICT DO NOTHING";
+
+        for (index, node) in nodes {
+            let id = i64::try_from(index.inner()).expect("id is a valid i64");
+            tx.execute(QUERY, &[&id, &node.to_hex()]).await?;
+        }
+        Ok(())
+    }
+
+    /// Inserts a block header using a [`tokio_postgres::Transaction`].
+    ///
+    /// If the block header exists and `has_client_notes` is `true` then the `has_client_notes`
+    /// column is updated to `true` to signify that the block now contains a relevant note.
+    pub(crate) async fn insert_block_header_tx(
+        tx: &Transaction<'_>,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: &MmrPeaks,
+        has_client_notes: bool,
+    ) -> Result<(), StoreError> {
+        let block_num = block_header.block_num();
+        let header = block_header.to_bytes();
+        let partial_blockchain_peaks = partial_blockchain_peaks.peaks().to_vec().to_bytes();
+
+        const QUERY: &str = "\
+            INSERT INTO block_headers (block_num, header, partial_blockchain_peaks, has_client_notes) \
+            VALUES ($1, $2, $3, $4) \
+            ON CONFLICT DO NOTHING";
+        tx.execute(
+            QUERY,
+            &[
+                &i64::from(block_num.as_u32()),
+                &header,
+                &partial_blockchain_peaks,
+                &has_client_notes,
+            ],
+        )
+        .await?;
+
+        set_block_header_has_client_notes(tx, block_num, has_client_notes).await?;
+        Ok(())
+    }
+
+    /// Removes the block headers that the policy doesn't keep at the current sync height, along
+    /// with the partial blockchain nodes that were only needed to authenticate them.
+    pub async fn prune_block_headers(
+        conn: &mut Client,
+        policy: &BlockRetentionPolicy,
+    ) -> Result<(), StoreError> {
+        let sync_height = Self::get_sync_height(conn).await?;
+        let tx = conn.transaction().await?;
+
+        // Mirrors `BlockRetentionPolicy::retains`
+        const DELETE_QUERY: &str = "\
+            DELETE FROM block_headers
+            WHERE block_num != 0
+            AND (block_num < $1 OR block_num > $2)
+            AND NOT ($3 AND has_client_notes)
+            AND NOT ($4 AND block_num % $5 = 0)
+            AND NOT (block_num = ANY($6))
+            RETURNING block_num";
+        let pinned_blocks: Vec<i64> = policy
+            .pinned_blocks()
+            .iter()
+            .map(|block_num| i64::from(block_num.as_u32()))
+            .collect();
+        let pruned_blocks = tx
+            .query(
+                DELETE_QUERY,
+                &[
+                    &i64::from(policy.first_recent_block(sync_height).as_u32()),
+                    &i64::from(sync_height.as_u32()),
+                    &policy.keeps_relevant_blocks(),
+                    &policy.keeps_epoch_blocks(),
+                    &(1i64 << BlockNumber::EPOCH_LENGTH_EXPONENT),
+                    &pinned_blocks,
+                ],
+            )
+            .await?
+            .iter()
+            .map(|row| i64_to_block_number(row.try_get(0)?))
+            .collect::<Result<Vec<BlockNumber>, StoreError>>()?;
+
+        if !pruned_blocks.is_empty() {
+            remove_stale_partial_blockchain_nodes(&tx, &pruned_blocks, sync_height).await?;
+        }
+
+        Ok(tx.commit().await?)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Removes the partial blockchain nodes that were only needed to authenticate the pruned blocks.
+async fn remove_stale_partial_blockchain_nodes(
+    tx: &Transaction<'_>,
+    pruned_blocks: &[BlockNumber],
+    sync_height: BlockNumber,
+) -> Result<(), StoreError> {
+    let candidates: Vec<i64> = authentication_node_candidates(pruned_blocks, sync_height)
+        .into_iter()
+        .map(|id| i64::try_from(id.inner()).expect("id is a valid i64"))
+        .collect();
+
+    const SELECT_QUERY: &str = "SELECT id FROM partial_blockchain_nodes WHERE id = ANY($1)";
+    let stored_nodes = tx
+        .query(SELECT_QUERY, &[&candidates])
+        .await?
+        .iter()
+        .map(|row| {
+            let id: i64 = row.try_get(0)?;
+            let id = usize::try_from(i64_to_u64(id)).expect("id is u64, should not fail");
+            Ok(InOrderIndex::new(NonZeroUsize::new(id).expect("node ids are never zero")))
+        })
+        .collect::<Result<BTreeSet<InOrderIndex>, StoreError>>()?;
+
+    let stale_nodes: Vec<i64> = stale_authentication_nodes(pruned_blocks, stored_nodes)
+        .into_iter()
+        .map(|id| i64::try_from(id.inner()).expect("id is a valid i64"))
+        .collect();
+
+    const DELETE_QUERY: &str = "DELETE FROM partial_blockchain_nodes WHERE id = ANY($1)";
+    tx.execute(DELETE_QUERY, &[&stale_nodes]).await?;
+    Ok(())
+}
+
+fn parse_partial_blockchain_peaks(forest: u32, peaks_nodes: &[u8]) -> Result<MmrPeaks, StoreError> {
+    let mmr_peaks_nodes = Vec::<Digest>::read_from_bytes(peaks_nodes)?;
+
+    MmrPeaks::new(forest as usize, mmr_peaks_nodes).map_err(StoreError::MmrError)
+}
+
+fn parse_block_header(row: &Row) -> Result<(BlockHeader, bool), StoreError> {
+    let header: Vec<u8> = row.try_get(0)?;
+    let has_client_notes: bool = row.try_get(1)?;
+
+    Ok((BlockHeader::read_from_bytes(&header)?, has_client_notes))
+}
+
+fn parse_partial_blockchain_node(row: &Row) -> Result<(InOrderIndex, Digest), StoreError> {
+    let id: i64 = row.try_get(0)?;
+    let node: String = row.try_get(1)?;
+
+    let id = InOrderIndex::new(
+        NonZeroUsize::new(usize::try_from(i64_to_u64(id)).expect("id is u64, should not fail"))
+            .unwrap(),
+    );
+    let node: Digest = Digest::try_from(&node)?;
+    Ok((id, node))
+}
+
+pub(crate) async fn set_block_header_has_client_notes(
+    tx: &Transaction<'_>,
+    block_num: BlockNumber,
+    has_client_notes: bool,
+) -> Result<(), StoreError> {
+    // Only update to change has_client_notes to true if it was false previously
+    const QUERY: &str = "\
+    UPDATE block_headers
+        SET has_client_notes = $1
+        WHERE block_num = $2 AND has_client_notes = FALSE";
+    tx.execute(QUERY, &[&has_client_notes, &i64::from(block_num.as_u32())]).await?;
+    Ok(())
+}
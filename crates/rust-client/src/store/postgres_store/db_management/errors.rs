@@ -0,0 +1,28 @@
+use std::string::{String, ToString};
+
+use thiserror::Error;
+use tokio_postgres::Error as PostgresError;
+
+// ERRORS
+// ================================================================================================
+
+/// Errors generated from the `PostgreSQL` store.
+#[derive(Debug, Error)]
+pub enum PostgresStoreError {
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+    #[error("Migration error: {0}")]
+    MigrationError(String),
+    #[error("No settings table in the database")]
+    MissingSettingsTable,
+    #[error("Migration hashes mismatch")]
+    MigrationHashMismatch,
+    #[error("Failed to decode hex string: {0}")]
+    HexDecodeError(String),
+}
+
+impl From<PostgresError> for PostgresStoreError {
+    fn from(err: PostgresError) -> Self {
+        PostgresStoreError::DatabaseError(err.to_string())
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod errors;
+pub(crate) mod utils;
@@ -0,0 +1,161 @@
+use std::{
+    string::{String, ToString},
+    sync::LazyLock,
+    vec::Vec,
+};
+
+use miden_objects::crypto::hash::blake::{Blake3_160, Blake3Digest};
+use tokio_postgres::{Client, Transaction};
+
+use super::errors::PostgresStoreError;
+
+// MIGRATIONS
+// ================================================================================================
+
+type Hash = Blake3Digest<20>;
+
+const MIGRATION_SCRIPTS: [&str; 4] = [
+    include_str!("../store.sql"),
+    include_str!("../note_discovery_rules.sql"),
+    include_str!("../transaction_queue.sql"),
+    include_str!("../discovered_notes.sql"),
+];
+static MIGRATION_HASHES: LazyLock<Vec<Hash>> = LazyLock::new(compute_migration_hashes);
+
+const DB_MIGRATION_HASH_FIELD: &str = "db-migration-hash";
+const DB_MIGRATION_VERSION_FIELD: &str = "db-migration-version";
+
+/// Key of the transaction-level advisory lock taken while migrating, so that client instances
+/// starting at the same time against the same database don't apply the migrations twice.
+const MIGRATION_LOCK_KEY: i64 = 0x6d69_6465_6e5f_6462;
+
+/// Applies the pending migrations to the database.
+///
+/// All migrations are applied within a single transaction, so a failed migration leaves the
+/// database untouched.
+pub async fn apply_migrations(conn: &mut Client) -> Result<(), PostgresStoreError> {
+    let tx = conn.transaction().await?;
+    tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY]).await?;
+
+    let version_before = if table_exists(&tx, "settings").await? {
+        let version: usize = get_settings_value(&tx, DB_MIGRATION_VERSION_FIELD)
+            .await?
+            .ok_or(PostgresStoreError::MissingSettingsTable)?
+            .parse()
+            .map_err(|_| {
+                PostgresStoreError::DatabaseError("Invalid migration version".to_string())
+            })?;
+
+        if version == 0 || version > MIGRATION_SCRIPTS.len() {
+            return Err(PostgresStoreError::MigrationError(format!(
+                "unknown migration version {version}"
+            )));
+        }
+
+        let expected_hash = &*MIGRATION_HASHES[version - 1];
+        let actual_hash =
+            hex::decode(get_settings_value(&tx, DB_MIGRATION_HASH_FIELD).await?.ok_or_else(
+                || PostgresStoreError::DatabaseError("Migration hash not found".to_string()),
+            )?)
+            .map_err(|e| PostgresStoreError::HexDecodeError(e.to_string()))?;
+
+        if actual_hash != expected_hash {
+            return Err(PostgresStoreError::MigrationHashMismatch);
+        }
+
+        version
+    } else {
+        0
+    };
+
+    for script in &MIGRATION_SCRIPTS[version_before..] {
+        tx.batch_execute(script)
+            .await
+            .map_err(|err| PostgresStoreError::MigrationError(err.to_string()))?;
+    }
+
+    if version_before != MIGRATION_SCRIPTS.len() {
+        let new_hash = hex::encode(&*MIGRATION_HASHES[MIGRATION_HASHES.len() - 1]);
+        set_settings_value(&tx, DB_MIGRATION_HASH_FIELD, &new_hash).await?;
+        set_settings_value(&tx, DB_MIGRATION_VERSION_FIELD, &MIGRATION_SCRIPTS.len().to_string())
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Creates the tenants registry if it doesn't exist yet.
+///
+/// The registry is shared by all the schemas of the database, so it's created outside of the
+/// per-schema migrations, under the same lock.
+pub async fn create_tenants_table(conn: &mut Client) -> Result<(), PostgresStoreError> {
+    let tx = conn.transaction().await?;
+    tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY]).await?;
+    tx.batch_execute(include_str!("../tenants.sql"))
+        .await
+        .map_err(|err| PostgresStoreError::MigrationError(err.to_string()))?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+fn compute_migration_hashes() -> Vec<Hash> {
+    let mut accumulator = Hash::default();
+    MIGRATION_SCRIPTS
+        .iter()
+        .map(|sql| {
+            let script_hash = Blake3_160::hash(remove_spaces(sql).as_bytes());
+            accumulator = Blake3_160::merge(&[accumulator, script_hash]);
+            accumulator
+        })
+        .collect()
+}
+
+fn remove_spaces(str: &str) -> String {
+    str.chars().filter(|chr| !chr.is_whitespace()).collect()
+}
+
+pub async fn get_settings_value(
+    tx: &Transaction<'_>,
+    name: &str,
+) -> Result<Option<String>, tokio_postgres::Error> {
+    Ok(tx
+        .query_opt("SELECT value FROM settings WHERE name = $1", &[&name])
+        .await?
+        .map(|row| row.get(0)))
+}
+
+pub async fn set_settings_value(
+    tx: &Transaction<'_>,
+    name: &str,
+    value: &str,
+) -> Result<(), tokio_postgres::Error> {
+    let count = tx
+        .execute(
+            "INSERT INTO settings (name, value) VALUES ($1, $2) \
+             ON CONFLICT (name) DO UPDATE SET value = EXCLUDED.value",
+            &[&name, &value],
+        )
+        .await?;
+
+    debug_assert_eq!(count, 1);
+
+    Ok(())
+}
+
+/// Checks if a table exists in the current schema of the database.
+pub async fn table_exists(
+    tx: &Transaction<'_>,
+    table_name: &str,
+) -> Result<bool, tokio_postgres::Error> {
+    Ok(tx
+        .query_opt(
+            "SELECT 1 FROM information_schema.tables \
+             WHERE table_schema = current_schema() AND table_name = $1",
+            &[&table_name],
+        )
+        .await?
+        .is_some())
+}
@@ -0,0 +1,18 @@
+use alloc::string::ToString;
+
+use crate::store::StoreError;
+
+// STORE ERROR
+// ================================================================================================
+
+impl From<tokio_postgres::Error> for StoreError {
+    fn from(value: tokio_postgres::Error) -> Self {
+        // Errors reported by the server (constraint violations, malformed queries, etc.) carry a
+        // SQLSTATE code, while connection and conversion errors don't.
+        if value.code().is_some() {
+            Self::QueryError(value.to_string())
+        } else {
+            Self::DatabaseError(value.to_string())
+        }
+    }
+}
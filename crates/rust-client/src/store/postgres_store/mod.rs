@@ -0,0 +1,628 @@
+//! This module provides a PostgreSQL-backed implementation of the [Store] trait.
+//!
+//! [`PostgresStore`] enables the persistence of accounts, transactions, notes, block headers, and
+//! MMR nodes using a `PostgreSQL` database, which allows many client instances to run as part of a
+//! server-side deployment.
+//! It is compiled only when the `postgres` feature flag is enabled.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use std::{format, string::ToString};
+
+use db_management::utils::apply_migrations;
+use deadpool_postgres::{Hook, HookError, Manager, ManagerConfig, Object, Pool, RecyclingMethod};
+use miden_objects::{
+    Digest, Word,
+    account::{Account, AccountCode, AccountHeader, AccountId, AccountStorage},
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{InOrderIndex, MmrPeaks},
+    note::{NoteId, NoteTag, Nullifier},
+    transaction::TransactionId,
+};
+use miden_tx::utils::sync::RwLock;
+pub use tokio_postgres::Config;
+use tokio_postgres::NoTls;
+use tonic::async_trait;
+
+use super::{
+    AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, OutputNoteRecord,
+    PartialBlockchainFilter, PruneSummary, RetentionPolicy, Store, StoreBackup, TenantId,
+    TransactionFilter,
+};
+use crate::{
+    store::StoreError,
+    sync::{
+        BlockRetentionPolicy, ChainRollback, NoteDiscoveryRule, NoteTagRecord, StateSyncUpdate,
+    },
+    transaction::{QueuedTransaction, TransactionRecord, TransactionStoreUpdate},
+};
+
+mod account;
+mod backup;
+mod chain_data;
+mod db_management;
+mod errors;
+mod note;
+mod prune;
+mod sync;
+mod tenant;
+mod transaction;
+
+/// Default maximum number of connections kept by the pool.
+const DEFAULT_MAX_CONNECTIONS: usize = 16;
+
+// POSTGRES STORE
+// ================================================================================================
+
+/// Represents a pool of connections with a `PostgreSQL` database. The pool is used to interact
+/// concurrently with the underlying database in a safe and efficient manner.
+///
+/// Every client instance should use its own database, its own schema (selected through the
+/// `search_path` option of the connection [`Config`]) or its own tenant (see
+/// [`Store::with_tenant`]). Each tenant keeps its data in a dedicated schema and gets its own
+/// connection pool, whose connections point to that schema.
+///
+/// Current table definitions can be found at `store.sql` migration file.
+pub struct PostgresStore {
+    pub(crate) pool: Pool,
+    /// Configuration used to open the connections of the tenant pools.
+    config: Config,
+    /// Maximum number of connections kept by each pool.
+    max_connections: usize,
+    /// Schema the store was opened on. Tenants are registered under this schema.
+    root_schema: String,
+    /// Connection pools of the tenants, by schema, shared by all the handles of the store.
+    tenant_pools: Arc<RwLock<BTreeMap<String, Pool>>>,
+}
+
+impl PostgresStore {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new instance of [Store] connected to the database described by `config`, with a
+    /// pool of up to 16 connections.
+    ///
+    /// Pending migrations are applied before returning.
+    pub async fn new(config: Config) -> Result<Self, StoreError> {
+        Self::with_max_connections(config, DEFAULT_MAX_CONNECTIONS).await
+    }
+
+    /// Returns a new instance of [Store] connected to the database described by `config`, with a
+    /// pool of up to `max_connections` connections.
+    ///
+    /// Pending migrations are applied before returning.
+    pub async fn with_max_connections(
+        config: Config,
+        max_connections: usize,
+    ) -> Result<Self, StoreError> {
+        let pool = build_pool(config.clone(), max_connections, None)?;
+
+        let conn = pool.get().await.map_err(|err| StoreError::DatabaseError(err.to_string()))?;
+        let root_schema: Option<String> =
+            conn.query_one("SELECT current_schema()", &[]).await?.get(0);
+        let root_schema = root_schema.ok_or_else(|| {
+            StoreError::DatabaseError("no schema in the connection's search path".to_string())
+        })?;
+        drop(conn);
+
+        let store = PostgresStore {
+            pool,
+            config,
+            max_connections,
+            root_schema,
+            tenant_pools: Arc::new(RwLock::new(BTreeMap::new())),
+        };
+
+        let mut conn = store.get_connection().await?;
+        apply_migrations(&mut conn)
+            .await
+            .map_err(|err| StoreError::DatabaseError(err.to_string()))?;
+
+        Ok(store)
+    }
+
+    /// Returns a connection from the store's pool.
+    async fn get_connection(&self) -> Result<Object, StoreError> {
+        self.pool.get().await.map_err(|err| StoreError::DatabaseError(err.to_string()))
+    }
+}
+
+/// Builds a pool of up to `max_connections` connections to the database described by `config`.
+///
+/// If a `schema` is provided, the search path of every connection is set to it once, right after
+/// the connection is created. Connections are recycled with [`RecyclingMethod::Fast`], which keeps
+/// the session's settings.
+fn build_pool(
+    config: Config,
+    max_connections: usize,
+    schema: Option<&str>,
+) -> Result<Pool, StoreError> {
+    let manager = Manager::from_config(
+        config,
+        NoTls,
+        ManagerConfig { recycling_method: RecyclingMethod::Fast },
+    );
+    let mut builder = Pool::builder(manager).max_size(max_connections);
+
+    if let Some(schema) = schema {
+        let query = format!("SET search_path TO {}", tenant::quote_identifier(schema));
+        builder = builder.post_create(Hook::async_fn(move |conn, _| {
+            let query = query.clone();
+            Box::pin(async move { conn.batch_execute(&query).await.map_err(HookError::Backend) })
+        }));
+    }
+
+    builder.build().map_err(|err| StoreError::DatabaseError(err.to_string()))
+}
+
+// PostgreSQL implementation of the Store trait
+//
+// As with the SQLite store, all implementations rely on inner PostgresStore functions that map 1:1
+// by name, grouped by entity types in their own sub-modules.
+#[async_trait]
+impl Store for PostgresStore {
+    fn get_current_timestamp(&self) -> Option<u64> {
+        let now = chrono::Utc::now();
+        Some(u64::try_from(now.timestamp()).expect("timestamp is always after epoch"))
+    }
+
+    async fn get_note_tags(&self) -> Result<Vec<NoteTagRecord>, StoreError> {
+        PostgresStore::get_note_tags(&mut self.get_connection().await?).await
+    }
+
+    async fn get_unique_note_tags(&self) -> Result<BTreeSet<NoteTag>, StoreError> {
+        PostgresStore::get_unique_note_tags(&mut self.get_connection().await?).await
+    }
+
+    async fn add_note_tag(&self, tag: NoteTagRecord) -> Result<bool, StoreError> {
+        PostgresStore::add_note_tag(&mut self.get_connection().await?, tag).await
+    }
+
+    async fn remove_note_tag(&self, tag: NoteTagRecord) -> Result<usize, StoreError> {
+        PostgresStore::remove_note_tag(&mut self.get_connection().await?, tag).await
+    }
+
+    async fn get_note_discovery_rules(&self) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        PostgresStore::get_note_discovery_rules(&mut self.get_connection().await?).await
+    }
+
+    async fn add_note_discovery_rule(&self, rule: NoteDiscoveryRule) -> Result<bool, StoreError> {
+        PostgresStore::add_note_discovery_rule(&mut self.get_connection().await?, rule).await
+    }
+
+    async fn remove_note_discovery_rule(
+        &self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        PostgresStore::remove_note_discovery_rule(&mut self.get_connection().await?, rule).await
+    }
+
+    async fn get_discovered_notes(&self) -> Result<Vec<NoteId>, StoreError> {
+        PostgresStore::get_discovered_notes(&mut self.get_connection().await?).await
+    }
+
+    async fn remove_discovered_notes(&self, note_ids: &[NoteId]) -> Result<(), StoreError> {
+        PostgresStore::remove_discovered_notes(&mut self.get_connection().await?, note_ids).await
+    }
+
+    async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
+        PostgresStore::get_sync_height(&mut self.get_connection().await?).await
+    }
+
+    async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError> {
+        PostgresStore::apply_state_sync(&mut self.get_connection().await?, state_sync_update).await
+    }
+
+    async fn rollback_to(&self, rollback: ChainRollback) -> Result<(), StoreError> {
+        PostgresStore::rollback_to(&mut self.get_connection().await?, &rollback).await
+    }
+
+    async fn get_transactions(
+        &self,
+        transaction_filter: TransactionFilter,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        PostgresStore::get_transactions(&mut self.get_connection().await?, &transaction_filter)
+            .await
+    }
+
+    async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError> {
+        PostgresStore::apply_transaction(&mut self.get_connection().await?, &tx_update).await
+    }
+
+    async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, StoreError> {
+        PostgresStore::get_queued_transactions(&mut self.get_connection().await?).await
+    }
+
+    async fn upsert_queued_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        PostgresStore::upsert_queued_transaction(
+            &mut self.get_connection().await?,
+            queued_transaction,
+        )
+        .await
+    }
+
+    async fn remove_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        PostgresStore::remove_queued_transaction(&mut self.get_connection().await?, transaction_id)
+            .await
+    }
+
+    async fn get_input_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        PostgresStore::get_input_notes(&mut self.get_connection().await?, &filter).await
+    }
+
+    async fn get_output_notes(
+        &self,
+        note_filter: NoteFilter,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        PostgresStore::get_output_notes(&mut self.get_connection().await?, &note_filter).await
+    }
+
+    async fn upsert_input_notes(&self, notes: &[InputNoteRecord]) -> Result<(), StoreError> {
+        PostgresStore::upsert_input_notes(&mut self.get_connection().await?, notes).await
+    }
+
+    async fn insert_block_header(
+        &self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: MmrPeaks,
+        has_client_notes: bool,
+    ) -> Result<(), StoreError> {
+        PostgresStore::insert_block_header(
+            &mut self.get_connection().await?,
+            block_header,
+            &partial_blockchain_peaks,
+            has_client_notes,
+        )
+        .await
+    }
+
+    async fn prune_block_headers(&self, policy: &BlockRetentionPolicy) -> Result<(), StoreError> {
+        PostgresStore::prune_block_headers(&mut self.get_connection().await?, policy).await
+    }
+
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<PruneSummary, StoreError> {
+        PostgresStore::prune(&mut self.get_connection().await?, policy).await
+    }
+
+    async fn import_backup(&self, backup: StoreBackup) -> Result<(), StoreError> {
+        PostgresStore::import_backup(&mut self.get_connection().await?, &backup).await
+    }
+
+    async fn get_block_headers(
+        &self,
+        block_numbers: &BTreeSet<BlockNumber>,
+    ) -> Result<Vec<(BlockHeader, bool)>, StoreError> {
+        PostgresStore::get_block_headers(&mut self.get_connection().await?, block_numbers).await
+    }
+
+    async fn get_tracked_block_headers(&self) -> Result<Vec<BlockHeader>, StoreError> {
+        PostgresStore::get_tracked_block_headers(&mut self.get_connection().await?).await
+    }
+
+    async fn get_partial_blockchain_nodes(
+        &self,
+        filter: PartialBlockchainFilter,
+    ) -> Result<BTreeMap<InOrderIndex, Digest>, StoreError> {
+        PostgresStore::get_partial_blockchain_nodes(&mut self.get_connection().await?, &filter)
+            .await
+    }
+
+    async fn insert_partial_blockchain_nodes(
+        &self,
+        nodes: &[(InOrderIndex, Digest)],
+    ) -> Result<(), StoreError> {
+        PostgresStore::insert_partial_blockchain_nodes(&mut self.get_connection().await?, nodes)
+            .await
+    }
+
+    async fn get_partial_blockchain_peaks_by_block_num(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<MmrPeaks, StoreError> {
+        PostgresStore::get_partial_blockchain_peaks_by_block_num(
+            &mut self.get_connection().await?,
+            block_num,
+        )
+        .await
+    }
+
+    async fn insert_account(
+        &self,
+        account: &Account,
+        account_seed: Option<Word>,
+    ) -> Result<(), StoreError> {
+        PostgresStore::insert_account(&mut self.get_connection().await?, account, account_seed)
+            .await
+    }
+
+    async fn update_account(&self, account: &Account) -> Result<(), StoreError> {
+        PostgresStore::update_account(&mut self.get_connection().await?, account).await
+    }
+
+    async fn get_account_ids(&self) -> Result<Vec<AccountId>, StoreError> {
+        PostgresStore::get_account_ids(&mut self.get_connection().await?).await
+    }
+
+    async fn get_account_headers(&self) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
+        PostgresStore::get_account_headers(&mut self.get_connection().await?).await
+    }
+
+    async fn get_account_header(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
+        PostgresStore::get_account_header(&mut self.get_connection().await?, account_id).await
+    }
+
+    async fn get_account_header_by_commitment(
+        &self,
+        account_commitment: Digest,
+    ) -> Result<Option<AccountHeader>, StoreError> {
+        PostgresStore::get_account_header_by_commitment(
+            &mut self.get_connection().await?,
+            account_commitment,
+        )
+        .await
+    }
+
+    async fn get_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        PostgresStore::get_account(&mut self.get_connection().await?, account_id).await
+    }
+
+    async fn get_accounts_code_and_storage(
+        &self,
+    ) -> Result<Vec<(AccountId, AccountCode, AccountStorage)>, StoreError> {
+        PostgresStore::get_accounts_code_and_storage(&mut self.get_connection().await?).await
+    }
+
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        PostgresStore::get_account_history(&mut self.get_connection().await?, account_id).await
+    }
+
+    async fn upsert_foreign_account_code(
+        &self,
+        account_id: AccountId,
+        code: AccountCode,
+    ) -> Result<(), StoreError> {
+        PostgresStore::upsert_foreign_account_code(
+            &mut self.get_connection().await?,
+            account_id,
+            &code,
+        )
+        .await
+    }
+
+    async fn get_foreign_account_code(
+        &self,
+        account_ids: Vec<AccountId>,
+    ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError> {
+        PostgresStore::get_foreign_account_code(&mut self.get_connection().await?, account_ids)
+            .await
+    }
+
+    async fn get_unspent_input_note_nullifiers(&self) -> Result<Vec<Nullifier>, StoreError> {
+        PostgresStore::get_unspent_input_note_nullifiers(&mut self.get_connection().await?).await
+    }
+
+    async fn with_tenant(&self, tenant_id: TenantId) -> Result<Arc<dyn Store>, StoreError> {
+        Ok(Arc::new(self.tenant_store(tenant_id).await?))
+    }
+
+    async fn get_tenants(&self) -> Result<Vec<TenantId>, StoreError> {
+        PostgresStore::get_tenants(self).await
+    }
+}
+
+// UTILS
+// ================================================================================================
+
+/// Converts a `u64` into an `i64` to be stored in a `BIGINT` column.
+///
+/// `PostgreSQL` has no unsigned integer types. Note that the `as` operator performs a lossless
+/// conversion from `u64` to `i64`.
+pub fn u64_to_i64(value: u64) -> i64 {
+    #[allow(
+        clippy::cast_possible_wrap,
+        reason = "We store u64 as i64 as postgres only allows the latter."
+    )]
+    let value = value as i64;
+    value
+}
+
+/// Converts an `i64` retrieved from a `BIGINT` column back into the original `u64`.
+pub fn i64_to_u64(value: i64) -> u64 {
+    #[allow(
+        clippy::cast_sign_loss,
+        reason = "We store u64 as i64 as postgres only allows the latter."
+    )]
+    let value = value as u64;
+    value
+}
+
+/// Converts a block number retrieved from a `BIGINT` column into a [`BlockNumber`].
+pub(crate) fn i64_to_block_number(value: i64) -> Result<BlockNumber, StoreError> {
+    u32::try_from(value)
+        .map(BlockNumber::from)
+        .map_err(|err| StoreError::ParsingError(err.to_string()))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+pub mod tests {
+    use std::{boxed::Box, env, format, sync::Arc};
+
+    use miden_objects::{
+        account::{AccountCode, AccountComponent, AccountType},
+        testing::account_component::BASIC_WALLET_CODE,
+    };
+    use uuid::Uuid;
+
+    use super::{Config, PostgresStore};
+    use crate::{
+        store::{NoteFilter, Store, TenantId, TransactionFilter, sqlite_store::SqliteStore},
+        sync::{NoteTagRecord, NoteTagSource},
+        tests::{create_test_store_path, run_store_scenario},
+    };
+
+    /// Environment variable holding the connection string of the database used by the tests.
+    const TEST_DATABASE_URL_VAR: &str = "MIDEN_CLIENT_TEST_POSTGRES_URL";
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn is_send_sync() {
+        assert_send_sync::<PostgresStore>();
+        assert_send_sync::<Box<dyn Store>>();
+    }
+
+    /// Creates a new schema on the test database and returns a connection config that uses it, so
+    /// tests don't share any state.
+    async fn create_test_config() -> Config {
+        let database_url = env::var(TEST_DATABASE_URL_VAR)
+            .unwrap_or_else(|_| panic!("{TEST_DATABASE_URL_VAR} should be set"));
+        let mut config: Config = database_url.parse().unwrap();
+
+        let schema = format!("test_{}", Uuid::new_v4().simple());
+        let (client, connection) = config.connect(tokio_postgres::NoTls).await.unwrap();
+        tokio::spawn(connection);
+        client.batch_execute(&format!("CREATE SCHEMA {schema}")).await.unwrap();
+
+        config.options(format!("-c search_path={schema}"));
+        config
+    }
+
+    pub(crate) async fn create_test_store() -> PostgresStore {
+        PostgresStore::new(create_test_config().await).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Postgres instance"]
+    async fn migrations_are_idempotent() {
+        let config = create_test_config().await;
+        PostgresStore::new(config.clone()).await.unwrap();
+
+        // Re-opening the store checks the applied migrations and doesn't apply them again
+        let store = PostgresStore::new(config).await.unwrap();
+        assert_eq!(store.get_sync_height().await.unwrap().as_u32(), 0);
+        assert!(store.get_input_notes(NoteFilter::All).await.unwrap().is_empty());
+        assert!(store.get_transactions(TransactionFilter::All).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Postgres instance"]
+    async fn note_tags_round_trip() {
+        let store = create_test_store().await;
+        let tag = NoteTagRecord {
+            tag: 10.into(),
+            source: NoteTagSource::User,
+        };
+
+        assert!(store.add_note_tag(tag).await.unwrap());
+        assert!(!store.add_note_tag(tag).await.unwrap());
+        assert_eq!(store.get_note_tags().await.unwrap(), vec![tag]);
+
+        assert_eq!(store.remove_note_tag(tag).await.unwrap(), 1);
+        assert!(store.get_note_tags().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Postgres instance"]
+    async fn tenants_are_isolated() {
+        let store = create_test_store().await;
+        let tenant_a = store.with_tenant(TenantId::new("a")).await.unwrap();
+        let tenant_b = store.with_tenant(TenantId::new("b")).await.unwrap();
+        let tag = NoteTagRecord {
+            tag: 10.into(),
+            source: NoteTagSource::User,
+        };
+
+        assert!(tenant_a.add_note_tag(tag).await.unwrap());
+        assert_eq!(tenant_a.get_note_tags().await.unwrap(), vec![tag]);
+        assert!(tenant_b.get_note_tags().await.unwrap().is_empty());
+        assert!(store.get_note_tags().await.unwrap().is_empty());
+
+        // Re-opening a tenant keeps its data
+        let tenant_a = tenant_b.with_tenant(TenantId::new("a")).await.unwrap();
+        assert_eq!(tenant_a.get_note_tags().await.unwrap(), vec![tag]);
+        assert_eq!(
+            store.get_tenants().await.unwrap(),
+            vec![TenantId::new("a"), TenantId::new("b")]
+        );
+        assert_eq!(tenant_a.get_tenants().await.unwrap(), store.get_tenants().await.unwrap());
+
+        // The tenants registry isn't part of the store's schemas
+        let conn = store.get_connection().await.unwrap();
+        let has_registry: bool = conn
+            .query_one("SELECT to_regclass('tenants') IS NOT NULL", &[])
+            .await
+            .unwrap()
+            .get(0);
+        assert!(!has_registry);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Postgres instance"]
+    async fn sync_transactions_and_rollback_match_sqlite() {
+        // Sync updates, transactions, rollbacks, account history and note state transitions are
+        // all written by hand for each backend
+        let sqlite_store = SqliteStore::new(create_test_store_path()).await.unwrap();
+        let expected = run_store_scenario(Arc::new(sqlite_store)).await;
+
+        let snapshots = run_store_scenario(Arc::new(create_test_store().await)).await;
+        assert_eq!(snapshots, expected);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Postgres instance"]
+    async fn foreign_account_code_round_trip() {
+        let store = create_test_store().await;
+        let assembler = miden_lib::transaction::TransactionKernel::assembler();
+        let account_component = AccountComponent::compile(BASIC_WALLET_CODE, assembler, vec![])
+            .unwrap()
+            .with_supports_all_types();
+        let account_code = AccountCode::from_components(
+            &[account_component],
+            AccountType::RegularAccountUpdatableCode,
+        )
+        .unwrap();
+        let account_id =
+            miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE
+                .try_into()
+                .unwrap();
+
+        // Upserting twice doesn't fail on the already existing code
+        store
+            .upsert_foreign_account_code(account_id, account_code.clone())
+            .await
+            .unwrap();
+        store
+            .upsert_foreign_account_code(account_id, account_code.clone())
+            .await
+            .unwrap();
+
+        let code = store.get_foreign_account_code(vec![account_id]).await.unwrap();
+        assert_eq!(code.get(&account_id), Some(&account_code));
+    }
+}
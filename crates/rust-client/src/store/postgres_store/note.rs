@@ -0,0 +1,400 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest, Word,
+    block::BlockNumber,
+    crypto::utils::{Deserializable, Serializable},
+    note::{
+        NoteAssets, NoteDetails, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, Nullifier,
+    },
+};
+use tokio_postgres::{Client, Row, Transaction};
+
+use super::{PostgresStore, chain_data::set_block_header_has_client_notes, i64_to_u64, u64_to_i64};
+use crate::{
+    note::NoteUpdateTracker,
+    store::{
+        NoteFilter, StoreError,
+        note_record::{InputNoteRecord, InputNoteState, OutputNoteRecord, OutputNoteState},
+    },
+};
+
+// NOTE FILTER
+// ================================================================================================
+
+/// Returns the condition used to filter notes by ID or nullifier, along with the list of values it
+/// expects as its only parameter. Returns `None` for filters that don't need parameters.
+fn note_filter_list_condition(filter: &NoteFilter) -> Option<(&'static str, Vec<String>)> {
+    match filter {
+        NoteFilter::Unique(note_id) => {
+            Some(("note.note_id = ANY($1)", vec![note_id.inner().to_string()]))
+        },
+        NoteFilter::List(note_ids) => Some((
+            "note.note_id = ANY($1)",
+            note_ids.iter().map(|note_id| note_id.inner().to_string()).collect(),
+        )),
+        NoteFilter::Nullifiers(nullifiers) => {
+            Some(("note.nullifier = ANY($1)", nullifiers.iter().map(ToString::to_string).collect()))
+        },
+        _ => None,
+    }
+}
+
+/// Returns a [String] containing the output notes query conditions for the filter and the
+/// values of its parameter, if any.
+fn output_notes_condition(filter: &NoteFilter) -> (String, Option<Vec<String>>) {
+    if let Some((condition, params)) = note_filter_list_condition(filter) {
+        return (condition.to_string(), Some(params));
+    }
+
+    let condition = match filter {
+        NoteFilter::Committed => {
+            format!(
+                "state_discriminant in ({}, {})",
+                OutputNoteState::STATE_COMMITTED_PARTIAL,
+                OutputNoteState::STATE_COMMITTED_FULL
+            )
+        },
+        NoteFilter::Consumed => {
+            format!("state_discriminant = {}", OutputNoteState::STATE_CONSUMED)
+        },
+        NoteFilter::Expected => {
+            format!(
+                "state_discriminant in ({}, {})",
+                OutputNoteState::STATE_EXPECTED_PARTIAL,
+                OutputNoteState::STATE_EXPECTED_FULL
+            )
+        },
+        // There are no processing or unverified output notes
+        NoteFilter::Processing | NoteFilter::Unverified => "1 = 0".to_string(),
+        NoteFilter::Unspent => {
+            format!(
+                "state_discriminant in ({}, {})",
+                OutputNoteState::STATE_EXPECTED_FULL,
+                OutputNoteState::STATE_COMMITTED_FULL,
+            )
+        },
+        NoteFilter::All
+        | NoteFilter::Unique(_)
+        | NoteFilter::List(_)
+        | NoteFilter::Nullifiers(_) => "1 = 1".to_string(),
+    };
+
+    (condition, None)
+}
+
+/// Returns a [String] containing the input notes query conditions for the filter and the
+/// values of its parameter, if any.
+fn input_notes_condition(filter: &NoteFilter) -> (String, Option<Vec<String>>) {
+    if let Some((condition, params)) = note_filter_list_condition(filter) {
+        return (condition.to_string(), Some(params));
+    }
+
+    let condition = match filter {
+        NoteFilter::Committed => {
+            format!("state_discriminant = {}", InputNoteState::STATE_COMMITTED)
+        },
+        NoteFilter::Consumed => {
+            format!(
+                "state_discriminant in ({}, {}, {})",
+                InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL,
+                InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL,
+                InputNoteState::STATE_CONSUMED_EXTERNAL
+            )
+        },
+        NoteFilter::Expected => {
+            format!("state_discriminant = {}", InputNoteState::STATE_EXPECTED)
+        },
+        NoteFilter::Processing => {
+            format!(
+                "state_discriminant in ({}, {})",
+                InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+                InputNoteState::STATE_PROCESSING_UNAUTHENTICATED
+            )
+        },
+        NoteFilter::Unverified => {
+            format!("state_discriminant = {}", InputNoteState::STATE_UNVERIFIED)
+        },
+        NoteFilter::Unspent => {
+            format!(
+                "state_discriminant in ({}, {}, {}, {}, {})",
+                InputNoteState::STATE_EXPECTED,
+                InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+                InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+                InputNoteState::STATE_UNVERIFIED,
+                InputNoteState::STATE_COMMITTED
+            )
+        },
+        NoteFilter::All
+        | NoteFilter::Unique(_)
+        | NoteFilter::List(_)
+        | NoteFilter::Nullifiers(_) => "1 = 1".to_string(),
+    };
+
+    (condition, None)
+}
+
+/// Runs `query` with the optional list parameter produced by a note filter.
+async fn query_notes(
+    conn: &Client,
+    query: &str,
+    params: Option<Vec<String>>,
+) -> Result<Vec<Row>, StoreError> {
+    Ok(match params {
+        Some(params) => conn.query(query, &[&params]).await?,
+        None => conn.query(query, &[]).await?,
+    })
+}
+
+// NOTES STORE METHODS
+// --------------------------------------------------------------------------------------------
+
+impl PostgresStore {
+    pub(crate) async fn get_input_notes(
+        conn: &mut Client,
+        filter: &NoteFilter,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        const BASE_QUERY: &str = "SELECT
+                note.assets,
+                note.serial_number,
+                note.inputs,
+                script.serialized_note_script,
+                note.state,
+                note.created_at
+                FROM input_notes AS note
+                LEFT OUTER JOIN notes_scripts AS script
+                    ON note.script_root = script.script_root";
+
+        let (condition, params) = input_notes_condition(filter);
+        let query = format!("{BASE_QUERY} WHERE {condition}");
+
+        query_notes(conn, &query, params)
+            .await?
+            .iter()
+            .map(parse_input_note)
+            .collect::<Result<Vec<InputNoteRecord>, _>>()
+    }
+
+    /// Retrieves the output notes from the database.
+    pub(crate) async fn get_output_notes(
+        conn: &mut Client,
+        filter: &NoteFilter,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        const BASE_QUERY: &str = "SELECT
+                    note.recipient_digest,
+                    note.assets,
+                    note.metadata,
+                    note.expected_height,
+                    note.state
+                    FROM output_notes AS note";
+
+        let (condition, params) = output_notes_condition(filter);
+        let query = format!("{BASE_QUERY} WHERE {condition}");
+
+        query_notes(conn, &query, params)
+            .await?
+            .iter()
+            .map(parse_output_note)
+            .collect::<Result<Vec<OutputNoteRecord>, _>>()
+    }
+
+    pub(crate) async fn upsert_input_notes(
+        conn: &mut Client,
+        notes: &[InputNoteRecord],
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        for note in notes {
+            upsert_input_note_tx(&tx, note).await?;
+
+            // Whenever we insert a note, we also update block relevance
+            if let Some(inclusion_proof) = note.inclusion_proof() {
+                set_block_header_has_client_notes(
+                    &tx,
+                    inclusion_proof.location().block_num(),
+                    true,
+                )
+                .await?;
+            }
+        }
+
+        Ok(tx.commit().await?)
+    }
+
+    pub(crate) async fn get_unspent_input_note_nullifiers(
+        conn: &mut Client,
+    ) -> Result<Vec<Nullifier>, StoreError> {
+        const QUERY: &str =
+            "SELECT nullifier FROM input_notes WHERE NOT (state_discriminant = ANY($1))";
+        let spent_states = vec![
+            i16::from(InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL),
+            i16::from(InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL),
+            i16::from(InputNoteState::STATE_CONSUMED_EXTERNAL),
+        ];
+
+        conn.query(QUERY, &[&spent_states])
+            .await?
+            .iter()
+            .map(|row| {
+                let nullifier: String = row.try_get(0)?;
+                Digest::try_from(nullifier)
+                    .map(Nullifier::from)
+                    .map_err(StoreError::HexParseError)
+            })
+            .collect::<Result<Vec<Nullifier>, _>>()
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Inserts the provided input note into the database, if the note already exists, it will be
+/// replaced.
+pub(super) async fn upsert_input_note_tx(
+    tx: &Transaction<'_>,
+    note: &InputNoteRecord,
+) -> Result<(), StoreError> {
+    let details = note.details();
+    let recipient = details.recipient();
+    let script_root = recipient.script().root().to_hex();
+
+    const SCRIPT_QUERY: &str = "\
+        INSERT INTO notes_scripts (script_root, serialized_note_script) VALUES ($1, $2) \
+        ON CONFLICT (script_root) DO UPDATE SET serialized_note_script = EXCLUDED.serialized_note_script";
+    tx.execute(SCRIPT_QUERY, &[&script_root, &recipient.script().to_bytes()])
+        .await?;
+
+    const NOTE_QUERY: &str = "\
+        INSERT INTO input_notes (note_id, assets, serial_number, inputs, script_root, nullifier, state_discriminant, state, created_at) \
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+        ON CONFLICT (note_id) DO UPDATE SET \
+            assets = EXCLUDED.assets, \
+            serial_number = EXCLUDED.serial_number, \
+            inputs = EXCLUDED.inputs, \
+            script_root = EXCLUDED.script_root, \
+            nullifier = EXCLUDED.nullifier, \
+            state_discriminant = EXCLUDED.state_discriminant, \
+            state = EXCLUDED.state, \
+            created_at = EXCLUDED.created_at";
+
+    tx.execute(
+        NOTE_QUERY,
+        &[
+            &note.id().inner().to_string(),
+            &details.assets().to_bytes(),
+            &recipient.serial_num().to_bytes(),
+            &recipient.inputs().to_bytes(),
+            &script_root,
+            &note.nullifier().to_hex(),
+            &i16::from(note.state().discriminant()),
+            &note.state().to_bytes(),
+            &u64_to_i64(note.created_at().unwrap_or(0)),
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Inserts the provided output note into the database, if the note already exists, it will be
+/// replaced.
+pub(super) async fn upsert_output_note_tx(
+    tx: &Transaction<'_>,
+    note: &OutputNoteRecord,
+) -> Result<(), StoreError> {
+    const NOTE_QUERY: &str = "\
+        INSERT INTO output_notes (note_id, assets, recipient_digest, metadata, nullifier, expected_height, state_discriminant, state) \
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+        ON CONFLICT (note_id) DO UPDATE SET \
+            assets = EXCLUDED.assets, \
+            recipient_digest = EXCLUDED.recipient_digest, \
+            metadata = EXCLUDED.metadata, \
+            nullifier = EXCLUDED.nullifier, \
+            expected_height = EXCLUDED.expected_height, \
+            state_discriminant = EXCLUDED.state_discriminant, \
+            state = EXCLUDED.state";
+
+    tx.execute(
+        NOTE_QUERY,
+        &[
+            &note.id().inner().to_string(),
+            &note.assets().to_bytes(),
+            &note.recipient_digest().to_hex(),
+            &note.metadata().to_bytes(),
+            &note.nullifier().map(|nullifier| nullifier.to_hex()),
+            &i64::from(note.expected_height().as_u32()),
+            &i16::from(note.state().discriminant()),
+            &note.state().to_bytes(),
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Parse an input note from the provided row.
+fn parse_input_note(row: &Row) -> Result<InputNoteRecord, StoreError> {
+    let assets: Vec<u8> = row.try_get(0)?;
+    let serial_number: Vec<u8> = row.try_get(1)?;
+    let inputs: Vec<u8> = row.try_get(2)?;
+    let script: Vec<u8> = row.try_get(3)?;
+    let state: Vec<u8> = row.try_get(4)?;
+    let created_at: i64 = row.try_get(5)?;
+
+    let assets = NoteAssets::read_from_bytes(&assets)?;
+
+    let serial_number = Word::read_from_bytes(&serial_number)?;
+    let script = NoteScript::read_from_bytes(&script)?;
+    let inputs = NoteInputs::read_from_bytes(&inputs)?;
+    let recipient = NoteRecipient::new(serial_number, script, inputs);
+
+    let details = NoteDetails::new(assets, recipient);
+
+    let state = InputNoteState::read_from_bytes(&state)?;
+
+    Ok(InputNoteRecord::new(details, Some(i64_to_u64(created_at)), state))
+}
+
+/// Parse an output note from the provided row.
+fn parse_output_note(row: &Row) -> Result<OutputNoteRecord, StoreError> {
+    let recipient_digest: String = row.try_get(0)?;
+    let assets: Vec<u8> = row.try_get(1)?;
+    let metadata: Vec<u8> = row.try_get(2)?;
+    let expected_height: i64 = row.try_get(3)?;
+    let state: Vec<u8> = row.try_get(4)?;
+
+    let recipient_digest = Digest::try_from(recipient_digest)?;
+    let assets = NoteAssets::read_from_bytes(&assets)?;
+    let metadata = NoteMetadata::read_from_bytes(&metadata)?;
+    let state = OutputNoteState::read_from_bytes(&state)?;
+    let expected_height =
+        u32::try_from(expected_height).map_err(|err| StoreError::ParsingError(err.to_string()))?;
+
+    Ok(OutputNoteRecord::new(
+        recipient_digest,
+        assets,
+        metadata,
+        state,
+        BlockNumber::from(expected_height),
+    ))
+}
+
+pub(crate) async fn apply_note_updates_tx(
+    tx: &Transaction<'_>,
+    note_updates: &NoteUpdateTracker,
+) -> Result<(), StoreError> {
+    for input_note in note_updates.updated_input_notes() {
+        upsert_input_note_tx(tx, input_note.inner()).await?;
+    }
+
+    for output_note in note_updates.updated_output_notes() {
+        upsert_output_note_tx(tx, output_note.inner()).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+-- Create note discovery rules table
+CREATE TABLE note_discovery_rules (
+    rule BYTEA NOT NULL,                -- the serialized note discovery rule
+    PRIMARY KEY (rule)
+);
@@ -0,0 +1,141 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use miden_objects::account::AccountId;
+use tokio_postgres::{Client, Transaction};
+
+use super::PostgresStore;
+use crate::{
+    store::{NoteFilter, PruneSummary, RetentionPolicy, StoreError, TransactionFilter},
+    transaction::TransactionStatus,
+};
+
+impl PostgresStore {
+    pub(crate) async fn prune(
+        conn: &mut Client,
+        policy: &RetentionPolicy,
+    ) -> Result<PruneSummary, StoreError> {
+        let sync_height = Self::get_sync_height(conn).await?;
+        let transactions = Self::get_transactions(conn, &TransactionFilter::All).await?;
+
+        let expired_input_notes: Vec<String> = policy
+            .expired_input_notes(
+                &Self::get_input_notes(conn, &NoteFilter::Consumed).await?,
+                sync_height,
+            )
+            .into_iter()
+            .map(|note_id| note_id.inner().to_string())
+            .collect();
+        let expired_output_notes: Vec<String> = policy
+            .expired_output_notes(
+                &Self::get_output_notes(conn, &NoteFilter::Consumed).await?,
+                sync_height,
+            )
+            .into_iter()
+            .map(|note_id| note_id.inner().to_string())
+            .collect();
+        let expired_transactions: Vec<String> = transactions
+            .iter()
+            .filter(|tx| {
+                matches!(tx.status, TransactionStatus::Discarded(_))
+                    && policy.is_discarded_transaction_expired(tx.details.block_num, sync_height)
+            })
+            .map(|tx| tx.id.to_string())
+            .collect();
+
+        // Previous states of accounts with pending transactions are needed to roll them back if
+        // the transactions get discarded
+        let accounts_with_pending_transactions: BTreeSet<AccountId> = transactions
+            .iter()
+            .filter(|tx| matches!(tx.status, TransactionStatus::Pending))
+            .map(|tx| tx.details.account_id)
+            .collect();
+
+        let account_ids = Self::get_account_ids(conn).await?;
+
+        let tx = conn.transaction().await?;
+        let mut summary = PruneSummary::default();
+
+        if let Some(states_to_keep) = policy.states_to_keep_per_account() {
+            for account_id in account_ids {
+                if !accounts_with_pending_transactions.contains(&account_id) {
+                    summary.removed_account_states +=
+                        remove_old_account_states(&tx, account_id, states_to_keep).await?;
+                }
+            }
+        }
+
+        const INPUT_NOTE_QUERY: &str = "DELETE FROM input_notes WHERE note_id = ANY($1)";
+        summary.removed_input_notes =
+            row_count(tx.execute(INPUT_NOTE_QUERY, &[&expired_input_notes]).await?);
+
+        const OUTPUT_NOTE_QUERY: &str = "DELETE FROM output_notes WHERE note_id = ANY($1)";
+        summary.removed_output_notes =
+            row_count(tx.execute(OUTPUT_NOTE_QUERY, &[&expired_output_notes]).await?);
+
+        const TRANSACTION_QUERY: &str = "DELETE FROM transactions WHERE id = ANY($1)";
+        summary.removed_transactions =
+            row_count(tx.execute(TRANSACTION_QUERY, &[&expired_transactions]).await?);
+
+        if policy.remove_unreferenced_data {
+            summary.removed_unreferenced_entries = remove_unreferenced_data(&tx).await?;
+        }
+
+        tx.commit().await?;
+
+        // Space freed by removed rows is only reused by the database, not released, so it can't
+        // be measured
+        Ok(summary)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Removes the states of the account except for the `states_to_keep` ones with the highest nonce.
+/// Returns the number of removed states.
+async fn remove_old_account_states(
+    tx: &Transaction<'_>,
+    account_id: AccountId,
+    states_to_keep: usize,
+) -> Result<usize, StoreError> {
+    const QUERY: &str = "\
+        DELETE FROM accounts
+        WHERE id = $1
+        AND account_commitment NOT IN (
+            SELECT account_commitment FROM accounts WHERE id = $1 ORDER BY nonce DESC LIMIT $2
+        )";
+
+    let states_to_keep = i64::try_from(states_to_keep).unwrap_or(i64::MAX);
+    Ok(row_count(tx.execute(QUERY, &[&account_id.to_hex(), &states_to_keep]).await?))
+}
+
+/// Removes the account code, storage, vaults and scripts that aren't referenced by any account,
+/// note or transaction. Returns the number of removed entries.
+async fn remove_unreferenced_data(tx: &Transaction<'_>) -> Result<usize, StoreError> {
+    const QUERIES: [&str; 5] = [
+        "DELETE FROM account_code WHERE root NOT IN (SELECT code_root FROM accounts) \
+            AND root NOT IN (SELECT code_root FROM foreign_account_code)",
+        "DELETE FROM account_storage WHERE root NOT IN (SELECT storage_root FROM accounts)",
+        "DELETE FROM account_vaults WHERE root NOT IN (SELECT vault_root FROM accounts)",
+        "DELETE FROM notes_scripts WHERE script_root NOT IN (SELECT script_root FROM input_notes)",
+        "DELETE FROM transaction_scripts WHERE script_root NOT IN \
+            (SELECT script_root FROM transactions WHERE script_root IS NOT NULL)",
+    ];
+
+    let mut removed_entries = 0;
+    for query in QUERIES {
+        removed_entries += row_count(tx.execute(query, &[]).await?);
+    }
+
+    Ok(removed_entries)
+}
+
+fn row_count(rows: u64) -> usize {
+    usize::try_from(rows).expect("row count should fit in a usize")
+}
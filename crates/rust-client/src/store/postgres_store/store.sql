@@ -0,0 +1,152 @@
+-- Table for storing different settings in run-time, which need to persist over runs.
+CREATE TABLE settings (
+    name  TEXT NOT NULL,
+    value TEXT NOT NULL,
+
+    PRIMARY KEY (name),
+    CONSTRAINT settings_name_is_not_empty CHECK (length(name) > 0)
+);
+
+-- Create account_code table
+CREATE TABLE account_code (
+    root TEXT NOT NULL,         -- root of the Merkle tree for all exported procedures in account module.
+    code BYTEA NOT NULL,        -- serialized account code.
+    PRIMARY KEY (root)
+);
+
+-- Create account_storage table
+CREATE TABLE account_storage (
+    root TEXT NOT NULL,         -- root of the account storage Merkle tree.
+    slots BYTEA NOT NULL,       -- serialized key-value pair of non-empty account slots.
+    PRIMARY KEY (root)
+);
+
+-- Create account_vaults table
+CREATE TABLE account_vaults (
+    root TEXT NOT NULL,         -- root of the Merkle tree for the account asset vault.
+    assets BYTEA NOT NULL,      -- serialized account vault assets.
+    PRIMARY KEY (root)
+);
+
+-- Create foreign_account_code table
+CREATE TABLE foreign_account_code(
+    account_id TEXT NOT NULL,              -- ID of the account
+    code_root TEXT NOT NULL,               -- Root of the account_code
+    PRIMARY KEY (account_id),
+    FOREIGN KEY (code_root) REFERENCES account_code(root)
+);
+
+-- Create accounts table
+CREATE TABLE accounts (
+    account_commitment TEXT NOT NULL,           -- Account state commitment
+    id TEXT NOT NULL,                           -- Account ID.
+    code_root TEXT NOT NULL,                    -- Root of the account_code
+    storage_root TEXT NOT NULL,                 -- Root of the account_storage Merkle tree.
+    vault_root TEXT NOT NULL,                   -- Root of the account_vault Merkle tree.
+    nonce BIGINT NOT NULL,                      -- Account nonce.
+    committed BOOLEAN NOT NULL,                 -- True if recorded, false if not.
+    account_seed BYTEA NULL,                    -- Account seed used to generate the ID. Expected to be NULL for non-new accounts
+    locked BOOLEAN NOT NULL,                    -- True if the account is locked, false if not.
+    PRIMARY KEY (account_commitment),
+    FOREIGN KEY (code_root) REFERENCES account_code(root),
+    FOREIGN KEY (storage_root) REFERENCES account_storage(root),
+    FOREIGN KEY (vault_root) REFERENCES account_vaults(root),
+
+    CONSTRAINT check_seed_nonzero CHECK (NOT (nonce = 0 AND account_seed IS NULL))
+);
+
+CREATE INDEX idx_accounts_id_nonce ON accounts(id, nonce);
+
+-- Create transaction scripts table
+CREATE TABLE transaction_scripts (
+    script_root BYTEA NOT NULL,                      -- Transaction script root
+    script BYTEA,                                    -- serialized Transaction script
+
+    PRIMARY KEY (script_root)
+);
+
+-- Create transactions table
+CREATE TABLE transactions (
+    id TEXT NOT NULL,                                -- Transaction ID (commitment of various components)
+    details BYTEA NOT NULL,                          -- Serialized transaction details
+    script_root BYTEA,                               -- Transaction script root
+    block_num BIGINT,                                -- Block number for the block against which the transaction was executed.
+    commit_height BIGINT NULL,                       -- Block number of the block at which the transaction was included in the chain.
+    discard_cause BYTEA NULL,                        -- Serialized cause of the discarded transaction
+    FOREIGN KEY (script_root) REFERENCES transaction_scripts(script_root),
+    PRIMARY KEY (id)
+);
+
+-- Create note's scripts table, used for both input and output notes
+CREATE TABLE notes_scripts (
+    script_root TEXT NOT NULL,                       -- Note script root
+    serialized_note_script BYTEA,                    -- NoteScript, serialized
+
+    PRIMARY KEY (script_root)
+);
+
+-- Create input notes table
+CREATE TABLE input_notes (
+    note_id TEXT NOT NULL,                                  -- the note id
+    assets BYTEA NOT NULL,                                  -- the serialized list of assets
+    serial_number BYTEA NOT NULL,                           -- the serial number of the note
+    inputs BYTEA NOT NULL,                                  -- the serialized list of note inputs
+    script_root TEXT NOT NULL,                              -- the script root of the note, used to join with the notes_scripts table
+    nullifier TEXT NOT NULL,                                -- the nullifier of the note, used to query by nullifier
+    state_discriminant SMALLINT NOT NULL,                   -- state discriminant of the note, used to query by state
+    state BYTEA NOT NULL,                                   -- serialized note state
+    created_at BIGINT NOT NULL,                             -- timestamp of the note creation/import
+
+    PRIMARY KEY (note_id),
+    FOREIGN KEY (script_root) REFERENCES notes_scripts(script_root)
+);
+
+CREATE INDEX idx_input_notes_nullifier ON input_notes(nullifier);
+CREATE INDEX idx_input_notes_state_discriminant ON input_notes(state_discriminant);
+
+-- Create output notes table
+CREATE TABLE output_notes (
+    note_id TEXT NOT NULL,                                  -- the note id
+    recipient_digest TEXT NOT NULL,                         -- the note recipient
+    assets BYTEA NOT NULL,                                  -- the serialized NoteAssets, including vault commitment and list of assets
+    metadata BYTEA NOT NULL,                                -- serialized metadata
+    nullifier TEXT NULL,
+    expected_height BIGINT NOT NULL,                        -- the block height after which the note is expected to be created
+    state_discriminant SMALLINT NOT NULL,                   -- state discriminant of the note, used to query by state
+    state BYTEA NOT NULL,                                   -- serialized note state
+
+    PRIMARY KEY (note_id)
+);
+
+CREATE INDEX idx_output_notes_nullifier ON output_notes(nullifier);
+
+-- Create state sync table
+CREATE TABLE state_sync (
+    block_num BIGINT NOT NULL,    -- the block number of the most recent state sync
+    PRIMARY KEY (block_num)
+);
+
+-- Create tags table
+CREATE TABLE tags (
+    tag BYTEA NOT NULL,                 -- the serialized tag
+    source BYTEA NOT NULL               -- the serialized tag source
+);
+
+-- insert initial row into state_sync table
+INSERT INTO state_sync (block_num) VALUES (0);
+
+-- Create block headers table
+CREATE TABLE block_headers (
+    block_num BIGINT NOT NULL,                      -- block number
+    header BYTEA NOT NULL,                          -- serialized block header
+    partial_blockchain_peaks BYTEA NOT NULL,        -- serialized peaks of the partial blockchain MMR at this block
+    has_client_notes BOOLEAN NOT NULL,              -- whether the block has notes relevant to the client
+    PRIMARY KEY (block_num)
+);
+
+-- Create partial blockchain nodes
+CREATE TABLE partial_blockchain_nodes (
+    id BIGINT NOT NULL,             -- in-order index of the internal MMR node
+    node TEXT NOT NULL,             -- internal node value (commitment)
+    PRIMARY KEY (id)
+);
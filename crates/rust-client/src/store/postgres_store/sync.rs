@@ -0,0 +1,335 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+use miden_objects::{
+    Digest,
+    block::BlockNumber,
+    note::{NoteId, NoteTag},
+};
+use miden_tx::utils::{Deserializable, Serializable};
+use tokio_postgres::{Client, Transaction};
+
+use super::{PostgresStore, account::undo_account_state, i64_to_block_number};
+use crate::{
+    store::{
+        StoreError,
+        postgres_store::{
+            account::{lock_account_on_unexpected_commitment, update_account},
+            note::{apply_note_updates_tx, upsert_input_note_tx, upsert_output_note_tx},
+            transaction::upsert_transaction_record,
+        },
+    },
+    sync::{ChainRollback, NoteDiscoveryRule, NoteTagRecord, NoteTagSource, StateSyncUpdate},
+};
+
+impl PostgresStore {
+    pub(crate) async fn get_note_tags(conn: &mut Client) -> Result<Vec<NoteTagRecord>, StoreError> {
+        const QUERY: &str = "SELECT tag, source FROM tags";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let tag: Vec<u8> = row.try_get(0)?;
+                let source: Vec<u8> = row.try_get(1)?;
+                Ok(NoteTagRecord {
+                    tag: NoteTag::read_from_bytes(&tag)
+                        .map_err(StoreError::DataDeserializationError)?,
+                    source: NoteTagSource::read_from_bytes(&source)
+                        .map_err(StoreError::DataDeserializationError)?,
+                })
+            })
+            .collect::<Result<Vec<NoteTagRecord>, _>>()
+    }
+
+    pub(crate) async fn get_unique_note_tags(
+        conn: &mut Client,
+    ) -> Result<BTreeSet<NoteTag>, StoreError> {
+        const QUERY: &str = "SELECT DISTINCT tag FROM tags";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let tag: Vec<u8> = row.try_get(0)?;
+                NoteTag::read_from_bytes(&tag).map_err(StoreError::DataDeserializationError)
+            })
+            .collect::<Result<BTreeSet<NoteTag>, _>>()
+    }
+
+    pub(super) async fn add_note_tag(
+        conn: &mut Client,
+        tag: NoteTagRecord,
+    ) -> Result<bool, StoreError> {
+        let tx = conn.transaction().await?;
+
+        const QUERY: &str = "SELECT 1 FROM tags WHERE tag = $1 AND source = $2";
+        if tx
+            .query_opt(QUERY, &[&tag.tag.to_bytes(), &tag.source.to_bytes()])
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+
+        add_note_tag_tx(&tx, &tag).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    pub(super) async fn remove_note_tag(
+        conn: &mut Client,
+        tag: NoteTagRecord,
+    ) -> Result<usize, StoreError> {
+        let tx = conn.transaction().await?;
+        let removed_tags = remove_note_tag_tx(&tx, tag).await?;
+
+        tx.commit().await?;
+
+        Ok(removed_tags)
+    }
+
+    pub(crate) async fn get_note_discovery_rules(
+        conn: &mut Client,
+    ) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        const QUERY: &str = "SELECT rule FROM note_discovery_rules";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let rule: Vec<u8> = row.try_get(0)?;
+                NoteDiscoveryRule::read_from_bytes(&rule)
+                    .map_err(StoreError::DataDeserializationError)
+            })
+            .collect::<Result<Vec<NoteDiscoveryRule>, _>>()
+    }
+
+    pub(super) async fn add_note_discovery_rule(
+        conn: &mut Client,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        let tx = conn.transaction().await?;
+        let added = add_note_discovery_rule_tx(&tx, &rule).await?;
+
+        tx.commit().await?;
+
+        Ok(added)
+    }
+
+    pub(super) async fn remove_note_discovery_rule(
+        conn: &mut Client,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        const QUERY: &str = "DELETE FROM note_discovery_rules WHERE rule = $1";
+        let removed_rules = conn.execute(QUERY, &[&rule.to_bytes()]).await?;
+
+        Ok(removed_rules > 0)
+    }
+
+    pub(super) async fn get_discovered_notes(conn: &mut Client) -> Result<Vec<NoteId>, StoreError> {
+        const QUERY: &str = "SELECT note_id FROM discovered_notes";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let note_id: String = row.try_get(0)?;
+                Ok(NoteId::try_from_hex(&note_id)?)
+            })
+            .collect()
+    }
+
+    pub(super) async fn remove_discovered_notes(
+        conn: &mut Client,
+        note_ids: &[NoteId],
+    ) -> Result<(), StoreError> {
+        const QUERY: &str = "DELETE FROM discovered_notes WHERE note_id = $1";
+
+        let tx = conn.transaction().await?;
+        for note_id in note_ids {
+            tx.execute(QUERY, &[&note_id.to_hex()]).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn get_sync_height(conn: &mut Client) -> Result<BlockNumber, StoreError> {
+        const QUERY: &str = "SELECT block_num FROM state_sync";
+
+        let row = conn.query_one(QUERY, &[]).await?;
+        i64_to_block_number(row.try_get(0)?)
+    }
+
+    pub(super) async fn apply_state_sync(
+        conn: &mut Client,
+        state_sync_update: StateSyncUpdate,
+    ) -> Result<(), StoreError> {
+        let StateSyncUpdate {
+            block_num,
+            block_updates,
+            note_updates,
+            transaction_updates,
+            account_updates,
+            discovered_notes,
+        } = state_sync_update;
+
+        let tx = conn.transaction().await?;
+
+        // Update state sync block number
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = $1";
+        tx.execute(BLOCK_NUMBER_QUERY, &[&i64::from(block_num.as_u32())]).await?;
+
+        for (block_header, block_has_relevant_notes, new_mmr_peaks) in block_updates.block_headers()
+        {
+            Self::insert_block_header_tx(
+                &tx,
+                block_header,
+                new_mmr_peaks,
+                *block_has_relevant_notes,
+            )
+            .await?;
+        }
+
+        // Insert new authentication nodes (inner nodes of the PartialBlockchain)
+        Self::insert_partial_blockchain_nodes_tx(&tx, block_updates.new_authentication_nodes())
+            .await?;
+
+        // Update notes
+        apply_note_updates_tx(&tx, &note_updates).await?;
+
+        // Remove tags
+        let tags_to_remove = note_updates
+            .updated_input_notes()
+            .filter_map(|note_update| {
+                let note = note_update.inner();
+                if note.is_committed() {
+                    Some(NoteTagRecord {
+                        tag: note.metadata().expect("Committed notes should have metadata").tag(),
+                        source: NoteTagSource::Note(note.id()),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for tag in tags_to_remove {
+            remove_note_tag_tx(&tx, tag).await?;
+        }
+
+        for transaction_record in transaction_updates
+            .committed_transactions()
+            .chain(transaction_updates.discarded_transactions())
+        {
+            upsert_transaction_record(&tx, transaction_record).await?;
+        }
+
+        // Remove the accounts that are originated from the discarded transactions
+        let account_hashes_to_delete: Vec<Digest> = transaction_updates
+            .discarded_transactions()
+            .map(|tx| tx.details.final_account_state)
+            .collect();
+
+        undo_account_state(&tx, &account_hashes_to_delete).await?;
+
+        // Update public accounts on the db that have been updated onchain
+        for account in account_updates.updated_public_accounts() {
+            update_account(&tx, account).await?;
+        }
+
+        for (account_id, digest) in account_updates.mismatched_private_accounts() {
+            lock_account_on_unexpected_commitment(&tx, account_id, digest).await?;
+        }
+
+        // Keep the discovered notes until they are imported
+        const DISCOVERED_NOTES_QUERY: &str =
+            "INSERT INTO discovered_notes (note_id) VALUES ($1) ON CONFLICT (note_id) DO NOTHING";
+        for note_id in discovered_notes {
+            tx.execute(DISCOVERED_NOTES_QUERY, &[&note_id.to_hex()]).await?;
+        }
+
+        // Commit the updates
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn rollback_to(
+        conn: &mut Client,
+        rollback: &ChainRollback,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = $1";
+        tx.execute(BLOCK_NUMBER_QUERY, &[&i64::from(rollback.block_num.as_u32())])
+            .await?;
+
+        const BLOCK_HEADERS_QUERY: &str = "DELETE FROM block_headers WHERE block_num > $1";
+        tx.execute(BLOCK_HEADERS_QUERY, &[&i64::from(rollback.block_num.as_u32())])
+            .await?;
+
+        // The authentication nodes of the rollback replace the stored ones
+        tx.execute("DELETE FROM partial_blockchain_nodes", &[]).await?;
+        Self::insert_partial_blockchain_nodes_tx(&tx, &rollback.partial_blockchain_nodes).await?;
+
+        for transaction_record in &rollback.transactions {
+            upsert_transaction_record(&tx, transaction_record).await?;
+        }
+
+        for note in &rollback.input_notes {
+            upsert_input_note_tx(&tx, note).await?;
+        }
+
+        for note in &rollback.output_notes {
+            upsert_output_note_tx(&tx, note).await?;
+        }
+
+        for tag in &rollback.tags {
+            add_note_tag_tx(&tx, tag).await?;
+        }
+
+        // The notes found in the removed blocks are found again by the next sync
+        tx.execute("DELETE FROM discovered_notes", &[]).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+pub(super) async fn add_note_tag_tx(
+    tx: &Transaction<'_>,
+    tag: &NoteTagRecord,
+) -> Result<(), StoreError> {
+    const QUERY: &str = "INSERT INTO tags (tag, source) VALUES ($1, $2)";
+    tx.execute(QUERY, &[&tag.tag.to_bytes(), &tag.source.to_bytes()]).await?;
+
+    Ok(())
+}
+
+pub(super) async fn remove_note_tag_tx(
+    tx: &Transaction<'_>,
+    tag: NoteTagRecord,
+) -> Result<usize, StoreError> {
+    const QUERY: &str = "DELETE FROM tags WHERE tag = $1 AND source = $2";
+    let removed_tags = tx.execute(QUERY, &[&tag.tag.to_bytes(), &tag.source.to_bytes()]).await?;
+
+    Ok(usize::try_from(removed_tags).expect("row count should fit in a usize"))
+}
+
+/// Adds the note discovery rule, returning false if it was already present.
+pub(super) async fn add_note_discovery_rule_tx(
+    tx: &Transaction<'_>,
+    rule: &NoteDiscoveryRule,
+) -> Result<bool, StoreError> {
+    const QUERY: &str =
+        "INSERT INTO note_discovery_rules (rule) VALUES ($1) ON CONFLICT (rule) DO NOTHING";
+    let added_rules = tx.execute(QUERY, &[&rule.to_bytes()]).await?;
+
+    Ok(added_rules > 0)
+}
@@ -0,0 +1,114 @@
+//! Tenant management for the `PostgreSQL` store.
+//!
+//! Each tenant's data lives in its own schema of the database, with the same tables as the schema
+//! of the store the tenant was created from. Tenants are registered in the `tenants` table of the
+//! shared `miden_tenants` schema, along with the schema of the store they were created from.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use std::format;
+
+use miden_objects::crypto::hash::blake::Blake3_160;
+
+use super::{
+    PostgresStore, build_pool,
+    db_management::utils::{apply_migrations, create_tenants_table},
+};
+use crate::store::{StoreError, TenantId};
+
+/// Prefix of the schemas holding tenant data.
+const TENANT_SCHEMA_PREFIX: &str = "miden_tenant_";
+
+impl PostgresStore {
+    /// Returns a store that operates on the schema of the provided tenant, creating and migrating
+    /// it if needed.
+    ///
+    /// The tenant's connection pool is created on first use and shared by all the handles of the
+    /// tenant.
+    pub(super) async fn tenant_store(&self, tenant_id: TenantId) -> Result<Self, StoreError> {
+        let schema = tenant_schema_name(&self.root_schema, &tenant_id);
+
+        let mut conn = self.get_connection().await?;
+        create_tenants_table(&mut conn)
+            .await
+            .map_err(|err| StoreError::DatabaseError(err.to_string()))?;
+
+        let tx = conn.transaction().await?;
+        tx.batch_execute(&format!("CREATE SCHEMA IF NOT EXISTS {}", quote_identifier(&schema)))
+            .await?;
+        tx.execute(
+            "INSERT INTO miden_tenants.tenants (root_schema, tenant_id, schema_name) \
+             VALUES ($1, $2, $3) ON CONFLICT (root_schema, tenant_id) DO NOTHING",
+            &[&self.root_schema, &tenant_id.as_str(), &schema],
+        )
+        .await?;
+        tx.commit().await?;
+        drop(conn);
+
+        let existing_pool = self.tenant_pools.read().get(&schema).cloned();
+        let pool = if let Some(pool) = existing_pool {
+            pool
+        } else {
+            let pool = build_pool(self.config.clone(), self.max_connections, Some(&schema))?;
+            self.tenant_pools.write().entry(schema).or_insert(pool).clone()
+        };
+
+        let tenant_store = PostgresStore {
+            pool,
+            config: self.config.clone(),
+            max_connections: self.max_connections,
+            root_schema: self.root_schema.clone(),
+            tenant_pools: self.tenant_pools.clone(),
+        };
+
+        let mut conn = tenant_store.get_connection().await?;
+        apply_migrations(&mut conn)
+            .await
+            .map_err(|err| StoreError::DatabaseError(err.to_string()))?;
+
+        Ok(tenant_store)
+    }
+
+    /// Returns the tenants created from the store's root schema.
+    pub(super) async fn get_tenants(&self) -> Result<Vec<TenantId>, StoreError> {
+        let conn = self.get_connection().await?;
+
+        // The registry is only created along with the first tenant
+        let registry_exists: bool = conn
+            .query_one("SELECT to_regclass('miden_tenants.tenants') IS NOT NULL", &[])
+            .await?
+            .get(0);
+        if !registry_exists {
+            return Ok(Vec::new());
+        }
+
+        let rows = conn
+            .query(
+                "SELECT tenant_id FROM miden_tenants.tenants WHERE root_schema = $1 \
+                 ORDER BY tenant_id",
+                &[&self.root_schema],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(|row| TenantId::new(row.get::<_, String>(0))).collect())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the name of the schema holding the data of the tenant.
+///
+/// The name is derived from a hash of the root schema and the tenant ID, so arbitrary tenant IDs
+/// map to valid identifiers that fit in `PostgreSQL`'s 63 byte limit.
+fn tenant_schema_name(root_schema: &str, tenant_id: &TenantId) -> String {
+    let digest = Blake3_160::hash(format!("{root_schema}/{tenant_id}").as_bytes());
+    format!("{TENANT_SCHEMA_PREFIX}{}", hex::encode(&*digest))
+}
+
+/// Quotes an identifier so it can be safely used in a query.
+pub(super) fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
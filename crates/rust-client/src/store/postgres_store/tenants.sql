@@ -0,0 +1,12 @@
+-- Create the tenants registry. It lives in its own schema, shared by every store of the database,
+-- while the data of each tenant lives in the tenant's own schema.
+CREATE SCHEMA IF NOT EXISTS miden_tenants;
+
+CREATE TABLE IF NOT EXISTS miden_tenants.tenants (
+    root_schema TEXT NOT NULL,  -- schema of the store the tenant was created from.
+    tenant_id TEXT NOT NULL,    -- identifier of the tenant.
+    schema_name TEXT NOT NULL,  -- name of the schema holding the tenant's data.
+
+    PRIMARY KEY (root_schema, tenant_id),
+    CONSTRAINT tenants_tenant_id_is_not_empty CHECK (length(tenant_id) > 0)
+);
@@ -0,0 +1,261 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest,
+    crypto::utils::{Deserializable, Serializable},
+    transaction::{ToInputNoteCommitments, TransactionId, TransactionScript},
+};
+use tokio_postgres::{Client, Row, Transaction};
+
+use super::{
+    PostgresStore, account::update_account, i64_to_block_number, note::apply_note_updates_tx,
+    sync::add_note_tag_tx,
+};
+use crate::{
+    store::{StoreError, TransactionFilter},
+    transaction::{
+        DiscardCause, QueuedTransaction, TransactionDetails, TransactionRecord, TransactionStatus,
+        TransactionStoreUpdate,
+    },
+};
+
+const UPSERT_TRANSACTION_QUERY: &str = "\
+    INSERT INTO transactions (id, details, script_root, block_num, commit_height, discard_cause) \
+    VALUES ($1, $2, $3, $4, $5, $6) \
+    ON CONFLICT (id) DO UPDATE SET \
+        details = EXCLUDED.details, \
+        script_root = EXCLUDED.script_root, \
+        block_num = EXCLUDED.block_num, \
+        commit_height = EXCLUDED.commit_height, \
+        discard_cause = EXCLUDED.discard_cause";
+
+const INSERT_TRANSACTION_SCRIPT_QUERY: &str = "\
+    INSERT INTO transaction_scripts (script_root, script) VALUES ($1, $2) ON CONFLICT DO NOTHING";
+
+// TRANSACTIONS FILTERS
+// ================================================================================================
+
+/// Returns a [String] containing the query for the provided [`TransactionFilter`].
+///
+/// The query for [`TransactionFilter::Ids`] expects the list of IDs as its only parameter.
+fn transaction_filter_query(filter: &TransactionFilter) -> String {
+    const QUERY: &str = "SELECT tx.id, script.script, tx.details, tx.commit_height, tx.discard_cause \
+        FROM transactions AS tx LEFT JOIN transaction_scripts AS script ON tx.script_root = script.script_root";
+    match filter {
+        TransactionFilter::All => QUERY.to_string(),
+        TransactionFilter::Uncommitted => format!("{QUERY} WHERE tx.commit_height IS NULL"),
+        TransactionFilter::Ids(_) => format!("{QUERY} WHERE tx.id = ANY($1)"),
+        TransactionFilter::ExpiredBefore(block_num) => {
+            format!(
+                "{QUERY} WHERE tx.block_num < {} AND tx.discard_cause IS NULL AND tx.commit_height IS NULL",
+                block_num.as_u32()
+            )
+        },
+    }
+}
+
+// TRANSACTIONS
+// ================================================================================================
+
+impl PostgresStore {
+    /// Retrieves tracked transactions, filtered by [`TransactionFilter`].
+    pub async fn get_transactions(
+        conn: &mut Client,
+        filter: &TransactionFilter,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        let query = transaction_filter_query(filter);
+        let rows = match filter {
+            TransactionFilter::Ids(ids) => {
+                let id_strings = ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+                conn.query(&query, &[&id_strings]).await?
+            },
+            _ => conn.query(&query, &[]).await?,
+        };
+
+        rows.iter()
+            .map(parse_transaction)
+            .collect::<Result<Vec<TransactionRecord>, _>>()
+    }
+
+    /// Inserts a transaction and updates the current state based on the `tx_result` changes.
+    pub async fn apply_transaction(
+        conn: &mut Client,
+        tx_update: &TransactionStoreUpdate,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+
+        // Build transaction record
+        let executed_transaction = tx_update.executed_transaction();
+
+        let nullifiers: Vec<Digest> = executed_transaction
+            .input_notes()
+            .iter()
+            .map(|x| x.nullifier().inner())
+            .collect();
+
+        let output_notes = executed_transaction.output_notes();
+
+        let details = TransactionDetails {
+            account_id: executed_transaction.account_id(),
+            init_account_state: executed_transaction.initial_account().commitment(),
+            final_account_state: executed_transaction.final_account().commitment(),
+            input_note_nullifiers: nullifiers,
+            output_notes: output_notes.clone(),
+            block_num: executed_transaction.block_header().block_num(),
+            submission_height: tx_update.submission_height(),
+            expiration_block_num: executed_transaction.expiration_block_num(),
+        };
+
+        let transaction_record = TransactionRecord::new(
+            executed_transaction.id(),
+            details,
+            executed_transaction.tx_args().tx_script().cloned(),
+            TransactionStatus::Pending,
+        );
+
+        // Insert transaction data
+        upsert_transaction_record(&tx, &transaction_record).await?;
+
+        // Account Data
+        update_account(&tx, tx_update.updated_account()).await?;
+
+        // Note Updates
+        apply_note_updates_tx(&tx, tx_update.note_updates()).await?;
+
+        for tag_record in tx_update.new_tags() {
+            add_note_tag_tx(&tx, tag_record).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the entries of the outbound transaction queue.
+    pub async fn get_queued_transactions(
+        conn: &mut Client,
+    ) -> Result<Vec<QueuedTransaction>, StoreError> {
+        const QUERY: &str = "SELECT entry FROM transaction_queue";
+
+        conn.query(QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| {
+                let entry: Vec<u8> = row.try_get(0)?;
+                QueuedTransaction::read_from_bytes(&entry)
+                    .map_err(StoreError::DataDeserializationError)
+            })
+            .collect::<Result<Vec<QueuedTransaction>, _>>()
+    }
+
+    /// Updates the entry of the outbound transaction queue, inserting it if it doesn't exist.
+    pub async fn upsert_queued_transaction(
+        conn: &mut Client,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction().await?;
+        upsert_queued_transaction_tx(&tx, queued_transaction).await?;
+
+        Ok(tx.commit().await?)
+    }
+
+    /// Removes the transaction from the outbound transaction queue, returning false if it wasn't
+    /// queued.
+    pub async fn remove_queued_transaction(
+        conn: &mut Client,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        const QUERY: &str = "DELETE FROM transaction_queue WHERE id = $1";
+        let removed_entries = conn.execute(QUERY, &[&transaction_id.to_string()]).await?;
+
+        Ok(removed_entries > 0)
+    }
+}
+
+/// Updates the entry of the outbound transaction queue, inserting it if it doesn't exist.
+pub(crate) async fn upsert_queued_transaction_tx(
+    tx: &Transaction<'_>,
+    queued_transaction: &QueuedTransaction,
+) -> Result<(), StoreError> {
+    const QUERY: &str = "\
+        INSERT INTO transaction_queue (id, entry) VALUES ($1, $2) \
+        ON CONFLICT (id) DO UPDATE SET entry = EXCLUDED.entry";
+    tx.execute(QUERY, &[&queued_transaction.id().to_string(), &queued_transaction.to_bytes()])
+        .await?;
+
+    Ok(())
+}
+
+/// Updates the transaction record in the database, inserting it if it doesn't exist.
+pub(crate) async fn upsert_transaction_record(
+    tx: &Transaction<'_>,
+    transaction: &TransactionRecord,
+) -> Result<(), StoreError> {
+    let id: String = transaction.id.inner().into();
+    let script_root = transaction.script.as_ref().map(|script| script.root().to_bytes());
+    let tx_script = transaction.script.as_ref().map(TransactionScript::to_bytes);
+
+    let (commit_height, discard_cause) = match &transaction.status {
+        TransactionStatus::Pending => (None, None),
+        TransactionStatus::Committed(block_num) => (Some(i64::from(block_num.as_u32())), None),
+        TransactionStatus::Discarded(cause) => (None, Some(cause.to_bytes())),
+    };
+
+    if let Some(root) = &script_root {
+        tx.execute(INSERT_TRANSACTION_SCRIPT_QUERY, &[root, &tx_script]).await?;
+    }
+
+    tx.execute(
+        UPSERT_TRANSACTION_QUERY,
+        &[
+            &id,
+            &transaction.details.to_bytes(),
+            &script_root,
+            &i64::from(transaction.details.block_num.as_u32()),
+            &commit_height,
+            &discard_cause,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Parse a transaction from the provided row.
+fn parse_transaction(row: &Row) -> Result<TransactionRecord, StoreError> {
+    let id: String = row.try_get(0)?;
+    let tx_script: Option<Vec<u8>> = row.try_get(1)?;
+    let details: Vec<u8> = row.try_get(2)?;
+    let commit_height: Option<i64> = row.try_get(3)?;
+    let discard_cause: Option<Vec<u8>> = row.try_get(4)?;
+
+    let id: Digest = id.try_into()?;
+
+    let script: Option<TransactionScript> = tx_script
+        .map(|script| TransactionScript::read_from_bytes(&script))
+        .transpose()?;
+
+    let status = if let Some(cause) = discard_cause {
+        let cause = DiscardCause::read_from_bytes(&cause)?;
+        TransactionStatus::Discarded(cause)
+    } else {
+        match commit_height {
+            Some(commit_height) => {
+                TransactionStatus::Committed(i64_to_block_number(commit_height)?)
+            },
+            None => TransactionStatus::Pending,
+        }
+    };
+
+    Ok(TransactionRecord {
+        id: id.into(),
+        details: TransactionDetails::read_from_bytes(&details)?,
+        script,
+        status,
+    })
+}
@@ -0,0 +1,6 @@
+-- Create outbound transaction queue table
+CREATE TABLE transaction_queue (
+    id TEXT NOT NULL,                   -- the transaction ID (hash of the transaction)
+    entry BYTEA NOT NULL,               -- the serialized queue entry
+    PRIMARY KEY (id)
+);
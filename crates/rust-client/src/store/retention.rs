@@ -0,0 +1,187 @@
+// RETENTION POLICY
+// ================================================================================================
+use alloc::vec::Vec;
+
+use miden_objects::{block::BlockNumber, note::NoteId};
+
+use super::{InputNoteRecord, OutputNoteRecord};
+
+/// Describes which data can be removed from the store when pruning it with [`super::Store::prune`].
+///
+/// Data that the client still needs to operate is never removed, regardless of the policy: the
+/// latest state of every account, states of accounts with uncommitted transactions and notes that
+/// haven't been consumed are always kept.
+///
+/// The default policy only removes unreferenced data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Number of states to keep for each account, counting from the latest one. Older states are
+    /// removed. If `None`, every account state is kept.
+    ///
+    /// At least one state is always kept for each account. Accounts with uncommitted transactions
+    /// aren't pruned, as their previous states are needed if the transactions get discarded.
+    pub account_states_to_keep: Option<usize>,
+    /// Number of blocks after which consumed input and output notes are removed, counting from
+    /// the block at which they were nullified. If `None`, consumed notes are kept.
+    pub consumed_notes_max_age: Option<u32>,
+    /// Number of blocks after which discarded transactions are removed, counting from the block
+    /// against which they were executed. If `None`, discarded transactions are kept.
+    pub discarded_transactions_max_age: Option<u32>,
+    /// Whether to remove data that isn't referenced by any account, note or transaction, such as
+    /// account code, storage, vaults and scripts left behind by removed or replaced records.
+    pub remove_unreferenced_data: bool,
+    /// Whether to compact the underlying storage after pruning, so the space freed by the removed
+    /// data is released. Compacting can take a while on large stores, and stores that can't be
+    /// compacted ignore it.
+    pub compact: bool,
+}
+
+impl RetentionPolicy {
+    /// Returns the number of states to keep per account, or `None` if account states shouldn't be
+    /// pruned.
+    pub(crate) fn states_to_keep_per_account(&self) -> Option<usize> {
+        self.account_states_to_keep.map(|states| states.max(1))
+    }
+
+    /// Returns the IDs of the consumed input notes that should be removed according to the policy.
+    ///
+    /// Notes that aren't consumed are ignored.
+    pub(crate) fn expired_input_notes<'a>(
+        &self,
+        notes: impl IntoIterator<Item = &'a InputNoteRecord>,
+        sync_height: BlockNumber,
+    ) -> Vec<NoteId> {
+        let Some(max_age) = self.consumed_notes_max_age else {
+            return Vec::new();
+        };
+
+        notes
+            .into_iter()
+            .filter(|note| {
+                note.state()
+                    .nullifier_block_height()
+                    .is_some_and(|height| is_expired(height, max_age, sync_height))
+            })
+            .map(InputNoteRecord::id)
+            .collect()
+    }
+
+    /// Returns the IDs of the consumed output notes that should be removed according to the
+    /// policy.
+    ///
+    /// Notes that aren't consumed are ignored.
+    pub(crate) fn expired_output_notes<'a>(
+        &self,
+        notes: impl IntoIterator<Item = &'a OutputNoteRecord>,
+        sync_height: BlockNumber,
+    ) -> Vec<NoteId> {
+        let Some(max_age) = self.consumed_notes_max_age else {
+            return Vec::new();
+        };
+
+        notes
+            .into_iter()
+            .filter(|note| {
+                note.state()
+                    .nullifier_block_height()
+                    .is_some_and(|height| is_expired(height, max_age, sync_height))
+            })
+            .map(OutputNoteRecord::id)
+            .collect()
+    }
+
+    /// Returns whether a discarded transaction executed against the provided block should be
+    /// removed according to the policy.
+    pub(crate) fn is_discarded_transaction_expired(
+        &self,
+        block_num: BlockNumber,
+        sync_height: BlockNumber,
+    ) -> bool {
+        self.discarded_transactions_max_age
+            .is_some_and(|max_age| is_expired(block_num.as_u32(), max_age, sync_height))
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            account_states_to_keep: None,
+            consumed_notes_max_age: None,
+            discarded_transactions_max_age: None,
+            remove_unreferenced_data: true,
+            compact: false,
+        }
+    }
+}
+
+/// Returns whether data created at `height` is at least `max_age` blocks old at `sync_height`.
+fn is_expired(height: u32, max_age: u32, sync_height: BlockNumber) -> bool {
+    height.saturating_add(max_age) <= sync_height.as_u32()
+}
+
+// PRUNE SUMMARY
+// ================================================================================================
+
+/// Contains the amount of data removed from the store by [`super::Store::prune`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneSummary {
+    /// Number of removed account states.
+    pub removed_account_states: usize,
+    /// Number of removed input notes.
+    pub removed_input_notes: usize,
+    /// Number of removed output notes.
+    pub removed_output_notes: usize,
+    /// Number of removed transactions.
+    pub removed_transactions: usize,
+    /// Number of removed account code, storage, vault and script entries that weren't referenced
+    /// anymore.
+    pub removed_unreferenced_entries: usize,
+    /// Number of bytes released by the underlying storage, if it was compacted and the store is
+    /// able to measure it.
+    pub reclaimed_bytes: Option<u64>,
+}
+
+impl PruneSummary {
+    /// Returns whether no data was removed.
+    pub fn is_empty(&self) -> bool {
+        self.removed_account_states == 0
+            && self.removed_input_notes == 0
+            && self.removed_output_notes == 0
+            && self.removed_transactions == 0
+            && self.removed_unreferenced_entries == 0
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::block::BlockNumber;
+
+    use super::{RetentionPolicy, is_expired};
+
+    #[test]
+    fn expiration_is_relative_to_sync_height() {
+        assert!(is_expired(10, 5, BlockNumber::from(15)));
+        assert!(!is_expired(11, 5, BlockNumber::from(15)));
+        assert!(!is_expired(u32::MAX, 5, BlockNumber::from(15)));
+
+        let policy = RetentionPolicy {
+            discarded_transactions_max_age: Some(0),
+            ..Default::default()
+        };
+        assert!(policy.is_discarded_transaction_expired(15.into(), 15.into()));
+        assert!(!RetentionPolicy::default().is_discarded_transaction_expired(0.into(), 15.into()));
+    }
+
+    #[test]
+    fn at_least_one_account_state_is_kept() {
+        let policy = RetentionPolicy {
+            account_states_to_keep: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(policy.states_to_keep_per_account(), Some(1));
+        assert_eq!(RetentionPolicy::default().states_to_keep_per_account(), None);
+    }
+}
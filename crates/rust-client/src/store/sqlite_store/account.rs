@@ -118,6 +118,53 @@ impl SqliteStore {
             .transpose()
     }
 
+    pub(crate) fn get_accounts_code_and_storage(
+        conn: &mut Connection,
+    ) -> Result<Vec<(AccountId, AccountCode, AccountStorage)>, StoreError> {
+        const QUERY: &str = "SELECT a.id, account_code.code, account_storage.slots \
+                            FROM accounts a \
+                            JOIN account_code ON a.code_root = account_code.root \
+                            JOIN account_storage ON a.storage_root = account_storage.root \
+                            WHERE a.nonce = (SELECT MAX(b.nonce) FROM accounts b WHERE b.id = a.id)";
+
+        conn.prepare(QUERY)?
+            .query_map([], |row| {
+                let id: String = row.get(0)?;
+                let code: Vec<u8> = row.get(1)?;
+                let storage: Vec<u8> = row.get(2)?;
+                Ok((id, code, storage))
+            })
+            .expect("no binding parameters used in query")
+            .map(|result| {
+                let (id, code, storage) = result?;
+                Ok((
+                    AccountId::from_hex(&id)
+                        .expect("Conversion from stored AccountID should not panic"),
+                    AccountCode::from_bytes(&code)?,
+                    AccountStorage::read_from_bytes(&storage)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub(crate) fn get_account_history(
+        conn: &mut Connection,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, account_storage.slots, account_vaults.assets, accounts.locked \
+                            FROM accounts \
+                            JOIN account_code ON accounts.code_root = account_code.root \
+                            JOIN account_storage ON accounts.storage_root = account_storage.root \
+                            JOIN account_vaults ON accounts.vault_root = account_vaults.root \
+                            WHERE accounts.id = ? \
+                            ORDER BY accounts.nonce ASC";
+
+        conn.prepare(QUERY)?
+            .query_map(params![account_id.to_hex()], parse_account_columns)?
+            .map(|result| Ok(result?).and_then(parse_account))
+            .collect()
+    }
+
     pub(crate) fn insert_account(
         conn: &mut Connection,
         account: &Account,
@@ -269,7 +316,10 @@ pub(super) fn insert_account_record(
 }
 
 /// Inserts an [`AccountCode`].
-fn insert_account_code(tx: &Transaction<'_>, account_code: &AccountCode) -> Result<(), StoreError> {
+pub(super) fn insert_account_code(
+    tx: &Transaction<'_>,
+    account_code: &AccountCode,
+) -> Result<(), StoreError> {
     let (code_root, code) = serialize_account_code(account_code);
     const QUERY: &str = insert_sql!(account_code { root, code } | IGNORE);
     tx.execute(QUERY, params![code_root, code])?;
@@ -0,0 +1,196 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::string::ToString;
+
+use rusqlite::{Connection, Transaction, params};
+
+use super::{
+    SqliteStore,
+    account::{
+        insert_account_asset_vault, insert_account_code, insert_account_record,
+        insert_account_storage,
+    },
+    note::{upsert_input_note_tx, upsert_output_note_tx},
+    sync::{add_note_discovery_rule_tx, add_note_tag_tx},
+    transaction::{upsert_queued_transaction_tx, upsert_transaction_record},
+};
+use crate::store::{AccountStateBackup, StoreBackup, StoreError};
+
+impl SqliteStore {
+    pub(crate) fn import_backup(
+        conn: &mut Connection,
+        backup: &StoreBackup,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction()?;
+
+        clear_store(&tx)?;
+
+        for account_state in &backup.accounts {
+            insert_account_state(&tx, account_state)?;
+        }
+        for note in &backup.input_notes {
+            upsert_input_note_tx(&tx, note)?;
+        }
+        for note in &backup.output_notes {
+            upsert_output_note_tx(&tx, note)?;
+        }
+        for tag in &backup.tags {
+            add_note_tag_tx(&tx, tag)?;
+        }
+        for rule in &backup.note_discovery_rules {
+            add_note_discovery_rule_tx(&tx, rule)?;
+        }
+        for transaction in &backup.transactions {
+            upsert_transaction_record(&tx, transaction)?;
+        }
+        for queued_transaction in &backup.queued_transactions {
+            upsert_queued_transaction_tx(&tx, queued_transaction)?;
+        }
+        for block in &backup.block_headers {
+            Self::insert_block_header_tx(
+                &tx,
+                &block.header,
+                &block.partial_blockchain_peaks,
+                block.has_client_notes,
+            )?;
+        }
+        Self::insert_partial_blockchain_nodes_tx(&tx, &backup.partial_blockchain_nodes)?;
+
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = ?";
+        tx.execute(BLOCK_NUMBER_QUERY, params![i64::from(backup.sync_height.as_u32())])?;
+
+        Ok(tx.commit()?)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Removes all the data from the store, except for the settings.
+fn clear_store(tx: &Transaction<'_>) -> Result<(), StoreError> {
+    // Tables are cleared before the tables they reference
+    const QUERIES: [&str; 16] = [
+        "DELETE FROM accounts",
+        "DELETE FROM foreign_account_code",
+        "DELETE FROM account_code",
+        "DELETE FROM account_storage",
+        "DELETE FROM account_vaults",
+        "DELETE FROM transaction_queue",
+        "DELETE FROM transactions",
+        "DELETE FROM transaction_scripts",
+        "DELETE FROM input_notes",
+        "DELETE FROM output_notes",
+        "DELETE FROM notes_scripts",
+        "DELETE FROM tags",
+        "DELETE FROM note_discovery_rules",
+        "DELETE FROM discovered_notes",
+        "DELETE FROM block_headers",
+        "DELETE FROM partial_blockchain_nodes",
+    ];
+
+    for query in QUERIES {
+        tx.execute(query, [])?;
+    }
+
+    Ok(())
+}
+
+/// Inserts the account state along with its code, storage and vault.
+fn insert_account_state(
+    tx: &Transaction<'_>,
+    account_state: &AccountStateBackup,
+) -> Result<(), StoreError> {
+    let account = &account_state.account;
+
+    insert_account_code(tx, account.code())?;
+    insert_account_storage(tx, account.storage())?;
+    insert_account_asset_vault(tx, account.vault())?;
+    insert_account_record(tx, account, account_state.seed)?;
+
+    if account_state.locked {
+        const QUERY: &str = "UPDATE accounts SET locked = true WHERE account_commitment = ?";
+        tx.execute(QUERY, params![account.commitment().to_string()])?;
+    }
+
+    Ok(())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::transaction::TransactionKernel;
+    use miden_objects::{
+        Felt, Word,
+        account::Account,
+        block::{BlockHeader, BlockNumber},
+        crypto::merkle::MmrPeaks,
+        note::NoteTag,
+        testing::account_id::{
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET, ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+        },
+    };
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use crate::{
+        store::{Store, StoreBackup, sqlite_store::tests::create_test_store},
+        sync::{NoteTagRecord, NoteTagSource},
+    };
+
+    #[tokio::test]
+    async fn backup_replaces_store_contents() {
+        let source = create_test_store().await;
+
+        let account = Account::mock(
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+            Felt::new(0),
+            TransactionKernel::testing_assembler(),
+        );
+        source.insert_account(&account, Some(Word::default())).await.unwrap();
+        let updated_account = Account::mock(
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+            Felt::new(1),
+            TransactionKernel::testing_assembler(),
+        );
+        source.update_account(&updated_account).await.unwrap();
+
+        let tag = NoteTagRecord::with_account_source(NoteTag::from(42), account.id());
+        source.add_note_tag(tag).await.unwrap();
+
+        let genesis = BlockHeader::mock(0, None, None, &[], TransactionKernel::kernel_commitment());
+        source
+            .insert_block_header(&genesis, MmrPeaks::new(0, vec![]).unwrap(), false)
+            .await
+            .unwrap();
+
+        // The backup goes through its serialized form, as it would when moved between stores
+        let bytes = source.export_backup().await.unwrap().to_bytes();
+        let backup = StoreBackup::read_from_bytes(&bytes).unwrap();
+        assert_eq!(backup.accounts.len(), 2);
+
+        let target = create_test_store().await;
+        let other_account = Account::mock(
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            Felt::new(0),
+            TransactionKernel::testing_assembler(),
+        );
+        target.insert_account(&other_account, Some(Word::default())).await.unwrap();
+
+        target.import_backup(backup).await.unwrap();
+
+        assert_eq!(target.get_account_ids().await.unwrap(), vec![account.id()]);
+        let history = target.get_account_history(account.id()).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].seed(), Some(&Word::default()));
+        assert_eq!(history[1].account().commitment(), updated_account.commitment());
+
+        let tags = target.get_note_tags().await.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].source, NoteTagSource::Account(account.id()));
+
+        let (stored_genesis, _) =
+            target.get_block_header_by_num(BlockNumber::GENESIS).await.unwrap().unwrap();
+        assert_eq!(stored_genesis, genesis);
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
     insert_sql,
     store::{PartialBlockchainFilter, StoreError},
     subst,
+    sync::{BlockRetentionPolicy, authentication_node_candidates, stale_authentication_nodes},
 };
 
 struct SerializedBlockHeaderData {
@@ -216,20 +217,49 @@ impl SqliteStore {
         Ok(())
     }
 
-    /// Removes block headers that do not contain any client notes and aren't the genesis or last
-    /// block.
-    pub fn prune_irrelevant_blocks(conn: &mut Connection) -> Result<(), StoreError> {
+    /// Removes the block headers that the policy doesn't keep at the current sync height, along
+    /// with the partial blockchain nodes that were only needed to authenticate them.
+    pub fn prune_block_headers(
+        conn: &mut Connection,
+        policy: &BlockRetentionPolicy,
+    ) -> Result<(), StoreError> {
+        let sync_height = Self::get_sync_height(conn)?;
         let tx = conn.transaction()?;
 
-        let query = format!(
-            "\
+        // Mirrors `BlockRetentionPolicy::retains`
+        const DELETE_QUERY: &str = "\
             DELETE FROM block_headers
-            WHERE has_client_notes = FALSE
-            AND block_num != {}
-            AND block_num NOT IN (SELECT block_num FROM state_sync)",
-            BlockNumber::GENESIS.as_u32()
-        );
-        tx.execute(query.as_str(), params![])?;
+            WHERE block_num != 0
+            AND (block_num < ?1 OR block_num > ?2)
+            AND NOT (?3 AND has_client_notes)
+            AND NOT (?4 AND block_num % ?5 = 0)
+            AND block_num NOT IN rarray(?6)
+            RETURNING block_num";
+        let pinned_blocks: Vec<Value> = policy
+            .pinned_blocks()
+            .iter()
+            .map(|block_num| Value::Integer(i64::from(block_num.as_u32())))
+            .collect();
+        let pruned_blocks = tx
+            .prepare(DELETE_QUERY)?
+            .query_map(
+                params![
+                    policy.first_recent_block(sync_height).as_u32(),
+                    sync_height.as_u32(),
+                    policy.keeps_relevant_blocks(),
+                    policy.keeps_epoch_blocks(),
+                    1u32 << BlockNumber::EPOCH_LENGTH_EXPONENT,
+                    Rc::new(pinned_blocks),
+                ],
+                |row| row.get::<_, u32>(0),
+            )?
+            .map(|result| Ok(result?.into()))
+            .collect::<Result<Vec<BlockNumber>, StoreError>>()?;
+
+        if !pruned_blocks.is_empty() {
+            remove_stale_partial_blockchain_nodes(&tx, &pruned_blocks, sync_height)?;
+        }
+
         Ok(tx.commit().map(|_| ())?)
     }
 }
@@ -237,6 +267,37 @@ impl SqliteStore {
 // HELPERS
 // ================================================================================================
 
+/// Removes the partial blockchain nodes that were only needed to authenticate the pruned blocks.
+fn remove_stale_partial_blockchain_nodes(
+    tx: &Transaction<'_>,
+    pruned_blocks: &[BlockNumber],
+    sync_height: BlockNumber,
+) -> Result<(), StoreError> {
+    let candidates = authentication_node_candidates(pruned_blocks, sync_height)
+        .into_iter()
+        .map(|id| Value::Integer(i64::try_from(id.inner()).expect("id is a valid i64")))
+        .collect::<Vec<_>>();
+
+    const SELECT_QUERY: &str = "SELECT id FROM partial_blockchain_nodes WHERE id IN rarray(?)";
+    let stored_nodes = tx
+        .prepare(SELECT_QUERY)?
+        .query_map(params![Rc::new(candidates)], |row| row.get::<_, u64>(0))?
+        .map(|result| {
+            let id = usize::try_from(result?).expect("id is u64, should not fail");
+            Ok(InOrderIndex::new(NonZeroUsize::new(id).expect("node ids are never zero")))
+        })
+        .collect::<Result<BTreeSet<InOrderIndex>, StoreError>>()?;
+
+    let stale_nodes = stale_authentication_nodes(pruned_blocks, stored_nodes)
+        .into_iter()
+        .map(|id| Value::Integer(i64::try_from(id.inner()).expect("id is a valid i64")))
+        .collect::<Vec<_>>();
+
+    const DELETE_QUERY: &str = "DELETE FROM partial_blockchain_nodes WHERE id IN rarray(?)";
+    tx.execute(DELETE_QUERY, params![Rc::new(stale_nodes)])?;
+    Ok(())
+}
+
 /// Inserts a node represented by its in-order index and the node value.
 fn insert_partial_blockchain_node(
     tx: &Transaction<'_>,
@@ -58,7 +58,12 @@ macro_rules! insert_sql {
 
 type Hash = Blake3Digest<20>;
 
-const MIGRATION_SCRIPTS: [&str; 1] = [include_str!("../store.sql")];
+const MIGRATION_SCRIPTS: [&str; 4] = [
+    include_str!("../store.sql"),
+    include_str!("../note_discovery_rules.sql"),
+    include_str!("../transaction_queue.sql"),
+    include_str!("../discovered_notes.sql"),
+];
 static MIGRATION_HASHES: LazyLock<Vec<Hash>> = LazyLock::new(compute_migration_hashes);
 static MIGRATIONS: LazyLock<Migrations> = LazyLock::new(prepare_migrations);
 
@@ -0,0 +1,5 @@
+-- Create discovered notes table
+CREATE TABLE discovered_notes (
+    note_id TEXT NOT NULL,              -- ID of a note found by a discovery rule that wasn't imported yet
+    PRIMARY KEY (note_id)
+);
@@ -7,6 +7,7 @@
 use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    sync::Arc,
     vec::Vec,
 };
 use std::{path::PathBuf, string::ToString};
@@ -17,30 +18,38 @@ use db_management::{
 };
 use miden_objects::{
     Digest, Word,
-    account::{Account, AccountCode, AccountHeader, AccountId},
+    account::{Account, AccountCode, AccountHeader, AccountId, AccountStorage},
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
-    note::{NoteTag, Nullifier},
+    note::{NoteId, NoteTag, Nullifier},
+    transaction::TransactionId,
 };
+use miden_tx::utils::sync::RwLock;
 use rusqlite::{Connection, types::Value};
 use tonic::async_trait;
 
 use super::{
     AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, OutputNoteRecord,
-    PartialBlockchainFilter, Store, TransactionFilter,
+    PartialBlockchainFilter, PruneSummary, RetentionPolicy, Store, StoreBackup, TenantId,
+    TransactionFilter,
 };
 use crate::{
     store::StoreError,
-    sync::{NoteTagRecord, StateSyncUpdate},
-    transaction::{TransactionRecord, TransactionStoreUpdate},
+    sync::{
+        BlockRetentionPolicy, ChainRollback, NoteDiscoveryRule, NoteTagRecord, StateSyncUpdate,
+    },
+    transaction::{QueuedTransaction, TransactionRecord, TransactionStoreUpdate},
 };
 
 mod account;
+mod backup;
 mod chain_data;
 mod db_management;
 mod errors;
 mod note;
+mod prune;
 mod sync;
+mod tenant;
 mod transaction;
 
 // SQLITE STORE
@@ -49,9 +58,18 @@ mod transaction;
 /// Represents a pool of connections with an `SQLite` database. The pool is used to interact
 /// concurrently with the underlying database in a safe and efficient manner.
 ///
+/// Tenants (see [`Store::with_tenant`]) are kept in their own database files, next to the
+/// database the store was opened on. Each tenant has its own connection pool.
+///
 /// Current table definitions can be found at `store.sql` migration file.
 pub struct SqliteStore {
     pub(crate) pool: Pool,
+    /// Path of the database the store was opened on. Tenant databases are stored next to it.
+    root_filepath: PathBuf,
+    /// Connection pool of the database the store was opened on, where tenants are registered.
+    root_pool: Pool,
+    /// Connection pools of the tenants, by database path, shared by all the handles of the store.
+    tenant_pools: Arc<RwLock<BTreeMap<PathBuf, Pool>>>,
 }
 
 impl SqliteStore {
@@ -60,19 +78,14 @@ impl SqliteStore {
 
     /// Returns a new instance of [Store] instantiated with the specified configuration options.
     pub async fn new(database_filepath: PathBuf) -> Result<Self, StoreError> {
-        let sqlite_pool_manager = SqlitePoolManager::new(database_filepath.clone());
-        let pool = Pool::builder(sqlite_pool_manager)
-            .build()
-            .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
-
-        let conn = pool.get().await.map_err(|e| StoreError::DatabaseError(e.to_string()))?;
+        let pool = build_pool(database_filepath.clone()).await?;
 
-        let _ = conn
-            .interact(apply_migrations)
-            .await
-            .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
-
-        Ok(SqliteStore { pool })
+        Ok(SqliteStore {
+            root_pool: pool.clone(),
+            pool,
+            root_filepath: database_filepath,
+            tenant_pools: Arc::new(RwLock::new(BTreeMap::new())),
+        })
     }
 
     /// Interacts with the database by executing the provided function on a connection from the
@@ -86,13 +99,7 @@ impl SqliteStore {
         F: FnOnce(&mut Connection) -> Result<R, StoreError> + Send + 'static,
         R: Send + 'static,
     {
-        self.pool
-            .get()
-            .await
-            .map_err(|err| StoreError::DatabaseError(err.to_string()))?
-            .interact(f)
-            .await
-            .map_err(|err| StoreError::DatabaseError(err.to_string()))?
+        interact_with_pool(&self.pool, f).await
     }
 }
 
@@ -125,6 +132,37 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn get_note_discovery_rules(&self) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        self.interact_with_connection(SqliteStore::get_note_discovery_rules).await
+    }
+
+    async fn add_note_discovery_rule(&self, rule: NoteDiscoveryRule) -> Result<bool, StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::add_note_discovery_rule(conn, rule))
+            .await
+    }
+
+    async fn remove_note_discovery_rule(
+        &self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::remove_note_discovery_rule(conn, rule)
+        })
+        .await
+    }
+
+    async fn get_discovered_notes(&self) -> Result<Vec<NoteId>, StoreError> {
+        self.interact_with_connection(SqliteStore::get_discovered_notes).await
+    }
+
+    async fn remove_discovered_notes(&self, note_ids: &[NoteId]) -> Result<(), StoreError> {
+        let note_ids = note_ids.to_vec();
+        self.interact_with_connection(move |conn| {
+            SqliteStore::remove_discovered_notes(conn, &note_ids)
+        })
+        .await
+    }
+
     async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
         self.interact_with_connection(SqliteStore::get_sync_height).await
     }
@@ -136,6 +174,11 @@ impl Store for SqliteStore {
         .await
     }
 
+    async fn rollback_to(&self, rollback: ChainRollback) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::rollback_to(conn, &rollback))
+            .await
+    }
+
     async fn get_transactions(
         &self,
         transaction_filter: TransactionFilter,
@@ -151,6 +194,31 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, StoreError> {
+        self.interact_with_connection(SqliteStore::get_queued_transactions).await
+    }
+
+    async fn upsert_queued_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        let queued_transaction = queued_transaction.clone();
+        self.interact_with_connection(move |conn| {
+            SqliteStore::upsert_queued_transaction(conn, &queued_transaction)
+        })
+        .await
+    }
+
+    async fn remove_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::remove_queued_transaction(conn, transaction_id)
+        })
+        .await
+    }
+
     async fn get_input_notes(
         &self,
         filter: NoteFilter,
@@ -191,8 +259,21 @@ impl Store for SqliteStore {
         .await
     }
 
-    async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
-        self.interact_with_connection(SqliteStore::prune_irrelevant_blocks).await
+    async fn prune_block_headers(&self, policy: &BlockRetentionPolicy) -> Result<(), StoreError> {
+        let policy = policy.clone();
+        self.interact_with_connection(move |conn| SqliteStore::prune_block_headers(conn, &policy))
+            .await
+    }
+
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<PruneSummary, StoreError> {
+        let policy = policy.clone();
+        self.interact_with_connection(move |conn| SqliteStore::prune(conn, &policy))
+            .await
+    }
+
+    async fn import_backup(&self, backup: StoreBackup) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::import_backup(conn, &backup))
+            .await
     }
 
     async fn get_block_headers(
@@ -295,6 +376,22 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn get_accounts_code_and_storage(
+        &self,
+    ) -> Result<Vec<(AccountId, AccountCode, AccountStorage)>, StoreError> {
+        self.interact_with_connection(SqliteStore::get_accounts_code_and_storage).await
+    }
+
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_history(conn, account_id)
+        })
+        .await
+    }
+
     async fn upsert_foreign_account_code(
         &self,
         account_id: AccountId,
@@ -320,11 +417,51 @@ impl Store for SqliteStore {
         self.interact_with_connection(SqliteStore::get_unspent_input_note_nullifiers)
             .await
     }
+
+    async fn with_tenant(&self, tenant_id: TenantId) -> Result<Arc<dyn Store>, StoreError> {
+        Ok(Arc::new(self.tenant_store(tenant_id).await?))
+    }
+
+    async fn get_tenants(&self) -> Result<Vec<TenantId>, StoreError> {
+        SqliteStore::get_tenants(self).await
+    }
 }
 
 // UTILS
 // ================================================================================================
 
+/// Returns a connection pool for the database at the provided path, creating the database and
+/// applying pending migrations if needed.
+async fn build_pool(database_filepath: PathBuf) -> Result<Pool, StoreError> {
+    let sqlite_pool_manager = SqlitePoolManager::new(database_filepath);
+    let pool = Pool::builder(sqlite_pool_manager)
+        .build()
+        .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
+
+    let conn = pool.get().await.map_err(|e| StoreError::DatabaseError(e.to_string()))?;
+
+    let _ = conn
+        .interact(apply_migrations)
+        .await
+        .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
+
+    Ok(pool)
+}
+
+/// Executes the provided function on a connection from the pool.
+async fn interact_with_pool<F, R>(pool: &Pool, f: F) -> Result<R, StoreError>
+where
+    F: FnOnce(&mut Connection) -> Result<R, StoreError> + Send + 'static,
+    R: Send + 'static,
+{
+    pool.get()
+        .await
+        .map_err(|err| StoreError::DatabaseError(err.to_string()))?
+        .interact(f)
+        .await
+        .map_err(|err| StoreError::DatabaseError(err.to_string()))?
+}
+
 /// Gets a `u64` value from the database.
 ///
 /// `Sqlite` uses `i64` as its internal representation format, and so when retrieving
@@ -358,10 +495,18 @@ pub fn u64_to_value(v: u64) -> Value {
 
 #[cfg(test)]
 pub mod tests {
-    use std::boxed::Box;
+    use std::{boxed::Box, vec};
+
+    use miden_lib::transaction::TransactionKernel;
+    use miden_objects::{
+        Felt, Word, account::Account, testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+    };
 
     use super::SqliteStore;
-    use crate::{store::Store, tests::create_test_store_path};
+    use crate::{
+        store::{Store, TenantId},
+        tests::create_test_store_path,
+    };
 
     fn assert_send_sync<T: Send + Sync>() {}
 
@@ -385,6 +530,44 @@ pub mod tests {
         tokio::task::spawn(async move { dyn_trait_send_fut(client).await });
     }
 
+    #[tokio::test]
+    async fn tenants_are_isolated() {
+        let store_path = create_test_store_path();
+        let store = SqliteStore::new(store_path.clone()).await.unwrap();
+        assert!(store.get_tenants().await.unwrap().is_empty());
+
+        let tenant_a = store.with_tenant(TenantId::new("a")).await.unwrap();
+        let tenant_b = store.with_tenant(TenantId::new("b")).await.unwrap();
+
+        let account = Account::mock(
+            ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+            Felt::new(0),
+            TransactionKernel::testing_assembler(),
+        );
+        tenant_a.insert_account(&account, Some(Word::default())).await.unwrap();
+
+        assert_eq!(tenant_a.get_account_ids().await.unwrap(), vec![account.id()]);
+        assert!(tenant_b.get_account_ids().await.unwrap().is_empty());
+        assert!(store.get_account_ids().await.unwrap().is_empty());
+
+        // Handles for the same tenant share their data, and tenants are visible from any handle
+        let tenant_a_again = tenant_b.with_tenant(TenantId::new("a")).await.unwrap();
+        assert_eq!(tenant_a_again.get_account_ids().await.unwrap(), vec![account.id()]);
+        assert_eq!(
+            tenant_b.get_tenants().await.unwrap(),
+            vec![TenantId::new("a"), TenantId::new("b")]
+        );
+
+        // Tenants are kept when the store is reopened
+        let reopened_store = SqliteStore::new(store_path).await.unwrap();
+        assert_eq!(
+            reopened_store.get_tenants().await.unwrap(),
+            vec![TenantId::new("a"), TenantId::new("b")]
+        );
+        let tenant_a = reopened_store.with_tenant(TenantId::new("a")).await.unwrap();
+        assert_eq!(tenant_a.get_account_ids().await.unwrap(), vec![account.id()]);
+    }
+
     pub(crate) async fn create_test_store() -> SqliteStore {
         SqliteStore::new(create_test_store_path()).await.unwrap()
     }
@@ -0,0 +1,218 @@
+#![allow(clippy::items_after_statements)]
+
+use alloc::{collections::BTreeSet, string::ToString, vec::Vec};
+
+use miden_objects::account::AccountId;
+use rusqlite::{Connection, params};
+
+use super::SqliteStore;
+use crate::{
+    store::{NoteFilter, PruneSummary, RetentionPolicy, StoreError, TransactionFilter},
+    transaction::TransactionStatus,
+};
+
+impl SqliteStore {
+    pub(crate) fn prune(
+        conn: &mut Connection,
+        policy: &RetentionPolicy,
+    ) -> Result<PruneSummary, StoreError> {
+        let size_before = if policy.compact {
+            Some(database_size(conn)?)
+        } else {
+            None
+        };
+
+        // The data to remove is selected while holding the write lock, so changes committed by
+        // other connections in between can't make it remove data that's still needed
+        conn.execute_batch("BEGIN IMMEDIATE")?;
+        let mut summary = match prune_data(conn, policy) {
+            Ok(summary) => {
+                conn.execute_batch("COMMIT")?;
+                summary
+            },
+            Err(err) => {
+                conn.execute_batch("ROLLBACK")?;
+                return Err(err);
+            },
+        };
+
+        // Removed rows only free pages inside the database file, so the file needs to be rebuilt
+        // for the space to be released
+        if let Some(size_before) = size_before {
+            conn.execute_batch("VACUUM")?;
+            summary.reclaimed_bytes = Some(size_before.saturating_sub(database_size(conn)?));
+        }
+
+        Ok(summary)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Removes the data that the policy doesn't keep. Must be called within a transaction.
+fn prune_data(conn: &mut Connection, policy: &RetentionPolicy) -> Result<PruneSummary, StoreError> {
+    let sync_height = SqliteStore::get_sync_height(conn)?;
+    let transactions = SqliteStore::get_transactions(conn, &TransactionFilter::All)?;
+
+    let expired_input_notes = policy.expired_input_notes(
+        &SqliteStore::get_input_notes(conn, &NoteFilter::Consumed)?,
+        sync_height,
+    );
+    let expired_output_notes = policy.expired_output_notes(
+        &SqliteStore::get_output_notes(conn, &NoteFilter::Consumed)?,
+        sync_height,
+    );
+    let expired_transactions: Vec<_> = transactions
+        .iter()
+        .filter(|tx| {
+            matches!(tx.status, TransactionStatus::Discarded(_))
+                && policy.is_discarded_transaction_expired(tx.details.block_num, sync_height)
+        })
+        .map(|tx| tx.id)
+        .collect();
+
+    // Previous states of accounts with pending transactions are needed to roll them back if the
+    // transactions get discarded
+    let accounts_with_pending_transactions: BTreeSet<AccountId> = transactions
+        .iter()
+        .filter(|tx| matches!(tx.status, TransactionStatus::Pending))
+        .map(|tx| tx.details.account_id)
+        .collect();
+
+    let mut summary = PruneSummary::default();
+
+    if let Some(states_to_keep) = policy.states_to_keep_per_account() {
+        for account_id in SqliteStore::get_account_ids(conn)? {
+            if !accounts_with_pending_transactions.contains(&account_id) {
+                summary.removed_account_states +=
+                    remove_old_account_states(conn, account_id, states_to_keep)?;
+            }
+        }
+    }
+
+    const INPUT_NOTE_QUERY: &str = "DELETE FROM input_notes WHERE note_id = ?";
+    for note_id in expired_input_notes {
+        summary.removed_input_notes +=
+            conn.execute(INPUT_NOTE_QUERY, params![note_id.inner().to_string()])?;
+    }
+
+    const OUTPUT_NOTE_QUERY: &str = "DELETE FROM output_notes WHERE note_id = ?";
+    for note_id in expired_output_notes {
+        summary.removed_output_notes +=
+            conn.execute(OUTPUT_NOTE_QUERY, params![note_id.inner().to_string()])?;
+    }
+
+    const TRANSACTION_QUERY: &str = "DELETE FROM transactions WHERE id = ?";
+    for transaction_id in expired_transactions {
+        summary.removed_transactions +=
+            conn.execute(TRANSACTION_QUERY, params![transaction_id.to_string()])?;
+    }
+
+    if policy.remove_unreferenced_data {
+        summary.removed_unreferenced_entries = remove_unreferenced_data(conn)?;
+    }
+
+    Ok(summary)
+}
+
+/// Removes the states of the account except for the `states_to_keep` ones with the highest nonce.
+/// Returns the number of removed states.
+fn remove_old_account_states(
+    conn: &Connection,
+    account_id: AccountId,
+    states_to_keep: usize,
+) -> Result<usize, StoreError> {
+    const QUERY: &str = "\
+        DELETE FROM accounts
+        WHERE id = ?1
+        AND account_commitment NOT IN (
+            SELECT account_commitment FROM accounts WHERE id = ?1 ORDER BY nonce DESC LIMIT ?2
+        )";
+
+    let states_to_keep = i64::try_from(states_to_keep).unwrap_or(i64::MAX);
+    Ok(conn.execute(QUERY, params![account_id.to_hex(), states_to_keep])?)
+}
+
+/// Removes the account code, storage, vaults and scripts that aren't referenced by any account,
+/// note or transaction. Returns the number of removed entries.
+fn remove_unreferenced_data(conn: &Connection) -> Result<usize, StoreError> {
+    const QUERIES: [&str; 5] = [
+        "DELETE FROM account_code WHERE root NOT IN (SELECT code_root FROM accounts) \
+            AND root NOT IN (SELECT code_root FROM foreign_account_code)",
+        "DELETE FROM account_storage WHERE root NOT IN (SELECT storage_root FROM accounts)",
+        "DELETE FROM account_vaults WHERE root NOT IN (SELECT vault_root FROM accounts)",
+        "DELETE FROM notes_scripts WHERE script_root NOT IN (SELECT script_root FROM input_notes)",
+        "DELETE FROM transaction_scripts WHERE script_root NOT IN \
+            (SELECT script_root FROM transactions WHERE script_root IS NOT NULL)",
+    ];
+
+    let mut removed_entries = 0;
+    for query in QUERIES {
+        removed_entries += conn.execute(query, [])?;
+    }
+
+    Ok(removed_entries)
+}
+
+/// Returns the size of the database file, in bytes.
+fn database_size(conn: &Connection) -> Result<u64, StoreError> {
+    let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+
+    Ok(u64::try_from(page_count * page_size).unwrap_or_default())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::transaction::TransactionKernel;
+    use miden_objects::{
+        Felt, Word, account::Account, testing::account_id::ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+    };
+
+    use crate::store::{RetentionPolicy, Store, sqlite_store::tests::create_test_store};
+
+    #[tokio::test]
+    async fn old_account_states_are_pruned() {
+        let store = create_test_store().await;
+
+        let account = Account::mock(
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+            Felt::new(0),
+            TransactionKernel::testing_assembler(),
+        );
+        store.insert_account(&account, Some(Word::default())).await.unwrap();
+        for nonce in 1..=2 {
+            let updated_account = Account::mock(
+                ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET,
+                Felt::new(nonce),
+                TransactionKernel::testing_assembler(),
+            );
+            store.update_account(&updated_account).await.unwrap();
+        }
+
+        // The default policy keeps every account state, and doesn't compact the database
+        let summary = store.prune(&RetentionPolicy::default()).await.unwrap();
+        assert_eq!(summary.removed_account_states, 0);
+        assert!(summary.reclaimed_bytes.is_none());
+
+        let policy = RetentionPolicy {
+            account_states_to_keep: Some(1),
+            compact: true,
+            ..Default::default()
+        };
+        let summary = store.prune(&policy).await.unwrap();
+        assert_eq!(summary.removed_account_states, 2);
+        assert!(summary.reclaimed_bytes.is_some());
+
+        let history = store.get_account_history(account.id()).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].account().nonce(), Felt::new(2));
+
+        // The remaining state can still be loaded, so the blobs it references were kept
+        assert!(store.get_account(account.id()).await.unwrap().is_some());
+    }
+}
@@ -1,8 +1,12 @@
 #![allow(clippy::items_after_statements)]
 
-use alloc::{collections::BTreeSet, vec::Vec};
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
 
-use miden_objects::{Digest, block::BlockNumber, note::NoteTag};
+use miden_objects::{
+    Digest,
+    block::BlockNumber,
+    note::{NoteId, NoteTag},
+};
 use miden_tx::utils::{Deserializable, Serializable};
 use rusqlite::{Connection, Transaction, params};
 
@@ -13,12 +17,12 @@ use crate::{
         StoreError,
         sqlite_store::{
             account::{lock_account_on_unexpected_commitment, update_account},
-            note::apply_note_updates_tx,
+            note::{apply_note_updates_tx, upsert_input_note_tx, upsert_output_note_tx},
             transaction::upsert_transaction_record,
         },
     },
     subst,
-    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate},
+    sync::{ChainRollback, NoteDiscoveryRule, NoteTagRecord, NoteTagSource, StateSyncUpdate},
 };
 
 impl SqliteStore {
@@ -85,6 +89,73 @@ impl SqliteStore {
         Ok(removed_tags)
     }
 
+    pub(crate) fn get_note_discovery_rules(
+        conn: &mut Connection,
+    ) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        const QUERY: &str = "SELECT rule FROM note_discovery_rules";
+
+        conn.prepare(QUERY)?
+            .query_map([], |row| row.get(0))
+            .expect("no binding parameters used in query")
+            .map(|result| {
+                Ok(result?).and_then(|rule: Vec<u8>| {
+                    NoteDiscoveryRule::read_from_bytes(&rule)
+                        .map_err(StoreError::DataDeserializationError)
+                })
+            })
+            .collect::<Result<Vec<NoteDiscoveryRule>, _>>()
+    }
+
+    pub(super) fn add_note_discovery_rule(
+        conn: &mut Connection,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        let tx = conn.transaction()?;
+        let added = add_note_discovery_rule_tx(&tx, &rule)?;
+
+        tx.commit()?;
+
+        Ok(added)
+    }
+
+    pub(super) fn remove_note_discovery_rule(
+        conn: &mut Connection,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        const QUERY: &str = "DELETE FROM note_discovery_rules WHERE rule = ?";
+        let removed_rules = conn.execute(QUERY, params![rule.to_bytes()])?;
+
+        Ok(removed_rules > 0)
+    }
+
+    pub(super) fn get_discovered_notes(conn: &mut Connection) -> Result<Vec<NoteId>, StoreError> {
+        const QUERY: &str = "SELECT note_id FROM discovered_notes";
+
+        conn.prepare(QUERY)?
+            .query_map([], |row| row.get(0))
+            .expect("no binding parameters used in query")
+            .map(|result| {
+                let note_id: String = result?;
+                Ok(NoteId::try_from_hex(&note_id)?)
+            })
+            .collect()
+    }
+
+    pub(super) fn remove_discovered_notes(
+        conn: &mut Connection,
+        note_ids: &[NoteId],
+    ) -> Result<(), StoreError> {
+        const QUERY: &str = "DELETE FROM discovered_notes WHERE note_id = ?";
+
+        let tx = conn.transaction()?;
+        for note_id in note_ids {
+            tx.execute(QUERY, params![note_id.to_hex()])?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
     pub(super) fn get_sync_height(conn: &mut Connection) -> Result<BlockNumber, StoreError> {
         const QUERY: &str = "SELECT block_num FROM state_sync";
 
@@ -110,6 +181,7 @@ impl SqliteStore {
             note_updates,
             transaction_updates,
             account_updates,
+            discovered_notes,
         } = state_sync_update;
 
         let tx = conn.transaction()?;
@@ -178,11 +250,57 @@ impl SqliteStore {
             lock_account_on_unexpected_commitment(&tx, account_id, digest)?;
         }
 
+        // Keep the discovered notes until they are imported
+        const DISCOVERED_NOTES_QUERY: &str = insert_sql!(discovered_notes { note_id } | IGNORE);
+        for note_id in discovered_notes {
+            tx.execute(DISCOVERED_NOTES_QUERY, params![note_id.to_hex()])?;
+        }
+
         // Commit the updates
         tx.commit()?;
 
         Ok(())
     }
+
+    pub(super) fn rollback_to(
+        conn: &mut Connection,
+        rollback: &ChainRollback,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction()?;
+
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = ?";
+        tx.execute(BLOCK_NUMBER_QUERY, params![i64::from(rollback.block_num.as_u32())])?;
+
+        const BLOCK_HEADERS_QUERY: &str = "DELETE FROM block_headers WHERE block_num > ?";
+        tx.execute(BLOCK_HEADERS_QUERY, params![i64::from(rollback.block_num.as_u32())])?;
+
+        // The authentication nodes of the rollback replace the stored ones
+        tx.execute("DELETE FROM partial_blockchain_nodes", [])?;
+        Self::insert_partial_blockchain_nodes_tx(&tx, &rollback.partial_blockchain_nodes)?;
+
+        for transaction_record in &rollback.transactions {
+            upsert_transaction_record(&tx, transaction_record)?;
+        }
+
+        for note in &rollback.input_notes {
+            upsert_input_note_tx(&tx, note)?;
+        }
+
+        for note in &rollback.output_notes {
+            upsert_output_note_tx(&tx, note)?;
+        }
+
+        for tag in &rollback.tags {
+            add_note_tag_tx(&tx, tag)?;
+        }
+
+        // The notes found in the removed blocks are found again by the next sync
+        tx.execute("DELETE FROM discovered_notes", [])?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
 }
 
 pub(super) fn add_note_tag_tx(tx: &Transaction<'_>, tag: &NoteTagRecord) -> Result<(), StoreError> {
@@ -201,3 +319,14 @@ pub(super) fn remove_note_tag_tx(
 
     Ok(removed_tags)
 }
+
+/// Adds the note discovery rule, returning false if it was already present.
+pub(super) fn add_note_discovery_rule_tx(
+    tx: &Transaction<'_>,
+    rule: &NoteDiscoveryRule,
+) -> Result<bool, StoreError> {
+    const QUERY: &str = insert_sql!(note_discovery_rules { rule } | IGNORE);
+    let added_rules = tx.execute(QUERY, params![rule.to_bytes()])?;
+
+    Ok(added_rules > 0)
+}
@@ -0,0 +1,102 @@
+//! Tenant management for the `SQLite` store.
+//!
+//! Each tenant's data lives in its own database file, with the same tables as the database the
+//! store was opened on and stored in the same directory. Tenants are registered in the `tenants`
+//! table of the database the store was opened on.
+
+use alloc::{string::String, vec::Vec};
+use std::{
+    ffi::OsStr,
+    format,
+    path::{Path, PathBuf},
+};
+
+use miden_objects::crypto::hash::blake::Blake3_160;
+use rusqlite::params;
+
+use super::{SqliteStore, build_pool, interact_with_pool};
+use crate::store::{StoreError, TenantId};
+
+/// Suffix appended to the name of the root database to get the name of a tenant's database.
+const TENANT_FILE_SUFFIX: &str = ".tenant_";
+
+impl SqliteStore {
+    /// Returns a store that operates on the database of the provided tenant, creating and
+    /// migrating it if needed.
+    ///
+    /// The tenant's connection pool is created on first use and shared by all the handles of the
+    /// tenant.
+    pub(super) async fn tenant_store(&self, tenant_id: TenantId) -> Result<Self, StoreError> {
+        let tenant_filepath = tenant_database_path(&self.root_filepath, &tenant_id);
+        let file_name = tenant_filepath
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        interact_with_pool(&self.root_pool, move |conn| {
+            let tx = conn.transaction()?;
+            tx.execute_batch(include_str!("tenants.sql"))?;
+            tx.execute(
+                "INSERT OR IGNORE INTO tenants (tenant_id, file_name) VALUES (?, ?)",
+                params![tenant_id.as_str(), file_name],
+            )?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await?;
+
+        let existing_pool = self.tenant_pools.read().get(&tenant_filepath).cloned();
+        let pool = if let Some(pool) = existing_pool {
+            pool
+        } else {
+            let pool = build_pool(tenant_filepath.clone()).await?;
+            self.tenant_pools.write().entry(tenant_filepath).or_insert(pool).clone()
+        };
+
+        Ok(SqliteStore {
+            pool,
+            root_filepath: self.root_filepath.clone(),
+            root_pool: self.root_pool.clone(),
+            tenant_pools: self.tenant_pools.clone(),
+        })
+    }
+
+    /// Returns the tenants registered in the database the store was opened on.
+    pub(super) async fn get_tenants(&self) -> Result<Vec<TenantId>, StoreError> {
+        interact_with_pool(&self.root_pool, |conn| {
+            // The registry is only created along with the first tenant
+            let registry_exists: bool = conn.query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master \
+                 WHERE type = 'table' AND name = 'tenants')",
+                [],
+                |row| row.get(0),
+            )?;
+            if !registry_exists {
+                return Ok(Vec::new());
+            }
+
+            let mut stmt = conn.prepare("SELECT tenant_id FROM tenants ORDER BY tenant_id")?;
+            let tenants = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .map(|tenant_id| tenant_id.map(TenantId::new))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(tenants)
+        })
+        .await
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the path of the database holding the data of the tenant.
+///
+/// The file name is derived from a hash of the tenant ID, so arbitrary tenant IDs map to valid file
+/// names.
+fn tenant_database_path(root_filepath: &Path, tenant_id: &TenantId) -> PathBuf {
+    let digest = Blake3_160::hash(tenant_id.as_str().as_bytes());
+    let mut file_name = root_filepath.file_name().map(OsStr::to_os_string).unwrap_or_default();
+    file_name.push(format!("{TENANT_FILE_SUFFIX}{}", hex::encode(&*digest)));
+    root_filepath.with_file_name(file_name)
+}
@@ -0,0 +1,9 @@
+-- Create the tenants registry. It lives in the database the store was opened on, while the data of
+-- each tenant lives in the tenant's own database file.
+CREATE TABLE IF NOT EXISTS tenants (
+    tenant_id TEXT NOT NULL,  -- identifier of the tenant.
+    file_name TEXT NOT NULL,  -- name of the database file holding the tenant's data.
+
+    PRIMARY KEY (tenant_id),
+    CONSTRAINT tenants_tenant_id_is_not_empty CHECK (length(tenant_id) > 0)
+);
@@ -10,7 +10,7 @@ use miden_objects::{
     Digest,
     block::BlockNumber,
     crypto::utils::{Deserializable, Serializable},
-    transaction::{ToInputNoteCommitments, TransactionScript},
+    transaction::{ToInputNoteCommitments, TransactionId, TransactionScript},
 };
 use rusqlite::{Connection, Transaction, params, types::Value};
 
@@ -22,7 +22,7 @@ use crate::{
     store::{StoreError, TransactionFilter},
     subst,
     transaction::{
-        DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus,
+        DiscardCause, QueuedTransaction, TransactionDetails, TransactionRecord, TransactionStatus,
         TransactionStoreUpdate,
     },
 };
@@ -180,6 +180,61 @@ impl SqliteStore {
 
         Ok(())
     }
+
+    /// Retrieves the entries of the outbound transaction queue.
+    pub fn get_queued_transactions(
+        conn: &mut Connection,
+    ) -> Result<Vec<QueuedTransaction>, StoreError> {
+        const QUERY: &str = "SELECT entry FROM transaction_queue";
+
+        conn.prepare(QUERY)?
+            .query_map([], |row| row.get(0))
+            .expect("no binding parameters used in query")
+            .map(|result| {
+                Ok(result?).and_then(|entry: Vec<u8>| {
+                    QueuedTransaction::read_from_bytes(&entry)
+                        .map_err(StoreError::DataDeserializationError)
+                })
+            })
+            .collect::<Result<Vec<QueuedTransaction>, _>>()
+    }
+
+    /// Updates the entry of the outbound transaction queue, inserting it if it doesn't exist.
+    pub fn upsert_queued_transaction(
+        conn: &mut Connection,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction()?;
+        upsert_queued_transaction_tx(&tx, queued_transaction)?;
+
+        Ok(tx.commit()?)
+    }
+
+    /// Removes the transaction from the outbound transaction queue, returning false if it wasn't
+    /// queued.
+    pub fn remove_queued_transaction(
+        conn: &mut Connection,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        const QUERY: &str = "DELETE FROM transaction_queue WHERE id = ?";
+        let removed_entries = conn.execute(QUERY, params![transaction_id.to_string()])?;
+
+        Ok(removed_entries > 0)
+    }
+}
+
+/// Updates the entry of the outbound transaction queue, inserting it if it doesn't exist.
+pub(crate) fn upsert_queued_transaction_tx(
+    tx: &Transaction<'_>,
+    queued_transaction: &QueuedTransaction,
+) -> Result<(), StoreError> {
+    const QUERY: &str = insert_sql!(transaction_queue { id, entry } | REPLACE);
+    tx.execute(
+        QUERY,
+        params![queued_transaction.id().to_string(), queued_transaction.to_bytes()],
+    )?;
+
+    Ok(())
 }
 
 /// Updates the transaction record in the database, inserting it if it doesn't exist.
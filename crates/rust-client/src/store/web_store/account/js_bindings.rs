@@ -17,6 +17,9 @@ extern "C" {
     #[wasm_bindgen(js_name = getAccountHeader)]
     pub fn idxdb_get_account_header(account_id: String) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getAccountHistory)]
+    pub fn idxdb_get_account_history(account_id: String) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = getAccountHeaderByCommitment)]
     pub fn idxdb_get_account_header_by_commitment(account_commitment: String) -> js_sys::Promise;
 
@@ -35,6 +38,9 @@ extern "C" {
     #[wasm_bindgen(js_name = fetchAndCacheAccountAuthByPubKey)]
     pub fn idxdb_fetch_and_cache_account_auth_by_pub_key(pub_key: String) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getAccountAuthPubKeys)]
+    pub fn idxdb_get_account_auth_pub_keys() -> js_sys::Promise;
+
     // INSERTS
     // ================================================================================================
 
@@ -87,4 +93,7 @@ extern "C" {
 
     #[wasm_bindgen(js_name = undoAccountStates)]
     pub fn idxdb_undo_account_states(account_hashes: Vec<String>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = removeAccountAuth)]
+    pub fn idxdb_remove_account_auth(pub_key: String) -> js_sys::Promise;
 }
@@ -20,9 +20,9 @@ mod js_bindings;
 use js_bindings::{
     idxdb_fetch_and_cache_account_auth_by_pub_key, idxdb_get_account_asset_vault,
     idxdb_get_account_code, idxdb_get_account_header, idxdb_get_account_header_by_commitment,
-    idxdb_get_account_headers, idxdb_get_account_ids, idxdb_get_account_storage,
-    idxdb_get_foreign_account_code, idxdb_lock_account, idxdb_undo_account_states,
-    idxdb_upsert_foreign_account_code,
+    idxdb_get_account_headers, idxdb_get_account_history, idxdb_get_account_ids,
+    idxdb_get_account_storage, idxdb_get_foreign_account_code, idxdb_lock_account,
+    idxdb_undo_account_states, idxdb_upsert_foreign_account_code,
 };
 
 mod models;
@@ -132,6 +132,37 @@ impl WebStore {
             None => return Ok(None),
             Some((account_header, status)) => (account_header, status),
         };
+
+        Ok(Some(self.get_account_record(&account_header, status).await?))
+    }
+
+    pub(crate) async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        let promise = idxdb_get_account_history(account_id.to_string());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to fetch account history: {js_error:?}",))
+        })?;
+
+        let account_headers_idxdb: Vec<AccountRecordIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        let mut account_history = Vec::with_capacity(account_headers_idxdb.len());
+        for account_header_idxdb in account_headers_idxdb {
+            let (account_header, status) = parse_account_record_idxdb_object(account_header_idxdb)?;
+            account_history.push(self.get_account_record(&account_header, status).await?);
+        }
+
+        Ok(account_history)
+    }
+
+    /// Builds the full [`AccountRecord`] for the account state described by the header.
+    async fn get_account_record(
+        &self,
+        account_header: &AccountHeader,
+        status: AccountStatus,
+    ) -> Result<AccountRecord, StoreError> {
         let account_code = self.get_account_code(account_header.code_commitment()).await?;
 
         let account_storage = self.get_account_storage(account_header.storage_commitment()).await?;
@@ -146,7 +177,7 @@ impl WebStore {
             account_header.nonce(),
         );
 
-        Ok(Some(AccountRecord::new(account, status)))
+        Ok(AccountRecord::new(account, status))
     }
 
     pub(super) async fn get_account_code(&self, root: Digest) -> Result<AccountCode, StoreError> {
@@ -342,6 +373,11 @@ impl WebStore {
             }
         }
 
+        self.lock_account(account_id).await
+    }
+
+    /// Locks every stored state of the account.
+    pub(super) async fn lock_account(&self, account_id: &AccountId) -> Result<(), StoreError> {
         let account_id_str = account_id.to_string();
         let promise = idxdb_lock_account(account_id_str);
         JsFuture::from(promise).await.map_err(|js_error| {
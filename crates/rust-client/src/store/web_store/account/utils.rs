@@ -16,9 +16,9 @@ use wasm_bindgen_futures::JsFuture;
 
 use super::{
     js_bindings::{
-        idxdb_get_account_auth_by_pub_key, idxdb_insert_account_asset_vault,
-        idxdb_insert_account_auth, idxdb_insert_account_code, idxdb_insert_account_record,
-        idxdb_insert_account_storage,
+        idxdb_get_account_auth_by_pub_key, idxdb_get_account_auth_pub_keys,
+        idxdb_insert_account_asset_vault, idxdb_insert_account_auth, idxdb_insert_account_code,
+        idxdb_insert_account_record, idxdb_insert_account_storage, idxdb_remove_account_auth,
     },
     models::{AccountAuthIdxdbObject, AccountRecordIdxdbObject},
 };
@@ -73,6 +73,20 @@ pub fn get_account_auth_by_pub_key(pub_key: String) -> Result<String, StoreError
     }
 }
 
+pub async fn get_account_auth_pub_keys() -> Result<Vec<String>, JsValue> {
+    let promise = idxdb_get_account_auth_pub_keys();
+    let js_value = JsFuture::from(promise).await?;
+
+    from_value(js_value).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+pub async fn remove_account_auth(pub_key: String) -> Result<(), JsValue> {
+    let promise = idxdb_remove_account_auth(pub_key);
+    JsFuture::from(promise).await?;
+
+    Ok(())
+}
+
 pub async fn insert_account_record(
     account: &Account,
     account_seed: Option<Word>,
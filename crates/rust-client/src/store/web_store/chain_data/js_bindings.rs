@@ -15,6 +15,9 @@ extern "C" {
     #[wasm_bindgen(js_name = getTrackedBlockHeaders)]
     pub fn idxdb_get_tracked_block_headers() -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getBlockHeaderNumbers)]
+    pub fn idxdb_get_block_header_numbers() -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = getPartialBlockchainNodesAll)]
     pub fn idxdb_get_partial_blockchain_nodes_all() -> js_sys::Promise;
 
@@ -44,6 +47,9 @@ extern "C" {
     // DELETES
     // ================================================================================================
 
-    #[wasm_bindgen(js_name = pruneIrrelevantBlocks)]
-    pub fn idxdb_prune_irrelevant_blocks() -> js_sys::Promise;
+    #[wasm_bindgen(js_name = deleteBlockHeaders)]
+    pub fn idxdb_delete_block_headers(block_numbers: Vec<String>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = deletePartialBlockchainNodes)]
+    pub fn idxdb_delete_partial_blockchain_nodes(ids: Vec<String>) -> js_sys::Promise;
 }
@@ -14,19 +14,24 @@ use serde_wasm_bindgen::from_value;
 use wasm_bindgen_futures::JsFuture;
 
 use super::WebStore;
-use crate::store::{PartialBlockchainFilter, StoreError};
+use crate::{
+    store::{PartialBlockchainFilter, StoreError},
+    sync::{BlockRetentionPolicy, authentication_node_candidates, stale_authentication_nodes},
+};
 
 mod js_bindings;
 use js_bindings::{
-    idxdb_get_block_headers, idxdb_get_partial_blockchain_nodes,
+    idxdb_delete_block_headers, idxdb_delete_partial_blockchain_nodes,
+    idxdb_get_block_header_numbers, idxdb_get_block_headers, idxdb_get_partial_blockchain_nodes,
     idxdb_get_partial_blockchain_nodes_all, idxdb_get_partial_blockchain_peaks_by_block_num,
     idxdb_get_tracked_block_headers, idxdb_insert_block_header,
-    idxdb_insert_partial_blockchain_nodes, idxdb_prune_irrelevant_blocks,
+    idxdb_insert_partial_blockchain_nodes,
 };
 
 mod models;
 use models::{
-    BlockHeaderIdxdbObject, PartialBlockchainNodeIdxdbObject, PartialBlockchainPeaksIdxdbObject,
+    BlockHeaderIdxdbObject, BlockHeaderNumberIdxdbObject, PartialBlockchainNodeIdxdbObject,
+    PartialBlockchainPeaksIdxdbObject,
 };
 
 pub mod utils;
@@ -193,10 +198,57 @@ impl WebStore {
         Ok(())
     }
 
-    pub(crate) async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
-        let promise = idxdb_prune_irrelevant_blocks();
+    pub(crate) async fn prune_block_headers(
+        &self,
+        policy: &BlockRetentionPolicy,
+    ) -> Result<(), StoreError> {
+        let sync_height = self.get_sync_height().await?;
+
+        let promise = idxdb_get_block_header_numbers();
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get block header numbers: {js_error:?}",))
+        })?;
+        let block_headers_idxdb: Vec<BlockHeaderNumberIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        let mut pruned_blocks = Vec::new();
+        for block_header in block_headers_idxdb {
+            let block_num: u32 = block_header.block_num.parse().map_err(|err| {
+                StoreError::ParsingError(format!("failed to parse block number: {err:?}"))
+            })?;
+            if !policy.retains(block_num.into(), block_header.has_client_notes, sync_height) {
+                pruned_blocks.push(BlockNumber::from(block_num));
+            }
+        }
+        if pruned_blocks.is_empty() {
+            return Ok(());
+        }
+
+        // Only the nodes that can authenticate the pruned blocks are loaded
+        let candidates = authentication_node_candidates(&pruned_blocks, sync_height);
+        let stored_nodes = self
+            .get_partial_blockchain_nodes(PartialBlockchainFilter::List(
+                candidates.into_iter().collect(),
+            ))
+            .await?
+            .into_keys()
+            .collect();
+        let stale_nodes: Vec<String> = stale_authentication_nodes(&pruned_blocks, stored_nodes)
+            .into_iter()
+            .map(|id| Into::<u64>::into(id).to_string())
+            .collect();
+
+        let pruned_blocks = pruned_blocks.iter().map(ToString::to_string).collect();
+        let promise = idxdb_delete_block_headers(pruned_blocks);
         JsFuture::from(promise).await.map_err(|js_error| {
-            StoreError::DatabaseError(format!("failed to prune block header: {js_error:?}",))
+            StoreError::DatabaseError(format!("failed to prune block headers: {js_error:?}",))
+        })?;
+
+        let promise = idxdb_delete_partial_blockchain_nodes(stale_nodes);
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!(
+                "failed to prune partial blockchain nodes: {js_error:?}",
+            ))
         })?;
 
         Ok(())
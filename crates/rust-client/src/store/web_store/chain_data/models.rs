@@ -14,6 +14,13 @@ pub struct BlockHeaderIdxdbObject {
     pub has_client_notes: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHeaderNumberIdxdbObject {
+    pub block_num: String,
+    pub has_client_notes: bool,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PartialBlockchainNodeIdxdbObject {
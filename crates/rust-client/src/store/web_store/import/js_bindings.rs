@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::{js_sys, wasm_bindgen};
 
@@ -6,4 +8,7 @@ extern "C" {
     #[wasm_bindgen(js_name = forceImportStore)]
     pub fn idxdb_force_import_store(store_dump: JsValue) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = clearStore)]
+    pub fn idxdb_clear_store(block_num: String) -> js_sys::Promise;
+
 }
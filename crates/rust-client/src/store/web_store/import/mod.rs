@@ -1,8 +1,18 @@
-use super::WebStore;
-use crate::store::StoreError;
+use alloc::string::ToString;
+
+use super::{
+    WebStore,
+    account::utils::{
+        insert_account_asset_vault, insert_account_code, insert_account_record,
+        insert_account_storage,
+    },
+    note::utils::{upsert_input_note_tx, upsert_output_note_tx},
+    transaction::utils::upsert_transaction_record,
+};
+use crate::store::{StoreBackup, StoreError};
 
 mod js_bindings;
-use js_bindings::idxdb_force_import_store;
+use js_bindings::{idxdb_clear_store, idxdb_force_import_store};
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 
@@ -14,4 +24,58 @@ impl WebStore {
             .map_err(|err| StoreError::DatabaseError(format!("Failed to import store: {err:?}")))?;
         Ok(())
     }
+
+    /// Replaces the store contents with the backup. Account keys are kept.
+    ///
+    /// `IndexedDB` transactions can't span multiple calls to the database, so the data is
+    /// inserted after the store is cleared instead of atomically.
+    pub(crate) async fn import_backup(&self, backup: StoreBackup) -> Result<(), StoreError> {
+        let promise = idxdb_clear_store(backup.sync_height.as_u32().to_string());
+        JsFuture::from(promise)
+            .await
+            .map_err(|err| StoreError::DatabaseError(format!("Failed to clear store: {err:?}")))?;
+
+        for account_state in &backup.accounts {
+            let account = &account_state.account;
+            insert_account_code(account.code()).await.map_err(import_error)?;
+            insert_account_storage(account.storage()).await.map_err(import_error)?;
+            insert_account_asset_vault(account.vault()).await.map_err(import_error)?;
+            insert_account_record(account, account_state.seed).await.map_err(import_error)?;
+
+            if account_state.locked {
+                self.lock_account(&account.id()).await?;
+            }
+        }
+        for note in &backup.input_notes {
+            upsert_input_note_tx(note).await?;
+        }
+        for note in &backup.output_notes {
+            upsert_output_note_tx(note).await?;
+        }
+        for tag in backup.tags {
+            self.add_note_tag(tag).await?;
+        }
+        for rule in backup.note_discovery_rules {
+            self.add_note_discovery_rule(rule).await?;
+        }
+        for transaction in &backup.transactions {
+            upsert_transaction_record(transaction).await?;
+        }
+        for queued_transaction in &backup.queued_transactions {
+            self.upsert_queued_transaction(queued_transaction).await?;
+        }
+        for block in backup.block_headers {
+            self.insert_block_header(
+                &block.header,
+                block.partial_blockchain_peaks,
+                block.has_client_notes,
+            )
+            .await?;
+        }
+        self.insert_partial_blockchain_nodes(&backup.partial_blockchain_nodes).await
+    }
+}
+
+fn import_error(err: JsValue) -> StoreError {
+    StoreError::DatabaseError(format!("Failed to import account: {err:?}"))
 }
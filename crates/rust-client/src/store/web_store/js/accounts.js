@@ -125,6 +125,49 @@ export async function getAccountHeader(accountId) {
   }
 }
 
+export async function getAccountHistory(accountId) {
+  try {
+    const allMatchingRecords = await accounts
+      .where("id")
+      .equals(accountId)
+      .toArray();
+
+    // Sort by ascending nonce, so that the oldest state comes first
+    const sortedRecords = allMatchingRecords.sort((a, b) => {
+      const bigIntA = BigInt(a.nonce);
+      const bigIntB = BigInt(b.nonce);
+      return bigIntA < bigIntB ? -1 : bigIntA > bigIntB ? 1 : 0;
+    });
+
+    return await Promise.all(
+      sortedRecords.map(async (record) => {
+        let accountSeedBase64 = null;
+        if (record.accountSeed) {
+          let accountSeedArrayBuffer = await record.accountSeed.arrayBuffer();
+          let accountSeedArray = new Uint8Array(accountSeedArrayBuffer);
+          accountSeedBase64 = uint8ArrayToBase64(accountSeedArray);
+        }
+
+        return {
+          id: record.id,
+          nonce: record.nonce,
+          vaultRoot: record.vaultRoot,
+          storageRoot: record.storageRoot,
+          codeRoot: record.codeRoot,
+          accountSeed: accountSeedBase64,
+          locked: record.locked,
+        };
+      })
+    );
+  } catch (error) {
+    console.error(
+      `Error fetching account history for ID ${accountId}:`,
+      error.toString()
+    );
+    throw error;
+  }
+}
+
 export async function getAccountHeaderByCommitment(accountCommitment) {
   try {
     // Fetch all records matching the given commitment
@@ -317,6 +360,15 @@ export async function fetchAndCacheAccountAuthByPubKey(pubKey) {
   }
 }
 
+export async function getAccountAuthPubKeys() {
+  try {
+    return await accountAuths.toCollection().primaryKeys();
+  } catch (error) {
+    console.error("Error fetching account auth public keys:", error.toString());
+    throw error;
+  }
+}
+
 // INSERT FUNCTIONS
 
 export async function insertAccountCode(codeRoot, code) {
@@ -428,8 +480,8 @@ export async function insertAccountAuth(pubKey, secretKey) {
       secretKey: secretKey,
     };
 
-    // Perform the insert using Dexie
-    await accountAuths.add(data);
+    // Perform the upsert using Dexie
+    await accountAuths.put(data);
   } catch (error) {
     console.error(
       `Error inserting auth for account: ${accountId}:`,
@@ -526,6 +578,19 @@ export async function undoAccountStates(accountCommitments) {
   }
 }
 
+export async function removeAccountAuth(pubKey) {
+  try {
+    await accountAuths.where("pubKey").equals(pubKey).delete();
+    ACCOUNT_AUTH_MAP.delete(pubKey);
+  } catch (error) {
+    console.error(
+      `Error removing account auth for pubKey ${pubKey}:`,
+      error.toString()
+    );
+    throw error;
+  }
+}
+
 function uint8ArrayToBase64(bytes) {
   const binary = bytes.reduce(
     (acc, byte) => acc + String.fromCharCode(byte),
@@ -187,25 +187,42 @@ export async function getPartialBlockchainNodes(ids) {
   try {
     const results = await partialBlockchainNodes.bulkGet(ids);
 
-    return results;
+    // Nodes that aren't stored are returned as undefined
+    return results.filter((result) => result !== undefined);
   } catch (err) {
     console.error("Failed to get partial blockchain nodes: ", err.toString());
     throw err;
   }
 }
 
-export async function pruneIrrelevantBlocks() {
+export async function getBlockHeaderNumbers() {
   try {
-    const syncHeight = await stateSync.get(1).blockNum;
-    const allMatchingRecords = await blockHeaders
-      .where("hasClientNotes")
-      .equals("false")
-      .and((record) => record.blockNum !== 0 && record.blockNum !== syncHeight)
-      .toArray();
+    const allRecords = await blockHeaders.toArray();
+
+    return allRecords.map((record) => ({
+      blockNum: record.blockNum,
+      hasClientNotes: record.hasClientNotes === "true",
+    }));
+  } catch (err) {
+    console.error("Failed to get block header numbers: ", err.toString());
+    throw err;
+  }
+}
+
+export async function deleteBlockHeaders(blockNumbers) {
+  try {
+    await blockHeaders.bulkDelete(blockNumbers);
+  } catch (err) {
+    console.error("Failed to delete block headers: ", err.toString());
+    throw err;
+  }
+}
 
-    await blockHeaders.bulkDelete(allMatchingRecords.map((r) => r.blockNum));
+export async function deletePartialBlockchainNodes(ids) {
+  try {
+    await partialBlockchainNodes.bulkDelete(ids);
   } catch (err) {
-    console.error("Failed to prune irrelevant blocks: ", err.toString());
+    console.error("Failed to delete partial blockchain nodes: ", err.toString());
     throw err;
   }
 }
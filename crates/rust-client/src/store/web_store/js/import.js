@@ -1,4 +1,4 @@
-import { db, openDatabase } from "./schema.js";
+import { db, openDatabase, accountAuths, stateSync } from "./schema.js";
 
 async function recursivelyTransformForImport(obj) {
   if (obj && typeof obj === "object") {
@@ -77,6 +77,26 @@ export async function forceImportStore(jsonStr) {
   }
 }
 
+// Removes all the data tracked by the client, except for the account keys, and sets the sync
+// height. Used before restoring a store backup.
+export async function clearStore(blockNum) {
+  try {
+    if (!db.isOpen) {
+      await openDatabase();
+    }
+
+    const tablesToClear = db.tables.filter((t) => t.name !== accountAuths.name);
+
+    await db.transaction("rw", tablesToClear, async () => {
+      await Promise.all(tablesToClear.map((t) => t.clear()));
+      await stateSync.put({ id: 1, blockNum: blockNum });
+    });
+  } catch (err) {
+    console.error("Failed to clear store: ", err.toString());
+    throw err;
+  }
+}
+
 function base64ToUint8Array(base64) {
   const binaryString = atob(base64);
   const len = binaryString.length;
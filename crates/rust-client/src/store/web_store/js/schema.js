@@ -29,6 +29,9 @@ const Table = {
   BlockHeaders: "blockHeaders",
   PartialBlockchainNodes: "partialBlockchainNodes",
   Tags: "tags",
+  NoteDiscoveryRules: "noteDiscoveryRules",
+  TransactionQueue: "transactionQueue",
+  DiscoveredNotes: "discoveredNotes",
 };
 
 const db = new Dexie(DATABASE_NAME);
@@ -60,6 +63,15 @@ db.version(1).stores({
   [Table.Tags]: indexes("id++", "tag", "source_note_id", "source_account_id"),
   [Table.ForeignAccountCode]: indexes("accountId"),
 });
+db.version(2).stores({
+  [Table.NoteDiscoveryRules]: indexes("&rule"),
+});
+db.version(3).stores({
+  [Table.TransactionQueue]: indexes("&id"),
+});
+db.version(4).stores({
+  [Table.DiscoveredNotes]: indexes("&noteId"),
+});
 
 function indexes(...items) {
   return items.join(",");
@@ -84,6 +96,9 @@ const stateSync = db.table(Table.StateSync);
 const blockHeaders = db.table(Table.BlockHeaders);
 const partialBlockchainNodes = db.table(Table.PartialBlockchainNodes);
 const tags = db.table(Table.Tags);
+const noteDiscoveryRules = db.table(Table.NoteDiscoveryRules);
+const transactionQueue = db.table(Table.TransactionQueue);
+const discoveredNotes = db.table(Table.DiscoveredNotes);
 const foreignAccountCode = db.table(Table.ForeignAccountCode);
 
 export {
@@ -102,5 +117,8 @@ export {
   blockHeaders,
   partialBlockchainNodes,
   tags,
+  noteDiscoveryRules,
+  transactionQueue,
+  discoveredNotes,
   foreignAccountCode,
 };
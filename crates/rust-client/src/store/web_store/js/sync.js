@@ -7,6 +7,8 @@ import {
   blockHeaders,
   partialBlockchainNodes,
   tags,
+  noteDiscoveryRules,
+  discoveredNotes,
 } from "./schema.js";
 
 export async function getNoteTags() {
@@ -28,6 +30,25 @@ export async function getNoteTags() {
   }
 }
 
+export async function getNoteDiscoveryRules() {
+  try {
+    return await noteDiscoveryRules.toArray();
+  } catch (error) {
+    console.error("Error fetching note discovery rules:", error.toString());
+    throw error;
+  }
+}
+
+export async function getDiscoveredNotes() {
+  try {
+    const records = await discoveredNotes.toArray();
+    return records.map((record) => record.noteId);
+  } catch (error) {
+    console.error("Error fetching discovered notes:", error.toString());
+    throw error;
+  }
+}
+
 export async function getSyncHeight() {
   try {
     const record = await stateSync.get(1); // Since id is the primary key and always 1
@@ -78,6 +99,41 @@ export async function removeNoteTag(tag, sourceNoteId, sourceAccountId) {
   }
 }
 
+export async function addNoteDiscoveryRule(rule) {
+  try {
+    let ruleBase64 = uint8ArrayToBase64(new Uint8Array(rule));
+    if ((await noteDiscoveryRules.get(ruleBase64)) !== undefined) {
+      return false;
+    }
+
+    await noteDiscoveryRules.add({ rule: ruleBase64 });
+    return true;
+  } catch (err) {
+    console.error("Failed to add note discovery rule: ", err.toString());
+    throw err;
+  }
+}
+
+export async function removeNoteDiscoveryRule(rule) {
+  try {
+    let ruleBase64 = uint8ArrayToBase64(new Uint8Array(rule));
+
+    return await noteDiscoveryRules.where({ rule: ruleBase64 }).delete();
+  } catch (err) {
+    console.log("Failed to remove note discovery rule: ", err.toString());
+    throw err;
+  }
+}
+
+export async function removeDiscoveredNotes(noteIds) {
+  try {
+    await discoveredNotes.bulkDelete(noteIds);
+  } catch (error) {
+    console.error("Error removing discovered notes:", error.toString());
+    throw error;
+  }
+}
+
 export async function applyStateSync(
   blockNum,
   newBlockHeadersAsFlattenedVec,
@@ -86,7 +142,8 @@ export async function applyStateSync(
   hasClientNotes,
   nodeIndexes,
   nodes,
-  inputNoteIds
+  inputNoteIds,
+  discoveredNoteIds
 ) {
   const newBlockHeaders = reconstructFlattenedVec(
     newBlockHeadersAsFlattenedVec
@@ -104,6 +161,7 @@ export async function applyStateSync(
     blockHeaders,
     partialBlockchainNodes,
     tags,
+    discoveredNotes,
     async (tx) => {
       await updateSyncHeight(tx, blockNum);
       for (let i = 0; i < newBlockHeaders.length; i++) {
@@ -117,6 +175,31 @@ export async function applyStateSync(
       }
       await updatePartialBlockchainNodes(tx, nodeIndexes, nodes);
       await updateCommittedNoteTags(tx, inputNoteIds);
+      // Keep the discovered notes until they are imported
+      await tx.discoveredNotes.bulkPut(
+        discoveredNoteIds.map((noteId) => ({ noteId }))
+      );
+    }
+  );
+}
+
+export async function rollbackTo(blockNum, nodeIndexes, nodes) {
+  return db.transaction(
+    "rw",
+    stateSync,
+    blockHeaders,
+    partialBlockchainNodes,
+    discoveredNotes,
+    async (tx) => {
+      await updateSyncHeight(tx, blockNum);
+      await tx.blockHeaders
+        .filter((record) => parseInt(record.blockNum) > parseInt(blockNum))
+        .delete();
+      // The authentication nodes of the rollback replace the stored ones
+      await tx.partialBlockchainNodes.clear();
+      await updatePartialBlockchainNodes(tx, nodeIndexes, nodes);
+      // The notes found in the removed blocks are found again by the next sync
+      await tx.discoveredNotes.clear();
     }
   );
 }
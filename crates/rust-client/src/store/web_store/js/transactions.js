@@ -1,4 +1,8 @@
-import { transactions, transactionScripts } from "./schema.js";
+import {
+  transactions,
+  transactionScripts,
+  transactionQueue,
+} from "./schema.js";
 
 const IDS_FILTER_PREFIX = "Ids:";
 export async function getTransactions(filter) {
@@ -178,6 +182,35 @@ export async function upsertTransactionRecord(
   }
 }
 
+export async function getQueuedTransactions() {
+  try {
+    return await transactionQueue.toArray();
+  } catch (err) {
+    console.error("Failed to get queued transactions: ", err.toString());
+    throw err;
+  }
+}
+
+export async function upsertQueuedTransaction(transactionId, entry) {
+  try {
+    let entryBase64 = uint8ArrayToBase64(new Uint8Array(entry));
+
+    await transactionQueue.put({ id: transactionId, entry: entryBase64 });
+  } catch (err) {
+    console.error("Failed to upsert queued transaction: ", err.toString());
+    throw err;
+  }
+}
+
+export async function removeQueuedTransaction(transactionId) {
+  try {
+    return await transactionQueue.where({ id: transactionId }).delete();
+  } catch (err) {
+    console.error("Failed to remove queued transaction: ", err.toString());
+    throw err;
+  }
+}
+
 function uint8ArrayToBase64(bytes) {
   const binary = bytes.reduce(
     (acc, byte) => acc + String.fromCharCode(byte),
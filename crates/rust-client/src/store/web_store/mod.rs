@@ -18,7 +18,8 @@ use miden_objects::{
     account::{Account, AccountCode, AccountHeader, AccountId},
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
-    note::Nullifier,
+    note::{NoteId, Nullifier},
+    transaction::TransactionId,
 };
 use tonic::async_trait;
 use wasm_bindgen::prelude::*;
@@ -26,11 +27,13 @@ use wasm_bindgen_futures::{JsFuture, js_sys, wasm_bindgen};
 
 use super::{
     AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, OutputNoteRecord,
-    PartialBlockchainFilter, Store, StoreError, TransactionFilter,
+    PartialBlockchainFilter, Store, StoreBackup, StoreError, TransactionFilter,
 };
 use crate::{
-    sync::{NoteTagRecord, StateSyncUpdate},
-    transaction::{TransactionRecord, TransactionStoreUpdate},
+    sync::{
+        BlockRetentionPolicy, ChainRollback, NoteDiscoveryRule, NoteTagRecord, StateSyncUpdate,
+    },
+    transaction::{QueuedTransaction, TransactionRecord, TransactionStoreUpdate},
 };
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -81,6 +84,21 @@ impl Store for WebStore {
         self.remove_note_tag(tag).await
     }
 
+    async fn get_note_discovery_rules(&self) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        self.get_note_discovery_rules().await
+    }
+
+    async fn add_note_discovery_rule(&self, rule: NoteDiscoveryRule) -> Result<bool, StoreError> {
+        self.add_note_discovery_rule(rule).await
+    }
+
+    async fn remove_note_discovery_rule(
+        &self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        self.remove_note_discovery_rule(rule).await
+    }
+
     async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
         self.get_sync_height().await
     }
@@ -89,6 +107,18 @@ impl Store for WebStore {
         self.apply_state_sync(state_sync_update).await
     }
 
+    async fn rollback_to(&self, rollback: ChainRollback) -> Result<(), StoreError> {
+        self.rollback_to(rollback).await
+    }
+
+    async fn get_discovered_notes(&self) -> Result<Vec<NoteId>, StoreError> {
+        self.get_discovered_notes().await
+    }
+
+    async fn remove_discovered_notes(&self, note_ids: &[NoteId]) -> Result<(), StoreError> {
+        self.remove_discovered_notes(note_ids).await
+    }
+
     // TRANSACTIONS
     // --------------------------------------------------------------------------------------------
 
@@ -103,6 +133,24 @@ impl Store for WebStore {
         self.apply_transaction(tx_update).await
     }
 
+    async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, StoreError> {
+        self.get_queued_transactions().await
+    }
+
+    async fn upsert_queued_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        self.upsert_queued_transaction(queued_transaction).await
+    }
+
+    async fn remove_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        self.remove_queued_transaction(transaction_id).await
+    }
+
     // NOTES
     // --------------------------------------------------------------------------------------------
     async fn get_input_notes(
@@ -168,8 +216,12 @@ impl Store for WebStore {
         self.get_partial_blockchain_peaks_by_block_num(block_num).await
     }
 
-    async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
-        self.prune_irrelevant_blocks().await
+    async fn prune_block_headers(&self, policy: &BlockRetentionPolicy) -> Result<(), StoreError> {
+        self.prune_block_headers(policy).await
+    }
+
+    async fn import_backup(&self, backup: StoreBackup) -> Result<(), StoreError> {
+        self.import_backup(backup).await
     }
 
     // ACCOUNTS
@@ -216,6 +268,13 @@ impl Store for WebStore {
         self.get_account(account_id).await
     }
 
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountRecord>, StoreError> {
+        self.get_account_history(account_id).await
+    }
+
     async fn upsert_foreign_account_code(
         &self,
         account_id: AccountId,
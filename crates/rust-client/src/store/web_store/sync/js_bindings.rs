@@ -18,6 +18,12 @@ extern "C" {
     #[wasm_bindgen(js_name = getNoteTags)]
     pub fn idxdb_get_note_tags() -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getNoteDiscoveryRules)]
+    pub fn idxdb_get_note_discovery_rules() -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = getDiscoveredNotes)]
+    pub fn idxdb_get_discovered_notes() -> js_sys::Promise;
+
     // INSERTS
     // ================================================================================================
 
@@ -28,6 +34,9 @@ extern "C" {
         source_account_id: Option<String>,
     ) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = addNoteDiscoveryRule)]
+    pub fn idxdb_add_note_discovery_rule(rule: Vec<u8>) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = applyStateSync)]
     pub fn idxdb_apply_state_sync(
         block_num: String,
@@ -38,6 +47,7 @@ extern "C" {
         serialized_node_ids: Vec<String>,
         serialized_nodes: Vec<String>,
         note_tags_to_remove_as_str: Vec<String>,
+        discovered_note_ids: Vec<String>,
     ) -> js_sys::Promise;
 
     // DELETES
@@ -49,6 +59,19 @@ extern "C" {
         source_account_id: Option<String>,
     ) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = removeNoteDiscoveryRule)]
+    pub fn idxdb_remove_note_discovery_rule(rule: Vec<u8>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = rollbackTo)]
+    pub fn idxdb_rollback_to(
+        block_num: String,
+        node_indexes: Vec<String>,
+        nodes: Vec<String>,
+    ) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = removeDiscoveredNotes)]
+    pub fn idxdb_remove_discovered_notes(note_ids: Vec<String>) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = discardTransactions)]
     pub fn idxdb_discard_transactions(transactions: Vec<String>) -> js_sys::Promise;
 }
@@ -16,22 +16,24 @@ use super::{
     WebStore,
     account::utils::update_account,
     chain_data::utils::{SerializedPartialBlockchainNodeData, serialize_partial_blockchain_node},
-    note::utils::apply_note_updates_tx,
+    note::utils::{apply_note_updates_tx, upsert_input_note_tx, upsert_output_note_tx},
     transaction::utils::upsert_transaction_record,
 };
 use crate::{
     store::StoreError,
-    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate},
+    sync::{ChainRollback, NoteDiscoveryRule, NoteTagRecord, NoteTagSource, StateSyncUpdate},
 };
 
 mod js_bindings;
 use js_bindings::{
-    idxdb_add_note_tag, idxdb_apply_state_sync, idxdb_get_note_tags, idxdb_get_sync_height,
-    idxdb_remove_note_tag,
+    idxdb_add_note_discovery_rule, idxdb_add_note_tag, idxdb_apply_state_sync,
+    idxdb_get_discovered_notes, idxdb_get_note_discovery_rules, idxdb_get_note_tags,
+    idxdb_get_sync_height, idxdb_remove_discovered_notes, idxdb_remove_note_discovery_rule,
+    idxdb_remove_note_tag, idxdb_rollback_to,
 };
 
 mod models;
-use models::{NoteTagIdxdbObject, SyncHeightIdxdbObject};
+use models::{NoteDiscoveryRuleIdxdbObject, NoteTagIdxdbObject, SyncHeightIdxdbObject};
 
 mod flattened_vec;
 use flattened_vec::flatten_nested_u8_vec;
@@ -117,6 +119,75 @@ impl WebStore {
         Ok(removed_tags)
     }
 
+    pub(crate) async fn get_note_discovery_rules(
+        &self,
+    ) -> Result<Vec<NoteDiscoveryRule>, StoreError> {
+        let promise = idxdb_get_note_discovery_rules();
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get note discovery rules: {js_error:?}"))
+        })?;
+        let rules_idxdb: Vec<NoteDiscoveryRuleIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        rules_idxdb
+            .into_iter()
+            .map(|r| NoteDiscoveryRule::read_from_bytes(&r.rule).map_err(Into::into))
+            .collect()
+    }
+
+    pub(super) async fn add_note_discovery_rule(
+        &self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        let promise = idxdb_add_note_discovery_rule(rule.to_bytes());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to add note discovery rule: {js_error:?}"))
+        })?;
+
+        from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))
+    }
+
+    pub(super) async fn remove_note_discovery_rule(
+        &self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<bool, StoreError> {
+        let promise = idxdb_remove_note_discovery_rule(rule.to_bytes());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove note discovery rule: {js_error:?}"))
+        })?;
+        let removed_rules: usize = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        Ok(removed_rules > 0)
+    }
+
+    pub(super) async fn get_discovered_notes(&self) -> Result<Vec<NoteId>, StoreError> {
+        let promise = idxdb_get_discovered_notes();
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get discovered notes: {js_error:?}"))
+        })?;
+        let note_ids: Vec<String> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        note_ids
+            .iter()
+            .map(|note_id| NoteId::try_from_hex(note_id).map_err(Into::into))
+            .collect()
+    }
+
+    pub(super) async fn remove_discovered_notes(
+        &self,
+        note_ids: &[NoteId],
+    ) -> Result<(), StoreError> {
+        let promise = idxdb_remove_discovered_notes(note_ids.iter().map(NoteId::to_hex).collect());
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove discovered notes: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
+
     pub(super) async fn apply_state_sync(
         &self,
         state_sync_update: StateSyncUpdate,
@@ -127,6 +198,7 @@ impl WebStore {
             note_updates,
             transaction_updates,
             account_updates,
+            discovered_notes,
         } = state_sync_update;
 
         // Serialize data for updating block header
@@ -215,6 +287,7 @@ impl WebStore {
             serialized_node_ids,
             serialized_nodes,
             note_tags_to_remove_as_str,
+            discovered_notes.iter().map(NoteId::to_hex).collect(),
         );
         JsFuture::from(promise).await.map_err(|js_error| {
             StoreError::DatabaseError(format!("failed to apply state sync: {js_error:?}"))
@@ -222,4 +295,49 @@ impl WebStore {
 
         Ok(())
     }
+
+    pub(super) async fn rollback_to(&self, rollback: ChainRollback) -> Result<(), StoreError> {
+        let ChainRollback {
+            block_num,
+            partial_blockchain_nodes,
+            transactions,
+            input_notes,
+            output_notes,
+            tags,
+        } = rollback;
+
+        // Serialize data for replacing partial blockchain nodes
+        let mut serialized_node_ids = Vec::new();
+        let mut serialized_nodes = Vec::new();
+        for (id, node) in partial_blockchain_nodes {
+            let SerializedPartialBlockchainNodeData { id, node } =
+                serialize_partial_blockchain_node(id, node)?;
+            serialized_node_ids.push(id);
+            serialized_nodes.push(node);
+        }
+
+        for transaction_record in &transactions {
+            upsert_transaction_record(transaction_record).await?;
+        }
+
+        for note in &input_notes {
+            upsert_input_note_tx(note).await?;
+        }
+
+        for note in &output_notes {
+            upsert_output_note_tx(note).await?;
+        }
+
+        for tag in tags {
+            self.add_note_tag(tag).await?;
+        }
+
+        let promise =
+            idxdb_rollback_to(block_num.to_string(), serialized_node_ids, serialized_nodes);
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to roll back: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
 }
@@ -13,6 +13,9 @@ extern "C" {
     #[wasm_bindgen(js_name = getTransactions)]
     pub fn idxdb_get_transactions(filter: String) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getQueuedTransactions)]
+    pub fn idxdb_get_queued_transactions() -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = insertTransactionScript)]
     pub fn idxdb_insert_transaction_script(
         script_root: Vec<u8>,
@@ -28,4 +31,16 @@ extern "C" {
         committed: Option<String>,
         discard_cause: Option<Vec<u8>>,
     ) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = upsertQueuedTransaction)]
+    pub fn idxdb_upsert_queued_transaction(
+        transaction_id: String,
+        entry: Vec<u8>,
+    ) -> js_sys::Promise;
+
+    // DELETES
+    // ================================================================================================
+
+    #[wasm_bindgen(js_name = removeQueuedTransaction)]
+    pub fn idxdb_remove_queued_transaction(transaction_id: String) -> js_sys::Promise;
 }
@@ -3,8 +3,12 @@ use alloc::{
     vec::Vec,
 };
 
-use miden_objects::{Digest, block::BlockNumber, transaction::TransactionScript};
-use miden_tx::utils::Deserializable;
+use miden_objects::{
+    Digest,
+    block::BlockNumber,
+    transaction::{TransactionId, TransactionScript},
+};
+use miden_tx::utils::{Deserializable, Serializable};
 use serde_wasm_bindgen::from_value;
 use wasm_bindgen_futures::JsFuture;
 
@@ -12,16 +16,19 @@ use super::{WebStore, account::utils::update_account, note::utils::apply_note_up
 use crate::{
     store::{StoreError, TransactionFilter},
     transaction::{
-        DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus,
+        DiscardCause, QueuedTransaction, TransactionDetails, TransactionRecord, TransactionStatus,
         TransactionStoreUpdate,
     },
 };
 
 mod js_bindings;
-use js_bindings::idxdb_get_transactions;
+use js_bindings::{
+    idxdb_get_queued_transactions, idxdb_get_transactions, idxdb_remove_queued_transaction,
+    idxdb_upsert_queued_transaction,
+};
 
 mod models;
-use models::TransactionIdxdbObject;
+use models::{QueuedTransactionIdxdbObject, TransactionIdxdbObject};
 
 pub mod utils;
 use utils::insert_proven_transaction_data;
@@ -112,4 +119,47 @@ impl WebStore {
 
         Ok(())
     }
+
+    pub async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, StoreError> {
+        let promise = idxdb_get_queued_transactions();
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get queued transactions: {js_error:?}"))
+        })?;
+        let entries_idxdb: Vec<QueuedTransactionIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        entries_idxdb
+            .into_iter()
+            .map(|e| QueuedTransaction::read_from_bytes(&e.entry).map_err(Into::into))
+            .collect()
+    }
+
+    pub async fn upsert_queued_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<(), StoreError> {
+        let promise = idxdb_upsert_queued_transaction(
+            queued_transaction.id().to_string(),
+            queued_transaction.to_bytes(),
+        );
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to upsert queued transaction: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
+
+    pub async fn remove_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        let promise = idxdb_remove_queued_transaction(transaction_id.to_string());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove queued transaction: {js_error:?}"))
+        })?;
+        let removed_entries: usize = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        Ok(removed_entries > 0)
+    }
 }
@@ -19,6 +19,14 @@ pub struct TransactionIdxdbObject {
     pub discard_cause: Option<Vec<u8>>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedTransactionIdxdbObject {
+    pub id: String,
+    #[serde(deserialize_with = "base64_to_vec_u8_required", default)]
+    pub entry: Vec<u8>,
+}
+
 fn base64_to_vec_u8_required<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
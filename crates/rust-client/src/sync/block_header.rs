@@ -1,4 +1,4 @@
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
 
 use crypto::merkle::{InOrderIndex, MmrPeaks, PartialMmr};
 use miden_objects::{
@@ -9,7 +9,7 @@ use miden_objects::{
 use tracing::warn;
 
 use crate::{
-    Client, ClientError,
+    Client, ClientError, VerificationError,
     rpc::NodeRpcClient,
     store::{PartialBlockchainFilter, StoreError},
 };
@@ -80,7 +80,7 @@ impl Client {
             .store
             .get_block_header_by_num(current_block_num)
             .await?
-            .expect("Current block should be in the store");
+            .ok_or(StoreError::BlockHeaderNotFound(current_block_num))?;
 
         current_partial_mmr.add(current_block.commitment(), has_client_notes);
 
@@ -90,21 +90,19 @@ impl Client {
     /// Retrieves and stores a [`BlockHeader`] by number, and stores its authentication data as
     /// well.
     ///
-    /// If the store already contains MMR data for the requested block number, the request isn't
-    /// done and the stored block header is returned.
+    /// If the store already contains the header and MMR data for the requested block number, the
+    /// request isn't done and the stored block header is returned. Headers pruned by the block
+    /// retention policy are fetched again.
     pub(crate) async fn get_and_store_authenticated_block(
         &self,
         block_num: BlockNumber,
         current_partial_mmr: &mut PartialMmr,
     ) -> Result<BlockHeader, ClientError> {
         if current_partial_mmr.is_tracked(block_num.as_usize()) {
-            warn!("Current partial MMR already contains the requested data");
-            let (block_header, _) = self
-                .store
-                .get_block_header_by_num(block_num)
-                .await?
-                .expect("Block header should be tracked");
-            return Ok(block_header);
+            if let Some((block_header, _)) = self.store.get_block_header_by_num(block_num).await? {
+                warn!("Current partial MMR already contains the requested data");
+                return Ok(block_header);
+            }
         }
 
         // Fetch the block header and MMR proof from the node
@@ -120,6 +118,115 @@ impl Client {
         Ok(block_header)
     }
 
+    /// Makes sure the headers of the provided blocks are stored along with their authentication
+    /// data, fetching the missing ones from the node.
+    ///
+    /// Headers that aren't kept by the block retention policy are fetched again when a
+    /// transaction needs them, and are then reused until the next sync prunes them. Blocks past
+    /// the sync height are ignored.
+    pub(crate) async fn ensure_block_headers(
+        &self,
+        block_nums: &BTreeSet<BlockNumber>,
+    ) -> Result<(), ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        let stored_blocks: BTreeSet<BlockNumber> = self
+            .store
+            .get_block_headers(block_nums)
+            .await?
+            .iter()
+            .map(|(block_header, _)| block_header.block_num())
+            .collect();
+
+        let mut missing_blocks = block_nums
+            .iter()
+            .filter(|block_num| **block_num < sync_height && !stored_blocks.contains(block_num))
+            .peekable();
+        if missing_blocks.peek().is_none() {
+            return Ok(());
+        }
+
+        let mut current_partial_mmr = self.build_current_partial_mmr().await?;
+        for block_num in missing_blocks {
+            self.get_and_store_authenticated_block(*block_num, &mut current_partial_mmr)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the header of the provided block, authenticated against the client's partial
+    /// blockchain. Used to anchor data received from the node in verified mode.
+    ///
+    /// If the header isn't tracked yet, it's fetched from the node and stored along with its
+    /// authentication path, but only once the path is verified against the client's MMR peaks.
+    ///
+    /// # Errors
+    ///
+    /// - [`VerificationError::BlockNotSynced`] if the block is ahead of the client's sync height.
+    ///   The client needs to be synced before the data can be verified.
+    /// - [`VerificationError::BlockHeaderNotInChain`] if the header doesn't match the client's
+    ///   partial blockchain.
+    pub(crate) async fn get_verified_block_header(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<BlockHeader, ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        if block_num > sync_height {
+            return Err(VerificationError::BlockNotSynced(block_num).into());
+        }
+
+        // The header at the sync height was checked against the node's chain commitment when it
+        // was synced, and it's the one the client's MMR peaks are built from
+        if block_num == sync_height {
+            let (block_header, _) = self
+                .store
+                .get_block_header_by_num(block_num)
+                .await?
+                .ok_or(StoreError::BlockHeaderNotFound(block_num))?;
+            return Ok(block_header);
+        }
+
+        let mut current_partial_mmr = self.build_current_partial_mmr().await?;
+        // Tracked blocks may have had their headers pruned by the block retention policy
+        let stored_header = if current_partial_mmr.is_tracked(block_num.as_usize()) {
+            self.store.get_block_header_by_num(block_num).await?.map(|(header, _)| header)
+        } else {
+            None
+        };
+        let is_stored = stored_header.is_some();
+        let (block_header, path_nodes) = if let Some(block_header) = stored_header {
+            (block_header, Vec::new())
+        } else {
+            match fetch_block_header(self.rpc_api.clone(), block_num, &mut current_partial_mmr)
+                .await
+            {
+                // The authentication path doesn't lead to the client's peaks
+                Err(ClientError::StoreError(StoreError::MmrError(_))) => {
+                    return Err(VerificationError::BlockHeaderNotInChain(block_num).into());
+                },
+                result => result?,
+            }
+        };
+
+        let mmr_proof = current_partial_mmr
+            .open(block_num.as_usize())
+            .map_err(StoreError::MmrError)?
+            .ok_or(VerificationError::BlockHeaderNotInChain(block_num))?;
+        current_partial_mmr
+            .peaks()
+            .verify(block_header.commitment(), mmr_proof)
+            .map_err(|_| VerificationError::BlockHeaderNotInChain(block_num))?;
+
+        if !is_stored {
+            self.store
+                .insert_block_header(&block_header, current_partial_mmr.peaks(), true)
+                .await?;
+            self.store.insert_partial_blockchain_nodes(&path_nodes).await?;
+        }
+
+        Ok(block_header)
+    }
+
     /// Returns the epoch block for the specified block number.
     ///
     /// If the epoch block header is not stored, it will be retrieved and stored.
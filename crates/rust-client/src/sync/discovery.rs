@@ -0,0 +1,264 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::{fmt, ops::RangeInclusive};
+
+use futures::{StreamExt, TryStreamExt, stream};
+use miden_objects::{
+    Digest,
+    account::AccountId,
+    block::{BlockNumber, ProvenBlock},
+    note::{Note, NoteFile, NoteId},
+    transaction::OutputNote,
+};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use tracing::{info, warn};
+
+use super::FetchLimits;
+use crate::{
+    Client, ClientError,
+    rpc::{NodeRpcClient, RpcError},
+};
+
+/// Note discovery methods.
+impl Client {
+    /// Returns the note discovery rules tracked by the client.
+    ///
+    /// Besides the notes matching its tags, the client looks for public notes matching these rules
+    /// in every block it syncs (see [`NoteDiscoveryRule`]).
+    pub async fn get_note_discovery_rules(&self) -> Result<Vec<NoteDiscoveryRule>, ClientError> {
+        self.store.get_note_discovery_rules().await.map_err(Into::into)
+    }
+
+    /// Adds a note discovery rule for the client to track.
+    pub async fn add_note_discovery_rule(
+        &mut self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<(), ClientError> {
+        if !self.store.add_note_discovery_rule(rule).await? {
+            warn!("Discovery rule {} is already being tracked", rule);
+        }
+
+        Ok(())
+    }
+
+    /// Removes a note discovery rule tracked by the client.
+    pub async fn remove_note_discovery_rule(
+        &mut self,
+        rule: NoteDiscoveryRule,
+    ) -> Result<(), ClientError> {
+        if !self.store.remove_note_discovery_rule(rule).await? {
+            warn!("Discovery rule {} wasn't being tracked", rule);
+        }
+
+        Ok(())
+    }
+
+    /// Looks for public notes matching any of the provided rules in the blocks of `block_range`,
+    /// and imports the ones the client isn't tracking yet. Returns the IDs of the imported notes.
+    ///
+    /// This is a one-off scan: the rules aren't stored, so later syncs don't look for them. Every
+    /// block in the range is fetched from the node.
+    pub async fn scan_blocks_for_notes(
+        &mut self,
+        rules: &[NoteDiscoveryRule],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Result<Vec<NoteId>, ClientError> {
+        let note_ids = discover_notes(
+            self.rpc_api.as_ref(),
+            rules,
+            block_range.start().as_u32()..=block_range.end().as_u32(),
+            self.sync_fetch_limits,
+        )
+        .await?;
+
+        self.import_discovered_notes(note_ids).await
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Imports the discovered notes that the client isn't tracking yet, returning their IDs.
+    pub(crate) async fn import_discovered_notes(
+        &mut self,
+        note_ids: impl IntoIterator<Item = NoteId>,
+    ) -> Result<Vec<NoteId>, ClientError> {
+        let mut imported_notes = Vec::new();
+        for note_id in note_ids {
+            if self.get_input_note(note_id).await?.is_some() {
+                continue;
+            }
+
+            info!("Importing note {note_id} found by a discovery rule");
+            imported_notes.push(self.import_note(NoteFile::NoteId(note_id)).await?);
+        }
+
+        Ok(imported_notes)
+    }
+
+    /// Imports the discovered notes stored by previous syncs, returning the IDs of the imported
+    /// ones.
+    ///
+    /// Notes are removed from the store once they're imported, or if the client was already
+    /// tracking them. A note that fails to import is kept, so it is retried on the next sync.
+    pub(crate) async fn import_pending_discovered_notes(
+        &mut self,
+    ) -> Result<Vec<NoteId>, ClientError> {
+        let mut imported_notes = Vec::new();
+        let mut handled_notes = Vec::new();
+        for note_id in self.store.get_discovered_notes().await? {
+            if self.get_input_note(note_id).await?.is_some() {
+                handled_notes.push(note_id);
+                continue;
+            }
+
+            info!("Importing note {note_id} found by a discovery rule");
+            match self.import_note(NoteFile::NoteId(note_id)).await {
+                Ok(imported_note) => {
+                    imported_notes.push(imported_note);
+                    handled_notes.push(note_id);
+                },
+                Err(err) => warn!(
+                    "Failed to import discovered note {note_id}, retrying on the next sync: {err}"
+                ),
+            }
+        }
+
+        self.store.remove_discovered_notes(&handled_notes).await?;
+
+        Ok(imported_notes)
+    }
+}
+
+// NOTE DISCOVERY RULE
+// ================================================================================================
+
+/// Rule used to discover public notes that don't match any of the tags tracked by the client.
+///
+/// Tags are chosen by the note's creator, so a counterparty may send notes with a tag the client
+/// isn't watching. Discovery rules match notes by properties the client knows in advance instead.
+/// As the node can only filter notes by tag, looking for notes matching discovery rules requires
+/// fetching every synced block, so syncs are slower while any rule is tracked.
+///
+/// Only public notes are discovered, as the details of private notes aren't available on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NoteDiscoveryRule {
+    /// Matches notes created by the account.
+    Sender(AccountId),
+    /// Matches notes with the script root.
+    ScriptRoot(Digest),
+}
+
+impl NoteDiscoveryRule {
+    /// Returns whether the note matches the rule.
+    pub fn matches(&self, note: &Note) -> bool {
+        match self {
+            NoteDiscoveryRule::Sender(sender) => note.metadata().sender() == *sender,
+            NoteDiscoveryRule::ScriptRoot(script_root) => note.script().root() == *script_root,
+        }
+    }
+}
+
+impl fmt::Display for NoteDiscoveryRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoteDiscoveryRule::Sender(sender) => write!(f, "sender {sender}"),
+            NoteDiscoveryRule::ScriptRoot(script_root) => {
+                write!(f, "script root {}", script_root.to_hex())
+            },
+        }
+    }
+}
+
+impl Serializable for NoteDiscoveryRule {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            NoteDiscoveryRule::Sender(sender) => {
+                target.write_u8(0);
+                sender.write_into(target);
+            },
+            NoteDiscoveryRule::ScriptRoot(script_root) => {
+                target.write_u8(1);
+                script_root.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for NoteDiscoveryRule {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(NoteDiscoveryRule::Sender(AccountId::read_from(source)?)),
+            1 => Ok(NoteDiscoveryRule::ScriptRoot(Digest::read_from(source)?)),
+            val => {
+                Err(DeserializationError::InvalidValue(format!("Invalid discovery rule: {val}")))
+            },
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Fetches the blocks in `block_range` and returns the IDs of the public notes created in them
+/// that match any of the rules. Blocks are fetched concurrently according to the `limits`.
+pub(crate) async fn discover_notes(
+    rpc_api: &dyn NodeRpcClient,
+    rules: &[NoteDiscoveryRule],
+    block_range: RangeInclusive<u32>,
+    limits: FetchLimits,
+) -> Result<BTreeSet<NoteId>, RpcError> {
+    if rules.is_empty() || block_range.is_empty() {
+        return Ok(BTreeSet::new());
+    }
+
+    let note_ids: Vec<Vec<NoteId>> = stream::iter(block_range.map(BlockNumber::from))
+        .map(|block_num| async move {
+            let block = rpc_api.get_block_by_number(block_num).await?;
+            Ok::<_, RpcError>(matching_notes(&block, rules))
+        })
+        .buffered(limits.max_concurrent_requests.max(1))
+        .try_collect()
+        .await?;
+
+    Ok(note_ids.into_iter().flatten().collect())
+}
+
+/// Returns the IDs of the public notes created in the block that match any of the rules.
+fn matching_notes(block: &ProvenBlock, rules: &[NoteDiscoveryRule]) -> Vec<NoteId> {
+    block
+        .output_notes()
+        .filter_map(|(_, note)| match note {
+            OutputNote::Full(note) if rules.iter().any(|rule| rule.matches(note)) => {
+                Some(note.id())
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        Digest, account::AccountId,
+        testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+    };
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use super::NoteDiscoveryRule;
+
+    #[test]
+    fn discovery_rule_serialization_round_trip() {
+        let rules = [
+            NoteDiscoveryRule::Sender(
+                AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE).unwrap(),
+            ),
+            NoteDiscoveryRule::ScriptRoot(Digest::default()),
+        ];
+
+        for rule in rules {
+            assert_eq!(NoteDiscoveryRule::read_from_bytes(&rule.to_bytes()).unwrap(), rule);
+        }
+    }
+}
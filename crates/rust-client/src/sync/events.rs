@@ -0,0 +1,139 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use miden_objects::{
+    account::AccountId, block::BlockNumber, note::NoteId, transaction::TransactionId,
+};
+
+use super::StateSyncUpdate;
+use crate::{
+    note::NoteUpdateType,
+    transaction::{DiscardCause, TransactionStatus},
+};
+
+// SYNC EVENT
+// ================================================================================================
+
+/// A change to the client's state that was applied to the store during a sync.
+///
+/// Events are emitted to the listeners registered with [`crate::Client::add_sync_listener`] once
+/// the sync update they belong to has been persisted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEvent {
+    /// A block with notes relevant to the client was tracked.
+    BlockTracked(BlockNumber),
+    /// A new public note relevant to the client was received.
+    NoteReceived(NoteId),
+    /// A tracked input or output note was committed.
+    NoteCommitted(NoteId),
+    /// A tracked input or output note was consumed.
+    NoteNullified(NoteId),
+    /// A tracked transaction was committed in the provided block.
+    TransactionCommitted {
+        transaction_id: TransactionId,
+        block_num: BlockNumber,
+    },
+    /// A tracked transaction was discarded.
+    TransactionDiscarded {
+        transaction_id: TransactionId,
+        cause: DiscardCause,
+    },
+    /// A tracked public account was updated to its latest state on the network.
+    AccountUpdated(AccountId),
+    /// A tracked private account was locked because its state doesn't match the network.
+    AccountLocked(AccountId),
+}
+
+impl SyncEvent {
+    /// Returns the events for the changes contained in the state sync update.
+    pub(crate) fn from_state_sync_update(update: &StateSyncUpdate) -> Vec<SyncEvent> {
+        let mut events = Vec::new();
+
+        events.extend(
+            update
+                .block_updates
+                .block_headers()
+                .iter()
+                .filter(|(_, has_client_notes, _)| *has_client_notes)
+                .map(|(header, ..)| SyncEvent::BlockTracked(header.block_num())),
+        );
+
+        let mut committed_notes = BTreeSet::new();
+        let mut nullified_notes = BTreeSet::new();
+        for note_update in update.note_updates.updated_input_notes() {
+            let note = note_update.inner();
+            match note_update.update_type() {
+                NoteUpdateType::Insert => events.push(SyncEvent::NoteReceived(note.id())),
+                NoteUpdateType::Update if note.is_committed() => {
+                    committed_notes.insert(note.id());
+                },
+                _ => {},
+            }
+            if note.is_consumed() {
+                nullified_notes.insert(note.id());
+            }
+        }
+        for note_update in update.note_updates.updated_output_notes() {
+            let note = note_update.inner();
+            if matches!(note_update.update_type(), NoteUpdateType::Update) && note.is_committed() {
+                committed_notes.insert(note.id());
+            }
+            if note.is_consumed() {
+                nullified_notes.insert(note.id());
+            }
+        }
+        events.extend(committed_notes.into_iter().map(SyncEvent::NoteCommitted));
+        events.extend(nullified_notes.into_iter().map(SyncEvent::NoteNullified));
+
+        for transaction in update.transaction_updates.committed_transactions() {
+            if let TransactionStatus::Committed(block_num) = transaction.status {
+                events.push(SyncEvent::TransactionCommitted {
+                    transaction_id: transaction.id,
+                    block_num,
+                });
+            }
+        }
+        for transaction in update.transaction_updates.discarded_transactions() {
+            if let TransactionStatus::Discarded(cause) = transaction.status {
+                events.push(SyncEvent::TransactionDiscarded {
+                    transaction_id: transaction.id,
+                    cause,
+                });
+            }
+        }
+
+        events.extend(
+            update
+                .account_updates
+                .updated_public_accounts()
+                .iter()
+                .map(|account| SyncEvent::AccountUpdated(account.id())),
+        );
+        events.extend(
+            update
+                .account_updates
+                .mismatched_private_accounts()
+                .iter()
+                .map(|(account_id, _)| SyncEvent::AccountLocked(*account_id)),
+        );
+
+        events
+    }
+}
+
+// SYNC LISTENER
+// ================================================================================================
+
+/// Receives the [`SyncEvent`]s emitted by the client while syncing.
+///
+/// Listeners are called in the order they were registered, after each sync update is persisted.
+/// Any closure taking a `&SyncEvent` can be used as a listener.
+pub trait SyncListener {
+    /// Handles an event emitted during a sync.
+    fn on_sync_event(&self, event: &SyncEvent);
+}
+
+impl<F: Fn(&SyncEvent)> SyncListener for F {
+    fn on_sync_event(&self, event: &SyncEvent) {
+        self(event);
+    }
+}
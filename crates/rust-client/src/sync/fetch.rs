@@ -0,0 +1,123 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use futures::{StreamExt, TryStreamExt, stream};
+use miden_objects::{
+    account::{Account, AccountHeader},
+    note::NoteId,
+};
+
+use crate::{
+    rpc::{NodeRpcClient, RpcError},
+    store::InputNoteRecord,
+};
+
+/// Default maximum number of requests sent to the node at once while syncing.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Default maximum number of notes or accounts requested in a single request while syncing.
+pub const DEFAULT_MAX_ITEMS_PER_REQUEST: usize = 100;
+
+// FETCH LIMITS
+// ================================================================================================
+
+/// Limits for the requests used to fetch public notes and accounts during a sync.
+///
+/// The notes or accounts to fetch are split into as many requests as needed to use up to
+/// `max_concurrent_requests` concurrent requests, without any of them including more than
+/// `max_items_per_request` items. A limit of 0 is treated as 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchLimits {
+    /// Maximum number of requests sent to the node at once.
+    pub max_concurrent_requests: usize,
+    /// Maximum number of notes or accounts included in a single request.
+    pub max_items_per_request: usize,
+}
+
+impl FetchLimits {
+    /// Returns new [`FetchLimits`] with the provided values.
+    pub fn new(max_concurrent_requests: usize, max_items_per_request: usize) -> Self {
+        Self {
+            max_concurrent_requests,
+            max_items_per_request,
+        }
+    }
+
+    /// Returns the number of items to include in each request to fetch `num_items` items.
+    fn chunk_size(self, num_items: usize) -> usize {
+        num_items
+            .div_ceil(self.max_concurrent_requests.max(1))
+            .clamp(1, self.max_items_per_request.max(1))
+    }
+}
+
+impl Default for FetchLimits {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_ITEMS_PER_REQUEST)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Fetches the details of the provided public notes, splitting them into concurrent requests
+/// according to the `limits`.
+pub(crate) async fn fetch_public_notes(
+    rpc_api: &dyn NodeRpcClient,
+    note_ids: &[NoteId],
+    limits: FetchLimits,
+) -> Result<BTreeMap<NoteId, InputNoteRecord>, RpcError> {
+    if note_ids.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    let notes: Vec<Vec<InputNoteRecord>> =
+        stream::iter(note_ids.chunks(limits.chunk_size(note_ids.len())))
+            .map(|chunk| rpc_api.get_public_note_records(chunk, None))
+            .buffered(limits.max_concurrent_requests.max(1))
+            .try_collect()
+            .await?;
+
+    Ok(notes.into_iter().flatten().map(|note| (note.id(), note)).collect())
+}
+
+/// Fetches the latest state of the provided public accounts, splitting them into concurrent
+/// requests according to the `limits`. Only accounts that are newer than the provided headers are
+/// returned.
+pub(crate) async fn fetch_updated_public_accounts(
+    rpc_api: &dyn NodeRpcClient,
+    accounts: &[&AccountHeader],
+    limits: FetchLimits,
+) -> Result<Vec<Account>, RpcError> {
+    if accounts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let updated_accounts: Vec<Vec<Account>> =
+        stream::iter(accounts.chunks(limits.chunk_size(accounts.len())))
+            .map(|chunk| rpc_api.get_updated_public_accounts(chunk))
+            .buffered(limits.max_concurrent_requests.max(1))
+            .try_collect()
+            .await?;
+
+    Ok(updated_accounts.into_iter().flatten().collect())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::FetchLimits;
+
+    #[test]
+    fn requests_are_split_between_concurrent_requests() {
+        let limits = FetchLimits::new(4, 10);
+
+        assert_eq!(limits.chunk_size(1), 1);
+        assert_eq!(limits.chunk_size(8), 2);
+        assert_eq!(limits.chunk_size(9), 3);
+        // Requests never exceed the maximum size, even if that requires more requests
+        assert_eq!(limits.chunk_size(100), 10);
+        assert_eq!(FetchLimits::new(0, 0).chunk_size(5), 1);
+    }
+}
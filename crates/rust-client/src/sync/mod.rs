@@ -19,7 +19,15 @@
 //! a summary of the new block number along with lists of received, committed, and consumed note
 //! IDs, updated account IDs, locked accounts, and committed transaction IDs.
 //!
-//! Once the data is requested and retrieved, updates are persisted in the client's store.
+//! Once the data is requested and retrieved, updates are persisted in the client's store. Each
+//! persisted change is also reported as a [`SyncEvent`] to the listeners registered with
+//! `add_sync_listener`.
+//!
+//! The block headers kept after a sync, along with the blocks tracked in the client's partial
+//! blockchain, are chosen by the client's [`BlockRetentionPolicy`].
+//!
+//! Syncs only move forward from the sync height. Notes committed in already synced blocks that
+//! match an account or tag added later can be looked for with `rescan`.
 //!
 //! ## Examples
 //!
@@ -49,16 +57,29 @@
 //! ```
 //!
 //! The `sync_state` method loops internally until the client is fully synced to the network tip.
+//! Clients that may be far behind the tip can use `sync_state_checkpointed` instead, which persists
+//! the progress periodically so an interrupted sync can resume from the last checkpoint.
+//! `sync_state_with_options` limits a sync to some of the tracked accounts or note tags, or to a
+//! target block (see [`SyncOptions`]).
+//! If the node's chain doesn't extend the blocks already tracked by the client (for example,
+//! after a chain reorganization), syncs fail with a [`crate::ChainDivergenceError`] instead of
+//! applying the data. `rollback_to_common_ancestor` rolls the client back to the last block it
+//! shares with the node so the next sync can apply the node's chain.
+//! Besides the notes matching its tags, the client imports public notes matching its note
+//! discovery rules (see [`NoteDiscoveryRule`]), such as notes from a given sender.
+//! Long-running applications can use a [`SyncScheduler`] to decide when the next sync is due,
+//! backing off when the node can't be reached. The scheduler doesn't sync in the background: the
+//! application keeps driving the loop and calling [`SyncScheduler::sync`].
 //!
 //! For more advanced usage, refer to the individual functions (such as
 //! `committed_note_updates` and `consumed_note_updates`) to understand how the sync data is
 //! processed and applied to the local store.
 
-use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeSet, sync::Arc, vec::Vec};
 use core::cmp::max;
 
 use miden_objects::{
-    account::AccountId,
+    account::{AccountHeader, AccountId},
     block::BlockNumber,
     note::{NoteId, NoteTag},
     transaction::{PartialBlockchain, TransactionId},
@@ -68,10 +89,41 @@ use miden_tx::utils::{Deserializable, DeserializationError, Serializable};
 use crate::{
     Client, ClientError,
     note::NoteScreener,
+    rpc::NodeRpcClient,
     store::{NoteFilter, TransactionFilter},
+    verification::verify_state_sync_update,
 };
 mod block_header;
 
+mod discovery;
+pub use discovery::NoteDiscoveryRule;
+
+mod events;
+pub use events::{SyncEvent, SyncListener};
+
+mod fetch;
+pub use fetch::{DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_ITEMS_PER_REQUEST, FetchLimits};
+
+mod options;
+pub use options::SyncOptions;
+
+mod rescan;
+pub use rescan::RescanScope;
+
+mod retention;
+pub use retention::BlockRetentionPolicy;
+pub(crate) use retention::{authentication_node_candidates, stale_authentication_nodes};
+
+mod rollback;
+pub use rollback::ChainRollback;
+
+mod scheduler;
+pub(crate) use scheduler::backoff_delay;
+pub use scheduler::{DEFAULT_MAX_SYNC_BACKOFF, DEFAULT_SYNC_INTERVAL, SyncScheduler};
+
+mod shared_rpc;
+use shared_rpc::SharedSyncRpcClient;
+
 mod tag;
 pub use tag::{NoteTagRecord, NoteTagSource};
 
@@ -113,13 +165,237 @@ impl Client {
     /// 7. The MMR is updated with the new peaks and authentication nodes.
     /// 8. All updates are applied to the store to be persisted.
     pub async fn sync_state(&mut self) -> Result<SyncSummary, ClientError> {
+        self.sync_state_with_options(SyncOptions::default()).await
+    }
+
+    /// Syncs the client's state like [`Client::sync_state`], limited to the scope defined by the
+    /// provided [`SyncOptions`].
+    ///
+    /// The sync can be limited to some of the tracked accounts or note tags, skip nullifier checks
+    /// or stop at a target block. Partial syncs (see [`SyncOptions::is_partial`]) apply the
+    /// changes found for the selected entities but don't advance the client's sync height, so the
+    /// skipped entities receive the changes from the same blocks in the next full sync. Notes
+    /// committed in those blocks are stored as unverified until then.
+    pub async fn sync_state_with_options(
+        &mut self,
+        options: SyncOptions,
+    ) -> Result<SyncSummary, ClientError> {
+        let state_sync_update =
+            self.get_state_sync_update(self.rpc_api.clone(), None, &options).await?;
+        self.apply_state_sync_update(state_sync_update).await
+    }
+
+    /// Syncs the client's state like [`Client::sync_state`], but persists the progress every
+    /// `checkpoint_interval` blocks instead of only once the chain tip is reached.
+    ///
+    /// Each checkpoint is applied to the store atomically, so if the sync is interrupted (for
+    /// example, because of a crash or a network error) only the progress made since the last
+    /// checkpoint is lost, and the next sync resumes from it. Only the updates since the last
+    /// checkpoint are kept in memory: with an interval of 1, every step of the sync is persisted
+    /// before the next one is requested.
+    ///
+    /// The returned [`SyncSummary`] combines the summaries of all checkpoints.
+    pub async fn sync_state_checkpointed(
+        &mut self,
+        checkpoint_interval: u32,
+    ) -> Result<SyncSummary, ClientError> {
+        let checkpoint_interval = checkpoint_interval.max(1);
+        let mut sync_summary = SyncSummary::new_empty(self.get_sync_height().await?);
+
+        loop {
+            let checkpoint_start = self.get_sync_height().await?;
+            let state_sync_update = self
+                .get_state_sync_update(
+                    self.rpc_api.clone(),
+                    Some(checkpoint_interval),
+                    &SyncOptions::default(),
+                )
+                .await?;
+            let checkpoint_end = state_sync_update.block_num;
+
+            sync_summary.combine_with(self.apply_state_sync_update(state_sync_update).await?);
+
+            // A checkpoint can only cover fewer blocks than the interval if it stopped at the chain
+            // tip
+            if checkpoint_end.as_u32().saturating_sub(checkpoint_start.as_u32())
+                < checkpoint_interval
+            {
+                return Ok(sync_summary);
+            }
+        }
+    }
+
+    /// Syncs the state of several clients, usually built on top of different tenants of the same
+    /// store (see [`crate::store::Store::with_tenant`]), and returns a [`SyncSummary`] for each of
+    /// them, in the same order.
+    ///
+    /// Chain data is requested once for all clients: sync requests include the union of the
+    /// clients' tracked accounts and note tags, and each client only processes the data matching
+    /// its own accounts and tags. All requests are sent through the RPC client of the first
+    /// client.
+    ///
+    /// Updates are computed for every client before any of them is applied, so a failed request
+    /// doesn't leave only some of the clients synced.
+    pub async fn sync_tenants(clients: &mut [Client]) -> Result<Vec<SyncSummary>, ClientError> {
+        let Some(first_client) = clients.first() else {
+            return Ok(Vec::new());
+        };
+
+        let mut account_ids = BTreeSet::new();
+        let mut note_tags = BTreeSet::new();
+        for client in clients.iter() {
+            account_ids.extend(client.store.get_account_ids().await?);
+            note_tags.extend(client.store.get_unique_note_tags().await?);
+        }
+
+        let shared_rpc_api: Arc<dyn NodeRpcClient + Send> = Arc::new(SharedSyncRpcClient::new(
+            first_client.rpc_api.clone(),
+            account_ids,
+            note_tags,
+        ));
+
+        let mut state_sync_updates = Vec::with_capacity(clients.len());
+        for client in clients.iter_mut() {
+            state_sync_updates.push(
+                client
+                    .get_state_sync_update(shared_rpc_api.clone(), None, &SyncOptions::default())
+                    .await?,
+            );
+        }
+
+        let mut sync_summaries = Vec::with_capacity(clients.len());
+        for (client, state_sync_update) in clients.iter_mut().zip(state_sync_updates) {
+            sync_summaries.push(client.apply_state_sync_update(state_sync_update).await?);
+        }
+
+        Ok(sync_summaries)
+    }
+
+    // SYNC EVENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers a listener that is notified of every [`SyncEvent`] from now on.
+    ///
+    /// Events are emitted after each sync update is persisted to the store, so listeners can
+    /// safely query the client's state from them. Any closure taking a `&SyncEvent` can be used
+    /// as a listener.
+    pub fn add_sync_listener(&mut self, listener: Arc<dyn SyncListener>) {
+        self.sync_listeners.push(listener);
+    }
+
+    /// Removes every registered sync listener.
+    pub fn clear_sync_listeners(&mut self) {
+        self.sync_listeners.clear();
+    }
+
+    /// Sets the limits for the concurrent requests used to fetch the details of public notes and
+    /// accounts during syncs. By default, up to [`DEFAULT_MAX_CONCURRENT_REQUESTS`] requests of
+    /// up to [`DEFAULT_MAX_ITEMS_PER_REQUEST`] items each are sent at once.
+    pub fn set_sync_fetch_limits(&mut self, fetch_limits: FetchLimits) {
+        self.sync_fetch_limits = fetch_limits;
+    }
+
+    /// Returns the policy deciding which block headers the client keeps.
+    pub fn block_retention_policy(&self) -> &BlockRetentionPolicy {
+        &self.block_retention_policy
+    }
+
+    /// Sets the policy deciding which block headers the client keeps in its store. The policy is
+    /// applied from the next sync on, which prunes the stored headers that it doesn't keep.
+    pub fn set_block_retention_policy(&mut self, block_retention_policy: BlockRetentionPolicy) {
+        self.block_retention_policy = block_retention_policy;
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Requests the state updates for the client's current state through the provided RPC client,
+    /// without applying them to the store.
+    ///
+    /// If a `checkpoint_interval` is provided, the updates stop at the first checkpoint instead of
+    /// the chain tip (see [`StateSync::with_checkpoint_interval`]). The requested accounts, note
+    /// tags and nullifiers are limited by the provided `options`.
+    async fn get_state_sync_update(
+        &mut self,
+        rpc_api: Arc<dyn NodeRpcClient + Send>,
+        checkpoint_interval: Option<u32>,
+        options: &SyncOptions,
+    ) -> Result<StateSyncUpdate, ClientError> {
         _ = self.ensure_genesis_in_place().await?;
 
+        let mut state_sync = self.new_state_sync(rpc_api);
+        if let Some(checkpoint_interval) = checkpoint_interval {
+            state_sync = state_sync.with_checkpoint_interval(checkpoint_interval);
+        }
+        if let Some(target_block) = options.target_block() {
+            state_sync = state_sync.with_target_block(target_block);
+        }
+        if options.skips_nullifiers() {
+            state_sync = state_sync.skip_nullifiers();
+        }
+        if options.is_partial() {
+            state_sync = state_sync.detached();
+        }
+        // Syncs limited to some note tags skip the discovery rules as well
+        if options.note_tags().is_none() {
+            state_sync =
+                state_sync.with_note_discovery_rules(self.store.get_note_discovery_rules().await?);
+        }
+
+        // Get current state of the client
+        let accounts: Vec<AccountHeader> = self
+            .store
+            .get_account_headers()
+            .await?
+            .into_iter()
+            .map(|(acc_header, _)| acc_header)
+            .filter(|acc_header| {
+                options.accounts().is_none_or(|accounts| accounts.contains(&acc_header.id()))
+            })
+            .collect();
+
+        let note_tags: Vec<NoteTag> = self
+            .store
+            .get_unique_note_tags()
+            .await?
+            .into_iter()
+            .filter(|tag| options.note_tags().is_none_or(|note_tags| note_tags.contains(tag)))
+            .collect();
+
+        let unspent_input_notes = self.store.get_input_notes(NoteFilter::Unspent).await?;
+        let unspent_output_notes = self.store.get_output_notes(NoteFilter::Unspent).await?;
+
+        // Transactions of skipped accounts are left untouched, as their commitments aren't
+        // requested
+        let uncommitted_transactions = self
+            .store
+            .get_transactions(TransactionFilter::Uncommitted)
+            .await?
+            .into_iter()
+            .filter(|tx| accounts.iter().any(|acc_header| acc_header.id() == tx.details.account_id))
+            .collect();
+
+        // Get the sync update from the network
+        state_sync
+            .sync_state(
+                self.build_current_partial_blockchain().await?,
+                accounts,
+                note_tags,
+                unspent_input_notes,
+                unspent_output_notes,
+                uncommitted_transactions,
+            )
+            .await
+    }
+
+    /// Returns a [`StateSync`] that requests data through the provided RPC client and uses the
+    /// client's store to screen the received notes.
+    fn new_state_sync(&self, rpc_api: Arc<dyn NodeRpcClient + Send>) -> StateSync<'_> {
         let note_screener =
             NoteScreener::new(self.store.clone(), &self.tx_executor, self.mast_store.clone());
 
-        let state_sync = StateSync::new(
-            self.rpc_api.clone(),
+        StateSync::new(
+            rpc_api,
             Box::new({
                 let store_clone = self.store.clone();
                 move |committed_note, public_note, note_screener| {
@@ -133,27 +409,14 @@ impl Client {
             }),
             self.tx_graceful_blocks,
             note_screener,
-        );
-
-        // Get current state of the client
-        let accounts = self
-            .store
-            .get_account_headers()
-            .await?
-            .into_iter()
-            .map(|(acc_header, _)| acc_header)
-            .collect();
-
-        let note_tags: Vec<NoteTag> =
-            self.store.get_unique_note_tags().await?.into_iter().collect();
-
-        let unspent_input_notes = self.store.get_input_notes(NoteFilter::Unspent).await?;
-        let unspent_output_notes = self.store.get_output_notes(NoteFilter::Unspent).await?;
-
-        let uncommitted_transactions =
-            self.store.get_transactions(TransactionFilter::Uncommitted).await?;
+        )
+        .with_fetch_limits(self.sync_fetch_limits)
+        .with_block_retention_policy(self.block_retention_policy.clone())
+    }
 
-        // Build current partial MMR
+    /// Builds the client's current [`PartialBlockchain`] from its partial MMR and the headers of
+    /// the blocks it tracks.
+    async fn build_current_partial_blockchain(&self) -> Result<PartialBlockchain, ClientError> {
         let current_partial_mmr = self.build_current_partial_mmr().await?;
 
         let all_block_numbers = (0..current_partial_mmr.forest())
@@ -171,19 +434,25 @@ impl Client {
             .into_iter()
             .map(|(header, _has_notes)| header);
 
-        // Get the sync update from the network
-        let state_sync_update = state_sync
-            .sync_state(
-                PartialBlockchain::new(current_partial_mmr, block_headers)?,
-                accounts,
-                note_tags,
-                unspent_input_notes,
-                unspent_output_notes,
-                uncommitted_transactions,
-            )
-            .await?;
+        PartialBlockchain::new(current_partial_mmr, block_headers).map_err(Into::into)
+    }
 
-        let sync_summary: SyncSummary = (&state_sync_update).into();
+    /// Applies the state updates to the client's store and returns the corresponding
+    /// [`SyncSummary`].
+    async fn apply_state_sync_update(
+        &mut self,
+        state_sync_update: StateSyncUpdate,
+    ) -> Result<SyncSummary, ClientError> {
+        if self.verified_mode {
+            verify_state_sync_update(&state_sync_update)?;
+        }
+
+        let mut sync_summary: SyncSummary = (&state_sync_update).into();
+        let mut sync_events = if self.sync_listeners.is_empty() {
+            Vec::new()
+        } else {
+            SyncEvent::from_state_sync_update(&state_sync_update)
+        };
 
         // Apply received and computed updates to the store
         self.store
@@ -191,8 +460,26 @@ impl Client {
             .await
             .map_err(ClientError::StoreError)?;
 
-        // Remove irrelevant block headers
-        self.store.prune_irrelevant_blocks().await?;
+        // Committed and discarded transactions no longer need to be retried
+        self.collect_finished_queued_transactions().await?;
+
+        // Remove the block headers that the retention policy doesn't keep
+        self.store.prune_block_headers(&self.block_retention_policy).await?;
+
+        // Notes found by the discovery rules are stored with the update and imported once the
+        // synced blocks are stored, so their inclusion proofs can be checked against the client's
+        // chain. Notes that fail to import are kept and retried on the next sync.
+        let imported_notes = self.import_pending_discovered_notes().await?;
+        if !self.sync_listeners.is_empty() {
+            sync_events.extend(imported_notes.iter().copied().map(SyncEvent::NoteReceived));
+        }
+        sync_summary.new_public_notes.extend(imported_notes);
+
+        for event in &sync_events {
+            for listener in &self.sync_listeners {
+                listener.on_sync_event(event);
+            }
+        }
 
         Ok(sync_summary)
     }
@@ -218,9 +505,13 @@ pub struct SyncSummary {
     pub locked_accounts: Vec<AccountId>,
     /// IDs of committed transactions.
     pub committed_transactions: Vec<TransactionId>,
+    /// IDs of pending transactions that have been discarded because they expired before being
+    /// committed.
+    pub expired_transactions: Vec<TransactionId>,
 }
 
 impl SyncSummary {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         block_num: BlockNumber,
         new_public_notes: Vec<NoteId>,
@@ -229,6 +520,7 @@ impl SyncSummary {
         updated_accounts: Vec<AccountId>,
         locked_accounts: Vec<AccountId>,
         committed_transactions: Vec<TransactionId>,
+        expired_transactions: Vec<TransactionId>,
     ) -> Self {
         Self {
             block_num,
@@ -238,6 +530,7 @@ impl SyncSummary {
             updated_accounts,
             locked_accounts,
             committed_transactions,
+            expired_transactions,
         }
     }
 
@@ -250,6 +543,7 @@ impl SyncSummary {
             updated_accounts: vec![],
             locked_accounts: vec![],
             committed_transactions: vec![],
+            expired_transactions: vec![],
         }
     }
 
@@ -260,6 +554,7 @@ impl SyncSummary {
             && self.updated_accounts.is_empty()
             && self.locked_accounts.is_empty()
             && self.committed_transactions.is_empty()
+            && self.expired_transactions.is_empty()
     }
 
     pub fn combine_with(&mut self, mut other: Self) {
@@ -270,6 +565,7 @@ impl SyncSummary {
         self.updated_accounts.append(&mut other.updated_accounts);
         self.locked_accounts.append(&mut other.locked_accounts);
         self.committed_transactions.append(&mut other.committed_transactions);
+        self.expired_transactions.append(&mut other.expired_transactions);
     }
 }
 
@@ -282,6 +578,7 @@ impl Serializable for SyncSummary {
         self.updated_accounts.write_into(target);
         self.locked_accounts.write_into(target);
         self.committed_transactions.write_into(target);
+        self.expired_transactions.write_into(target);
     }
 }
 
@@ -296,6 +593,7 @@ impl Deserializable for SyncSummary {
         let updated_accounts = Vec::<AccountId>::read_from(source)?;
         let locked_accounts = Vec::<AccountId>::read_from(source)?;
         let committed_transactions = Vec::<TransactionId>::read_from(source)?;
+        let expired_transactions = Vec::<TransactionId>::read_from(source)?;
 
         Ok(Self {
             block_num,
@@ -305,6 +603,7 @@ impl Deserializable for SyncSummary {
             updated_accounts,
             locked_accounts,
             committed_transactions,
+            expired_transactions,
         })
     }
 }
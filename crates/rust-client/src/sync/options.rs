@@ -0,0 +1,92 @@
+use alloc::collections::BTreeSet;
+
+use miden_objects::{account::AccountId, block::BlockNumber, note::NoteTag};
+
+// SYNC OPTIONS
+// ================================================================================================
+
+/// Limits the scope of a sync done with [`crate::Client::sync_state_with_options`].
+///
+/// By default, a sync includes every tracked account and note tag, checks the nullifiers of every
+/// unspent note and goes on until the chain tip, just like [`crate::Client::sync_state`].
+///
+/// A sync limited to some of the accounts or note tags, or that skips nullifiers, is a *partial*
+/// sync. As the skipped entities don't receive the changes from the synced blocks, partial syncs
+/// don't advance the client's sync height or partial blockchain: notes committed in the synced
+/// blocks are stored as unverified, and they are verified by the next full sync, which goes over
+/// the same blocks for every entity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncOptions {
+    /// Accounts to sync. If `None`, every tracked account is synced.
+    accounts: Option<BTreeSet<AccountId>>,
+    /// Note tags to sync. If `None`, every tracked note tag is synced.
+    note_tags: Option<BTreeSet<NoteTag>>,
+    /// Whether nullifier checks are skipped.
+    skip_nullifiers: bool,
+    /// Block at which the sync stops. If `None`, the sync goes on until the chain tip.
+    target_block: Option<BlockNumber>,
+}
+
+impl SyncOptions {
+    /// Returns options for a full sync up to the chain tip.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the sync to the provided accounts. Accounts that aren't tracked by the client are
+    /// ignored.
+    #[must_use]
+    pub fn with_accounts(mut self, accounts: impl IntoIterator<Item = AccountId>) -> Self {
+        self.accounts = Some(accounts.into_iter().collect());
+        self
+    }
+
+    /// Limits the sync to the provided note tags. Tags that aren't tracked by the client are
+    /// ignored.
+    #[must_use]
+    pub fn with_note_tags(mut self, note_tags: impl IntoIterator<Item = NoteTag>) -> Self {
+        self.note_tags = Some(note_tags.into_iter().collect());
+        self
+    }
+
+    /// Skips checking the nullifiers of unspent notes, so notes consumed in the synced blocks
+    /// aren't detected.
+    #[must_use]
+    pub fn skip_nullifiers(mut self) -> Self {
+        self.skip_nullifiers = true;
+        self
+    }
+
+    /// Stops the sync once it reaches `target_block`. The sync may go past the target block if the
+    /// node skips blocks without relevant changes.
+    #[must_use]
+    pub fn with_target_block(mut self, target_block: BlockNumber) -> Self {
+        self.target_block = Some(target_block);
+        self
+    }
+
+    /// Returns the accounts to sync, or `None` if every tracked account is synced.
+    pub fn accounts(&self) -> Option<&BTreeSet<AccountId>> {
+        self.accounts.as_ref()
+    }
+
+    /// Returns the note tags to sync, or `None` if every tracked note tag is synced.
+    pub fn note_tags(&self) -> Option<&BTreeSet<NoteTag>> {
+        self.note_tags.as_ref()
+    }
+
+    /// Returns whether nullifier checks are skipped.
+    pub fn skips_nullifiers(&self) -> bool {
+        self.skip_nullifiers
+    }
+
+    /// Returns the block at which the sync stops, if any.
+    pub fn target_block(&self) -> Option<BlockNumber> {
+        self.target_block
+    }
+
+    /// Returns whether the options skip some of the client's accounts, note tags or nullifiers.
+    pub fn is_partial(&self) -> bool {
+        self.accounts.is_some() || self.note_tags.is_some() || self.skip_nullifiers
+    }
+}
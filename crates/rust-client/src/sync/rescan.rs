@@ -0,0 +1,97 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use miden_objects::{account::AccountId, block::BlockNumber, note::NoteTag};
+use tracing::info;
+
+use super::{NoteTagSource, SyncSummary};
+use crate::{Client, ClientError, store::NoteFilter};
+
+/// Historical rescan methods.
+impl Client {
+    /// Looks for notes committed between `from_block` and `to_block` that match the tags in the
+    /// provided `scope`, and stores the relevant ones.
+    ///
+    /// Syncs only move forward from the sync height, so notes committed before an account or tag
+    /// was added are never received. A rescan replays the note sync over the blocks the client
+    /// already synced, and merges the blocks of the notes it finds, along with their
+    /// authentication nodes, into the client's partial blockchain. Notes consumed after they were
+    /// committed are stored as consumed. The peaks of the chain at the rescanned blocks aren't
+    /// known, so their headers can't be used as anchors by [`Client::rollback_to_block`].
+    ///
+    /// Blocks past the sync height are left for the next sync, so `to_block` is capped at the
+    /// sync height. The returned [`SyncSummary`] only covers the rescanned notes and keeps the
+    /// client's sync height.
+    pub async fn rescan(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        scope: RescanScope,
+    ) -> Result<SyncSummary, ClientError> {
+        let sync_height = self.get_sync_height().await?;
+        let to_block = to_block.min(sync_height);
+        if from_block > to_block {
+            return Ok(SyncSummary::new_empty(sync_height));
+        }
+
+        let note_tags = self.get_rescan_note_tags(&scope).await?;
+        info!("Rescanning blocks {from_block} to {to_block} for {} note tags", note_tags.len());
+
+        let unspent_input_notes = self.store.get_input_notes(NoteFilter::Unspent).await?;
+        let unspent_output_notes = self.store.get_output_notes(NoteFilter::Unspent).await?;
+
+        let state_sync_update = self
+            .new_state_sync(self.rpc_api.clone())
+            .rescan_notes(
+                self.build_current_partial_blockchain().await?,
+                note_tags,
+                from_block,
+                to_block,
+                unspent_input_notes,
+                unspent_output_notes,
+            )
+            .await?;
+
+        self.apply_state_sync_update(state_sync_update).await
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the tracked note tags that belong to the rescan scope.
+    async fn get_rescan_note_tags(&self, scope: &RescanScope) -> Result<Vec<NoteTag>, ClientError> {
+        let note_tags: BTreeSet<NoteTag> = match scope {
+            RescanScope::All => self.store.get_unique_note_tags().await?,
+            RescanScope::Accounts(account_ids) => self
+                .store
+                .get_note_tags()
+                .await?
+                .into_iter()
+                .filter(|record| {
+                    matches!(
+                        record.source,
+                        NoteTagSource::Account(account_id) if account_ids.contains(&account_id)
+                    )
+                })
+                .map(|record| record.tag)
+                .collect(),
+            RescanScope::NoteTags(note_tags) => note_tags.clone(),
+        };
+
+        Ok(note_tags.into_iter().collect())
+    }
+}
+
+// RESCAN SCOPE
+// ================================================================================================
+
+/// Notes looked for by a [`Client::rescan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RescanScope {
+    /// Notes matching any of the note tags tracked by the client.
+    All,
+    /// Notes matching the tags of the provided accounts. Accounts that aren't tracked by the
+    /// client are ignored.
+    Accounts(BTreeSet<AccountId>),
+    /// Notes matching the provided note tags, whether the client tracks them or not.
+    NoteTags(BTreeSet<NoteTag>),
+}
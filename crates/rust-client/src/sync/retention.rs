@@ -0,0 +1,261 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use miden_objects::{block::BlockNumber, crypto::merkle::InOrderIndex};
+
+// BLOCK RETENTION POLICY
+// ================================================================================================
+
+/// Decides which block headers the client keeps in its store, and which blocks it tracks in its
+/// partial blockchain during syncs.
+///
+/// The genesis block and the block at the sync height are always kept. By default, the client
+/// also keeps the blocks with notes relevant to it, which are needed to prove the inclusion of
+/// those notes when they're consumed.
+///
+/// Blocks that aren't kept are fetched from the node again when they're needed, for example to
+/// execute a transaction consuming a note committed in them. Blocks fetched on demand are stored
+/// like blocks with relevant notes, so they're reused until a sync prunes them according to the
+/// policy.
+///
+/// The node only returns the blocks with changes relevant to the client, so the epoch blocks, the
+/// last blocks and the pinned blocks are only kept if the client synced or fetched them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockRetentionPolicy {
+    /// Whether the blocks with notes relevant to the client are kept.
+    relevant_blocks: bool,
+    /// Whether the first block of every epoch is kept.
+    epoch_blocks: bool,
+    /// Number of blocks before the sync height that are kept.
+    last_blocks: u32,
+    /// Blocks kept regardless of their contents.
+    pinned_blocks: BTreeSet<BlockNumber>,
+}
+
+impl BlockRetentionPolicy {
+    /// Returns a policy that keeps the blocks with notes relevant to the client. This is the
+    /// default policy.
+    pub fn relevant_blocks() -> Self {
+        Self {
+            relevant_blocks: true,
+            epoch_blocks: false,
+            last_blocks: 0,
+            pinned_blocks: BTreeSet::new(),
+        }
+    }
+
+    /// Returns a policy that only keeps the genesis block and the block at the sync height.
+    pub fn chain_tip_only() -> Self {
+        Self {
+            relevant_blocks: false,
+            ..Self::relevant_blocks()
+        }
+    }
+
+    /// Also keeps the first block of every epoch, which new accounts are anchored to.
+    #[must_use]
+    pub fn with_epoch_blocks(mut self) -> Self {
+        self.epoch_blocks = true;
+        self
+    }
+
+    /// Also keeps the blocks within `last_blocks` blocks of the sync height.
+    #[must_use]
+    pub fn with_last_blocks(mut self, last_blocks: u32) -> Self {
+        self.last_blocks = last_blocks;
+        self
+    }
+
+    /// Also keeps the provided blocks, such as the blocks foreign account data is read from.
+    #[must_use]
+    pub fn with_pinned_blocks(
+        mut self,
+        pinned_blocks: impl IntoIterator<Item = BlockNumber>,
+    ) -> Self {
+        self.pinned_blocks.extend(pinned_blocks);
+        self
+    }
+
+    /// Returns whether the blocks with notes relevant to the client are kept.
+    pub fn keeps_relevant_blocks(&self) -> bool {
+        self.relevant_blocks
+    }
+
+    /// Returns whether the first block of every epoch is kept.
+    pub fn keeps_epoch_blocks(&self) -> bool {
+        self.epoch_blocks
+    }
+
+    /// Returns the number of blocks before the sync height that are kept.
+    pub fn last_blocks(&self) -> u32 {
+        self.last_blocks
+    }
+
+    /// Returns the blocks kept regardless of their contents.
+    pub fn pinned_blocks(&self) -> &BTreeSet<BlockNumber> {
+        &self.pinned_blocks
+    }
+
+    /// Returns the first block kept by [`Self::with_last_blocks`] when the client is synced up to
+    /// `sync_height`.
+    ///
+    /// Stores that select the pruned blocks in their query language keep the blocks from this one
+    /// up to the sync height, mirroring [`Self::retains`].
+    pub(crate) fn first_recent_block(&self, sync_height: BlockNumber) -> BlockNumber {
+        sync_height.as_u32().saturating_sub(self.last_blocks).into()
+    }
+
+    /// Returns whether the header of the block should be kept when the client is synced up to
+    /// `sync_height`.
+    pub fn retains(
+        &self,
+        block_num: BlockNumber,
+        has_client_notes: bool,
+        sync_height: BlockNumber,
+    ) -> bool {
+        block_num == BlockNumber::GENESIS
+            || block_num == sync_height
+            || (self.relevant_blocks && has_client_notes)
+            || (self.epoch_blocks && block_num == BlockNumber::from_epoch(block_num.block_epoch()))
+            || (block_num < sync_height
+                && sync_height.as_u32() - block_num.as_u32() <= self.last_blocks)
+            || self.pinned_blocks.contains(&block_num)
+    }
+}
+
+impl Default for BlockRetentionPolicy {
+    fn default() -> Self {
+        Self::relevant_blocks()
+    }
+}
+
+// AUTHENTICATION NODES
+// ================================================================================================
+
+/// Returns the partial blockchain nodes that can be part of the authentication paths of the
+/// provided blocks, when the client is synced up to `sync_height`.
+///
+/// These are the nodes on the path from each block's leaf to its peak, along with their siblings.
+/// Stores use them to only load the nodes that [`stale_authentication_nodes`] needs.
+pub(crate) fn authentication_node_candidates(
+    block_nums: &[BlockNumber],
+    sync_height: BlockNumber,
+) -> BTreeSet<InOrderIndex> {
+    // The tallest tree of the MMR holding the blocks up to the sync height
+    let max_level = u32::BITS - (sync_height.as_u32() + 1).leading_zeros();
+
+    let mut candidates = BTreeSet::new();
+    for block_num in block_nums {
+        let mut index = InOrderIndex::from_leaf_pos(block_num.as_usize());
+        for _ in 0..=max_level {
+            candidates.insert(index);
+            candidates.insert(index.sibling());
+            index = index.parent();
+        }
+    }
+
+    candidates
+}
+
+/// Returns the stored partial blockchain nodes that are only needed to authenticate the pruned
+/// blocks, and can be removed along with their headers.
+///
+/// This mirrors [`PartialMmr::untrack`](miden_objects::crypto::merkle::PartialMmr::untrack): the
+/// authentication path of a pruned block is removed up to the first node that is still needed to
+/// authenticate another tracked block. Blocks that weren't tracked don't remove any nodes.
+pub(crate) fn stale_authentication_nodes(
+    pruned_blocks: &[BlockNumber],
+    mut stored_nodes: BTreeSet<InOrderIndex>,
+) -> Vec<InOrderIndex> {
+    let mut stale_nodes = Vec::new();
+    for block_num in pruned_blocks {
+        let mut index = InOrderIndex::from_leaf_pos(block_num.as_usize());
+        while stored_nodes.remove(&index.sibling()) {
+            stale_nodes.push(index.sibling());
+            if stored_nodes.contains(&index) {
+                break;
+            }
+            index = index.parent();
+        }
+    }
+
+    stale_nodes
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use miden_objects::{
+        Felt,
+        block::BlockNumber,
+        crypto::merkle::{InOrderIndex, MmrPeaks, PartialMmr},
+    };
+
+    use super::{BlockRetentionPolicy, authentication_node_candidates, stale_authentication_nodes};
+
+    #[test]
+    fn retained_blocks() {
+        let sync_height = BlockNumber::from(100);
+        let chain_tip_only = BlockRetentionPolicy::chain_tip_only();
+        assert!(chain_tip_only.retains(BlockNumber::GENESIS, false, sync_height));
+        assert!(chain_tip_only.retains(sync_height, false, sync_height));
+        assert!(!chain_tip_only.retains(BlockNumber::from(50), true, sync_height));
+
+        assert!(BlockRetentionPolicy::default().retains(BlockNumber::from(50), true, sync_height));
+        assert!(!BlockRetentionPolicy::default().retains(
+            BlockNumber::from(50),
+            false,
+            sync_height
+        ));
+
+        let policy = BlockRetentionPolicy::chain_tip_only()
+            .with_last_blocks(10)
+            .with_pinned_blocks([BlockNumber::from(20)]);
+        assert!(policy.retains(BlockNumber::from(90), false, sync_height));
+        assert!(!policy.retains(BlockNumber::from(89), false, sync_height));
+        assert!(policy.retains(BlockNumber::from(20), false, sync_height));
+
+        let epoch_block = BlockNumber::from_epoch(1);
+        let policy = BlockRetentionPolicy::chain_tip_only().with_epoch_blocks();
+        assert!(policy.retains(epoch_block, false, BlockNumber::from(epoch_block.as_u32() + 1)));
+        assert!(!policy.retains(
+            BlockNumber::from(epoch_block.as_u32() + 1),
+            false,
+            BlockNumber::from(epoch_block.as_u32() + 2)
+        ));
+    }
+
+    #[test]
+    fn stale_authentication_nodes_match_untracked_partial_mmr() {
+        let mut partial_mmr = PartialMmr::from_peaks(MmrPeaks::new(0, vec![]).unwrap());
+        for leaf in 0..10u64 {
+            partial_mmr.add([Felt::new(leaf); 4].into(), leaf % 3 == 0);
+        }
+        let sync_height = BlockNumber::from(9);
+        let pruned_blocks = [BlockNumber::from(3), BlockNumber::from(4)];
+
+        let candidates = authentication_node_candidates(&pruned_blocks, sync_height);
+        let stored_nodes: BTreeSet<InOrderIndex> = partial_mmr
+            .nodes()
+            .map(|(index, _)| *index)
+            .filter(|index| candidates.contains(index))
+            .collect();
+        let stale_nodes: BTreeSet<InOrderIndex> =
+            stale_authentication_nodes(&pruned_blocks, stored_nodes).into_iter().collect();
+
+        let nodes_before: BTreeSet<InOrderIndex> =
+            partial_mmr.nodes().map(|(index, _)| *index).collect();
+        for block_num in pruned_blocks {
+            partial_mmr.untrack(block_num.as_usize());
+        }
+        let nodes_after: BTreeSet<InOrderIndex> =
+            partial_mmr.nodes().map(|(index, _)| *index).collect();
+
+        assert!(!stale_nodes.is_empty());
+        assert_eq!(stale_nodes, &nodes_before - &nodes_after);
+        assert!(partial_mmr.is_tracked(0) && partial_mmr.is_tracked(6));
+    }
+}
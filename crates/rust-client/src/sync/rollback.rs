@@ -0,0 +1,349 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use miden_objects::{
+    Digest,
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{InOrderIndex, PartialMmr},
+    note::NoteMetadata,
+};
+use tracing::info;
+
+use super::{NoteTagRecord, block_header::fetch_block_header};
+use crate::{
+    ChainDivergenceError, Client, ClientError,
+    store::{
+        InputNoteRecord, InputNoteState, NoteFilter, OutputNoteRecord, OutputNoteState,
+        TransactionFilter,
+        input_note_states::{
+            ExpectedNoteState, ProcessingAuthenticatedNoteState, ProcessingUnauthenticatedNoteState,
+        },
+    },
+    transaction::{TransactionRecord, TransactionStatus},
+};
+
+/// Chain rollback methods.
+impl Client {
+    /// Finds the last block shared by the client and the node, rolls the client back to it (see
+    /// [`Client::rollback_to_block`]) and returns its number.
+    ///
+    /// This is the recovery path for a [`ChainDivergenceError`] returned by a sync: once the
+    /// client is rolled back, the next sync re-applies the node's chain from the common ancestor.
+    ///
+    /// Only the genesis block, the blocks with relevant notes and the block at the sync height are
+    /// considered, and among them only the ones stored by a sync.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChainDivergenceError::NoCommonAncestor`] if not even the genesis block matches
+    /// the node's, which usually means the node belongs to a different network.
+    pub async fn rollback_to_common_ancestor(&mut self) -> Result<BlockNumber, ClientError> {
+        let (chain_tip, _) = self.rpc_api.get_block_header_by_number(None, false).await?;
+
+        let mut block_numbers: BTreeSet<BlockNumber> = self
+            .store
+            .get_tracked_block_headers()
+            .await?
+            .iter()
+            .map(BlockHeader::block_num)
+            .collect();
+        block_numbers.insert(BlockNumber::GENESIS);
+        block_numbers.insert(self.store.get_sync_height().await?);
+
+        // Every candidate header, from the newest to the oldest
+        let mut candidates = self.store.get_block_headers(&block_numbers).await?;
+        candidates.sort_by_key(|(header, _)| core::cmp::Reverse(header.block_num()));
+
+        for (candidate, _) in candidates {
+            let block_num = candidate.block_num();
+            if block_num > chain_tip.block_num() || !self.is_rollback_anchor(block_num).await? {
+                continue;
+            }
+
+            let (node_header, _) =
+                self.rpc_api.get_block_header_by_number(Some(block_num), false).await?;
+            if node_header.commitment() == candidate.commitment() {
+                self.rollback_to_block(block_num).await?;
+                return Ok(block_num);
+            }
+        }
+
+        Err(ChainDivergenceError::NoCommonAncestor.into())
+    }
+
+    /// Rolls the client's state back to the provided block, which must have been stored by a
+    /// sync (the genesis block, a block with relevant notes or a past sync height).
+    ///
+    /// The sync height is set to `block_num` and the block headers after it are removed. Data
+    /// derived from the removed blocks is reverted so that the next sync can apply it again:
+    ///
+    /// - Transactions committed after the block go back to pending.
+    /// - Input and output notes committed after the block go back to expected, and their tags are
+    ///   tracked again.
+    /// - Notes consumed after the block go back to the state they had before being consumed.
+    ///
+    /// Account states are kept as they are, as they're checked against the node in the next sync.
+    /// The authentication paths of the remaining tracked blocks are fetched again from the node.
+    /// The whole rollback is applied to the store at once (see
+    /// [`crate::store::Store::rollback_to`]).
+    ///
+    /// # Errors
+    ///
+    /// - [`ClientError::RollbackBlockNotFound`] if the block isn't stored.
+    /// - [`ClientError::RollbackBlockNotAnchor`] if the block was stored outside of a sync (for
+    ///   example, fetched to authenticate a note or found by a rescan). The MMR peaks stored with
+    ///   such blocks describe the chain at a later height, so the client's partial blockchain can't
+    ///   be rebuilt from them.
+    pub async fn rollback_to_block(&mut self, block_num: BlockNumber) -> Result<(), ClientError> {
+        if block_num > self.store.get_sync_height().await? {
+            return Err(ClientError::RollbackBlockNotFound(block_num));
+        }
+        let Some((rollback_header, has_client_notes)) =
+            self.store.get_block_header_by_num(block_num).await?
+        else {
+            return Err(ClientError::RollbackBlockNotFound(block_num));
+        };
+
+        // The peaks stored with a synced block describe the chain right before it, so its forest
+        // has as many leaves as its block number
+        let rollback_peaks =
+            self.store.get_partial_blockchain_peaks_by_block_num(block_num).await?;
+        if rollback_peaks.num_leaves() != block_num.as_usize() {
+            return Err(ClientError::RollbackBlockNotAnchor(block_num));
+        }
+
+        info!("Rolling back client state to block {block_num}");
+
+        // Rebuild the partial MMR at the rollback block, fetching the authentication paths of the
+        // tracked blocks for its forest
+        let mut partial_mmr = PartialMmr::from_parts(rollback_peaks, BTreeMap::new(), false);
+        let mut partial_blockchain_nodes: Vec<(InOrderIndex, Digest)> =
+            partial_mmr.add(rollback_header.commitment(), has_client_notes);
+        let tracked_blocks: Vec<BlockNumber> = self
+            .store
+            .get_tracked_block_headers()
+            .await?
+            .iter()
+            .map(BlockHeader::block_num)
+            .filter(|tracked_block| *tracked_block < block_num)
+            .collect();
+        for tracked_block in tracked_blocks {
+            let (_, path_nodes) =
+                fetch_block_header(self.rpc_api.clone(), tracked_block, &mut partial_mmr).await?;
+            partial_blockchain_nodes.extend(path_nodes);
+        }
+
+        let transactions = self
+            .store
+            .get_transactions(TransactionFilter::All)
+            .await?
+            .into_iter()
+            .filter_map(|transaction| rollback_transaction(transaction, block_num))
+            .collect();
+
+        let input_notes: Vec<InputNoteRecord> = self
+            .store
+            .get_input_notes(NoteFilter::All)
+            .await?
+            .iter()
+            .filter_map(|note| rollback_input_note(note, block_num))
+            .collect();
+
+        // Notes that go back to expected are looked for again in the next sync
+        let tracked_tags = self.store.get_note_tags().await?;
+        let mut tags = Vec::new();
+        for note in &input_notes {
+            if let InputNoteState::Expected(ExpectedNoteState { tag: Some(tag), .. }) = note.state()
+            {
+                let tag_record = NoteTagRecord::with_note_source(*tag, note.id());
+                if !tracked_tags.contains(&tag_record) && !tags.contains(&tag_record) {
+                    tags.push(tag_record);
+                }
+            }
+        }
+
+        let output_notes = self
+            .store
+            .get_output_notes(NoteFilter::All)
+            .await?
+            .iter()
+            .filter_map(|note| rollback_output_note(note, block_num))
+            .collect();
+
+        self.store
+            .rollback_to(ChainRollback {
+                block_num,
+                partial_blockchain_nodes,
+                transactions,
+                input_notes,
+                output_notes,
+                tags,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the block was stored by a sync, so the client can be rolled back to it.
+    async fn is_rollback_anchor(&self, block_num: BlockNumber) -> Result<bool, ClientError> {
+        let peaks = self.store.get_partial_blockchain_peaks_by_block_num(block_num).await?;
+        Ok(peaks.num_leaves() == block_num.as_usize())
+    }
+}
+
+// CHAIN ROLLBACK
+// ================================================================================================
+
+/// Changes applied to the store to roll the client's state back to a block (see
+/// [`Client::rollback_to_block`]).
+#[derive(Debug, Clone)]
+pub struct ChainRollback {
+    /// Block the client is rolled back to. It becomes the sync height, and the block headers after
+    /// it are removed.
+    pub block_num: BlockNumber,
+    /// Authentication nodes of the partial blockchain at the rollback block. They replace the
+    /// stored ones.
+    pub partial_blockchain_nodes: Vec<(InOrderIndex, Digest)>,
+    /// Transactions committed after the rollback block, moved back to pending.
+    pub transactions: Vec<TransactionRecord>,
+    /// Input notes whose state depended on blocks after the rollback block, reverted to their
+    /// state at it.
+    pub input_notes: Vec<InputNoteRecord>,
+    /// Output notes whose state depended on blocks after the rollback block, reverted to their
+    /// state at it.
+    pub output_notes: Vec<OutputNoteRecord>,
+    /// Tags of the reverted notes that need to be tracked again.
+    pub tags: Vec<NoteTagRecord>,
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the transaction moved back to pending if it was committed after `block_num`, or `None`
+/// otherwise.
+fn rollback_transaction(
+    mut transaction: TransactionRecord,
+    block_num: BlockNumber,
+) -> Option<TransactionRecord> {
+    match transaction.status {
+        TransactionStatus::Committed(commit_height) if commit_height > block_num => {
+            transaction.status = TransactionStatus::Pending;
+            Some(transaction)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the state the input note had at `block_num`, or `None` if its state doesn't depend on
+/// later blocks.
+fn rollback_input_note(note: &InputNoteRecord, block_num: BlockNumber) -> Option<InputNoteRecord> {
+    let expected = |metadata: Option<NoteMetadata>| ExpectedNoteState {
+        metadata,
+        after_block_num: block_num,
+        tag: metadata.map(|metadata| metadata.tag()),
+    };
+
+    let state: InputNoteState = match note.state() {
+        InputNoteState::Unverified(state)
+            if state.inclusion_proof.location().block_num() > block_num =>
+        {
+            expected(Some(state.metadata)).into()
+        },
+        InputNoteState::Committed(state)
+            if state.inclusion_proof.location().block_num() > block_num =>
+        {
+            expected(Some(state.metadata)).into()
+        },
+        InputNoteState::Invalid(state)
+            if state.invalid_inclusion_proof.location().block_num() > block_num =>
+        {
+            expected(Some(state.metadata)).into()
+        },
+        InputNoteState::ProcessingAuthenticated(state)
+            if state.inclusion_proof.location().block_num() > block_num =>
+        {
+            ProcessingUnauthenticatedNoteState {
+                metadata: state.metadata,
+                after_block_num: block_num,
+                submission_data: state.submission_data,
+            }
+            .into()
+        },
+        InputNoteState::ConsumedAuthenticatedLocal(state)
+            if state.nullifier_block_height > block_num.as_u32() =>
+        {
+            if state.inclusion_proof.location().block_num() > block_num {
+                ProcessingUnauthenticatedNoteState {
+                    metadata: state.metadata,
+                    after_block_num: block_num,
+                    submission_data: state.submission_data,
+                }
+                .into()
+            } else {
+                ProcessingAuthenticatedNoteState {
+                    metadata: state.metadata,
+                    inclusion_proof: state.inclusion_proof.clone(),
+                    block_note_root: state.block_note_root,
+                    submission_data: state.submission_data,
+                }
+                .into()
+            }
+        },
+        InputNoteState::ConsumedUnauthenticatedLocal(state)
+            if state.nullifier_block_height > block_num.as_u32() =>
+        {
+            ProcessingUnauthenticatedNoteState {
+                metadata: state.metadata,
+                after_block_num: block_num,
+                submission_data: state.submission_data,
+            }
+            .into()
+        },
+        // The note's inclusion proof isn't kept once consumed externally, so it can only go back
+        // to expected
+        InputNoteState::ConsumedExternal(state)
+            if state.nullifier_block_height > block_num.as_u32() =>
+        {
+            expected(note.metadata().copied()).into()
+        },
+        _ => return None,
+    };
+
+    Some(InputNoteRecord::new(note.details().clone(), note.created_at(), state))
+}
+
+/// Returns the state the output note had at `block_num`, or `None` if its state doesn't depend on
+/// later blocks.
+fn rollback_output_note(
+    note: &OutputNoteRecord,
+    block_num: BlockNumber,
+) -> Option<OutputNoteRecord> {
+    let state = match note.state() {
+        OutputNoteState::CommittedPartial { inclusion_proof }
+            if inclusion_proof.location().block_num() > block_num =>
+        {
+            OutputNoteState::ExpectedPartial
+        },
+        OutputNoteState::CommittedFull { recipient, inclusion_proof }
+            if inclusion_proof.location().block_num() > block_num =>
+        {
+            OutputNoteState::ExpectedFull { recipient: recipient.clone() }
+        },
+        OutputNoteState::Consumed { block_height, recipient }
+            if *block_height > block_num.as_u32() =>
+        {
+            OutputNoteState::ExpectedFull { recipient: recipient.clone() }
+        },
+        _ => return None,
+    };
+
+    Some(OutputNoteRecord::new(
+        note.recipient_digest(),
+        note.assets().clone(),
+        *note.metadata(),
+        state,
+        note.expected_height(),
+    ))
+}
@@ -0,0 +1,175 @@
+use core::time::Duration;
+
+use super::SyncSummary;
+use crate::{Client, ClientError};
+
+/// Default time between two consecutive syncs.
+pub const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default upper bound for the delay between syncs after consecutive RPC failures.
+pub const DEFAULT_MAX_SYNC_BACKOFF: Duration = Duration::from_secs(300);
+
+// SYNC SCHEDULER
+// ================================================================================================
+
+/// Decides when a [`Client`] should be synced with the network.
+///
+/// This is not a background service: the scheduler doesn't own the client, spawn any task nor
+/// depend on any async runtime, so it's usable both natively and in the browser. The caller drives
+/// it by waiting for [`SyncScheduler::next_sync_delay`] with its own timer and then calling
+/// [`SyncScheduler::sync`]. Nothing is synced unless the caller does so. Since the client is only
+/// borrowed during a sync, it can be used to create transactions or perform any other operation
+/// between syncs.
+///
+/// - Syncs are due every `interval` while the scheduler is running.
+/// - [`SyncScheduler::notify_new_block`] makes the next sync due immediately, so syncs can be
+///   driven by new-block notifications instead of (or along with) the interval.
+/// - After a sync fails with an [`crate::rpc::RpcError`], the delay is doubled for every
+///   consecutive failure, up to `max_backoff`. A successful sync resets it.
+/// - While paused, [`SyncScheduler::sync`] is a no-op.
+#[derive(Debug, Clone)]
+pub struct SyncScheduler {
+    /// Time between two consecutive successful syncs.
+    interval: Duration,
+    /// Upper bound for the delay between syncs after RPC failures.
+    max_backoff: Duration,
+    /// Whether syncs are currently paused.
+    paused: bool,
+    /// Whether a new block was notified since the last sync.
+    new_block_notified: bool,
+    /// Number of consecutive syncs that failed with an RPC error.
+    consecutive_failures: u32,
+}
+
+impl SyncScheduler {
+    /// Returns a new [`SyncScheduler`] that makes syncs due every [`DEFAULT_SYNC_INTERVAL`].
+    pub fn new() -> Self {
+        Self {
+            interval: DEFAULT_SYNC_INTERVAL,
+            max_backoff: DEFAULT_MAX_SYNC_BACKOFF,
+            paused: false,
+            new_block_notified: false,
+            consecutive_failures: 0,
+        }
+    }
+
+    /// Sets the time between two consecutive syncs.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the upper bound for the delay between syncs after consecutive RPC failures.
+    #[must_use]
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns whether syncs are currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the number of consecutive syncs that failed with an RPC error.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    // CONTROL
+    // --------------------------------------------------------------------------------------------
+
+    /// Pauses the scheduler. Calls to [`SyncScheduler::sync`] do nothing until it's resumed.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes a paused scheduler.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Notifies the scheduler that a new block was produced, making the next sync due immediately
+    /// unless it's backing off after RPC failures.
+    pub fn notify_new_block(&mut self) {
+        self.new_block_notified = true;
+    }
+
+    /// Returns how long to wait before calling [`SyncScheduler::sync`] again.
+    pub fn next_sync_delay(&self) -> Duration {
+        if self.consecutive_failures > 0 {
+            backoff_delay(self.interval, self.max_backoff, self.consecutive_failures)
+        } else if self.new_block_notified {
+            Duration::ZERO
+        } else {
+            self.interval
+        }
+    }
+
+    // SYNC
+    // --------------------------------------------------------------------------------------------
+
+    /// Syncs the provided client with the network, unless the scheduler is paused.
+    ///
+    /// Returns the summary of the sync, or `None` if the scheduler is paused. RPC errors increase
+    /// the delay returned by [`SyncScheduler::next_sync_delay`] before being returned.
+    pub async fn sync(&mut self, client: &mut Client) -> Result<Option<SyncSummary>, ClientError> {
+        if self.paused {
+            return Ok(None);
+        }
+
+        self.new_block_notified = false;
+        match client.sync_state().await {
+            Ok(summary) => {
+                self.consecutive_failures = 0;
+                Ok(Some(summary))
+            },
+            Err(err @ ClientError::RpcError(_)) => {
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                Err(err)
+            },
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Default for SyncScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the delay before the next attempt after `failures` consecutive failures, doubling the
+/// interval for each failure without exceeding `max_backoff`.
+pub(crate) fn backoff_delay(interval: Duration, max_backoff: Duration, failures: u32) -> Duration {
+    let factor = 1u32.checked_shl(failures).unwrap_or(u32::MAX);
+    interval.checked_mul(factor).map_or(max_backoff, |delay| delay.min(max_backoff))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::backoff_delay;
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let interval = Duration::from_secs(5);
+        let max_backoff = Duration::from_secs(60);
+
+        assert_eq!(backoff_delay(interval, max_backoff, 1), Duration::from_secs(10));
+        assert_eq!(backoff_delay(interval, max_backoff, 3), Duration::from_secs(40));
+        assert_eq!(backoff_delay(interval, max_backoff, 4), max_backoff);
+        assert_eq!(backoff_delay(interval, max_backoff, u32::MAX), max_backoff);
+    }
+}
@@ -0,0 +1,272 @@
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
+
+use miden_objects::{
+    account::{Account, AccountCode, AccountDelta, AccountHeader, AccountId},
+    block::{BlockHeader, BlockNumber, ProvenBlock},
+    crypto::merkle::{MmrProof, SmtProof},
+    note::{NoteId, NoteTag, Nullifier},
+    transaction::ProvenTransaction,
+};
+use miden_tx::utils::sync::RwLock;
+
+use crate::{
+    rpc::{
+        NodeRpcClient, RpcError,
+        domain::{
+            account::{AccountProofs, FetchedAccount},
+            note::{FetchedNote, NoteSyncInfo},
+            nullifier::NullifierUpdate,
+            sync::StateSyncInfo,
+        },
+    },
+    store::InputNoteRecord,
+    transaction::ForeignAccount,
+};
+
+// SHARED SYNC RPC CLIENT
+// ================================================================================================
+
+/// A [`NodeRpcClient`] used to sync several tenants at once, fetching the chain data they share
+/// only once.
+///
+/// State sync requests are always sent for the union of the tenants' account IDs and note tags,
+/// and responses are cached by the requested block number. Each tenant then only receives the
+/// note inclusions, account updates and transactions that match its own request. Nullifiers and
+/// public note details are cached as well, so that they are fetched once for all tenants.
+///
+/// Calls to the rest of the endpoints are forwarded to the inner client.
+pub(crate) struct SharedSyncRpcClient {
+    inner: Arc<dyn NodeRpcClient + Send>,
+    /// Union of the account IDs tracked by all tenants.
+    account_ids: Vec<AccountId>,
+    /// Union of the note tags tracked by all tenants.
+    note_tags: Vec<NoteTag>,
+    /// State sync responses, by requested block number.
+    state_syncs: RwLock<BTreeMap<BlockNumber, StateSyncInfo>>,
+    /// Nullifier prefixes that were already requested and the received nullifier updates, by
+    /// requested block number.
+    nullifiers: RwLock<BTreeMap<BlockNumber, (BTreeSet<u16>, Vec<NullifierUpdate>)>>,
+    /// Fetched public notes. Private or missing notes are stored as `None`.
+    public_notes: RwLock<BTreeMap<NoteId, Option<InputNoteRecord>>>,
+}
+
+impl SharedSyncRpcClient {
+    pub fn new(
+        inner: Arc<dyn NodeRpcClient + Send>,
+        account_ids: BTreeSet<AccountId>,
+        note_tags: BTreeSet<NoteTag>,
+    ) -> Self {
+        Self {
+            inner,
+            account_ids: account_ids.into_iter().collect(),
+            note_tags: note_tags.into_iter().collect(),
+            state_syncs: RwLock::new(BTreeMap::new()),
+            nullifiers: RwLock::new(BTreeMap::new()),
+            public_notes: RwLock::new(BTreeMap::new()),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for SharedSyncRpcClient {
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        self.inner.submit_proven_transaction(proven_transaction).await
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        self.inner.get_block_header_by_number(block_num, include_mmr_proof).await
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        self.inner.get_block_by_number(block_num).await
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        self.inner.get_notes_by_id(note_ids).await
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &[NoteTag],
+    ) -> Result<StateSyncInfo, RpcError> {
+        // Requests that aren't covered by the shared filters can't be served from the cache
+        if !account_ids.iter().all(|id| self.account_ids.contains(id))
+            || !note_tags.iter().all(|tag| self.note_tags.contains(tag))
+        {
+            return self.inner.sync_state(block_num, account_ids, note_tags).await;
+        }
+
+        let cached_response = self.state_syncs.read().get(&block_num).cloned();
+        let mut response = match cached_response {
+            Some(response) => response,
+            None => {
+                let response =
+                    self.inner.sync_state(block_num, &self.account_ids, &self.note_tags).await?;
+                self.state_syncs.write().insert(block_num, response.clone());
+                response
+            },
+        };
+
+        response
+            .note_inclusions
+            .retain(|note| note_tags.contains(&note.metadata().tag()));
+        response
+            .account_commitment_updates
+            .retain(|(account_id, _)| account_ids.contains(account_id));
+        response
+            .transactions
+            .retain(|transaction| account_ids.contains(&transaction.account_id));
+
+        Ok(response)
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        self.inner.get_account_details(account_id).await
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &[NoteTag],
+    ) -> Result<NoteSyncInfo, RpcError> {
+        self.inner.sync_notes(block_num, note_tags).await
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        let missing_prefixes: Vec<u16> = {
+            let nullifiers = self.nullifiers.read();
+            let fetched_prefixes = nullifiers.get(&block_num).map(|(prefixes, _)| prefixes);
+            prefix
+                .iter()
+                .filter(|prefix| fetched_prefixes.is_none_or(|fetched| !fetched.contains(prefix)))
+                .copied()
+                .collect()
+        };
+
+        if !missing_prefixes.is_empty() {
+            let new_nullifiers =
+                self.inner.check_nullifiers_by_prefix(&missing_prefixes, block_num).await?;
+
+            let mut nullifiers = self.nullifiers.write();
+            let (fetched_prefixes, fetched_nullifiers) = nullifiers.entry(block_num).or_default();
+            fetched_prefixes.extend(missing_prefixes);
+            fetched_nullifiers.extend(new_nullifiers);
+        }
+
+        Ok(self
+            .nullifiers
+            .read()
+            .get(&block_num)
+            .map(|(_, nullifiers)| {
+                nullifiers
+                    .iter()
+                    .filter(|update| prefix.contains(&update.nullifier.prefix()))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        self.inner.check_nullifiers(nullifiers).await
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        self.inner
+            .get_account_proofs(account_storage_requests, known_account_codes)
+            .await
+    }
+
+    async fn get_account_state_delta(
+        &self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<AccountDelta, RpcError> {
+        self.inner.get_account_state_delta(account_id, from_block, to_block).await
+    }
+
+    async fn get_nullifier_commit_height(
+        &self,
+        nullifier: &Nullifier,
+        block_num: BlockNumber,
+    ) -> Result<Option<u32>, RpcError> {
+        self.inner.get_nullifier_commit_height(nullifier, block_num).await
+    }
+
+    /// Returns the public notes, fetching only the ones that weren't requested before.
+    ///
+    /// Records of cached notes keep the timestamp of the request that fetched them.
+    async fn get_public_note_records(
+        &self,
+        note_ids: &[NoteId],
+        current_timestamp: Option<u64>,
+    ) -> Result<Vec<InputNoteRecord>, RpcError> {
+        let missing_notes: Vec<NoteId> = {
+            let public_notes = self.public_notes.read();
+            note_ids.iter().filter(|id| !public_notes.contains_key(id)).copied().collect()
+        };
+
+        if !missing_notes.is_empty() {
+            let mut fetched_notes: BTreeMap<NoteId, InputNoteRecord> = self
+                .inner
+                .get_public_note_records(&missing_notes, current_timestamp)
+                .await?
+                .into_iter()
+                .map(|note| (note.id(), note))
+                .collect();
+
+            let mut public_notes = self.public_notes.write();
+            for note_id in missing_notes {
+                public_notes.insert(note_id, fetched_notes.remove(&note_id));
+            }
+        }
+
+        let public_notes = self.public_notes.read();
+        Ok(note_ids
+            .iter()
+            .filter_map(|note_id| public_notes.get(note_id).cloned().flatten())
+            .collect())
+    }
+
+    async fn get_updated_public_accounts(
+        &self,
+        local_accounts: &[&AccountHeader],
+    ) -> Result<Vec<Account>, RpcError> {
+        self.inner.get_updated_public_accounts(local_accounts).await
+    }
+
+    async fn get_block_header_with_proof(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<(BlockHeader, MmrProof), RpcError> {
+        self.inner.get_block_header_with_proof(block_num).await
+    }
+
+    async fn get_note_by_id(&self, note_id: NoteId) -> Result<FetchedNote, RpcError> {
+        self.inner.get_note_by_id(note_id).await
+    }
+}
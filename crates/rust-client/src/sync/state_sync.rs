@@ -1,21 +1,31 @@
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
 use core::{future::Future, pin::Pin};
 
 use miden_objects::{
     Digest,
     account::{Account, AccountHeader, AccountId},
     block::{BlockHeader, BlockNumber},
-    crypto::merkle::{InOrderIndex, MmrDelta, MmrPeaks, PartialMmr},
+    crypto::merkle::{InOrderIndex, MerklePath, MmrDelta, MmrPeaks, PartialMmr},
     note::{NoteId, NoteTag},
     transaction::PartialBlockchain,
 };
 use tracing::info;
 
 use super::{
-    AccountUpdates, BlockUpdates, StateSyncUpdate, state_sync_update::TransactionUpdateTracker,
+    AccountUpdates, BlockRetentionPolicy, BlockUpdates, FetchLimits, NoteDiscoveryRule,
+    StateSyncUpdate,
+    block_header::adjust_merkle_path_for_forest,
+    discovery::discover_notes,
+    fetch::{fetch_public_notes, fetch_updated_public_accounts},
+    state_sync_update::TransactionUpdateTracker,
 };
 use crate::{
-    ClientError,
+    ChainDivergenceError, ClientError,
     note::{NoteScreener, NoteUpdateTracker},
     rpc::{
         NodeRpcClient,
@@ -59,6 +69,21 @@ pub struct StateSync<'a> {
     on_note_received: OnNoteReceived<'a>,
     tx_graceful_blocks: Option<u32>,
     note_screener: Arc<NoteScreener<'a>>,
+    /// Number of blocks after which the sync stops so its progress can be persisted. If `None`,
+    /// the sync goes on until the chain tip.
+    checkpoint_interval: Option<u32>,
+    /// Block at which the sync stops. If `None`, the sync goes on until the chain tip.
+    target_block: Option<BlockNumber>,
+    /// Whether the nullifiers of unspent notes are checked.
+    sync_nullifiers: bool,
+    /// Whether the returned update leaves the client's chain data untouched.
+    detached: bool,
+    /// Limits for the concurrent requests used to fetch public notes and accounts.
+    fetch_limits: FetchLimits,
+    /// Rules used to discover public notes that don't match any of the synced note tags.
+    note_discovery_rules: Vec<NoteDiscoveryRule>,
+    /// Policy deciding which of the synced blocks are tracked in the partial blockchain.
+    block_retention_policy: BlockRetentionPolicy,
 }
 
 impl<'a> StateSync<'a> {
@@ -80,11 +105,101 @@ impl<'a> StateSync<'a> {
             tx_graceful_blocks,
             #[allow(clippy::arc_with_non_send_sync)]
             note_screener: Arc::new(note_screener),
+            checkpoint_interval: None,
+            target_block: None,
+            sync_nullifiers: true,
+            detached: false,
+            fetch_limits: FetchLimits::default(),
+            note_discovery_rules: Vec::new(),
+            block_retention_policy: BlockRetentionPolicy::default(),
         }
     }
 
-    /// Syncs the state of the client with the chain tip of the node, returning the updates that
-    /// should be applied to the store.
+    /// Makes [`StateSync::sync_state`] stop once the sync has advanced at least
+    /// `checkpoint_interval` blocks, instead of going on until the chain tip. The returned update
+    /// can then be persisted as a checkpoint before syncing the remaining blocks.
+    ///
+    /// The sync always stops at the end of a step, so a checkpoint may cover more blocks than the
+    /// interval if the node skips blocks without relevant changes. An interval of 1 (or 0) stops
+    /// after every step.
+    #[must_use]
+    pub fn with_checkpoint_interval(mut self, checkpoint_interval: u32) -> Self {
+        self.checkpoint_interval = Some(checkpoint_interval.max(1));
+        self
+    }
+
+    /// Makes [`StateSync::sync_state`] stop once the sync reaches `target_block`, instead of going
+    /// on until the chain tip.
+    ///
+    /// As with checkpoints, the sync stops at the end of a step, so it may go past the target block
+    /// if the node skips blocks without relevant changes.
+    #[must_use]
+    pub fn with_target_block(mut self, target_block: BlockNumber) -> Self {
+        self.target_block = Some(target_block);
+        self
+    }
+
+    /// Makes [`StateSync::sync_state`] skip checking the nullifiers of unspent notes, so notes
+    /// consumed during the synced blocks aren't detected.
+    #[must_use]
+    pub fn skip_nullifiers(mut self) -> Self {
+        self.sync_nullifiers = false;
+        self
+    }
+
+    /// Makes [`StateSync::sync_state`] return an update that doesn't advance the client's chain
+    /// data. This is used when only part of the client's accounts, note tags or nullifiers are
+    /// synced, as advancing the sync height would make the rest of them miss the changes from the
+    /// synced blocks.
+    ///
+    /// The returned update keeps the starting block number and doesn't include any block headers
+    /// or authentication nodes. Notes committed in the synced blocks get their inclusion proofs
+    /// but stay unverified until their blocks are tracked by a later sync.
+    #[must_use]
+    pub fn detached(mut self) -> Self {
+        self.detached = true;
+        self
+    }
+
+    /// Sets the limits for the concurrent requests used to fetch the details of public notes and
+    /// accounts updated in the synced blocks. See [`FetchLimits`].
+    #[must_use]
+    pub fn with_fetch_limits(mut self, fetch_limits: FetchLimits) -> Self {
+        self.fetch_limits = fetch_limits;
+        self
+    }
+
+    /// Makes [`StateSync::sync_state`] look for public notes matching any of the rules in every
+    /// synced block. The IDs of the notes found are returned in
+    /// [`StateSyncUpdate::discovered_notes`].
+    ///
+    /// As the node only filters notes by tag, every synced block is fetched while there are rules
+    /// to check. See [`NoteDiscoveryRule`].
+    #[must_use]
+    pub fn with_note_discovery_rules(
+        mut self,
+        note_discovery_rules: Vec<NoteDiscoveryRule>,
+    ) -> Self {
+        self.note_discovery_rules = note_discovery_rules;
+        self
+    }
+
+    /// Sets the policy deciding which of the synced blocks are tracked in the partial blockchain
+    /// along with their authentication nodes. By default, only the blocks with relevant notes
+    /// are tracked. See [`BlockRetentionPolicy`].
+    #[must_use]
+    pub fn with_block_retention_policy(
+        mut self,
+        block_retention_policy: BlockRetentionPolicy,
+    ) -> Self {
+        self.block_retention_policy = block_retention_policy;
+        self
+    }
+
+    /// Syncs the state of the client with the chain tip of the node (or up to the next checkpoint
+    /// or target block, see [`StateSync::with_checkpoint_interval`] and
+    /// [`StateSync::with_target_block`]), returning the updates that should be applied to the
+    /// store.
     ///
     /// During the sync process, the client will go through the following steps:
     /// 1. A request is sent to the node to get the state updates. This request includes tracked
@@ -97,7 +212,8 @@ impl<'a> StateSync<'a> {
     /// 4. Tracked notes are updated with their new states. Notes might be committed or nullified
     ///    during the sync processing.
     /// 5. New notes are checked, and only relevant ones are stored. Relevance is determined by the
-    ///    [`OnNoteReceived`] callback.
+    ///    [`OnNoteReceived`] callback. Public notes matching the note discovery rules are looked
+    ///    for in the synced blocks.
     /// 6. Transactions are updated with their new states. Transactions might be committed or
     ///    discarded.
     /// 7. The MMR is updated with the new peaks and authentication nodes.
@@ -129,22 +245,162 @@ impl<'a> StateSync<'a> {
 
         let mut partial_mmr = current_partial_blockchain.mmr().clone();
 
+        let checkpoint_block = self.checkpoint_interval.map(|checkpoint_interval| {
+            BlockNumber::from(block_num.as_u32().saturating_add(checkpoint_interval))
+        });
+        let stop_block = match (checkpoint_block, self.target_block) {
+            (Some(checkpoint_block), Some(target_block)) => {
+                Some(checkpoint_block.min(target_block))
+            },
+            (checkpoint_block, target_block) => checkpoint_block.or(target_block),
+        };
+
+        // The client may already be past the target block
+        if stop_block.is_some_and(|stop_block| stop_block <= block_num) {
+            return Ok(state_sync_update);
+        }
+
         loop {
             if !self
-                .sync_state_step(&mut state_sync_update, &mut partial_mmr, &accounts, &note_tags)
+                .sync_state_step(
+                    &mut state_sync_update,
+                    &mut partial_mmr,
+                    &accounts,
+                    &note_tags,
+                    stop_block,
+                )
                 .await?
             {
                 break;
             }
         }
 
-        self.sync_nullifiers(&mut state_sync_update, block_num).await?;
+        if self.sync_nullifiers {
+            self.sync_nullifiers(&mut state_sync_update, block_num).await?;
+        }
+
+        if self.detached {
+            state_sync_update.block_num = block_num;
+            state_sync_update.block_updates = BlockUpdates::default();
+        }
+
+        Ok(state_sync_update)
+    }
+
+    /// Looks for notes matching the provided tags that were committed between `from_block` and
+    /// `to_block`, which must not be past the client's sync height, returning the updates that
+    /// should be applied to the store.
+    ///
+    /// This replays the note sync for blocks the client already synced, so notes for tags or
+    /// accounts that were added afterwards are discovered. Relevance is determined by the
+    /// [`OnNoteReceived`] callback, as in a regular sync. Unless the block retention policy
+    /// doesn't keep them, the headers of the blocks with relevant notes are authenticated against
+    /// the client's partial MMR, and their authentication nodes are included in the update. The
+    /// nullifiers of the notes are checked from `from_block` on.
+    ///
+    /// The returned update keeps the client's sync height.
+    ///
+    /// # Arguments
+    /// * `current_partial_blockchain` - The current partial view of the blockchain.
+    /// * `note_tags` - The note tags to be used in the note sync requests.
+    /// * `unspent_input_notes` - The current state of unspent input notes tracked by the client.
+    /// * `unspent_output_notes` - The current state of unspent output notes tracked by the client.
+    pub async fn rescan_notes(
+        self,
+        current_partial_blockchain: PartialBlockchain,
+        note_tags: Vec<NoteTag>,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        unspent_input_notes: Vec<InputNoteRecord>,
+        unspent_output_notes: Vec<OutputNoteRecord>,
+    ) -> Result<StateSyncUpdate, ClientError> {
+        let block_num =
+            current_partial_blockchain.chain_length().checked_sub(1).unwrap_or_default();
+
+        let mut state_sync_update = StateSyncUpdate {
+            block_num,
+            note_updates: NoteUpdateTracker::new(unspent_input_notes, unspent_output_notes),
+            ..Default::default()
+        };
+
+        if note_tags.is_empty() || from_block > to_block.min(block_num) {
+            return Ok(state_sync_update);
+        }
+
+        let mut partial_mmr = current_partial_blockchain.mmr().clone();
+        let mut new_blocks = vec![];
+        let mut new_authentication_nodes = vec![];
+
+        // Note syncs return the notes committed after the provided block
+        let mut last_scanned_block = BlockNumber::from(from_block.as_u32().saturating_sub(1));
+        loop {
+            let response = self.rpc_api.sync_notes(last_scanned_block, &note_tags).await?;
+            let response_block_num = response.block_header.block_num();
+
+            // Without matching notes, the node returns the chain tip
+            if response.notes.is_empty()
+                || response_block_num <= last_scanned_block
+                || response_block_num > to_block.min(block_num)
+            {
+                break;
+            }
+
+            let found_relevant_note = self
+                .note_state_sync(
+                    &mut state_sync_update.note_updates,
+                    &mut state_sync_update.discovered_notes,
+                    response.notes,
+                    last_scanned_block,
+                    &response.block_header,
+                )
+                .await?;
+
+            if found_relevant_note && self.block_retention_policy.keeps_relevant_blocks() {
+                if response_block_num == block_num {
+                    // The block at the sync height isn't a leaf of the partial MMR yet, and the
+                    // current peaks are the ones of the chain at that block
+                    new_blocks.push((response.block_header, true, partial_mmr.peaks()));
+                } else if !partial_mmr.is_tracked(response_block_num.as_usize()) {
+                    let path_nodes = adjust_merkle_path_for_forest(
+                        &response.mmr_path,
+                        response_block_num,
+                        partial_mmr.forest(),
+                    );
+                    let merkle_path = MerklePath::new(path_nodes.iter().map(|(_, n)| *n).collect());
+
+                    // Tracking the block fails if its header isn't part of the client's chain
+                    partial_mmr
+                        .track(
+                            response_block_num.as_usize(),
+                            response.block_header.commitment(),
+                            &merkle_path,
+                        )
+                        .map_err(StoreError::MmrError)?;
+
+                    // The peaks of the chain at the rescanned block aren't known, so the block is
+                    // stored with the current peaks. Headers that are already stored keep their
+                    // peaks, and new ones can't be used as rollback anchors.
+                    new_blocks.push((response.block_header, true, partial_mmr.peaks()));
+                    new_authentication_nodes.extend(path_nodes);
+                }
+            }
+
+            last_scanned_block = response_block_num;
+        }
+
+        state_sync_update
+            .block_updates
+            .extend(BlockUpdates::new(new_blocks, new_authentication_nodes));
+
+        if self.sync_nullifiers {
+            self.sync_nullifiers(&mut state_sync_update, from_block).await?;
+        }
 
         Ok(state_sync_update)
     }
 
     /// Executes a single step of the state sync process, returning `true` if the client should
-    /// continue syncing and `false` if the client has reached the chain tip.
+    /// continue syncing and `false` if the client has reached the chain tip or the `stop_block`.
     ///
     /// A step in this context means a single request to the node to get the next relevant block and
     /// the changes that happened in it. This block may not be the last one in the chain and
@@ -158,6 +414,7 @@ impl<'a> StateSync<'a> {
         current_partial_mmr: &mut PartialMmr,
         accounts: &[AccountHeader],
         note_tags: &[NoteTag],
+        stop_block: Option<BlockNumber>,
     ) -> Result<bool, ClientError> {
         let account_ids: Vec<AccountId> = accounts.iter().map(AccountHeader::id).collect();
 
@@ -166,11 +423,23 @@ impl<'a> StateSync<'a> {
             .sync_state(state_sync_update.block_num, &account_ids, note_tags)
             .await?;
 
+        // A node that went backwards can't be trusted to extend the client's chain
+        if response.chain_tip < state_sync_update.block_num
+            || response.block_header.block_num() < state_sync_update.block_num
+        {
+            return Err(ChainDivergenceError::NodeBehind {
+                sync_height: state_sync_update.block_num,
+                chain_tip: response.chain_tip,
+            }
+            .into());
+        }
+
         // We don't need to continue if the chain has not advanced, there are no new changes
         if response.block_header.block_num() == state_sync_update.block_num {
             return Ok(false);
         }
 
+        let previous_block_num = state_sync_update.block_num;
         let new_block_num = response.block_header.block_num();
         state_sync_update.block_num = new_block_num;
 
@@ -190,21 +459,46 @@ impl<'a> StateSync<'a> {
         let found_relevant_note = self
             .note_state_sync(
                 &mut state_sync_update.note_updates,
+                &mut state_sync_update.discovered_notes,
                 response.note_inclusions,
+                previous_block_num,
                 &response.block_header,
             )
             .await?;
 
+        // The chain tip is tracked once the next block is added to the partial MMR, so it's only
+        // tracked here if it has relevant notes
+        let track_block = if new_block_num == response.chain_tip {
+            found_relevant_note && self.block_retention_policy.keeps_relevant_blocks()
+        } else {
+            self.block_retention_policy.retains(
+                new_block_num,
+                found_relevant_note,
+                response.chain_tip,
+            )
+        };
+
         let (new_mmr_peaks, new_authentication_nodes) = apply_mmr_changes(
             &response.block_header,
-            found_relevant_note,
+            track_block,
             current_partial_mmr,
             response.mmr_delta,
         )?;
 
+        // The new block commits to the chain before it, which must be the client's chain extended
+        // with the received MMR delta
+        if new_mmr_peaks.hash_peaks() != response.block_header.chain_commitment() {
+            return Err(ChainDivergenceError::ChainMismatch(new_block_num).into());
+        }
+
+        let reached_chain_tip = response.chain_tip == new_block_num;
+        let reached_stop_block = stop_block.is_some_and(|block| new_block_num >= block);
+
         let mut new_blocks = vec![];
-        if found_relevant_note || response.chain_tip == new_block_num {
-            // Only track relevant blocks or the chain tip
+        if track_block || found_relevant_note || reached_chain_tip || reached_stop_block {
+            // Only store the tracked blocks, the blocks with relevant notes and the last synced
+            // block, which is needed to resume the sync from it. Blocks that the retention policy
+            // doesn't keep are pruned once the update is applied
             new_blocks.push((response.block_header, found_relevant_note, new_mmr_peaks));
         }
 
@@ -212,11 +506,7 @@ impl<'a> StateSync<'a> {
             .block_updates
             .extend(BlockUpdates::new(new_blocks, new_authentication_nodes));
 
-        if response.chain_tip == new_block_num {
-            Ok(false)
-        } else {
-            Ok(true)
-        }
+        Ok(!reached_chain_tip && !reached_stop_block)
     }
 
     // HELPERS
@@ -244,6 +534,14 @@ impl<'a> StateSync<'a> {
             .get_updated_public_accounts(account_commitment_updates, &public_accounts)
             .await?;
 
+        let public_account_commitments = account_commitment_updates
+            .iter()
+            .filter(|(account_id, _)| {
+                public_accounts.iter().any(|account| account.id() == *account_id)
+            })
+            .copied()
+            .collect::<BTreeMap<_, _>>();
+
         let mismatched_private_accounts = account_commitment_updates
             .iter()
             .filter(|(account_id, digest)| {
@@ -254,8 +552,10 @@ impl<'a> StateSync<'a> {
             .copied()
             .collect::<Vec<_>>();
 
-        account_updates
-            .extend(AccountUpdates::new(updated_public_accounts, mismatched_private_accounts));
+        account_updates.extend(
+            AccountUpdates::new(updated_public_accounts, mismatched_private_accounts)
+                .with_public_account_commitments(public_account_commitments),
+        );
 
         Ok(())
     }
@@ -279,10 +579,13 @@ impl<'a> StateSync<'a> {
             }
         }
 
-        self.rpc_api
-            .get_updated_public_accounts(&mismatched_public_accounts)
-            .await
-            .map_err(ClientError::RpcError)
+        fetch_updated_public_accounts(
+            self.rpc_api.as_ref(),
+            &mismatched_public_accounts,
+            self.fetch_limits,
+        )
+        .await
+        .map_err(ClientError::RpcError)
     }
 
     /// Applies the changes received from the sync response to the notes and transactions tracked
@@ -296,10 +599,15 @@ impl<'a> StateSync<'a> {
     /// * Tracked expected notes that were committed in the block.
     /// * Tracked notes that were being processed by a transaction that got committed.
     /// * Tracked notes that were nullified by an external transaction.
+    ///
+    /// The IDs of public notes matching the note discovery rules in the blocks after
+    /// `previous_block_num`, up to the block of `block_header`, are added to `discovered_notes`.
     async fn note_state_sync(
         &self,
         note_updates: &mut NoteUpdateTracker,
+        discovered_notes: &mut BTreeSet<NoteId>,
         note_inclusions: Vec<CommittedNote>,
+        previous_block_num: BlockNumber,
         block_header: &BlockHeader,
     ) -> Result<bool, ClientError> {
         let public_note_ids: Vec<NoteId> = note_inclusions
@@ -323,14 +631,34 @@ impl<'a> StateSync<'a> {
             {
                 found_relevant_note = true;
 
-                note_updates.apply_committed_note_state_transitions(
-                    &committed_note,
-                    public_note,
-                    block_header,
-                )?;
+                if self.detached {
+                    note_updates.apply_unverified_note_state_transitions(
+                        &committed_note,
+                        public_note,
+                        block_header.block_num(),
+                    )?;
+                } else {
+                    note_updates.apply_committed_note_state_transitions(
+                        &committed_note,
+                        public_note,
+                        block_header,
+                    )?;
+                }
             }
         }
 
+        // The node only filters notes by tag, so the blocks skipped by this step have to be
+        // checked for notes matching the discovery rules too
+        discovered_notes.extend(
+            discover_notes(
+                self.rpc_api.as_ref(),
+                &self.note_discovery_rules,
+                previous_block_num.as_u32() + 1..=block_header.block_num().as_u32(),
+                self.fetch_limits,
+            )
+            .await?,
+        );
+
         Ok(found_relevant_note)
     }
 
@@ -347,9 +675,9 @@ impl<'a> StateSync<'a> {
         }
         info!("Getting note details for notes that are not being tracked.");
 
-        let return_notes = self.rpc_api.get_public_note_records(query_notes, None).await?;
-
-        Ok(return_notes.into_iter().map(|note| (note.id(), note)).collect())
+        fetch_public_notes(self.rpc_api.as_ref(), query_notes, self.fetch_limits)
+            .await
+            .map_err(ClientError::RpcError)
     }
 
     /// Collects the nullifier tags for the notes that were updated in the sync response and uses
@@ -427,7 +755,7 @@ impl<'a> StateSync<'a> {
 /// authentication nodes for leaves we track.
 fn apply_mmr_changes(
     new_block: &BlockHeader,
-    new_block_has_relevant_notes: bool,
+    track_new_block: bool,
     current_partial_mmr: &mut PartialMmr,
     mmr_delta: MmrDelta,
 ) -> Result<(MmrPeaks, Vec<(InOrderIndex, Digest)>), ClientError> {
@@ -438,7 +766,7 @@ fn apply_mmr_changes(
     let new_peaks = current_partial_mmr.peaks();
 
     new_authentication_nodes
-        .append(&mut current_partial_mmr.add(new_block.commitment(), new_block_has_relevant_notes));
+        .append(&mut current_partial_mmr.add(new_block.commitment(), track_new_block));
 
     Ok((new_peaks, new_authentication_nodes))
 }
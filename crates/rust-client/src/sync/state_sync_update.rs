@@ -36,6 +36,10 @@ pub struct StateSyncUpdate {
     pub transaction_updates: TransactionUpdateTracker,
     /// Public account updates and mismatched private accounts after the sync.
     pub account_updates: AccountUpdates,
+    /// Public notes matching the client's note discovery rules, found in the synced blocks. Their
+    /// IDs are stored with the rest of the update, and the notes are imported by the client
+    /// afterwards.
+    pub discovered_notes: BTreeSet<NoteId>,
 }
 
 impl From<&StateSyncUpdate> for SyncSummary {
@@ -98,6 +102,12 @@ impl From<&StateSyncUpdate> for SyncSummary {
                 .map(|(id, _)| *id)
                 .collect(),
             value.transaction_updates.committed_transactions().map(|t| t.id).collect(),
+            value
+                .transaction_updates
+                .discarded_transactions()
+                .filter(|t| matches!(t.status, TransactionStatus::Discarded(DiscardCause::Expired)))
+                .map(|t| t.id)
+                .collect(),
         )
     }
 }
@@ -277,6 +287,9 @@ pub struct AccountUpdates {
     /// hasn't been committed). If this is not the case, the account may be locked until the state
     /// is restored manually.
     mismatched_private_accounts: Vec<(AccountId, Digest)>,
+    /// Latest account commitments received from the network for the tracked public accounts.
+    /// The updated public accounts are checked against them in verified mode.
+    public_account_commitments: BTreeMap<AccountId, Digest>,
 }
 
 impl AccountUpdates {
@@ -288,9 +301,20 @@ impl AccountUpdates {
         Self {
             updated_public_accounts,
             mismatched_private_accounts,
+            public_account_commitments: BTreeMap::new(),
         }
     }
 
+    /// Sets the account commitments received from the network for the tracked public accounts.
+    #[must_use]
+    pub fn with_public_account_commitments(
+        mut self,
+        public_account_commitments: BTreeMap<AccountId, Digest>,
+    ) -> Self {
+        self.public_account_commitments = public_account_commitments;
+        self
+    }
+
     /// Returns the updated public accounts.
     pub fn updated_public_accounts(&self) -> &[Account] {
         &self.updated_public_accounts
@@ -301,8 +325,20 @@ impl AccountUpdates {
         &self.mismatched_private_accounts
     }
 
+    /// Returns the latest account commitments received from the network for the tracked public
+    /// accounts.
+    pub fn public_account_commitments(&self) -> &BTreeMap<AccountId, Digest> {
+        &self.public_account_commitments
+    }
+
+    /// Extends the updates with newer ones. Public accounts fetched again replace their previous
+    /// state.
     pub fn extend(&mut self, other: AccountUpdates) {
+        self.updated_public_accounts.retain(|account| {
+            !other.updated_public_accounts.iter().any(|updated| updated.id() == account.id())
+        });
         self.updated_public_accounts.extend(other.updated_public_accounts);
         self.mismatched_private_accounts.extend(other.mismatched_private_accounts);
+        self.public_account_commitments.extend(other.public_account_commitments);
     }
 }
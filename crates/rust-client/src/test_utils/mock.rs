@@ -1,4 +1,4 @@
-use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
+use alloc::{collections::BTreeSet, string::ToString, sync::Arc, vec::Vec};
 
 use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
@@ -43,6 +43,8 @@ pub type MockClient = Client;
 pub struct MockRpcApi {
     committed_transactions: Arc<RwLock<Vec<TransactionSummary>>>, /* TODO: Should this be tracked by the mock_chain? */
     pub mock_chain: Arc<RwLock<MockChain>>,
+    /// Number of upcoming `sync_state` requests that fail with a connection error.
+    failing_syncs: Arc<RwLock<u32>>,
 }
 impl Default for MockRpcApi {
     fn default() -> Self {
@@ -56,6 +58,7 @@ impl MockRpcApi {
         let api = Self {
             committed_transactions: Arc::new(RwLock::new(vec![])),
             mock_chain: Arc::new(RwLock::new(mock_chain)),
+            failing_syncs: Arc::new(RwLock::new(0)),
         };
 
         let from_account_id = AccountId::try_from(ACCOUNT_ID_PRIVATE_SENDER).unwrap();
@@ -207,6 +210,12 @@ impl MockRpcApi {
         let mut mock_chain = self.mock_chain.write();
         mock_chain.prove_until_block(current_height + num_blocks).unwrap();
     }
+
+    /// Makes the next `num_requests` calls to `sync_state` fail as if the node couldn't be
+    /// reached.
+    pub fn fail_next_syncs(&self, num_requests: u32) {
+        *self.failing_syncs.write() = num_requests;
+    }
 }
 use alloc::boxed::Box;
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
@@ -248,6 +257,14 @@ impl NodeRpcClient for MockRpcApi {
         _account_ids: &[AccountId],
         note_tags: &[NoteTag],
     ) -> Result<StateSyncInfo, RpcError> {
+        {
+            let mut failing_syncs = self.failing_syncs.write();
+            if *failing_syncs > 0 {
+                *failing_syncs -= 1;
+                return Err(RpcError::ConnectionError("node unavailable".into()));
+            }
+        }
+
         let response = self.get_sync_state_request(block_num, note_tags);
 
         Ok(response.try_into().unwrap())
@@ -301,6 +318,19 @@ impl NodeRpcClient for MockRpcApi {
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, RpcError> {
         // TODO: add some basic validations to test error cases
+        let transaction_id = Some(proven_transaction.id().into());
+        if self
+            .committed_transactions
+            .read()
+            .iter()
+            .any(|transaction| transaction.transaction_id == transaction_id)
+        {
+            return Err(RpcError::RequestError(
+                "SubmitProvenTransaction".to_string(),
+                "transaction was already committed".to_string(),
+            ));
+        }
+
         let notes: Vec<OutputNote> = proven_transaction.output_notes().iter().cloned().collect();
 
         let nullifiers: Vec<Nullifier> = proven_transaction
@@ -311,7 +341,7 @@ impl NodeRpcClient for MockRpcApi {
 
         self.seal_block(notes, nullifiers);
         self.committed_transactions.write().push(TransactionSummary {
-            transaction_id: Some(proven_transaction.id().into()),
+            transaction_id,
             block_num: self.get_chain_tip_block_num().as_u32(),
             account_id: Some(proven_transaction.account_id().into()),
         });
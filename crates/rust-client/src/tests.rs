@@ -1,5 +1,13 @@
 use alloc::vec::Vec;
-use std::{boxed::Box, collections::BTreeSet, env::temp_dir, println, sync::Arc};
+use std::{
+    boxed::Box,
+    collections::BTreeSet,
+    env::temp_dir,
+    println,
+    string::{String, ToString},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 // TESTS
 // ================================================================================================
@@ -12,12 +20,13 @@ use miden_lib::{
     transaction::TransactionKernel,
 };
 use miden_objects::{
-    Felt, FieldElement, Word, ZERO,
+    Digest, Felt, FieldElement, Word, ZERO,
     account::{
-        Account, AccountBuilder, AccountCode, AccountHeader, AccountId, AccountStorageMode,
-        AccountType, AuthSecretKey,
+        Account, AccountBuilder, AccountCode, AccountDelta, AccountHeader, AccountId,
+        AccountStorageMode, AccountType, AuthSecretKey,
     },
     asset::{Asset, FungibleAsset, TokenSymbol},
+    block::BlockNumber,
     crypto::{
         dsa::rpo_falcon512::SecretKey,
         rand::{FeltRng, RpoRandomCoin},
@@ -32,27 +41,37 @@ use miden_objects::{
         ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
         ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
     },
-    transaction::{InputNote, OutputNote},
+    transaction::{InputNote, OutputNote, TransactionWitness},
     vm::AdviceInputs,
 };
 use miden_tx::{
-    TransactionExecutorError,
+    TransactionExecutorError, TransactionProver, TransactionProverError,
+    auth::TransactionAuthenticator,
     utils::{Deserializable, Serializable},
 };
 use rand::{Rng, RngCore, rngs::StdRng};
 use uuid::Uuid;
 
 use crate::{
-    Client, ClientError,
+    Client, ClientError, VerificationError,
+    account::{component::RpoFalcon512WithRotation, get_account_public_key},
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
+    keystore::{
+        EncryptedFilesystemKeyStore, FilesystemKeyStore, KeyDerivationParams, KeyStore,
+        KeyStoreError,
+    },
     note::NoteRelevance,
     rpc::NodeRpcClient,
     store::{
-        InputNoteRecord, InputNoteState, NoteFilter, StoreError, TransactionFilter,
-        input_note_states::ConsumedAuthenticatedLocalNoteState, sqlite_store::SqliteStore,
+        InputNoteRecord, InputNoteState, NoteFilter, RetentionPolicy, Store, StoreBackup,
+        StoreError, TenantId, TransactionFilter,
+        input_note_states::ConsumedAuthenticatedLocalNoteState, memory_store::MemoryStore,
+        sqlite_store::SqliteStore,
+    },
+    sync::{
+        BlockRetentionPolicy, NoteDiscoveryRule, NoteTagSource, RescanScope, SyncEvent,
+        SyncOptions, SyncScheduler,
     },
-    sync::NoteTagSource,
     testing::{
         common::{
             ACCOUNT_ID_REGULAR, MINT_AMOUNT, RECALL_HEIGHT_DELTA, TRANSFER_AMOUNT,
@@ -63,8 +82,10 @@ use crate::{
         mock::{MockClient, MockRpcApi},
     },
     transaction::{
-        DiscardCause, PaymentTransactionData, TransactionRequestBuilder, TransactionRequestError,
-        TransactionStatus,
+        DiscardCause, PaymentTransactionData, ProvenTransaction, QueuedTransaction,
+        QueuedTransactionState, TransactionExpiration, TransactionPolicy, TransactionRecord,
+        TransactionRequestBuilder, TransactionRequestError, TransactionResult,
+        TransactionRetryPolicy, TransactionSimulation, TransactionStatus,
     },
 };
 
@@ -72,6 +93,13 @@ use crate::{
 /// stale.
 const TX_GRACEFUL_BLOCKS: u32 = 20;
 
+/// Cheap key derivation parameters to keep encrypted keystore tests fast.
+const TEST_KDF_PARAMS: KeyDerivationParams = KeyDerivationParams {
+    memory_kib: 64,
+    iterations: 1,
+    parallelism: 1,
+};
+
 // HELPERS
 // ================================================================================================
 
@@ -123,7 +151,7 @@ async fn insert_new_wallet(
     let key_pair = SecretKey::with_rng(&mut client.rng);
     let pub_key = key_pair.public_key();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).await.unwrap();
 
     let mut init_seed = [0u8; 32];
     client.rng.fill_bytes(&mut init_seed);
@@ -152,7 +180,7 @@ async fn insert_new_fungible_faucet(
     let key_pair = SecretKey::with_rng(&mut client.rng);
     let pub_key = key_pair.public_key();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).await.unwrap();
 
     // we need to use an initial seed to create the wallet account
     let mut init_seed = [0u8; 32];
@@ -177,6 +205,139 @@ async fn insert_new_fungible_faucet(
     Ok((account, seed))
 }
 
+/// Data read back from a client's store, without the timestamps the store sets, so it can be
+/// compared between store implementations.
+#[derive(Debug, PartialEq)]
+pub(crate) struct StoreSnapshot {
+    sync_height: BlockNumber,
+    account_histories: Vec<Vec<Account>>,
+    input_notes: Vec<(String, String)>,
+    output_notes: Vec<(String, String)>,
+    transactions: Vec<(String, TransactionStatus)>,
+}
+
+impl StoreSnapshot {
+    async fn read(client: &MockClient, account_ids: &[AccountId]) -> Self {
+        let mut account_histories = Vec::new();
+        for account_id in account_ids {
+            let history = client.get_account_history(*account_id).await.unwrap();
+            account_histories.push(history.iter().map(|record| record.account().clone()).collect());
+        }
+
+        let mut input_notes: Vec<(String, String)> = client
+            .get_input_notes(NoteFilter::All)
+            .await
+            .unwrap()
+            .iter()
+            .map(|note| {
+                let state = match note.state() {
+                    InputNoteState::ProcessingAuthenticated(_)
+                    | InputNoteState::ProcessingUnauthenticated(_) => "Processing".to_string(),
+                    state => state.to_string(),
+                };
+                (note.id().to_hex(), state)
+            })
+            .collect();
+        input_notes.sort();
+
+        let mut output_notes: Vec<(String, String)> = client
+            .get_output_notes(NoteFilter::All)
+            .await
+            .unwrap()
+            .iter()
+            .map(|note| (note.id().to_hex(), note.state().to_string()))
+            .collect();
+        output_notes.sort();
+
+        let mut transactions: Vec<(String, TransactionStatus)> = client
+            .get_transactions(TransactionFilter::All)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|transaction| (transaction.id.to_hex(), transaction.status))
+            .collect();
+        transactions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Self {
+            sync_height: client.get_sync_height().await.unwrap(),
+            account_histories,
+            input_notes,
+            output_notes,
+            transactions,
+        }
+    }
+}
+
+/// Runs the steps of `test_rollback_to_intermediate_block` on a client backed by the store: a
+/// mint and a consume transaction, each followed by a sync, a rollback to the mint block and a
+/// new sync. The client's RNG is fixed, so every run creates the same accounts, notes and
+/// transactions.
+///
+/// Returns the snapshots taken after the transactions are committed, after the rollback and after
+/// the last sync.
+pub(crate) async fn run_store_scenario(store: Arc<dyn Store>) -> Vec<StoreSnapshot> {
+    let (builder, _, keystore) = create_test_client_builder().await;
+    let mut client = builder
+        .with_store(store)
+        .with_rng(Box::new(RpoRandomCoin::new([0u64; 4].map(Felt::new))))
+        .build()
+        .await
+        .unwrap();
+
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let account_ids = [wallet.id(), faucet.id()];
+    client.sync_state().await.unwrap();
+
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            wallet.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    let note_id = mint_request.expected_output_notes().next().unwrap().id();
+    let mint_transaction = client.new_transaction(faucet.id(), mint_request).await.unwrap();
+    client.submit_transaction(mint_transaction).await.unwrap();
+    client.sync_state().await.unwrap();
+    let mint_block = client.get_sync_height().await.unwrap();
+
+    let consume_request =
+        TransactionRequestBuilder::new().build_consume_notes(vec![note_id]).unwrap();
+    let consume_transaction = client.new_transaction(wallet.id(), consume_request).await.unwrap();
+    client.submit_transaction(consume_transaction).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let mut snapshots = vec![StoreSnapshot::read(&client, &account_ids).await];
+
+    client.rollback_to_block(mint_block).await.unwrap();
+    snapshots.push(StoreSnapshot::read(&client, &account_ids).await);
+
+    client.sync_state().await.unwrap();
+    snapshots.push(StoreSnapshot::read(&client, &account_ids).await);
+
+    snapshots
+}
+
+/// Prover that fails every transaction, as an unreachable remote prover would.
+struct FailingProver;
+
+#[async_trait::async_trait(?Send)]
+impl TransactionProver for FailingProver {
+    async fn prove(
+        &self,
+        _tx_witness: TransactionWitness,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        Err(TransactionProverError::other("prover unavailable"))
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -420,14 +581,117 @@ async fn test_sync_state() {
 }
 
 #[tokio::test]
-async fn test_sync_state_mmr() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, keystore) = create_test_client().await;
-    // Import note and create wallet so that synced notes do not get discarded (due to being
-    // irrelevant)
-    insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+async fn test_sync_events() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let expected_notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &expected_notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    client.add_sync_listener(Arc::new({
+        let events = events.clone();
+        move |event: &SyncEvent| events.lock().unwrap().push(event.clone())
+    }));
+
+    let sync_details = client.sync_state().await.unwrap();
+
+    let received_events = events.lock().unwrap().clone();
+    let committed_notes: Vec<_> = received_events
+        .iter()
+        .filter_map(|event| match event {
+            SyncEvent::NoteCommitted(note_id) => Some(*note_id),
+            _ => None,
+        })
+        .collect();
+    let nullified_notes: Vec<_> = received_events
+        .iter()
+        .filter_map(|event| match event {
+            SyncEvent::NoteNullified(note_id) => Some(*note_id),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(committed_notes, sync_details.committed_notes);
+    assert_eq!(nullified_notes, sync_details.consumed_notes);
+    assert!(received_events.iter().any(|event| matches!(event, SyncEvent::BlockTracked(_))));
+
+    // Listeners aren't called anymore once removed
+    client.clear_sync_listeners();
+    events.lock().unwrap().clear();
+    rpc_api.advance_blocks(1);
+    client.sync_state().await.unwrap();
+    assert!(events.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_sync_state_with_options() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    // A partial sync finds the notes but doesn't advance the sync height, so the notes can't be
+    // verified yet
+    let options = SyncOptions::new()
+        .with_note_tags(notes.iter().map(|note| note.metadata().tag()))
+        .skip_nullifiers();
+    assert!(options.is_partial());
+    let sync_details = client.sync_state_with_options(options).await.unwrap();
+    assert_eq!(sync_details.block_num, 0.into());
+    assert_eq!(client.get_sync_height().await.unwrap(), 0.into());
+    assert!(client.get_input_notes(NoteFilter::Committed).await.unwrap().is_empty());
+    assert!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+    assert!(!client.get_input_notes(NoteFilter::Unverified).await.unwrap().is_empty());
+
+    // The next full sync goes over the same blocks and verifies them
+    let sync_details = client.sync_state().await.unwrap();
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert_eq!(client.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+    assert_eq!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().len(), 1);
+    assert!(client.get_input_notes(NoteFilter::Unverified).await.unwrap().is_empty());
+
+    // A full sync past the target block doesn't request anything
+    rpc_api.advance_blocks(2);
+    let target_block = client.get_sync_height().await.unwrap();
+    let sync_details = client
+        .sync_state_with_options(SyncOptions::new().with_target_block(target_block))
         .await
         .unwrap();
+    assert_eq!(sync_details.block_num, target_block);
+    assert!(sync_details.is_empty());
+}
+
+#[tokio::test]
+async fn test_rollback_to_block() {
+    let (mut client, rpc_api, _) = create_test_client().await;
 
     let notes = rpc_api
         .get_available_notes()
@@ -446,126 +710,959 @@ async fn test_sync_state_mmr() {
             .unwrap();
     }
 
-    // sync state
+    client.sync_state().await.unwrap();
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    assert_eq!(client.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+    assert_eq!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().len(), 1);
+
+    // The client and the node agree on the whole chain
+    assert_eq!(client.rollback_to_common_ancestor().await.unwrap(), chain_tip);
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(partial_mmr.peaks(), rpc_api.get_mmr().peaks());
+
+    // Only stored blocks can be rolled back to
+    assert!(matches!(
+        client.rollback_to_block((chain_tip.as_u32() + 1).into()).await,
+        Err(ClientError::RollbackBlockNotFound(_))
+    ));
+
+    // Rolling back to the genesis block reverts the notes found since then
+    client.rollback_to_block(BlockNumber::GENESIS).await.unwrap();
+    assert_eq!(client.get_sync_height().await.unwrap(), BlockNumber::GENESIS);
+    assert!(client.get_input_notes(NoteFilter::Committed).await.unwrap().is_empty());
+    assert!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+
+    // And the next sync applies them again
     let sync_details = client.sync_state().await.unwrap();
+    assert_eq!(sync_details.block_num, chain_tip);
+    assert_eq!(client.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+    assert_eq!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().len(), 1);
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(partial_mmr.peaks(), rpc_api.get_mmr().peaks());
+}
 
-    // verify that the client is synced to the latest block
-    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+#[tokio::test]
+async fn test_rollback_to_intermediate_block() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    client.sync_state().await.unwrap();
 
-    // verify that the latest block number has been updated
-    assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            wallet.id(),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+    let note_id = mint_request.expected_output_notes().next().unwrap().id();
+    let mint_transaction = client.new_transaction(faucet.id(), mint_request).await.unwrap();
+    let mint_transaction_id = mint_transaction.executed_transaction().id();
+    client.submit_transaction(mint_transaction).await.unwrap();
+    client.sync_state().await.unwrap();
+    let mint_block = client.get_sync_height().await.unwrap();
 
-    // verify that we inserted the latest block into the DB via the client
-    let latest_block = client.get_sync_height().await.unwrap();
-    assert_eq!(sync_details.block_num, latest_block);
+    // The note is consumed, and the consuming transaction committed, after the mint block
+    let consume_request =
+        TransactionRequestBuilder::new().build_consume_notes(vec![note_id]).unwrap();
+    let consume_transaction = client.new_transaction(wallet.id(), consume_request).await.unwrap();
+    let consume_transaction_id = consume_transaction.executed_transaction().id();
+    client.submit_transaction(consume_transaction).await.unwrap();
+    client.sync_state().await.unwrap();
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    assert!(chain_tip > mint_block);
+
+    client.rollback_to_block(mint_block).await.unwrap();
+    assert_eq!(client.get_sync_height().await.unwrap(), mint_block);
+    assert!(client.store.get_block_header_by_num(chain_tip).await.unwrap().is_none());
+
+    // Only the data derived from the removed blocks is reverted
+    let transaction_status = |transactions: &[TransactionRecord], id| {
+        transactions.iter().find(|tx| tx.id == id).unwrap().status.clone()
+    };
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
     assert_eq!(
-        rpc_api.get_block_header_by_number(None, false).await.unwrap().0.commitment(),
-        client
-            .test_store()
-            .get_block_headers(&[latest_block].into_iter().collect())
-            .await
-            .unwrap()[0]
-            .0
-            .commitment()
+        transaction_status(&transactions, mint_transaction_id),
+        TransactionStatus::Committed(mint_block)
+    );
+    assert_eq!(
+        transaction_status(&transactions, consume_transaction_id),
+        TransactionStatus::Pending
     );
+    let note = client.get_input_note(note_id).await.unwrap().unwrap();
+    assert!(matches!(note.state(), InputNoteState::ProcessingAuthenticated(_)));
 
-    // Try reconstructing the partial_mmr from what's in the database
+    // The next sync applies the removed blocks again
+    client.sync_state().await.unwrap();
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert_eq!(
+        transaction_status(&transactions, consume_transaction_id),
+        TransactionStatus::Committed(chain_tip)
+    );
+    let note = client.get_input_note(note_id).await.unwrap().unwrap();
+    assert!(matches!(note.state(), InputNoteState::ConsumedAuthenticatedLocal(_)));
     let partial_mmr = client.build_current_partial_mmr().await.unwrap();
-    assert_eq!(partial_mmr.forest(), 6);
-    assert!(partial_mmr.open(0).unwrap().is_some()); // Account anchor block
-    assert!(partial_mmr.open(1).unwrap().is_some());
-    assert!(partial_mmr.open(2).unwrap().is_none());
-    assert!(partial_mmr.open(3).unwrap().is_none());
-    assert!(partial_mmr.open(4).unwrap().is_some());
-    assert!(partial_mmr.open(5).unwrap().is_none());
+    assert_eq!(partial_mmr.peaks(), rpc_api.get_mmr().peaks());
+}
 
-    // Ensure the proofs are valid
-    let mmr_proof = partial_mmr.open(1).unwrap().unwrap();
-    let (block_1, _) = rpc_api.get_block_header_by_number(Some(1.into()), false).await.unwrap();
-    partial_mmr.peaks().verify(block_1.commitment(), mmr_proof).unwrap();
+#[tokio::test]
+async fn test_store_scenario_is_deterministic() {
+    // Snapshots of different stores can only be compared if the scenario always creates the same
+    // data
+    let snapshots =
+        run_store_scenario(Arc::new(SqliteStore::new(create_test_store_path()).await.unwrap()))
+            .await;
+    let expected =
+        run_store_scenario(Arc::new(SqliteStore::new(create_test_store_path()).await.unwrap()))
+            .await;
+    assert_eq!(snapshots, expected);
 
-    let mmr_proof = partial_mmr.open(4).unwrap().unwrap();
-    let (block_4, _) = rpc_api.get_block_header_by_number(Some(4.into()), false).await.unwrap();
-    partial_mmr.peaks().verify(block_4.commitment(), mmr_proof).unwrap();
+    // The rollback reverts the consume transaction, which the last sync applies again
+    assert_eq!(snapshots[0], snapshots[2]);
+    assert_ne!(snapshots[0], snapshots[1]);
+}
 
-    // the blocks for both notes should be stored as they are relevant for the client's accounts
-    // with the addition of the chain tip
-    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 3);
+#[tokio::test]
+async fn test_rollback_rejects_blocks_not_stored_by_sync() {
+    let (mut client, ..) = create_test_client().await;
+    client.sync_state().await.unwrap();
+
+    // Blocks fetched after the sync are stored with the peaks of the current chain
+    let mut partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    client
+        .get_and_store_authenticated_block(2.into(), &mut partial_mmr)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client.rollback_to_block(2.into()).await,
+        Err(ClientError::RollbackBlockNotAnchor(_))
+    ));
 }
 
 #[tokio::test]
-async fn test_sync_state_tags() {
-    // generate test client with a random store name
+async fn test_verified_note_import() {
+    let (builder, rpc_api, _) = create_test_client_builder().await;
+    let mut client = builder.in_verified_mode(true).build().await.unwrap();
+    assert!(client.is_in_verified_mode());
+
+    let available_notes = rpc_api.get_available_notes();
+    let public_note = available_notes.iter().find_map(|note| note.note()).unwrap().clone();
+    let (inclusion_proof, other_inclusion_proof) = {
+        let (public, private): (Vec<_>, Vec<_>) =
+            available_notes.iter().partition(|note| note.note().is_some());
+        (public[0].inclusion_proof().clone(), private[0].inclusion_proof().clone())
+    };
+
+    // Data anchored in blocks the client hasn't synced to yet can't be verified
+    let result = client
+        .import_note(NoteFile::NoteWithProof(public_note.clone(), inclusion_proof.clone()))
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClientError::VerificationError(VerificationError::BlockNotSynced(_)))
+    ));
+    client.sync_state().await.unwrap();
+
+    // A proof that doesn't match the note is rejected before anything is stored
+    let result = client
+        .import_note(NoteFile::NoteWithProof(public_note.clone(), other_inclusion_proof))
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClientError::VerificationError(VerificationError::NoteInclusionMismatch { .. }))
+    ));
+    assert!(client.get_input_note(public_note.id()).await.unwrap().is_none());
+
+    // The note's own proof is verified against the client's partial blockchain
+    client
+        .import_note(NoteFile::NoteWithProof(public_note.clone(), inclusion_proof))
+        .await
+        .unwrap();
+    let note_record = client.get_input_note(public_note.id()).await.unwrap().unwrap();
+    assert!(matches!(note_record.state(), InputNoteState::Committed(_)));
+
+    // Notes imported by ID are verified too
+    client.import_note(NoteFile::NoteId(public_note.id())).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sync_scheduler() {
     let (mut client, rpc_api, _) = create_test_client().await;
+    let interval = Duration::from_secs(10);
+    let mut scheduler = SyncScheduler::new().with_interval(interval);
+    assert_eq!(scheduler.next_sync_delay(), interval);
 
-    // Import first mockchain note as expected
-    let expected_notes = rpc_api.get_available_notes();
+    // Nothing is synced while paused
+    scheduler.pause();
+    assert!(scheduler.sync(&mut client).await.unwrap().is_none());
+    assert_eq!(client.get_sync_height().await.unwrap(), 0.into());
 
-    for tag in expected_notes.iter().map(|n| n.metadata().tag()) {
-        client.add_note_tag(tag).await.unwrap();
-    }
+    scheduler.resume();
+    let sync_details = scheduler.sync(&mut client).await.unwrap().unwrap();
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert_eq!(scheduler.consecutive_failures(), 0);
 
-    // assert that we have no  expected notes prior to syncing state
-    assert!(client.get_input_notes(NoteFilter::Expected).await.unwrap().is_empty());
+    // A new block makes the next sync due immediately
+    rpc_api.advance_blocks(1);
+    scheduler.notify_new_block();
+    assert_eq!(scheduler.next_sync_delay(), Duration::ZERO);
+    let sync_details = scheduler.sync(&mut client).await.unwrap().unwrap();
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert_eq!(scheduler.next_sync_delay(), interval);
+    assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
 
-    // sync state
-    let sync_details = client.sync_state().await.unwrap();
+    // Failing to reach the node backs off, and a successful sync resets the delay
+    rpc_api.advance_blocks(1);
+    rpc_api.fail_next_syncs(3);
+    let mut previous_delay = scheduler.next_sync_delay();
+    for failures in 1..=3 {
+        assert!(matches!(scheduler.sync(&mut client).await, Err(ClientError::RpcError(_))));
+        assert_eq!(scheduler.consecutive_failures(), failures);
+        assert!(scheduler.next_sync_delay() > previous_delay);
+        previous_delay = scheduler.next_sync_delay();
+    }
+    assert_eq!(scheduler.next_sync_delay(), interval * 8);
+    assert!(client.get_sync_height().await.unwrap() < rpc_api.get_chain_tip_block_num());
 
-    // verify that the client is synced to the latest block
-    assert_eq!(
-        sync_details.block_num,
+    let sync_details = scheduler.sync(&mut client).await.unwrap().unwrap();
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert_eq!(scheduler.consecutive_failures(), 0);
+    assert_eq!(scheduler.next_sync_delay(), interval);
+}
+
+#[tokio::test]
+async fn test_sync_state_checkpointed() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    // Every step is persisted on its own
+    let sync_details = client.sync_state_checkpointed(1).await.unwrap();
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+    assert_eq!(client.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+    assert_eq!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().len(), 1);
+
+    // The partial MMR built from the checkpoints is consistent with the chain
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(partial_mmr.forest(), rpc_api.get_mmr().forest());
+    assert_eq!(partial_mmr.peaks(), rpc_api.get_mmr().peaks());
+
+    // Syncing again from the last checkpoint doesn't find anything new
+    rpc_api.advance_blocks(3);
+    let sync_details = client.sync_state_checkpointed(2).await.unwrap();
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert!(sync_details.is_empty());
+}
+
+#[tokio::test]
+async fn test_sync_state_mmr() {
+    // generate test client with a random store name
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    // Import note and create wallet so that synced notes do not get discarded (due to being
+    // irrelevant)
+    insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    // sync state
+    let sync_details = client.sync_state().await.unwrap();
+
+    // verify that the client is synced to the latest block
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+
+    // verify that the latest block number has been updated
+    assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+
+    // verify that we inserted the latest block into the DB via the client
+    let latest_block = client.get_sync_height().await.unwrap();
+    assert_eq!(sync_details.block_num, latest_block);
+    assert_eq!(
+        rpc_api.get_block_header_by_number(None, false).await.unwrap().0.commitment(),
+        client
+            .test_store()
+            .get_block_headers(&[latest_block].into_iter().collect())
+            .await
+            .unwrap()[0]
+            .0
+            .commitment()
+    );
+
+    // Try reconstructing the partial_mmr from what's in the database
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(partial_mmr.forest(), 6);
+    assert!(partial_mmr.open(0).unwrap().is_some()); // Account anchor block
+    assert!(partial_mmr.open(1).unwrap().is_some());
+    assert!(partial_mmr.open(2).unwrap().is_none());
+    assert!(partial_mmr.open(3).unwrap().is_none());
+    assert!(partial_mmr.open(4).unwrap().is_some());
+    assert!(partial_mmr.open(5).unwrap().is_none());
+
+    // Ensure the proofs are valid
+    let mmr_proof = partial_mmr.open(1).unwrap().unwrap();
+    let (block_1, _) = rpc_api.get_block_header_by_number(Some(1.into()), false).await.unwrap();
+    partial_mmr.peaks().verify(block_1.commitment(), mmr_proof).unwrap();
+
+    let mmr_proof = partial_mmr.open(4).unwrap().unwrap();
+    let (block_4, _) = rpc_api.get_block_header_by_number(Some(4.into()), false).await.unwrap();
+    partial_mmr.peaks().verify(block_4.commitment(), mmr_proof).unwrap();
+
+    // the blocks for both notes should be stored as they are relevant for the client's accounts
+    // with the addition of the chain tip
+    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_sync_state_tags() {
+    // generate test client with a random store name
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    // Import first mockchain note as expected
+    let expected_notes = rpc_api.get_available_notes();
+
+    for tag in expected_notes.iter().map(|n| n.metadata().tag()) {
+        client.add_note_tag(tag).await.unwrap();
+    }
+
+    // assert that we have no  expected notes prior to syncing state
+    assert!(client.get_input_notes(NoteFilter::Expected).await.unwrap().is_empty());
+
+    // sync state
+    let sync_details = client.sync_state().await.unwrap();
+
+    // verify that the client is synced to the latest block
+    assert_eq!(
+        sync_details.block_num,
         rpc_api.get_block_header_by_number(None, false).await.unwrap().0.block_num()
     );
 
-    // as we are syncing with tags, the response should containt blocks for both notes but they
-    // shouldn't be stored as they are not relevant for the client's accounts. Only the chain
-    // tip should be stored in the database
-    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
+    // as we are syncing with tags, the response should containt blocks for both notes but they
+    // shouldn't be stored as they are not relevant for the client's accounts. Only the chain
+    // tip should be stored in the database
+    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_note_discovery_rules() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let public_note = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .find_map(|note| note.note().cloned())
+        .unwrap();
+    let rule = NoteDiscoveryRule::ScriptRoot(public_note.script().root());
+
+    client.add_note_discovery_rule(rule).await.unwrap();
+    client.add_note_discovery_rule(rule).await.unwrap();
+    assert_eq!(client.get_note_discovery_rules().await.unwrap(), vec![rule]);
+
+    // The note's tag isn't tracked, so it's only found through the discovery rule
+    let sync_summary = client.sync_state().await.unwrap();
+    assert_eq!(sync_summary.new_public_notes, vec![public_note.id()]);
+    let note_record = client.get_input_note(public_note.id()).await.unwrap().unwrap();
+    assert!(matches!(note_record.state(), InputNoteState::Committed(_)));
+
+    // Notes that are already tracked aren't imported again
+    let imported_notes = client
+        .scan_blocks_for_notes(
+            &[rule],
+            BlockNumber::GENESIS..=client.get_sync_height().await.unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(imported_notes.is_empty());
+
+    client.remove_note_discovery_rule(rule).await.unwrap();
+    assert!(client.get_note_discovery_rules().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_rescan() {
+    let (mut client, rpc_api, _) = create_test_client().await;
+
+    let sync_height = client.sync_state().await.unwrap().block_num;
+    let note_tags: BTreeSet<NoteTag> =
+        rpc_api.get_available_notes().iter().map(|n| n.metadata().tag()).collect();
+
+    // Rescans keep the sync height and, as with regular syncs, the notes they find aren't stored
+    // unless they're relevant to the client's accounts
+    let rescan_summary = client
+        .rescan(BlockNumber::GENESIS, sync_height, RescanScope::NoteTags(note_tags))
+        .await
+        .unwrap();
+    assert_eq!(rescan_summary.block_num, sync_height);
+    assert_eq!(client.get_sync_height().await.unwrap(), sync_height);
+    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
+
+    // Blocks past the sync height are left for the next sync
+    let rescan_summary = client
+        .rescan(
+            BlockNumber::from(sync_height.as_u32() + 1),
+            BlockNumber::from(sync_height.as_u32() + 10),
+            RescanScope::All,
+        )
+        .await
+        .unwrap();
+    assert!(rescan_summary.new_public_notes.is_empty());
+    assert_eq!(rescan_summary.block_num, sync_height);
+}
+
+#[tokio::test]
+async fn test_rescan_finds_notes_of_added_accounts() {
+    let (mut sender, rpc_api, keystore) = create_test_client().await;
+    let (wallet, wallet_seed) =
+        insert_new_wallet(&mut sender, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut sender, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    sender.sync_state().await.unwrap();
+
+    let mint_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            wallet.id(),
+            NoteType::Public,
+            sender.rng(),
+        )
+        .unwrap();
+    let note_id = mint_request.expected_output_notes().next().unwrap().id();
+    let mint_transaction = sender.new_transaction(faucet.id(), mint_request).await.unwrap();
+    sender.submit_transaction(mint_transaction).await.unwrap();
+    let mint_block = rpc_api.get_chain_tip_block_num();
+    rpc_api.advance_blocks(2);
+
+    // The wallet is added after the receiver synced past the block of its note
+    let (builder, ..) = create_test_client_builder().await;
+    let mut receiver = builder.with_rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    let sync_height = receiver.sync_state().await.unwrap().block_num;
+    receiver.add_account(&wallet, Some(wallet_seed), false).await.unwrap();
+    receiver.sync_state().await.unwrap();
+    assert!(receiver.get_input_note(note_id).await.unwrap().is_none());
+
+    let rescan_summary = receiver
+        .rescan(BlockNumber::GENESIS, sync_height, RescanScope::Accounts([wallet.id()].into()))
+        .await
+        .unwrap();
+    assert!(rescan_summary.new_public_notes.contains(&note_id));
+    let note = receiver.get_input_note(note_id).await.unwrap().unwrap();
+    assert!(matches!(note.state(), InputNoteState::Committed(_)));
+
+    // The note's block is tracked, but it wasn't stored by a sync
+    let partial_mmr = receiver.build_current_partial_mmr().await.unwrap();
+    assert!(partial_mmr.is_tracked(mint_block.as_usize()));
+    assert!(matches!(
+        receiver.rollback_to_block(mint_block).await,
+        Err(ClientError::RollbackBlockNotAnchor(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_block_retention_policy() {
+    let (builder, ..) = create_test_client_builder().await;
+    let policy = BlockRetentionPolicy::chain_tip_only().with_pinned_blocks([BlockNumber::from(1)]);
+    let mut client = builder.with_block_retention_policy(policy).build().await.unwrap();
+    client.sync_state().await.unwrap();
+
+    // Headers fetched on demand are stored until the next sync prunes them
+    let fetched_blocks: BTreeSet<BlockNumber> = [1, 2].into_iter().map(BlockNumber::from).collect();
+    client.ensure_block_headers(&fetched_blocks).await.unwrap();
+    assert_eq!(client.test_store().get_block_headers(&fetched_blocks).await.unwrap().len(), 2);
+
+    client.sync_state().await.unwrap();
+    let retained_blocks: Vec<BlockNumber> = client
+        .test_store()
+        .get_block_headers(&fetched_blocks)
+        .await
+        .unwrap()
+        .iter()
+        .map(|(header, _)| header.block_num())
+        .collect();
+    assert_eq!(retained_blocks, vec![BlockNumber::from(1)]);
+
+    // The authentication nodes of the pruned blocks are removed along with their headers
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert!(partial_mmr.is_tracked(1));
+    assert!(!partial_mmr.is_tracked(2));
+
+    // Pruned headers are fetched again when needed
+    client.ensure_block_headers(&fetched_blocks).await.unwrap();
+    assert_eq!(client.test_store().get_block_headers(&fetched_blocks).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_sync_tenants() {
+    let store = MemoryStore::new();
+    let (builder, rpc_api, _) = create_test_client_builder().await;
+    let mut client_a = builder
+        .with_store(store.with_tenant(TenantId::new("a")).await.unwrap())
+        .build()
+        .await
+        .unwrap();
+    let (builder, ..) = create_test_client_builder().await;
+    let client_b = builder
+        .with_rpc(Arc::new(rpc_api.clone()))
+        .with_store(store.with_tenant(TenantId::new("b")).await.unwrap())
+        .build()
+        .await
+        .unwrap();
+
+    // Only the first tenant expects the mockchain notes
+    let expected_notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &expected_notes {
+        client_a
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    assert!(client_b.get_note_tags().await.unwrap().is_empty());
+    assert!(client_b.get_input_notes(NoteFilter::All).await.unwrap().is_empty());
+
+    let mut clients = [client_a, client_b];
+    let sync_summaries = Client::sync_tenants(&mut clients).await.unwrap();
+    let [client_a, client_b] = &clients;
+
+    assert_eq!(sync_summaries.len(), 2);
+    for sync_summary in &sync_summaries {
+        assert_eq!(sync_summary.block_num, rpc_api.get_chain_tip_block_num());
+    }
+
+    // The notes are only updated for the tenant that tracks them
+    assert_eq!(client_a.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+    assert_eq!(client_a.get_input_notes(NoteFilter::Consumed).await.unwrap().len(), 1);
+    assert_eq!(sync_summaries[0].consumed_notes.len(), 1);
+    assert!(client_b.get_input_notes(NoteFilter::All).await.unwrap().is_empty());
+    assert!(sync_summaries[1].is_empty());
+
+    assert_eq!(client_a.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+    assert_eq!(client_b.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+}
+
+#[tokio::test]
+async fn test_tags() {
+    // generate test client with a random store name
+    let (mut client, _rpc_api, _) = create_test_client().await;
+
+    // Assert that the store gets created with the tag 0 (used for notes consumable by any account)
+    assert!(client.get_note_tags().await.unwrap().is_empty());
+
+    // add a tag
+    let tag_1: NoteTag = 1.into();
+    let tag_2: NoteTag = 2.into();
+    client.add_note_tag(tag_1).await.unwrap();
+    client.add_note_tag(tag_2).await.unwrap();
+
+    // verify that the tag is being tracked
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+
+    // attempt to add the same tag again
+    client.add_note_tag(tag_1).await.unwrap();
+
+    // verify that the tag is still being tracked only once
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+
+    // Try removing non-existent tag
+    let tag_4: NoteTag = 4.into();
+    client.remove_note_tag(tag_4).await.unwrap();
+
+    // verify that the tracked tags are unchanged
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+
+    // remove second tag
+    client.remove_note_tag(tag_1).await.unwrap();
+
+    // verify that tag_1 is not tracked anymore
+    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_2]);
+}
+
+#[tokio::test]
+async fn test_mint_transaction() {
+    // generate test client with a random store name
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    // Faucet account generation
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    // Test submitting a mint transaction
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+            miden_objects::note::NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+
+    assert!(transaction.executed_transaction().account_delta().nonce().is_some());
+}
+
+#[tokio::test]
+async fn test_simulate_transaction() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+            miden_objects::note::NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let simulation = client
+        .simulate_transaction(faucet.id(), transaction_request.clone())
+        .await
+        .unwrap();
+    let TransactionSimulation::Executed(simulated_transaction) = simulation else {
+        panic!("Simulated mint transaction should succeed");
+    };
+    assert_eq!(simulated_transaction.account_id(), faucet.id());
+    assert_eq!(simulated_transaction.created_notes().num_notes(), 1);
+    assert!(simulated_transaction.consumed_notes().is_empty());
+    assert!(simulated_transaction.account_delta().nonce().is_some());
+    assert!(simulated_transaction.total_cycles() > 0);
+    assert!(simulated_transaction.fee().is_none());
+
+    // Simulations don't change the client's state, so the request can still be executed
+    assert!(client.get_transactions(TransactionFilter::All).await.unwrap().is_empty());
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    assert_eq!(
+        transaction.executed_transaction().output_notes(),
+        simulated_transaction.created_notes()
+    );
+
+    // Executed transactions can be previewed before the same result is submitted
+    let preview = client.preview_transaction(&transaction);
+    assert_eq!(preview.executed_transaction().id(), transaction.executed_transaction().id());
+    assert!(preview.fee().is_none());
+
+    // Simulations run against the stored state, so the transaction isn't taken into account until
+    // it's submitted
+    let mut mint_request = || {
+        TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+                AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+                miden_objects::note::NoteType::Private,
+                client.rng(),
+            )
+            .unwrap()
+    };
+    let [first_request, second_request] = [mint_request(), mint_request()];
+    let TransactionSimulation::Executed(simulated_transaction) =
+        client.simulate_transaction(faucet.id(), first_request).await.unwrap()
+    else {
+        panic!("Simulated mint transaction should succeed");
+    };
+    assert_eq!(
+        simulated_transaction.executed_transaction().initial_account().nonce(),
+        faucet.nonce()
+    );
+
+    let final_account = transaction.executed_transaction().final_account().clone();
+    client.submit_transaction(transaction).await.unwrap();
+    let TransactionSimulation::Executed(simulated_transaction) =
+        client.simulate_transaction(faucet.id(), second_request).await.unwrap()
+    else {
+        panic!("Simulated mint transaction should succeed");
+    };
+    assert_eq!(
+        simulated_transaction.executed_transaction().initial_account().commitment(),
+        final_account.commitment()
+    );
+}
+
+#[tokio::test]
+async fn test_transaction_batch() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (wallet, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let mut mint_request = || {
+        TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+                wallet.id(),
+                miden_objects::note::NoteType::Private,
+                client.rng(),
+            )
+            .unwrap()
+    };
+    let mint_requests = vec![mint_request(), mint_request()];
+
+    // A failing request leaves the client's state as it was
+    let failing_script = client.compile_tx_script(vec![], "begin push.0 assert end").unwrap();
+    let failing_request = TransactionRequestBuilder::new()
+        .with_custom_script(failing_script)
+        .build()
+        .unwrap();
+    let err = client
+        .new_transaction_batch(faucet.id(), vec![mint_requests[0].clone(), failing_request])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClientError::TransactionBatchError { index: 1, .. }));
+
+    // The state left by the executed requests of the failed batch isn't used by later transactions
+    let tx_result = client.new_transaction(faucet.id(), mint_requests[0].clone()).await.unwrap();
+    assert_eq!(tx_result.executed_transaction().initial_account().nonce(), faucet.nonce());
+
+    // Each transaction is executed against the state left by the previous one
+    let tx_results = client.new_transaction_batch(faucet.id(), mint_requests).await.unwrap();
+    assert_eq!(tx_results.len(), 2);
+    assert_eq!(
+        tx_results[0].executed_transaction().final_account().commitment(),
+        tx_results[1].executed_transaction().initial_account().commitment()
+    );
+
+    let faucet_record = client.get_account(faucet.id()).await.unwrap().unwrap();
+    assert_eq!(faucet_record.account().nonce(), faucet.nonce());
+    assert!(client.get_transactions(TransactionFilter::All).await.unwrap().is_empty());
+
+    let transaction_ids: Vec<_> = tx_results
+        .iter()
+        .map(|tx_result| tx_result.executed_transaction().id())
+        .collect();
+    client.submit_transaction_batch(tx_results).await.unwrap();
+
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert!(transactions.iter().all(|transaction| transaction_ids.contains(&transaction.id)));
+    assert_eq!(client.get_output_notes(NoteFilter::All).await.unwrap().len(), 2);
+
+    let faucet_record = client.get_account(faucet.id()).await.unwrap().unwrap();
+    assert_eq!(faucet_record.account().nonce(), faucet.nonce() + Felt::new(2));
+}
+
+#[tokio::test]
+async fn test_transaction_queue() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    client.sync_state().await.unwrap();
+
+    let mut mint_request = || {
+        TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+                AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+                miden_objects::note::NoteType::Private,
+                client.rng(),
+            )
+            .unwrap()
+    };
+    let first_request = mint_request();
+    let second_request = mint_request();
+    let third_request = mint_request();
+
+    // Submitted transactions stay queued until they are committed
+    let tx_result = client.new_transaction(faucet.id(), first_request).await.unwrap();
+    let submitted_id = tx_result.executed_transaction().id();
+    client.submit_transaction(tx_result).await.unwrap();
+
+    let queued_transaction = client.get_queued_transaction(submitted_id).await.unwrap().unwrap();
+    assert!(matches!(queued_transaction.state(), QueuedTransactionState::Submitted(_)));
+    assert_eq!(queued_transaction.attempts(), 0);
+    assert!(!client.transaction_retry_policy().is_due(&queued_transaction, None));
+
+    let err = client.retry_queued_transaction(submitted_id).await.unwrap_err();
+    assert!(
+        matches!(err, ClientError::QueuedTransactionAlreadySubmitted(id) if id == submitted_id)
+    );
+    let err = client.cancel_queued_transaction(submitted_id).await.unwrap_err();
+    assert!(
+        matches!(err, ClientError::QueuedTransactionAlreadySubmitted(id) if id == submitted_id)
+    );
+
+    // Transactions that fail to be proven are proven and submitted by the retry loop
+    let tx_result = client.new_transaction(faucet.id(), second_request).await.unwrap();
+    let failed_id = tx_result.executed_transaction().id();
+    client
+        .submit_transaction_with_prover(tx_result, Arc::new(FailingProver))
+        .await
+        .unwrap_err();
+
+    let queued_transaction = client.get_queued_transaction(failed_id).await.unwrap().unwrap();
+    assert_eq!(queued_transaction.state(), &QueuedTransactionState::Executed);
+    assert_eq!(queued_transaction.attempts(), 1);
+
+    client.set_transaction_retry_policy(
+        TransactionRetryPolicy::new(5).with_retry_interval(Duration::ZERO),
+    );
+    assert_eq!(client.retry_queued_transactions().await.unwrap(), vec![failed_id]);
+    let queued_transaction = client.get_queued_transaction(failed_id).await.unwrap().unwrap();
+    assert!(matches!(queued_transaction.state(), QueuedTransactionState::Submitted(_)));
+
+    // Transactions queued to be proven elsewhere aren't proven by the retry loop
+    let tx_result = client.new_transaction(faucet.id(), third_request).await.unwrap();
+    let queued_transaction = client.enqueue_transaction(tx_result.clone()).await.unwrap();
+    assert_eq!(queued_transaction.state(), &QueuedTransactionState::AwaitingExternalProof);
+    assert!(!client.transaction_retry_policy().is_due(&queued_transaction, None));
+    assert!(client.retry_queued_transactions().await.unwrap().is_empty());
+    let stored_transaction =
+        client.get_queued_transaction(queued_transaction.id()).await.unwrap().unwrap();
+    assert_eq!(stored_transaction.state(), &QueuedTransactionState::AwaitingExternalProof);
+    assert_eq!(client.get_queued_transactions().await.unwrap().len(), 3);
+
+    let executed_transaction = QueuedTransaction::new(tx_result, None);
+    assert!(client.transaction_retry_policy().is_due(&executed_transaction, None));
+    assert!(!TransactionRetryPolicy::new(0).is_due(&executed_transaction, None));
+
+    // Transactions that weren't submitted can be cancelled
+    client.cancel_queued_transaction(queued_transaction.id()).await.unwrap();
+    let err = client.cancel_queued_transaction(queued_transaction.id()).await.unwrap_err();
+    assert!(matches!(err, ClientError::QueuedTransactionNotFound(_)));
+    assert_eq!(client.get_queued_transactions().await.unwrap().len(), 2);
 }
 
 #[tokio::test]
-async fn test_tags() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, _) = create_test_client().await;
-
-    // Assert that the store gets created with the tag 0 (used for notes consumable by any account)
-    assert!(client.get_note_tags().await.unwrap().is_empty());
-
-    // add a tag
-    let tag_1: NoteTag = 1.into();
-    let tag_2: NoteTag = 2.into();
-    client.add_note_tag(tag_1).await.unwrap();
-    client.add_note_tag(tag_2).await.unwrap();
+async fn test_externally_proven_transactions() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
 
-    // verify that the tag is being tracked
-    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
 
-    // attempt to add the same tag again
-    client.add_note_tag(tag_1).await.unwrap();
+    client.sync_state().await.unwrap();
 
-    // verify that the tag is still being tracked only once
-    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+    let mut mint_request = || {
+        TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+                AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+                miden_objects::note::NoteType::Private,
+                client.rng(),
+            )
+            .unwrap()
+    };
+    let first_request = mint_request();
+    let second_request = mint_request();
+
+    // The executed transaction and its proof go through their serialized forms, as they would
+    // when moved between machines
+    let tx_result = client.new_transaction(faucet.id(), first_request).await.unwrap();
+    let transaction_id = tx_result.executed_transaction().id();
+
+    let exported = TransactionResult::read_from_bytes(&tx_result.to_bytes()).unwrap();
+    let proven_transaction = client.prove_transaction(&exported).await.unwrap();
+    let proven_transaction =
+        ProvenTransaction::read_from_bytes(&proven_transaction.to_bytes()).unwrap();
+
+    // Only queued transactions have the data needed to update the local state
+    let err = client.apply_proven_transaction(proven_transaction.clone()).await.unwrap_err();
+    assert!(matches!(err, ClientError::QueuedTransactionNotFound(id) if id == transaction_id));
+
+    client.enqueue_transaction(tx_result).await.unwrap();
+    client.apply_proven_transaction(proven_transaction.clone()).await.unwrap();
+    let err = client.submit_proven_transaction(proven_transaction).await.unwrap_err();
+    assert!(
+        matches!(err, ClientError::QueuedTransactionAlreadySubmitted(id) if id == transaction_id)
+    );
 
-    // Try removing non-existent tag
-    let tag_4: NoteTag = 4.into();
-    client.remove_note_tag(tag_4).await.unwrap();
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].id, transaction_id);
+    let faucet_record = client.get_account(faucet.id()).await.unwrap().unwrap();
+    assert_eq!(faucet_record.account().nonce(), faucet.nonce() + Felt::ONE);
 
-    // verify that the tracked tags are unchanged
-    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_1, tag_2]);
+    // Proofs of queued transactions are submitted and saved into the store
+    let tx_result = client.new_transaction(faucet.id(), second_request).await.unwrap();
+    let transaction_id = tx_result.executed_transaction().id();
+    client.enqueue_transaction(tx_result.clone()).await.unwrap();
+    let proven_transaction = client.prove_transaction(&tx_result).await.unwrap();
 
-    // remove second tag
-    client.remove_note_tag(tag_1).await.unwrap();
+    client.submit_proven_transaction(proven_transaction).await.unwrap();
 
-    // verify that tag_1 is not tracked anymore
-    assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_2]);
+    let queued_transaction = client.get_queued_transaction(transaction_id).await.unwrap().unwrap();
+    assert!(matches!(queued_transaction.state(), QueuedTransactionState::Submitted(_)));
+    assert_eq!(client.get_transactions(TransactionFilter::All).await.unwrap().len(), 2);
 }
 
 #[tokio::test]
-async fn test_mint_transaction() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = create_test_client().await;
+async fn test_queued_transaction_known_by_node() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
 
-    // Faucet account generation
     let (faucet, _seed) =
         insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
             .await
@@ -573,8 +1670,7 @@ async fn test_mint_transaction() {
 
     client.sync_state().await.unwrap();
 
-    // Test submitting a mint transaction
-    let transaction_request = TransactionRequestBuilder::new()
+    let mint_request = TransactionRequestBuilder::new()
         .build_mint_fungible_asset(
             FungibleAsset::new(faucet.id(), 5u64).unwrap(),
             AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
@@ -582,10 +1678,26 @@ async fn test_mint_transaction() {
             client.rng(),
         )
         .unwrap();
-
-    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
-
-    assert!(transaction.executed_transaction().account_delta().nonce().is_some());
+    let tx_result = client.new_transaction(faucet.id(), mint_request).await.unwrap();
+    let transaction_id = tx_result.executed_transaction().id();
+    let proven_transaction = client.prove_transaction(&tx_result).await.unwrap();
+
+    // The node accepted a previous attempt that the client didn't record
+    rpc_api.submit_proven_transaction(proven_transaction.clone()).await.unwrap();
+    client.enqueue_transaction(tx_result).await.unwrap();
+
+    // Retrying the transaction records it as submitted instead of failed
+    client.submit_proven_transaction(proven_transaction).await.unwrap();
+    let queued_transaction = client.get_queued_transaction(transaction_id).await.unwrap().unwrap();
+    assert!(matches!(queued_transaction.state(), QueuedTransactionState::Submitted(_)));
+    assert_eq!(queued_transaction.attempts(), 0);
+    assert_eq!(client.get_transactions(TransactionFilter::All).await.unwrap().len(), 1);
+
+    // Committed transactions are removed from the queue by the next sync
+    client.sync_state().await.unwrap();
+    assert!(client.get_queued_transaction(transaction_id).await.unwrap().is_none());
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert!(matches!(transactions[0].status, TransactionStatus::Committed(_)));
 }
 
 #[tokio::test]
@@ -658,6 +1770,41 @@ async fn test_transaction_request_expiration() {
     assert_eq!(tx_outputs.expiration_block_num, current_height + 5);
 }
 
+#[tokio::test]
+async fn test_transaction_policy_expiration() {
+    let (mut client, _, keystore) = create_test_client().await;
+    client.sync_state().await.unwrap();
+    client.set_transaction_policy(TransactionPolicy::new().with_expiration_delta(5));
+
+    let current_height = client.get_sync_height().await.unwrap();
+    let (faucet, _seed) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let asset = FungibleAsset::new(faucet.id(), 5u64).unwrap();
+    let target_id = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+
+    // Requests without an expiration use the client's default
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(asset, target_id, NoteType::Private, client.rng())
+        .unwrap();
+    assert_eq!(transaction_request.expiration(), TransactionExpiration::ClientDefault);
+
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    let (_, tx_outputs, ..) = transaction.executed_transaction().clone().into_parts();
+    assert_eq!(tx_outputs.expiration_block_num, current_height + 5);
+
+    // The request's expiration overrides the client's default
+    let transaction_request = TransactionRequestBuilder::new()
+        .with_expiration_delta(3)
+        .build_mint_fungible_asset(asset, target_id, NoteType::Private, client.rng())
+        .unwrap();
+
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    let (_, tx_outputs, ..) = transaction.executed_transaction().clone().into_parts();
+    assert_eq!(tx_outputs.expiration_block_num, current_height + 3);
+}
+
 #[tokio::test]
 async fn test_import_processing_note_returns_error() {
     // generate test client with a random store name
@@ -950,6 +2097,147 @@ async fn test_added_notes() {
     assert!(notes.is_empty());
 }
 
+#[tokio::test]
+async fn test_account_history() {
+    let (mut client, _, authenticator) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let history = client.get_account_history(wallet.id()).await.unwrap();
+    let nonces: Vec<u64> = history.iter().map(|record| record.account().nonce().as_int()).collect();
+    assert_eq!(nonces, vec![0, 1]);
+
+    let initial_state = client.get_account_at_nonce(wallet.id(), 0).await.unwrap().unwrap();
+    assert_eq!(initial_state.account().commitment(), wallet.commitment());
+    assert!(client.get_account_at_nonce(wallet.id(), 5).await.unwrap().is_none());
+
+    let diff = client.get_account_state_diff(wallet.id(), 0, 1).await.unwrap();
+    assert_eq!(
+        diff.vault.added_assets,
+        vec![Asset::Fungible(FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap())]
+    );
+    assert!(diff.vault.removed_assets.is_empty());
+
+    // The consuming transaction determines the block from which the new state is valid
+    let commit_height = client
+        .get_transactions(TransactionFilter::All)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|tx| tx.details.account_id == wallet.id())
+        .map(|tx| match tx.status {
+            TransactionStatus::Committed(commit_height) => commit_height,
+            _ => panic!("transaction should be committed"),
+        })
+        .unwrap();
+
+    // The new account didn't exist on chain before its first transaction was committed
+    let state_before = client
+        .get_account_at_block(wallet.id(), (commit_height.as_u32() - 1).into())
+        .await
+        .unwrap();
+    assert!(state_before.is_none());
+    let state_after =
+        client.get_account_at_block(wallet.id(), commit_height).await.unwrap().unwrap();
+    assert_eq!(state_after.account().nonce().as_int(), 1);
+
+    let unknown_account = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2).unwrap();
+    assert!(matches!(
+        client.get_account_history(unknown_account).await,
+        Err(ClientError::AccountDataNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_prune_store() {
+    let (mut client, _, authenticator) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    assert!(!client.get_input_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+
+    // The default policy doesn't remove any account state or note
+    let summary = client.prune_store(&RetentionPolicy::default()).await.unwrap();
+    assert!(summary.is_empty());
+    assert_eq!(client.get_account_history(wallet.id()).await.unwrap().len(), 2);
+
+    let policy = RetentionPolicy {
+        account_states_to_keep: Some(1),
+        consumed_notes_max_age: Some(0),
+        ..Default::default()
+    };
+    let summary = client.prune_store(&policy).await.unwrap();
+    assert!(summary.removed_account_states >= 1);
+    assert!(summary.removed_input_notes >= 1);
+
+    let history = client.get_account_history(wallet.id()).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].account().nonce().as_int(), 1);
+    assert!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+    assert!(client.get_output_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+
+    // The client keeps working with the pruned store
+    let account = client.get_account(wallet.id()).await.unwrap().unwrap();
+    assert_eq!(account.account().commitment(), history[0].account().commitment());
+    client.sync_state().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_backup_and_restore_store() {
+    let (mut client, _, authenticator) = create_test_client().await;
+    wait_for_node(&mut client).await;
+
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    let backup = client.backup_store().await.unwrap();
+    let backup = StoreBackup::read_from_bytes(&backup.to_bytes()).unwrap();
+
+    let (restored_client, ..) = create_test_client().await;
+    restored_client.restore_store(backup).await.unwrap();
+
+    assert_eq!(
+        restored_client.get_sync_height().await.unwrap(),
+        client.get_sync_height().await.unwrap()
+    );
+    assert_eq!(
+        restored_client.get_account_history(wallet.id()).await.unwrap().len(),
+        client.get_account_history(wallet.id()).await.unwrap().len()
+    );
+    assert_eq!(
+        restored_client
+            .get_account(faucet.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .account()
+            .commitment(),
+        client.get_account(faucet.id()).await.unwrap().unwrap().account().commitment()
+    );
+    assert_eq!(
+        restored_client.get_input_notes(NoteFilter::All).await.unwrap(),
+        client.get_input_notes(NoteFilter::All).await.unwrap()
+    );
+    assert_eq!(
+        restored_client.get_output_notes(NoteFilter::All).await.unwrap(),
+        client.get_output_notes(NoteFilter::All).await.unwrap()
+    );
+    assert_eq!(
+        restored_client.get_transactions(TransactionFilter::All).await.unwrap().len(),
+        client.get_transactions(TransactionFilter::All).await.unwrap().len()
+    );
+    assert_eq!(
+        restored_client.get_note_tags().await.unwrap(),
+        client.get_note_tags().await.unwrap()
+    );
+}
+
 #[tokio::test]
 async fn test_p2id_transfer() {
     let (mut client, _, authenticator) = create_test_client().await;
@@ -1053,6 +2341,30 @@ async fn test_p2id_transfer() {
     assert_note_cannot_be_consumed_twice(&mut client, to_account_id, notes[0].id()).await;
 }
 
+#[tokio::test]
+async fn test_memory_store_client() {
+    const TIMESTAMP: u64 = 1_700_000_000;
+
+    let (builder, _, authenticator) = create_test_client_builder().await;
+    let store = MemoryStore::new().with_clock(|| TIMESTAMP);
+    let mut client = builder.with_store(Arc::new(store)).build().await.unwrap();
+    wait_for_node(&mut client).await;
+
+    assert_eq!(client.test_store().get_current_timestamp(), Some(TIMESTAMP));
+
+    let (wallet, _, faucet) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await;
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    assert_account_has_single_asset(&client, wallet.id(), faucet.id(), MINT_AMOUNT).await;
+
+    // Tenant handles share the store's clock
+    let tenant = client.test_store().with_tenant(TenantId::new("a")).await.unwrap();
+    assert_eq!(tenant.get_current_timestamp(), Some(TIMESTAMP));
+    assert_eq!(MemoryStore::new().get_current_timestamp(), None);
+}
+
 #[tokio::test]
 async fn test_p2id_transfer_failing_not_enough_balance() {
     let (mut client, _, authenticator) = create_test_client().await;
@@ -1576,7 +2888,8 @@ async fn test_subsequent_discarded_transactions() {
 
     // Sync the state, which should discard the first transaction
     rpc_api.advance_blocks(3);
-    client.sync_state().await.unwrap();
+    let sync_summary = client.sync_state().await.unwrap();
+    assert_eq!(sync_summary.expired_transactions, vec![first_tx_id]);
 
     let account_after_sync = client.get_account(account_id).await.unwrap().unwrap();
 
@@ -1614,3 +2927,251 @@ async fn test_subsequent_discarded_transactions() {
         account_before_tx.account().commitment(),
     );
 }
+
+#[tokio::test]
+async fn test_encrypted_keystore_lock_unlock() {
+    let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone())
+        .unwrap()
+        .with_kdf_params(TEST_KDF_PARAMS);
+    assert!(keystore.is_locked());
+
+    let secret_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+    let pub_key = match &secret_key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    };
+    assert!(matches!(keystore.add_key(&secret_key).await, Err(KeyStoreError::Locked)));
+
+    // The passphrase is checked even before any key is stored
+    keystore.unlock("passphrase").unwrap();
+    keystore.lock();
+    assert!(matches!(
+        keystore.unlock("wrong passphrase"),
+        Err(KeyStoreError::EncryptionError(_))
+    ));
+
+    keystore.unlock("passphrase").unwrap();
+    keystore.add_key(&secret_key).await.unwrap();
+    assert_eq!(keystore.get_key(pub_key).unwrap(), Some(secret_key.clone()));
+
+    // The key file shouldn't contain the plaintext key
+    let key_file = std::fs::read_dir(&keys_directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| !path.file_name().unwrap().to_string_lossy().starts_with('.'))
+        .unwrap();
+    let file_contents = std::fs::read(key_file).unwrap();
+    assert!(
+        !file_contents
+            .windows(32)
+            .any(|window| secret_key.to_bytes().starts_with(window))
+    );
+
+    keystore.lock();
+    assert!(keystore.is_locked());
+    assert!(matches!(keystore.get_key(pub_key), Err(KeyStoreError::Locked)));
+    assert!(
+        keystore
+            .get_signature(pub_key, Word::default(), &AccountDelta::default())
+            .is_err()
+    );
+
+    // A different keystore instance can only decrypt the key with the right passphrase
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone()).unwrap();
+    assert!(matches!(
+        keystore.unlock("wrong passphrase"),
+        Err(KeyStoreError::EncryptionError(_))
+    ));
+    assert!(keystore.is_locked());
+
+    keystore.unlock("passphrase").unwrap();
+    assert_eq!(keystore.get_key(pub_key).unwrap(), Some(secret_key));
+    assert!(
+        keystore
+            .get_signature(pub_key, Word::default(), &AccountDelta::default())
+            .is_ok()
+    );
+
+    // Key derivation parameters read from a tampered header are rejected before deriving a key
+    let mut tampered_file = b"MKEY\x01".to_vec();
+    tampered_file.extend_from_slice(&u32::MAX.to_le_bytes());
+    tampered_file.extend_from_slice(&1u32.to_le_bytes());
+    tampered_file.extend_from_slice(&1u32.to_le_bytes());
+    tampered_file.extend_from_slice(&[0; 44]);
+    std::fs::write(keys_directory.join("1"), tampered_file).unwrap();
+
+    keystore.lock();
+    let err = keystore.unlock("passphrase").unwrap_err();
+    assert!(err.to_string().contains("exceed the supported limits"));
+}
+
+#[tokio::test]
+async fn test_encrypted_keystore_migrates_plaintext_keys() {
+    let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+    let plaintext_keystore = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+
+    let secret_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+    let pub_key = match &secret_key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    };
+    plaintext_keystore.add_key(&secret_key).await.unwrap();
+
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone())
+        .unwrap()
+        .with_kdf_params(TEST_KDF_PARAMS);
+    assert!(matches!(keystore.migrate_plaintext_keys(), Err(KeyStoreError::Locked)));
+
+    keystore.unlock("passphrase").unwrap();
+    assert_eq!(keystore.get_key(pub_key).unwrap(), None);
+    assert_eq!(keystore.migrate_plaintext_keys().unwrap(), 1);
+    assert_eq!(keystore.get_key(pub_key).unwrap(), Some(secret_key.clone()));
+
+    // Migrated files are no longer readable as plaintext, and migrating again is a no-op
+    assert!(plaintext_keystore.get_key(pub_key).is_err());
+    assert_eq!(keystore.migrate_plaintext_keys().unwrap(), 0);
+
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory).unwrap();
+    keystore.unlock("passphrase").unwrap();
+    assert_eq!(keystore.get_key(pub_key).unwrap(), Some(secret_key));
+}
+
+#[tokio::test]
+async fn test_filesystem_keystore_key_management() {
+    let keystore = FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+    assert!(keystore.get_pub_keys().await.unwrap().is_empty());
+
+    let secret_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+    let pub_key = match &secret_key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    };
+    keystore.add_key(&secret_key).await.unwrap();
+
+    assert!(keystore.contains_key(pub_key).unwrap());
+    assert_eq!(keystore.get_pub_keys().await.unwrap(), vec![pub_key]);
+
+    keystore.remove_key(pub_key).await.unwrap();
+    assert!(!keystore.contains_key(pub_key).unwrap());
+    assert!(keystore.get_pub_keys().await.unwrap().is_empty());
+
+    // Removing a key that isn't stored is a no-op
+    keystore.remove_key(pub_key).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_filesystem_keystore_skips_non_key_files() {
+    let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+    let keystore = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+
+    let secret_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+    let pub_key = match &secret_key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    };
+    keystore.add_key(&secret_key).await.unwrap();
+
+    // Unrelated and temporary files are ignored, and so are keys encrypted by another keystore
+    std::fs::write(keys_directory.join("README"), "not a key").unwrap();
+    std::fs::write(keys_directory.join(".12345.tmp"), "not a key").unwrap();
+    let encrypted_keystore = EncryptedFilesystemKeyStore::new(keys_directory)
+        .unwrap()
+        .with_kdf_params(TEST_KDF_PARAMS);
+    encrypted_keystore.unlock("passphrase").unwrap();
+    encrypted_keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(SecretKey::new()))
+        .await
+        .unwrap();
+
+    assert_eq!(keystore.get_pub_keys().await.unwrap(), vec![pub_key]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_remote_authenticator() {
+    use std::os::unix::net::UnixListener;
+
+    use miden_tx::AuthenticationError;
+
+    use crate::keystore::{RemoteAuthenticator, remote_authenticator::serve_signer};
+
+    let signer_directory = temp_dir().join(Uuid::new_v4().to_string());
+    let keystore = FilesystemKeyStore::new(signer_directory.join("keys")).unwrap();
+
+    let secret_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+    let pub_key = match &secret_key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    };
+    keystore.add_key(&secret_key).await.unwrap();
+
+    let socket_path = signer_directory.join("signer.sock");
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    std::thread::spawn(move || serve_signer(&listener, &keystore));
+
+    let authenticator = RemoteAuthenticator::new(socket_path);
+    let signature = authenticator
+        .get_signature(pub_key, Word::default(), &AccountDelta::default())
+        .unwrap();
+    assert!(!signature.is_empty());
+
+    let unknown_pub_key = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    assert!(matches!(
+        authenticator.get_signature(unknown_pub_key, Word::default(), &AccountDelta::default()),
+        Err(AuthenticationError::UnknownPublicKey(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_key_rotation() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+
+    // Accounts using the standard auth component can't rotate their key
+    let (standard_account, _) =
+        insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let new_key = SecretKey::with_rng(&mut client.rng);
+    assert!(matches!(
+        client
+            .build_key_rotation_request(standard_account.id(), new_key.public_key())
+            .await,
+        Err(ClientError::AccountKeyNotRotatable(_))
+    ));
+
+    let old_key = SecretKey::with_rng(&mut client.rng);
+    let old_pub_key = Word::from(old_key.public_key());
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(old_key.clone())).await.unwrap();
+
+    let mut init_seed = [0u8; 32];
+    client.rng.fill_bytes(&mut init_seed);
+    let anchor_block = client.get_latest_epoch_block().await.unwrap();
+
+    let (account, seed) = AccountBuilder::new(init_seed)
+        .anchor((&anchor_block).try_into().unwrap())
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Private)
+        .with_component(RpoFalcon512WithRotation::new(old_key.public_key()))
+        .with_component(BasicWallet)
+        .build()
+        .unwrap();
+    client.add_account(&account, Some(seed), false).await.unwrap();
+
+    assert_eq!(
+        client.get_accounts_by_public_key().await.unwrap().get(&old_pub_key),
+        Some(&vec![account.id()])
+    );
+
+    let new_pub_key = Word::from(new_key.public_key());
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(new_key.clone())).await.unwrap();
+
+    let tx_request = client
+        .build_key_rotation_request(account.id(), new_key.public_key())
+        .await
+        .unwrap();
+    let tx_result = client.new_transaction(account.id(), tx_request).await.unwrap();
+    client.testing_apply_transaction(tx_result).await.unwrap();
+
+    let account_record = client.try_get_account(account.id()).await.unwrap();
+    assert_eq!(get_account_public_key(account_record.account()), Some(new_pub_key));
+
+    let accounts_by_key = client.get_accounts_by_public_key().await.unwrap();
+    assert!(!accounts_by_key.contains_key(&old_pub_key));
+    assert_eq!(accounts_by_key.get(&new_pub_key), Some(&vec![account.id()]));
+}
@@ -0,0 +1,193 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use futures::future::join_all;
+use miden_objects::{account::AccountId, transaction::TransactionId};
+use miden_tx::TransactionProver;
+use tracing::info;
+
+use super::{QueuedTransactionState, TransactionRequest, TransactionResult};
+use crate::{Client, ClientError, account::Account, store::data_store::PendingAccountsGuard};
+
+/// Transaction batch methods.
+impl Client {
+    /// Executes the requests against the account in order, and returns their results in the same
+    /// order. Each request is executed against the account state left by the previous ones, so it
+    /// can spend the assets they add and consume the notes they create as unauthenticated input
+    /// notes.
+    ///
+    /// Unlike [`Client::new_transaction`], unauthenticated input notes aren't stored, and the
+    /// account state isn't updated until the results are submitted with
+    /// [`Client::submit_transaction_batch`]. If any request fails, the client's state is left as it
+    /// was and no results are returned. Chain data needed to execute the requests may still be
+    /// fetched and cached, as described in [`Client::new_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::TransactionBatchError`] with the position of the first request
+    /// that couldn't be executed, and the error returned for it.
+    pub async fn new_transaction_batch(
+        &mut self,
+        account_id: AccountId,
+        transaction_requests: Vec<TransactionRequest>,
+    ) -> Result<Vec<TransactionResult>, ClientError> {
+        // Later transactions must be executed against the stored account state again, so the
+        // pending states are cleared when the guard is dropped, even if this future is dropped
+        let pending_accounts = self.data_store.pending_accounts_guard();
+
+        self.execute_transaction_batch(&pending_accounts, account_id, transaction_requests)
+            .await
+    }
+
+    /// Proves the results of [`Client::new_transaction_batch`] using a local prover, submits them
+    /// to the network in order, and saves them into the local database for tracking.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::submit_transaction_batch_with_prover`].
+    pub async fn submit_transaction_batch(
+        &mut self,
+        tx_results: Vec<TransactionResult>,
+    ) -> Result<(), ClientError> {
+        self.submit_transaction_batch_with_prover(tx_results, self.tx_prover.clone())
+            .await
+    }
+
+    /// Proves the results of [`Client::new_transaction_batch`] using the provided prover, submits
+    /// them to the network in order, and saves them into the local database for tracking.
+    ///
+    /// The transactions are added to the outbound transaction queue, and proven concurrently, so a
+    /// remote prover can prove them in parallel. None of them are submitted unless all of them are
+    /// proven. Each transaction is saved into the local database right after the network accepts
+    /// it, so if a later one is rejected, the local state only reflects the submitted ones. The
+    /// transactions that weren't submitted stay queued, so they can be retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::TransactionBatchError`] with the position of the first transaction
+    /// that couldn't be proven, submitted or saved, the transactions that were submitted before
+    /// it, and the error returned for it.
+    pub async fn submit_transaction_batch_with_prover(
+        &mut self,
+        tx_results: Vec<TransactionResult>,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<(), ClientError> {
+        let mut queued_transactions = Vec::with_capacity(tx_results.len());
+        for tx_result in tx_results {
+            let mut queued_transaction = self.enqueue_transaction(tx_result).await?;
+            self.update_queued_transaction(
+                &mut queued_transaction,
+                QueuedTransactionState::Proving,
+            )
+            .await?;
+            queued_transactions.push(queued_transaction);
+        }
+
+        info!("Proving {} transactions...", queued_transactions.len());
+
+        let proofs = join_all(queued_transactions.iter().map(|queued_transaction| {
+            tx_prover.prove(queued_transaction.tx_result().executed_transaction().clone().into())
+        }))
+        .await;
+
+        // Every proof is recorded in the queue before any failure is returned
+        let mut first_failure = None;
+        for (index, (queued_transaction, proof)) in
+            queued_transactions.iter_mut().zip(proofs).enumerate()
+        {
+            match proof {
+                Ok(proven_transaction) => {
+                    self.update_queued_transaction(
+                        queued_transaction,
+                        QueuedTransactionState::Proven(proven_transaction),
+                    )
+                    .await?;
+                },
+                Err(err) => {
+                    let err = ClientError::from(err);
+                    self.update_queued_transaction(
+                        queued_transaction,
+                        QueuedTransactionState::Executed,
+                    )
+                    .await?;
+                    self.record_queued_transaction_failure(queued_transaction, &err).await?;
+                    first_failure.get_or_insert((index, err));
+                },
+            }
+        }
+
+        if let Some((index, err)) = first_failure {
+            return Err(batch_error(index, Vec::new(), err));
+        }
+
+        info!("Transactions proven.");
+
+        let mut submitted: Vec<TransactionId> = Vec::with_capacity(queued_transactions.len());
+        for (index, queued_transaction) in queued_transactions.into_iter().enumerate() {
+            let transaction_id = queued_transaction.id();
+            if let Err(err) =
+                self.process_queued_transaction(queued_transaction, tx_prover.clone()).await
+            {
+                return Err(batch_error(index, submitted, err));
+            }
+            submitted.push(transaction_id);
+        }
+
+        Ok(())
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the requests of a batch in order, keeping the account state left by each one as
+    /// the pending state of the account.
+    async fn execute_transaction_batch(
+        &mut self,
+        pending_accounts: &PendingAccountsGuard,
+        account_id: AccountId,
+        transaction_requests: Vec<TransactionRequest>,
+    ) -> Result<Vec<TransactionResult>, ClientError> {
+        let mut account: Account = self.try_get_account(account_id).await?.into();
+
+        let mut tx_results = Vec::with_capacity(transaction_requests.len());
+        for (index, transaction_request) in transaction_requests.into_iter().enumerate() {
+            let tx_result = self
+                .execute_batched_request(account_id, transaction_request)
+                .await
+                .map_err(|err| batch_error(index, Vec::new(), err))?;
+
+            account
+                .apply_delta(tx_result.account_delta())
+                .map_err(|err| batch_error(index, Vec::new(), err.into()))?;
+            pending_accounts.set_pending_account(account.clone());
+
+            tx_results.push(tx_result);
+        }
+
+        Ok(tx_results)
+    }
+
+    /// Executes a request of a batch against the pending state of the account.
+    async fn execute_batched_request(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<TransactionResult, ClientError> {
+        self.validate_request(account_id, &transaction_request).await?;
+        self.check_authenticated_input_notes(&transaction_request).await?;
+
+        let notes = self.get_request_input_notes(&transaction_request).await?;
+
+        self.execute_request(account_id, notes, transaction_request).await
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+fn batch_error(index: usize, submitted: Vec<TransactionId>, source: ClientError) -> ClientError {
+    ClientError::TransactionBatchError {
+        index,
+        submitted,
+        source: Box::new(source),
+    }
+}
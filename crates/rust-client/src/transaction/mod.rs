@@ -97,11 +97,14 @@ use crate::{
     sync::NoteTagRecord,
 };
 
+mod batch;
+mod policy;
+mod queue;
 mod request;
+mod simulation;
 
 // RE-EXPORTS
 // ================================================================================================
-
 pub use miden_lib::{
     account::interface::{AccountComponentInterface, AccountInterface},
     transaction::TransactionKernel,
@@ -117,10 +120,18 @@ pub use miden_tx::{
     DataStoreError, LocalTransactionProver, ProvingOptions, TransactionExecutorError,
     TransactionProver, TransactionProverError, auth::TransactionAuthenticator,
 };
+pub use policy::{FeePolicy, TransactionPolicy};
+pub use queue::{
+    DEFAULT_MAX_TRANSACTION_ATTEMPTS, DEFAULT_MAX_TRANSACTION_RETRY_BACKOFF,
+    DEFAULT_TRANSACTION_RETRY_INTERVAL, QueuedTransaction, QueuedTransactionState,
+    TransactionRetryPolicy,
+};
 pub use request::{
-    ForeignAccount, NoteArgs, PaymentTransactionData, SwapTransactionData, TransactionRequest,
-    TransactionRequestBuilder, TransactionRequestError, TransactionScriptTemplate,
+    ForeignAccount, NoteArgs, PaymentTransactionData, SwapTransactionData, TransactionExpiration,
+    TransactionRequest, TransactionRequestBuilder, TransactionRequestError,
+    TransactionScriptTemplate,
 };
+pub use simulation::{SimulatedTransaction, TransactionSimulation};
 
 // TRANSACTION RESULT
 // ================================================================================================
@@ -296,6 +307,26 @@ impl TransactionRecord {
     }
 }
 
+impl Serializable for TransactionRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.id.write_into(target);
+        self.details.write_into(target);
+        self.script.write_into(target);
+        self.status.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let id = TransactionId::read_from(source)?;
+        let details = TransactionDetails::read_from(source)?;
+        let script = Option::<TransactionScript>::read_from(source)?;
+        let status = TransactionStatus::read_from(source)?;
+
+        Ok(Self { id, details, script, status })
+    }
+}
+
 /// Describes the details associated with a transaction.
 #[derive(Debug, Clone)]
 pub struct TransactionDetails {
@@ -432,6 +463,33 @@ impl fmt::Display for TransactionStatus {
     }
 }
 
+impl Serializable for TransactionStatus {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            TransactionStatus::Pending => target.write_u8(0),
+            TransactionStatus::Committed(block_num) => {
+                target.write_u8(1);
+                block_num.write_into(target);
+            },
+            TransactionStatus::Discarded(cause) => {
+                target.write_u8(2);
+                cause.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for TransactionStatus {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TransactionStatus::Pending),
+            1 => Ok(TransactionStatus::Committed(BlockNumber::read_from(source)?)),
+            2 => Ok(TransactionStatus::Discarded(DiscardCause::read_from(source)?)),
+            _ => Err(DeserializationError::InvalidValue("Invalid transaction status".to_string())),
+        }
+    }
+}
+
 // TRANSACTION STORE UPDATE
 // ================================================================================================
 
@@ -538,34 +596,7 @@ impl Client {
     ) -> Result<TransactionResult, ClientError> {
         // Validates the transaction request before executing
         self.validate_request(account_id, &transaction_request).await?;
-
-        // Ensure authenticated notes have their inclusion proofs (a.k.a they're in a committed
-        // state)
-        let authenticated_input_note_ids: Vec<NoteId> =
-            transaction_request.authenticated_input_note_ids().collect::<Vec<_>>();
-
-        let authenticated_note_records = self
-            .store
-            .get_input_notes(NoteFilter::List(authenticated_input_note_ids))
-            .await?;
-
-        for authenticated_note_record in authenticated_note_records {
-            if !authenticated_note_record.is_authenticated() {
-                return Err(ClientError::TransactionRequestError(
-                    TransactionRequestError::InputNoteNotAuthenticated(
-                        authenticated_note_record.id(),
-                    ),
-                ));
-            }
-
-            if authenticated_note_record.is_consumed() {
-                return Err(ClientError::TransactionRequestError(
-                    TransactionRequestError::InputNoteAlreadyConsumed(
-                        authenticated_note_record.id(),
-                    ),
-                ));
-            }
-        }
+        self.check_authenticated_input_notes(&transaction_request).await?;
 
         // If tx request contains unauthenticated_input_notes we should insert them
         let unauthenticated_input_notes = transaction_request
@@ -577,7 +608,7 @@ impl Client {
 
         self.store.upsert_input_notes(&unauthenticated_input_notes).await?;
 
-        let mut notes = {
+        let notes = {
             let note_ids = transaction_request.get_input_note_ids();
 
             let mut input_notes: Vec<InputNote> = Vec::new();
@@ -589,93 +620,13 @@ impl Client {
             InputNotes::new(input_notes).map_err(ClientError::TransactionInputError)?
         };
 
-        let output_notes: Vec<Note> =
-            transaction_request.expected_output_notes().cloned().collect();
-
-        let future_notes: Vec<(NoteDetails, NoteTag)> =
-            transaction_request.expected_future_notes().cloned().collect();
-
-        let tx_script = transaction_request.build_transaction_script(
-            &self.get_account_interface(account_id).await?,
-            self.in_debug_mode,
-        )?;
-
-        let foreign_accounts = transaction_request.foreign_accounts().clone();
-
-        // Inject state and code of foreign accounts
-        let (fpi_block_num, foreign_account_inputs) =
-            self.retrieve_foreign_account_inputs(foreign_accounts).await?;
-
-        let ignore_invalid_notes = transaction_request.ignore_invalid_input_notes();
-
-        let tx_args = transaction_request.into_transaction_args(tx_script, foreign_account_inputs);
-
-        let block_num = if let Some(block_num) = fpi_block_num {
-            block_num
-        } else {
-            self.store.get_sync_height().await?
-        };
-
-        // TODO: Refactor this to get account code only?
-        let account_record = self
-            .store
-            .get_account(account_id)
-            .await?
-            .ok_or(ClientError::AccountDataNotFound(account_id))?;
-        let account: Account = account_record.into();
-        self.mast_store.load_transaction_code(account.code(), &notes, &tx_args);
-
-        if ignore_invalid_notes {
-            // Remove invalid notes
-            notes = self.get_valid_input_notes(account_id, notes, tx_args.clone()).await?;
-        }
-
-        // Execute the transaction and get the witness
-        let executed_transaction = self
-            .tx_executor
-            .execute_transaction(
-                account_id,
-                block_num,
-                notes,
-                tx_args,
-                Arc::new(DefaultSourceManager::default()), // TODO: Use the correct source manager
-            )
-            .await?;
-
-        // Check that the expected output notes matches the transaction outcome.
-        // We compare authentication commitments where possible since that involves note IDs +
-        // metadata (as opposed to just note ID which remains the same regardless of
-        // metadata) We also do the check for partial output notes
-
-        let tx_note_auth_commitments: BTreeSet<Digest> =
-            notes_from_output(executed_transaction.output_notes())
-                .map(Note::commitment)
-                .collect();
-
-        let missing_note_ids: Vec<NoteId> = output_notes
-            .iter()
-            .filter_map(|n| (!tx_note_auth_commitments.contains(&n.commitment())).then_some(n.id()))
-            .collect();
-
-        if !missing_note_ids.is_empty() {
-            return Err(ClientError::MissingOutputNotes(missing_note_ids));
-        }
-
-        let screener =
-            NoteScreener::new(self.store.clone(), &self.tx_executor, self.mast_store.clone());
-
-        TransactionResult::new(
-            executed_transaction,
-            screener,
-            future_notes,
-            self.get_sync_height().await?,
-            self.store.get_current_timestamp(),
-        )
-        .await
+        self.execute_request(account_id, notes, transaction_request).await
     }
 
     /// Proves the specified transaction using a local prover, submits it to the network, and saves
     /// the transaction into the local database for tracking.
+    ///
+    /// See [`Client::submit_transaction_with_prover`] for how failures are handled.
     pub async fn submit_transaction(
         &mut self,
         tx_result: TransactionResult,
@@ -685,19 +636,40 @@ impl Client {
 
     /// Proves the specified transaction using the provided prover, submits it to the network, and
     /// saves the transaction into the local database for tracking.
+    ///
+    /// The transaction is added to the outbound transaction queue before being proven, and the
+    /// queue is updated as it's proven and submitted. If proving or submitting fails, the error is
+    /// returned and the transaction stays queued, so it can be retried with
+    /// [`Client::retry_queued_transaction`] or [`Client::retry_queued_transactions`], even after
+    /// a restart.
     pub async fn submit_transaction_with_prover(
         &mut self,
         tx_result: TransactionResult,
         tx_prover: Arc<dyn TransactionProver>,
     ) -> Result<(), ClientError> {
-        let proven_transaction = self.prove_transaction(&tx_result, tx_prover).await?;
-        let block_num = self.submit_proven_transaction(proven_transaction).await?;
-        self.apply_transaction(block_num, tx_result).await
+        let queued_transaction =
+            QueuedTransaction::new(tx_result, self.store.get_current_timestamp());
+        self.store.upsert_queued_transaction(&queued_transaction).await?;
+        self.process_queued_transaction(queued_transaction, tx_prover).await
     }
 
-    /// Proves the specified transaction result using the provided prover.
-    async fn prove_transaction(
-        &mut self,
+    /// Proves the specified transaction using a local prover, without submitting it or changing
+    /// the local database.
+    ///
+    /// This allows executing a transaction on one machine and proving it on another one. The
+    /// resulting [`ProvenTransaction`] can be submitted with [`Client::submit_proven_transaction`]
+    /// on the client that executed the transaction.
+    pub async fn prove_transaction(
+        &self,
+        tx_result: &TransactionResult,
+    ) -> Result<ProvenTransaction, ClientError> {
+        self.prove_transaction_with_prover(tx_result, self.tx_prover.clone()).await
+    }
+
+    /// Proves the specified transaction using the provided prover, without submitting it or
+    /// changing the local database.
+    pub async fn prove_transaction_with_prover(
+        &self,
         tx_result: &TransactionResult,
         tx_prover: Arc<dyn TransactionProver>,
     ) -> Result<ProvenTransaction, ClientError> {
@@ -711,7 +683,7 @@ impl Client {
         Ok(proven_transaction)
     }
 
-    async fn submit_proven_transaction(
+    async fn send_proven_transaction(
         &mut self,
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, ClientError> {
@@ -781,7 +753,14 @@ impl Client {
             .collect::<Vec<_>>();
 
         let consumed_note_ids = tx_result.consumed_notes().iter().map(InputNote::id).collect();
-        let consumed_notes = self.get_input_notes(NoteFilter::List(consumed_note_ids)).await?;
+        let mut consumed_notes = self.get_input_notes(NoteFilter::List(consumed_note_ids)).await?;
+
+        // Unauthenticated notes consumed by batched transactions aren't stored before execution
+        for input_note in tx_result.consumed_notes().iter() {
+            if consumed_notes.iter().all(|record| record.id() != input_note.id()) {
+                consumed_notes.push(input_note.clone().into());
+            }
+        }
 
         let mut updated_input_notes = vec![];
         for mut input_note_record in consumed_notes {
@@ -978,7 +957,11 @@ impl Client {
             }
         }
 
-        let account: Account = self.try_get_account(account_id).await?.into();
+        // Requests in a batch are validated against the state left by the previous ones
+        let account: Account = match self.data_store.pending_account(account_id) {
+            Some(account) => account,
+            None => self.try_get_account(account_id).await?.into(),
+        };
 
         if account.is_faucet() {
             // TODO(SantiagoPittella): Add faucet validations.
@@ -988,6 +971,174 @@ impl Client {
         }
     }
 
+    /// Checks that the authenticated input notes of the request have their inclusion proofs and
+    /// haven't been consumed yet.
+    async fn check_authenticated_input_notes(
+        &self,
+        transaction_request: &TransactionRequest,
+    ) -> Result<(), ClientError> {
+        let authenticated_input_note_ids: Vec<NoteId> =
+            transaction_request.authenticated_input_note_ids().collect::<Vec<_>>();
+
+        let authenticated_note_records = self
+            .store
+            .get_input_notes(NoteFilter::List(authenticated_input_note_ids))
+            .await?;
+
+        for authenticated_note_record in authenticated_note_records {
+            if !authenticated_note_record.is_authenticated() {
+                return Err(ClientError::TransactionRequestError(
+                    TransactionRequestError::InputNoteNotAuthenticated(
+                        authenticated_note_record.id(),
+                    ),
+                ));
+            }
+
+            if authenticated_note_record.is_consumed() {
+                return Err(ClientError::TransactionRequestError(
+                    TransactionRequestError::InputNoteAlreadyConsumed(
+                        authenticated_note_record.id(),
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes the request against the account with the provided input notes, and screens the
+    /// resulting notes for relevance.
+    async fn execute_request(
+        &mut self,
+        account_id: AccountId,
+        notes: InputNotes<InputNote>,
+        transaction_request: TransactionRequest,
+    ) -> Result<TransactionResult, ClientError> {
+        let future_notes: Vec<(NoteDetails, NoteTag)> =
+            transaction_request.expected_future_notes().cloned().collect();
+
+        let (block_num, notes, tx_args, output_notes) =
+            self.prepare_transaction(account_id, notes, transaction_request).await?;
+
+        // Execute the transaction and get the witness
+        let executed_transaction = self
+            .tx_executor
+            .execute_transaction(
+                account_id,
+                block_num,
+                notes,
+                tx_args,
+                Arc::new(DefaultSourceManager::default()), // TODO: Use the correct source manager
+            )
+            .await?;
+
+        check_expected_output_notes(&executed_transaction, &output_notes)?;
+
+        let screener =
+            NoteScreener::new(self.store.clone(), &self.tx_executor, self.mast_store.clone());
+
+        TransactionResult::new(
+            executed_transaction,
+            screener,
+            future_notes,
+            self.get_sync_height().await?,
+            self.store.get_current_timestamp(),
+        )
+        .await
+    }
+
+    /// Returns the input notes of the request without storing the unauthenticated ones, which are
+    /// consumed as provided by the request. Authenticated notes are read from the store.
+    async fn get_request_input_notes(
+        &self,
+        transaction_request: &TransactionRequest,
+    ) -> Result<InputNotes<InputNote>, ClientError> {
+        let authenticated_note_ids: Vec<NoteId> =
+            transaction_request.authenticated_input_note_ids().collect();
+
+        let mut input_notes: Vec<InputNote> = Vec::new();
+        for note in self.store.get_input_notes(NoteFilter::List(authenticated_note_ids)).await? {
+            input_notes.push(note.try_into().map_err(ClientError::NoteRecordConversionError)?);
+        }
+        input_notes.extend(
+            transaction_request
+                .unauthenticated_input_notes()
+                .iter()
+                .cloned()
+                .map(InputNote::unauthenticated),
+        );
+
+        InputNotes::new(input_notes).map_err(ClientError::TransactionInputError)
+    }
+
+    /// Gathers everything needed to execute the request against the account with the provided
+    /// input notes: the reference block, the input notes to consume (without the invalid ones, if
+    /// the request ignores them), the transaction arguments and the output notes the request
+    /// expects.
+    ///
+    /// Foreign account data and missing block headers are fetched from the node, and the code of
+    /// the transaction is loaded into the MAST store.
+    async fn prepare_transaction(
+        &mut self,
+        account_id: AccountId,
+        mut notes: InputNotes<InputNote>,
+        transaction_request: TransactionRequest,
+    ) -> Result<(BlockNumber, InputNotes<InputNote>, TransactionArgs, Vec<Note>), ClientError> {
+        let output_notes: Vec<Note> =
+            transaction_request.expected_output_notes().cloned().collect();
+
+        let tx_script = transaction_request.build_transaction_script(
+            &self.get_account_interface(account_id).await?,
+            self.transaction_policy.expiration_delta(),
+            self.in_debug_mode,
+        )?;
+
+        let foreign_accounts = transaction_request.foreign_accounts().clone();
+
+        // Inject state and code of foreign accounts
+        let (fpi_block_num, foreign_account_inputs) =
+            self.retrieve_foreign_account_inputs(foreign_accounts).await?;
+
+        let ignore_invalid_notes = transaction_request.ignore_invalid_input_notes();
+
+        let tx_args = transaction_request.into_transaction_args(tx_script, foreign_account_inputs);
+
+        let block_num = if let Some(block_num) = fpi_block_num {
+            block_num
+        } else {
+            self.store.get_sync_height().await?
+        };
+
+        // TODO: Refactor this to get account code only?
+        let account_record = self
+            .store
+            .get_account(account_id)
+            .await?
+            .ok_or(ClientError::AccountDataNotFound(account_id))?;
+
+        // The headers of the blocks the input notes were committed in, and of the anchor block of
+        // new accounts, may have been pruned according to the block retention policy
+        let mut authenticated_blocks: BTreeSet<BlockNumber> = notes
+            .iter()
+            .filter_map(|note| note.proof().map(|proof| proof.location().block_num()))
+            .filter(|note_block_num| *note_block_num < block_num)
+            .collect();
+        if account_record.seed().is_some() {
+            authenticated_blocks.insert(BlockNumber::from_epoch(account_id.anchor_epoch()));
+        }
+        self.ensure_block_headers(&authenticated_blocks).await?;
+
+        let account: Account = account_record.into();
+        self.mast_store.load_transaction_code(account.code(), &notes, &tx_args);
+
+        if ignore_invalid_notes {
+            // Remove invalid notes
+            notes = self.get_valid_input_notes(account_id, notes, tx_args.clone()).await?;
+        }
+
+        Ok((block_num, notes, tx_args, output_notes))
+    }
+
     async fn get_valid_input_notes(
         &self,
         account_id: AccountId,
@@ -1169,14 +1320,14 @@ impl Client {
         &mut self,
         tx_result: &TransactionResult,
     ) -> Result<ProvenTransaction, ClientError> {
-        self.prove_transaction(tx_result, self.tx_prover.clone()).await
+        self.prove_transaction(tx_result).await
     }
 
     pub async fn testing_submit_proven_transaction(
         &mut self,
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, ClientError> {
-        self.submit_proven_transaction(proven_transaction).await
+        self.send_proven_transaction(proven_transaction).await
     }
 
     pub async fn testing_apply_transaction(
@@ -1190,6 +1341,32 @@ impl Client {
 // HELPERS
 // ================================================================================================
 
+/// Checks that the expected output notes are a subset of the notes created by the transaction.
+///
+/// Authentication commitments are compared where possible, since they involve note IDs and
+/// metadata (as opposed to just note IDs, which remain the same regardless of metadata). Partial
+/// output notes are checked as well.
+fn check_expected_output_notes(
+    executed_transaction: &ExecutedTransaction,
+    expected_output_notes: &[Note],
+) -> Result<(), ClientError> {
+    let tx_note_auth_commitments: BTreeSet<Digest> =
+        notes_from_output(executed_transaction.output_notes())
+            .map(Note::commitment)
+            .collect();
+
+    let missing_note_ids: Vec<NoteId> = expected_output_notes
+        .iter()
+        .filter_map(|n| (!tx_note_auth_commitments.contains(&n.commitment())).then_some(n.id()))
+        .collect();
+
+    if !missing_note_ids.is_empty() {
+        return Err(ClientError::MissingOutputNotes(missing_note_ids));
+    }
+
+    Ok(())
+}
+
 fn collect_assets<'a>(
     assets: impl Iterator<Item = &'a Asset>,
 ) -> (BTreeMap<AccountId, u64>, BTreeSet<NonFungibleAsset>) {
@@ -1250,6 +1427,7 @@ mod test {
 
     use super::PaymentTransactionData;
     use crate::{
+        keystore::KeyStore,
         tests::create_test_client,
         transaction::{TransactionRequestBuilder, TransactionResult},
     };
@@ -1268,7 +1446,7 @@ mod test {
 
         let secret_key = SecretKey::new();
         let pub_key = secret_key.public_key();
-        keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).unwrap();
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
 
         let wallet_component = AccountComponent::compile(
             BASIC_WALLET_CODE,
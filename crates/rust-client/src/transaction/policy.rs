@@ -0,0 +1,99 @@
+use miden_objects::{asset::FungibleAsset, transaction::ExecutedTransaction};
+
+use crate::Client;
+
+/// Transaction policy methods.
+impl Client {
+    /// Returns the policy applied to the transactions created by the client.
+    pub fn transaction_policy(&self) -> &TransactionPolicy {
+        &self.transaction_policy
+    }
+
+    /// Sets the policy applied to the transactions created by the client from then on.
+    pub fn set_transaction_policy(&mut self, policy: TransactionPolicy) {
+        self.transaction_policy = policy;
+    }
+}
+
+// TRANSACTION POLICY
+// ================================================================================================
+
+/// Defaults applied to the transactions created by the client, unless their
+/// [`TransactionRequest`](super::TransactionRequest) overrides them.
+///
+/// The expiration delta is the number of blocks, after the transaction's reference block, after
+/// which the transaction expires. It only applies to requests whose transaction script is built by
+/// the client to create output notes (see
+/// [`TransactionScriptTemplate::SendNotes`](super::TransactionScriptTemplate::SendNotes)), such as
+/// mint, send and swap requests. Pending transactions that expire are discarded on the next sync,
+/// and reported in [`SyncSummary::expired_transactions`](crate::sync::SyncSummary).
+///
+/// By default, transactions don't expire and no fee is paid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionPolicy {
+    /// Default number of blocks after which transactions expire. If `None`, transactions don't
+    /// expire unless their request sets an expiration delta.
+    expiration_delta: Option<u16>,
+    /// Policy deciding the fee paid by transactions.
+    fee_policy: FeePolicy,
+}
+
+impl TransactionPolicy {
+    /// Returns a policy under which transactions don't expire and no fee is paid.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default number of blocks, after their reference block, after which transactions
+    /// expire.
+    #[must_use]
+    pub fn with_expiration_delta(mut self, expiration_delta: u16) -> Self {
+        self.expiration_delta = Some(expiration_delta);
+        self
+    }
+
+    /// Sets the policy deciding the fee paid by transactions.
+    #[must_use]
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.fee_policy = fee_policy;
+        self
+    }
+
+    /// Returns the default number of blocks after which transactions expire, if any.
+    pub fn expiration_delta(&self) -> Option<u16> {
+        self.expiration_delta
+    }
+
+    /// Returns the policy deciding the fee paid by transactions.
+    pub fn fee_policy(&self) -> &FeePolicy {
+        &self.fee_policy
+    }
+}
+
+// FEE POLICY
+// ================================================================================================
+
+/// Decides the fee paid by the transactions created by the client.
+///
+/// The protocol doesn't charge transaction fees yet, so [`FeePolicy::NoFee`] is the only policy.
+/// Further policies will be added once it does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FeePolicy {
+    /// Transactions don't pay a fee.
+    #[default]
+    NoFee,
+}
+
+impl FeePolicy {
+    /// Returns the fee that the executed transaction pays under this policy, or `None` if it
+    /// doesn't pay one.
+    pub fn transaction_fee(
+        &self,
+        _executed_transaction: &ExecutedTransaction,
+    ) -> Option<FungibleAsset> {
+        match self {
+            FeePolicy::NoFee => None,
+        }
+    }
+}
@@ -0,0 +1,750 @@
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{fmt, time::Duration};
+
+use miden_objects::{account::AccountId, block::BlockNumber};
+use miden_tx::{
+    TransactionProver,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+use tracing::info;
+
+use super::{ProvenTransaction, TransactionId, TransactionResult, TransactionStatus};
+use crate::{Client, ClientError, store::TransactionFilter, sync::backoff_delay};
+
+/// Default maximum number of attempts to prove and submit a queued transaction.
+pub const DEFAULT_MAX_TRANSACTION_ATTEMPTS: u32 = 5;
+
+/// Default time to wait before retrying a queued transaction after its first failed attempt.
+pub const DEFAULT_TRANSACTION_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default upper bound for the time to wait before retrying a queued transaction.
+pub const DEFAULT_MAX_TRANSACTION_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Transaction queue methods.
+impl Client {
+    // QUEUE DATA RETRIEVAL
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the transactions in the outbound transaction queue. Transactions of the same
+    /// account are returned in the order they have to be submitted.
+    pub async fn get_queued_transactions(&self) -> Result<Vec<QueuedTransaction>, ClientError> {
+        let mut queued_transactions = self.store.get_queued_transactions().await?;
+        queued_transactions.sort_by_key(|queued_transaction| {
+            let initial_account =
+                queued_transaction.tx_result.executed_transaction().initial_account();
+            (initial_account.id(), initial_account.nonce().as_int())
+        });
+
+        Ok(queued_transactions)
+    }
+
+    /// Returns the queued transaction with the specified ID, if it's in the outbound transaction
+    /// queue.
+    pub async fn get_queued_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Option<QueuedTransaction>, ClientError> {
+        Ok(self
+            .store
+            .get_queued_transactions()
+            .await?
+            .into_iter()
+            .find(|queued_transaction| queued_transaction.id() == transaction_id))
+    }
+
+    // QUEUE MANAGEMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the policy deciding when failed queued transactions are retried.
+    pub fn transaction_retry_policy(&self) -> &TransactionRetryPolicy {
+        &self.transaction_retry_policy
+    }
+
+    /// Sets the policy deciding when failed queued transactions are retried by
+    /// [`Client::retry_queued_transactions`].
+    pub fn set_transaction_retry_policy(&mut self, policy: TransactionRetryPolicy) {
+        self.transaction_retry_policy = policy;
+    }
+
+    /// Proves and submits the queued transaction again, regardless of the retry policy, starting
+    /// from the last state it reached. Transactions are proven with the client's local prover,
+    /// including the ones awaiting an external proof.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::QueuedTransactionNotFound`] if the transaction isn't queued.
+    /// - Returns [`ClientError::QueuedTransactionAlreadySubmitted`] if the transaction was already
+    ///   submitted.
+    /// - Returns the error of the failed attempt otherwise, which is also recorded in the queue.
+    pub async fn retry_queued_transaction(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<(), ClientError> {
+        let queued_transaction = self
+            .get_queued_transaction(transaction_id)
+            .await?
+            .ok_or(ClientError::QueuedTransactionNotFound(transaction_id))?;
+
+        if queued_transaction.state().is_submitted() {
+            return Err(ClientError::QueuedTransactionAlreadySubmitted(transaction_id));
+        }
+
+        self.process_queued_transaction(queued_transaction, self.tx_prover.clone())
+            .await
+    }
+
+    /// Retries the queued transactions that are due according to the client's
+    /// [`TransactionRetryPolicy`], and returns the IDs of the ones that were submitted.
+    ///
+    /// Transactions of the same account are retried in order, and the remaining ones are skipped
+    /// as soon as one of them fails. Failed attempts are recorded in the queue instead of being
+    /// returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the queue can't be read or updated.
+    pub async fn retry_queued_transactions(&mut self) -> Result<Vec<TransactionId>, ClientError> {
+        let now = self.store.get_current_timestamp();
+        let mut submitted = Vec::new();
+        let mut blocked_accounts: Vec<AccountId> = Vec::new();
+
+        for queued_transaction in self.get_queued_transactions().await? {
+            let transaction_id = queued_transaction.id();
+            let account_id = queued_transaction.account_id();
+
+            if queued_transaction.state().is_submitted() || blocked_accounts.contains(&account_id) {
+                continue;
+            }
+
+            if !self.transaction_retry_policy.is_due(&queued_transaction, now) {
+                blocked_accounts.push(account_id);
+                continue;
+            }
+
+            match self
+                .process_queued_transaction(queued_transaction, self.tx_prover.clone())
+                .await
+            {
+                Ok(()) => submitted.push(transaction_id),
+                Err(ClientError::StoreError(err)) => return Err(ClientError::StoreError(err)),
+                Err(err) => {
+                    info!("Queued transaction {transaction_id} failed again: {err}");
+                    blocked_accounts.push(account_id);
+                },
+            }
+        }
+
+        Ok(submitted)
+    }
+
+    /// Removes the transaction from the outbound transaction queue. Transactions that weren't
+    /// submitted yet are dropped.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::QueuedTransactionNotFound`] if the transaction isn't queued.
+    /// - Returns [`ClientError::QueuedTransactionAlreadySubmitted`] if the transaction was
+    ///   submitted but isn't committed yet, since the network may still include it.
+    pub async fn cancel_queued_transaction(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<(), ClientError> {
+        let queued_transaction = self
+            .get_queued_transaction(transaction_id)
+            .await?
+            .ok_or(ClientError::QueuedTransactionNotFound(transaction_id))?;
+
+        if queued_transaction.state().is_submitted() {
+            return Err(ClientError::QueuedTransactionAlreadySubmitted(transaction_id));
+        }
+
+        self.store.remove_queued_transaction(transaction_id).await?;
+        Ok(())
+    }
+
+    // EXTERNAL PROVING
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the executed transaction to the outbound transaction queue without proving it, and
+    /// returns its entry.
+    ///
+    /// This allows the transaction to be proven elsewhere, for example on a machine without access
+    /// to the client's keys, and submitted later with [`Client::submit_proven_transaction`]. The
+    /// entry is left in the [`QueuedTransactionState::AwaitingExternalProof`] state, so
+    /// [`Client::retry_queued_transactions`] doesn't prove it locally, but it can still be proven
+    /// with the client's prover through [`Client::retry_queued_transaction`].
+    pub async fn enqueue_transaction(
+        &self,
+        tx_result: TransactionResult,
+    ) -> Result<QueuedTransaction, ClientError> {
+        let mut queued_transaction =
+            QueuedTransaction::new(tx_result, self.store.get_current_timestamp());
+        queued_transaction.state = QueuedTransactionState::AwaitingExternalProof;
+        self.store.upsert_queued_transaction(&queued_transaction).await?;
+
+        Ok(queued_transaction)
+    }
+
+    /// Submits a transaction proven elsewhere to the network.
+    ///
+    /// If the transaction is in the outbound transaction queue, the proof is recorded in the queue
+    /// and the transaction is saved into the local database for tracking once the network accepts
+    /// it, as [`Client::submit_transaction`] does. Otherwise, the transaction is only submitted,
+    /// and the local database isn't changed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::QueuedTransactionAlreadySubmitted`] if the queued transaction was
+    ///   already submitted.
+    /// - Returns the error of the failed submission otherwise, which is also recorded in the queue
+    ///   if the transaction is queued.
+    pub async fn submit_proven_transaction(
+        &mut self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<(), ClientError> {
+        let transaction_id = proven_transaction.id();
+        let Some(mut queued_transaction) = self.get_queued_transaction(transaction_id).await?
+        else {
+            self.send_proven_transaction(proven_transaction).await?;
+            return Ok(());
+        };
+
+        if queued_transaction.state().is_submitted() {
+            return Err(ClientError::QueuedTransactionAlreadySubmitted(transaction_id));
+        }
+
+        self.update_queued_transaction(
+            &mut queued_transaction,
+            QueuedTransactionState::Proven(proven_transaction),
+        )
+        .await?;
+        self.process_queued_transaction(queued_transaction, self.tx_prover.clone())
+            .await
+    }
+
+    /// Saves a queued transaction into the local database for tracking, as if it had been
+    /// submitted by this client, after it was proven and submitted elsewhere.
+    ///
+    /// As the block at which the transaction was submitted is unknown, the client's sync height
+    /// is used as its submission height.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::QueuedTransactionNotFound`] if the transaction isn't queued, as the
+    ///   proven transaction alone doesn't have enough data to update the local state.
+    /// - Returns [`ClientError::QueuedTransactionAlreadySubmitted`] if the transaction was already
+    ///   submitted.
+    pub async fn apply_proven_transaction(
+        &mut self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<(), ClientError> {
+        let transaction_id = proven_transaction.id();
+        let mut queued_transaction = self
+            .get_queued_transaction(transaction_id)
+            .await?
+            .ok_or(ClientError::QueuedTransactionNotFound(transaction_id))?;
+
+        if queued_transaction.state().is_submitted() {
+            return Err(ClientError::QueuedTransactionAlreadySubmitted(transaction_id));
+        }
+
+        let submission_height = self.get_sync_height().await?;
+        self.update_queued_transaction(
+            &mut queued_transaction,
+            QueuedTransactionState::Proven(proven_transaction),
+        )
+        .await?;
+        self.apply_transaction(submission_height, queued_transaction.tx_result.clone())
+            .await?;
+
+        self.update_queued_transaction(
+            &mut queued_transaction,
+            QueuedTransactionState::Submitted(submission_height),
+        )
+        .await
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Moves the queued transaction through the remaining states up to
+    /// [`QueuedTransactionState::Submitted`], persisting each state as it's reached. The
+    /// transaction is saved into the local database for tracking once the network accepts it.
+    ///
+    /// If proving or submitting fails, the failure is recorded in the queue before the error is
+    /// returned, and the transaction stays in the last state it reached.
+    pub(crate) async fn process_queued_transaction(
+        &mut self,
+        mut queued_transaction: QueuedTransaction,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<(), ClientError> {
+        let unproven_state = match queued_transaction.state() {
+            QueuedTransactionState::Executed | QueuedTransactionState::Proving => {
+                Some(QueuedTransactionState::Executed)
+            },
+            QueuedTransactionState::AwaitingExternalProof => {
+                Some(QueuedTransactionState::AwaitingExternalProof)
+            },
+            QueuedTransactionState::Proven(_) | QueuedTransactionState::Submitted(_) => None,
+        };
+
+        if let Some(unproven_state) = unproven_state {
+            self.update_queued_transaction(
+                &mut queued_transaction,
+                QueuedTransactionState::Proving,
+            )
+            .await?;
+
+            match self
+                .prove_transaction_with_prover(&queued_transaction.tx_result, tx_prover)
+                .await
+            {
+                Ok(proven_transaction) => {
+                    self.update_queued_transaction(
+                        &mut queued_transaction,
+                        QueuedTransactionState::Proven(proven_transaction),
+                    )
+                    .await?;
+                },
+                Err(err) => {
+                    queued_transaction.state = unproven_state;
+                    self.record_queued_transaction_failure(&mut queued_transaction, &err).await?;
+                    return Err(err);
+                },
+            }
+        }
+
+        let QueuedTransactionState::Proven(proven_transaction) = queued_transaction.state().clone()
+        else {
+            return Ok(());
+        };
+
+        // The transaction may have been submitted and saved right before the client stopped
+        let transaction_id = queued_transaction.id();
+        let tracked_transaction = self
+            .store
+            .get_transactions(TransactionFilter::Ids(vec![transaction_id]))
+            .await?
+            .pop();
+
+        let block_num = if let Some(tracked_transaction) = tracked_transaction {
+            tracked_transaction.details.submission_height
+        } else {
+            let block_num = match self.send_proven_transaction(proven_transaction).await {
+                Ok(block_num) => block_num,
+                Err(err) => {
+                    // A previous attempt may have been accepted without the client recording it,
+                    // in which case the node rejects the transaction as a duplicate
+                    if let Some(block_num) =
+                        self.find_known_transaction(&queued_transaction).await?
+                    {
+                        block_num
+                    } else {
+                        self.record_queued_transaction_failure(&mut queued_transaction, &err)
+                            .await?;
+                        return Err(err);
+                    }
+                },
+            };
+
+            self.apply_transaction(block_num, queued_transaction.tx_result.clone()).await?;
+            block_num
+        };
+
+        self.update_queued_transaction(
+            &mut queued_transaction,
+            QueuedTransactionState::Submitted(block_num),
+        )
+        .await
+    }
+
+    /// Moves the queued transaction to the new state and persists it.
+    pub(crate) async fn update_queued_transaction(
+        &self,
+        queued_transaction: &mut QueuedTransaction,
+        state: QueuedTransactionState,
+    ) -> Result<(), ClientError> {
+        queued_transaction.state = state;
+        queued_transaction.updated_at = self.store.get_current_timestamp();
+        self.store.upsert_queued_transaction(queued_transaction).await?;
+
+        Ok(())
+    }
+
+    /// Returns the block in which the queued transaction was included, if a previous attempt to
+    /// submit it was accepted by the node without the client recording it.
+    ///
+    /// The account's transactions are synced from the block the transaction was executed against
+    /// up to its expiration block, as it can't be included after it. If it isn't found that way,
+    /// it's considered included in the block that last updated the account if the account's
+    /// commitment on chain is the one the transaction leads to.
+    async fn find_known_transaction(
+        &self,
+        queued_transaction: &QueuedTransaction,
+    ) -> Result<Option<BlockNumber>, ClientError> {
+        let executed_transaction = queued_transaction.tx_result.executed_transaction();
+        let transaction_id = executed_transaction.id();
+        let account_id = executed_transaction.account_id();
+        let expiration_block_num = executed_transaction.expiration_block_num();
+
+        let mut block_num = executed_transaction.block_header().block_num();
+        while block_num < expiration_block_num {
+            let Ok(response) = self.rpc_api.sync_state(block_num, &[account_id], &[]).await else {
+                break;
+            };
+
+            if let Some(transaction) = response
+                .transactions
+                .iter()
+                .find(|transaction| transaction.transaction_id == transaction_id)
+            {
+                return Ok(Some(transaction.block_num.into()));
+            }
+
+            let response_block_num = response.block_header.block_num();
+            if response_block_num >= response.chain_tip || response_block_num <= block_num {
+                break;
+            }
+            block_num = response_block_num;
+        }
+
+        let Ok(fetched_account) = self.rpc_api.get_account_details(account_id).await else {
+            return Ok(None);
+        };
+        if fetched_account.commitment() == executed_transaction.final_account().commitment() {
+            return Ok(Some(fetched_account.last_block_num()));
+        }
+
+        Ok(None)
+    }
+
+    /// Records a failed attempt to prove or submit the queued transaction, and persists it.
+    pub(crate) async fn record_queued_transaction_failure(
+        &self,
+        queued_transaction: &mut QueuedTransaction,
+        err: &ClientError,
+    ) -> Result<(), ClientError> {
+        queued_transaction.attempts = queued_transaction.attempts.saturating_add(1);
+        queued_transaction.last_error = Some(err.to_string());
+        queued_transaction.updated_at = self.store.get_current_timestamp();
+        self.store.upsert_queued_transaction(queued_transaction).await?;
+
+        Ok(())
+    }
+
+    /// Removes the submitted transactions that were committed or discarded from the outbound
+    /// transaction queue. Their records stay in the store.
+    pub(crate) async fn collect_finished_queued_transactions(&self) -> Result<(), ClientError> {
+        let submitted_transactions: Vec<TransactionId> = self
+            .store
+            .get_queued_transactions()
+            .await?
+            .iter()
+            .filter(|queued_transaction| queued_transaction.state().is_submitted())
+            .map(QueuedTransaction::id)
+            .collect();
+        if submitted_transactions.is_empty() {
+            return Ok(());
+        }
+
+        let transactions = self
+            .store
+            .get_transactions(TransactionFilter::Ids(submitted_transactions))
+            .await?;
+        for transaction in transactions {
+            if !matches!(transaction.status, TransactionStatus::Pending) {
+                self.store.remove_queued_transaction(transaction.id).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// QUEUED TRANSACTION
+// ================================================================================================
+
+/// A transaction in the client's outbound transaction queue.
+///
+/// Executed transactions are added to the queue when they're submitted, and the queue is updated
+/// as they're proven and submitted, so they can be retried after a failure or a restart. They're
+/// removed from the queue once a sync finds them committed or discarded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedTransaction {
+    /// The result of executing the transaction.
+    tx_result: TransactionResult,
+    /// The last state the transaction reached.
+    state: QueuedTransactionState,
+    /// Number of failed attempts to prove or submit the transaction.
+    attempts: u32,
+    /// Error of the last failed attempt, if any.
+    last_error: Option<String>,
+    /// Time of the last update to the entry, if the store provides timestamps.
+    updated_at: Option<u64>,
+}
+
+impl QueuedTransaction {
+    /// Returns a new entry for an executed transaction.
+    pub(crate) fn new(tx_result: TransactionResult, timestamp: Option<u64>) -> Self {
+        Self {
+            tx_result,
+            state: QueuedTransactionState::Executed,
+            attempts: 0,
+            last_error: None,
+            updated_at: timestamp,
+        }
+    }
+
+    /// Returns the ID of the transaction.
+    pub fn id(&self) -> TransactionId {
+        self.tx_result.executed_transaction().id()
+    }
+
+    /// Returns the ID of the account that executed the transaction.
+    pub fn account_id(&self) -> AccountId {
+        self.tx_result.executed_transaction().account_id()
+    }
+
+    /// Returns the result of executing the transaction.
+    pub fn tx_result(&self) -> &TransactionResult {
+        &self.tx_result
+    }
+
+    /// Returns the last state the transaction reached.
+    pub fn state(&self) -> &QueuedTransactionState {
+        &self.state
+    }
+
+    /// Returns the number of failed attempts to prove or submit the transaction.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the error of the last failed attempt, if any.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Returns the time of the last update to the entry, as a Unix timestamp in seconds.
+    pub fn updated_at(&self) -> Option<u64> {
+        self.updated_at
+    }
+}
+
+impl Serializable for QueuedTransaction {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.tx_result.write_into(target);
+        self.state.write_into(target);
+        self.attempts.write_into(target);
+        self.last_error.as_ref().map(|err| err.as_bytes().to_vec()).write_into(target);
+        self.updated_at.write_into(target);
+    }
+}
+
+impl Deserializable for QueuedTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tx_result = TransactionResult::read_from(source)?;
+        let state = QueuedTransactionState::read_from(source)?;
+        let attempts = u32::read_from(source)?;
+        let last_error = Option::<Vec<u8>>::read_from(source)?
+            .map(String::from_utf8)
+            .transpose()
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        let updated_at = Option::<u64>::read_from(source)?;
+
+        Ok(Self {
+            tx_result,
+            state,
+            attempts,
+            last_error,
+            updated_at,
+        })
+    }
+}
+
+// QUEUED TRANSACTION STATE
+// ================================================================================================
+
+/// The state of a transaction in the outbound transaction queue.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueuedTransactionState {
+    /// The transaction was executed, but not proven yet.
+    Executed,
+    /// The transaction was executed and exported to be proven elsewhere. It isn't proven by
+    /// [`Client::retry_queued_transactions`], which waits for its proof to be submitted with
+    /// [`Client::submit_proven_transaction`] or applied with
+    /// [`Client::apply_proven_transaction`].
+    AwaitingExternalProof,
+    /// The transaction is being proven. Transactions found in this state after a restart are
+    /// proven again.
+    Proving,
+    /// The transaction was proven, but not accepted by the network yet.
+    Proven(ProvenTransaction),
+    /// The transaction was accepted by the network at the specified block height.
+    Submitted(BlockNumber),
+}
+
+impl QueuedTransactionState {
+    const EXECUTED: u8 = 0;
+    const PROVING: u8 = 1;
+    const PROVEN: u8 = 2;
+    const SUBMITTED: u8 = 3;
+    const AWAITING_EXTERNAL_PROOF: u8 = 4;
+
+    /// Returns whether the transaction was accepted by the network.
+    pub fn is_submitted(&self) -> bool {
+        matches!(self, QueuedTransactionState::Submitted(_))
+    }
+}
+
+impl fmt::Display for QueuedTransactionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueuedTransactionState::Executed => write!(f, "Executed"),
+            QueuedTransactionState::AwaitingExternalProof => write!(f, "Awaiting external proof"),
+            QueuedTransactionState::Proving => write!(f, "Proving"),
+            QueuedTransactionState::Proven(_) => write!(f, "Proven"),
+            QueuedTransactionState::Submitted(block_num) => {
+                write!(f, "Submitted (Block: {block_num})")
+            },
+        }
+    }
+}
+
+impl Serializable for QueuedTransactionState {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            QueuedTransactionState::Executed => target.write_u8(Self::EXECUTED),
+            QueuedTransactionState::AwaitingExternalProof => {
+                target.write_u8(Self::AWAITING_EXTERNAL_PROOF);
+            },
+            QueuedTransactionState::Proving => target.write_u8(Self::PROVING),
+            QueuedTransactionState::Proven(proven_transaction) => {
+                target.write_u8(Self::PROVEN);
+                proven_transaction.write_into(target);
+            },
+            QueuedTransactionState::Submitted(block_num) => {
+                target.write_u8(Self::SUBMITTED);
+                block_num.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for QueuedTransactionState {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            Self::EXECUTED => Ok(QueuedTransactionState::Executed),
+            Self::AWAITING_EXTERNAL_PROOF => Ok(QueuedTransactionState::AwaitingExternalProof),
+            Self::PROVING => Ok(QueuedTransactionState::Proving),
+            Self::PROVEN => {
+                Ok(QueuedTransactionState::Proven(ProvenTransaction::read_from(source)?))
+            },
+            Self::SUBMITTED => {
+                Ok(QueuedTransactionState::Submitted(BlockNumber::read_from(source)?))
+            },
+            discriminant => Err(DeserializationError::InvalidValue(format!(
+                "Invalid queued transaction state discriminant: {discriminant}"
+            ))),
+        }
+    }
+}
+
+// TRANSACTION RETRY POLICY
+// ================================================================================================
+
+/// Decides when [`Client::retry_queued_transactions`] retries the queued transactions that failed
+/// to be proven or submitted.
+///
+/// A failed transaction is retried once `retry_interval` has passed since its last attempt, and
+/// the wait is doubled for every further failure, up to `max_backoff`. Transactions are no longer
+/// retried automatically after `max_attempts` failed attempts, but they can still be retried
+/// with [`Client::retry_queued_transaction`] or dropped with
+/// [`Client::cancel_queued_transaction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionRetryPolicy {
+    /// Maximum number of failed attempts after which the transaction isn't retried.
+    max_attempts: u32,
+    /// Time to wait before retrying a transaction after its first failed attempt.
+    retry_interval: Duration,
+    /// Upper bound for the time to wait before retrying a transaction.
+    max_backoff: Duration,
+}
+
+impl TransactionRetryPolicy {
+    /// Returns a policy that retries transactions until they fail `max_attempts` times.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            retry_interval: DEFAULT_TRANSACTION_RETRY_INTERVAL,
+            max_backoff: DEFAULT_MAX_TRANSACTION_RETRY_BACKOFF,
+        }
+    }
+
+    /// Sets the time to wait before retrying a transaction after its first failed attempt.
+    #[must_use]
+    pub fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+
+    /// Sets the upper bound for the time to wait before retrying a transaction.
+    #[must_use]
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the maximum number of failed attempts after which a transaction isn't retried.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the time to wait before retrying a transaction after its first failed attempt.
+    pub fn retry_interval(&self) -> Duration {
+        self.retry_interval
+    }
+
+    /// Returns the upper bound for the time to wait before retrying a transaction.
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Returns whether the queued transaction should be retried at the time `now`, as a Unix
+    /// timestamp in seconds. Without timestamps, transactions are retried as long as they have
+    /// attempts left. Transactions awaiting an external proof are never due.
+    pub fn is_due(&self, queued_transaction: &QueuedTransaction, now: Option<u64>) -> bool {
+        if queued_transaction.state().is_submitted()
+            || queued_transaction.state() == &QueuedTransactionState::AwaitingExternalProof
+            || queued_transaction.attempts() >= self.max_attempts
+        {
+            return false;
+        }
+
+        if queued_transaction.attempts() == 0 {
+            return true;
+        }
+
+        match (queued_transaction.updated_at(), now) {
+            (Some(updated_at), Some(now)) => {
+                let delay = backoff_delay(
+                    self.retry_interval,
+                    self.max_backoff,
+                    queued_transaction.attempts() - 1,
+                );
+                now.saturating_sub(updated_at) >= delay.as_secs()
+            },
+            _ => true,
+        }
+    }
+}
+
+impl Default for TransactionRetryPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TRANSACTION_ATTEMPTS)
+    }
+}
@@ -14,7 +14,7 @@ use miden_objects::{
 };
 
 use super::{
-    ForeignAccount, NoteArgs, TransactionRequest, TransactionRequestError,
+    ForeignAccount, NoteArgs, TransactionExpiration, TransactionRequest, TransactionRequestError,
     TransactionScriptTemplate,
 };
 use crate::ClientRng;
@@ -53,9 +53,8 @@ pub struct TransactionRequestBuilder {
     /// the network, and injected as advice inputs. Additionally, the account's code will be
     /// added to the executor and prover.
     foreign_accounts: BTreeMap<AccountId, ForeignAccount>,
-    /// The number of blocks in relation to the transaction's reference block after which the
-    /// transaction will expire. If `None`, the transaction will not expire.
-    expiration_delta: Option<u16>,
+    /// When the transaction expires, relative to its reference block.
+    expiration: TransactionExpiration,
     /// Indicates whether to **silently** ignore invalid input notes when executing the
     /// transaction. This will allow the transaction to be executed even if some input notes
     /// are invalid.
@@ -77,7 +76,7 @@ impl TransactionRequestBuilder {
             custom_script: None,
             advice_map: AdviceMap::default(),
             merkle_store: MerkleStore::default(),
-            expiration_delta: None,
+            expiration: TransactionExpiration::ClientDefault,
             foreign_accounts: BTreeMap::default(),
             ignore_invalid_input_notes: false,
         }
@@ -202,14 +201,25 @@ impl TransactionRequestBuilder {
     }
 
     /// The number of blocks in relation to the transaction's reference block after which the
-    /// transaction will expire. By default, the transaction will not expire.
+    /// transaction will expire. By default, the transaction expires according to the client's
+    /// [`TransactionPolicy`](crate::transaction::TransactionPolicy), and doesn't expire if the
+    /// policy doesn't set an expiration delta.
     ///
     /// Setting transaction expiration delta defines an upper bound for transaction expiration,
     /// but other code executed during the transaction may impose an even smaller transaction
     /// expiration delta.
     #[must_use]
     pub fn with_expiration_delta(mut self, expiration_delta: u16) -> Self {
-        self.expiration_delta = Some(expiration_delta);
+        self.expiration = TransactionExpiration::Delta(expiration_delta);
+        self
+    }
+
+    /// The resulting transaction won't expire, even if the client's
+    /// [`TransactionPolicy`](crate::transaction::TransactionPolicy) sets a default expiration
+    /// delta.
+    #[must_use]
+    pub fn without_expiration(mut self) -> Self {
+        self.expiration = TransactionExpiration::Never;
         self
     }
 
@@ -373,7 +383,7 @@ impl TransactionRequestBuilder {
                 ));
             },
             (Some(script), true) => {
-                if self.expiration_delta.is_some() {
+                if matches!(self.expiration, TransactionExpiration::Delta(_)) {
                     return Err(TransactionRequestError::ScriptTemplateError(
                         "Cannot set expiration delta when a custom script is set".to_string(),
                     ));
@@ -406,7 +416,7 @@ impl TransactionRequestBuilder {
             advice_map: self.advice_map,
             merkle_store: self.merkle_store,
             foreign_accounts: self.foreign_accounts.into_values().collect(),
-            expiration_delta: self.expiration_delta,
+            expiration: self.expiration,
             ignore_invalid_input_notes: self.ignore_invalid_input_notes,
         })
     }
@@ -46,6 +46,61 @@ pub enum TransactionScriptTemplate {
     SendNotes(Vec<PartialNote>),
 }
 
+/// Specifies when the transaction created from a request expires.
+///
+/// Expiration can only be set on requests whose transaction script is built by the client to
+/// create output notes (see [`TransactionScriptTemplate::SendNotes`]). It has no effect on other
+/// requests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransactionExpiration {
+    /// The transaction expires according to the client's
+    /// [`TransactionPolicy`](crate::transaction::TransactionPolicy). This is the default.
+    #[default]
+    ClientDefault,
+    /// The transaction expires this number of blocks after its reference block.
+    Delta(u16),
+    /// The transaction doesn't expire, regardless of the client's default.
+    Never,
+}
+
+impl TransactionExpiration {
+    /// Returns the expiration delta of the transaction, given the client's default expiration
+    /// delta.
+    pub fn expiration_delta(self, default_delta: Option<u16>) -> Option<u16> {
+        match self {
+            TransactionExpiration::ClientDefault => default_delta,
+            TransactionExpiration::Delta(delta) => Some(delta),
+            TransactionExpiration::Never => None,
+        }
+    }
+}
+
+impl Serializable for TransactionExpiration {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            TransactionExpiration::ClientDefault => target.write_u8(0),
+            TransactionExpiration::Delta(delta) => {
+                target.write_u8(1);
+                delta.write_into(target);
+            },
+            TransactionExpiration::Never => target.write_u8(2),
+        }
+    }
+}
+
+impl Deserializable for TransactionExpiration {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TransactionExpiration::ClientDefault),
+            1 => Ok(TransactionExpiration::Delta(u16::read_from(source)?)),
+            2 => Ok(TransactionExpiration::Never),
+            _ => Err(DeserializationError::InvalidValue(
+                "Invalid transaction expiration".to_string(),
+            )),
+        }
+    }
+}
+
 /// Specifies a transaction request that can be executed by an account.
 ///
 /// A request contains information about input notes to be consumed by the transaction (if any),
@@ -75,9 +130,8 @@ pub struct TransactionRequest {
     /// the network, and injected as advice inputs. Additionally, the account's code will be
     /// added to the executor and prover.
     foreign_accounts: BTreeSet<ForeignAccount>,
-    /// The number of blocks in relation to the transaction's reference block after which the
-    /// transaction will expire. If `None`, the transaction will not expire.
-    expiration_delta: Option<u16>,
+    /// When the transaction expires, relative to its reference block.
+    expiration: TransactionExpiration,
     /// Indicates whether to **silently** ignore invalid input notes when executing the
     /// transaction. This will allow the transaction to be executed even if some input notes
     /// are invalid.
@@ -109,6 +163,11 @@ impl TransactionRequest {
             .filter(move |note_id| !unauthenticated_note_ids.contains(note_id))
     }
 
+    /// Returns when the transaction created from the request expires.
+    pub fn expiration(&self) -> TransactionExpiration {
+        self.expiration
+    }
+
     /// Returns a mapping for input note IDs and their optional [`NoteArgs`].
     pub fn input_notes(&self) -> &BTreeMap<NoteId, Option<NoteArgs>> {
         &self.input_notes
@@ -192,16 +251,24 @@ impl TransactionRequest {
     }
 
     /// Builds the transaction script based on the account capabilities and the transaction request.
-    /// The debug mode enables the script debug logs.
+    /// The default expiration delta is used unless the request sets its own expiration, and the
+    /// debug mode enables the script debug logs.
     pub(crate) fn build_transaction_script(
         &self,
         account_interface: &AccountInterface,
+        default_expiration_delta: Option<u16>,
         in_debug_mode: bool,
     ) -> Result<TransactionScript, TransactionRequestError> {
         match &self.script_template {
             Some(TransactionScriptTemplate::CustomScript(script)) => Ok(script.clone()),
-            Some(TransactionScriptTemplate::SendNotes(notes)) => Ok(account_interface
-                .build_send_notes_script(notes, self.expiration_delta, in_debug_mode)?),
+            Some(TransactionScriptTemplate::SendNotes(notes)) => {
+                let expiration_delta = self.expiration.expiration_delta(default_expiration_delta);
+                Ok(account_interface.build_send_notes_script(
+                    notes,
+                    expiration_delta,
+                    in_debug_mode,
+                )?)
+            },
             None => {
                 if self.input_notes.is_empty() {
                     Err(TransactionRequestError::NoInputNotes)
@@ -236,7 +303,7 @@ impl Serializable for TransactionRequest {
         self.advice_map.clone().into_iter().collect::<Vec<_>>().write_into(target);
         self.merkle_store.write_into(target);
         self.foreign_accounts.write_into(target);
-        self.expiration_delta.write_into(target);
+        self.expiration.write_into(target);
         target.write_u8(u8::from(self.ignore_invalid_input_notes));
     }
 }
@@ -271,7 +338,7 @@ impl Deserializable for TransactionRequest {
         advice_map.extend(advice_vec);
         let merkle_store = MerkleStore::read_from(source)?;
         let foreign_accounts = BTreeSet::<ForeignAccount>::read_from(source)?;
-        let expiration_delta = Option::<u16>::read_from(source)?;
+        let expiration = TransactionExpiration::read_from(source)?;
         let ignore_invalid_input_notes = source.read_u8()? == 1;
 
         Ok(TransactionRequest {
@@ -283,7 +350,7 @@ impl Deserializable for TransactionRequest {
             advice_map,
             merkle_store,
             foreign_accounts,
-            expiration_delta,
+            expiration,
             ignore_invalid_input_notes,
         })
     }
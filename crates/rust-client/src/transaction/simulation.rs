@@ -0,0 +1,201 @@
+use alloc::sync::Arc;
+
+use miden_objects::{
+    account::{AccountDelta, AccountId},
+    assembly::DefaultSourceManager,
+    asset::FungibleAsset,
+    block::BlockNumber,
+    note::NoteId,
+    transaction::{ExecutedTransaction, TransactionMeasurements},
+};
+use miden_tx::{NoteAccountExecution, NoteConsumptionChecker, TransactionExecutorError};
+
+use super::{
+    FeePolicy, InputNote, InputNotes, OutputNotes, TransactionRequest, TransactionResult,
+    check_expected_output_notes,
+};
+use crate::{Client, ClientError};
+
+/// Transaction simulation methods.
+impl Client {
+    /// Executes the transaction specified by the request against the account and returns a report
+    /// of its effects, without changing the client's state.
+    ///
+    /// The transaction runs against the account and notes in the client's store. Transactions
+    /// that were executed but not submitted yet aren't applied to that state, so a request that
+    /// depends on them should be simulated once they're submitted, or executed along with them
+    /// with [`Client::new_transaction_batch`].
+    ///
+    /// Unlike [`Client::new_transaction`], unauthenticated input notes aren't stored, and the
+    /// result can't be proven or submitted. To confirm the effects of a transaction before
+    /// submitting it, execute it with [`Client::new_transaction`] and preview the result with
+    /// [`Client::preview_transaction`] instead. Chain data needed to execute the
+    /// request, such as foreign account code and block headers, may still be fetched and cached,
+    /// which can sync the client as described in [`Client::new_transaction`].
+    ///
+    /// Failures of the transaction itself are reported in the returned [`TransactionSimulation`],
+    /// along with the input note that failed, if any.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a [`ClientError::TransactionRequestError`] if the request is invalid.
+    /// - Returns [`ClientError::MissingOutputNotes`] if the [`TransactionRequest`] output notes are
+    ///   not a subset of executor's output notes.
+    pub async fn simulate_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<TransactionSimulation, ClientError> {
+        self.validate_request(account_id, &transaction_request).await?;
+        self.check_authenticated_input_notes(&transaction_request).await?;
+
+        // Unauthenticated notes are consumed as provided by the request instead of being stored
+        let notes = self.get_request_input_notes(&transaction_request).await?;
+
+        let (block_num, notes, tx_args, output_notes) =
+            self.prepare_transaction(account_id, notes, transaction_request).await?;
+
+        let execution = self
+            .tx_executor
+            .execute_transaction(
+                account_id,
+                block_num,
+                notes.clone(),
+                tx_args.clone(),
+                Arc::new(DefaultSourceManager::default()),
+            )
+            .await;
+
+        let error = match execution {
+            Ok(executed_transaction) => {
+                check_expected_output_notes(&executed_transaction, &output_notes)?;
+                return Ok(TransactionSimulation::Executed(SimulatedTransaction::new(
+                    executed_transaction,
+                    self.transaction_policy.fee_policy(),
+                )));
+            },
+            Err(error) => error,
+        };
+
+        // Execute the notes one by one to find out which one made the transaction fail
+        let failed_note = if notes.is_empty() {
+            None
+        } else {
+            let note_execution = NoteConsumptionChecker::new(&self.tx_executor)
+                .check_notes_consumability(
+                    account_id,
+                    block_num,
+                    notes,
+                    tx_args,
+                    Arc::new(DefaultSourceManager::default()),
+                )
+                .await?;
+
+            match note_execution {
+                NoteAccountExecution::Failure { failed_note_id, .. } => Some(failed_note_id),
+                NoteAccountExecution::Success => None,
+            }
+        };
+
+        Ok(TransactionSimulation::Failed { failed_note, error })
+    }
+
+    /// Returns a report of the effects of a transaction executed with
+    /// [`Client::new_transaction`], so they can be confirmed before the same result is submitted.
+    ///
+    /// The report reflects the state the transaction was executed against, which may be outdated
+    /// if other transactions of the account were submitted since then.
+    pub fn preview_transaction(&self, tx_result: &TransactionResult) -> SimulatedTransaction {
+        SimulatedTransaction::new(
+            tx_result.executed_transaction().clone(),
+            self.transaction_policy.fee_policy(),
+        )
+    }
+}
+
+// TRANSACTION SIMULATION
+// ================================================================================================
+
+/// Report of a transaction simulated with [`Client::simulate_transaction`].
+#[derive(Debug)]
+pub enum TransactionSimulation {
+    /// The transaction was executed successfully.
+    Executed(SimulatedTransaction),
+    /// The transaction failed.
+    Failed {
+        /// The input note that failed to be consumed by the account, if the failure was caused
+        /// by one of them.
+        failed_note: Option<NoteId>,
+        /// The error returned by the transaction executor.
+        error: TransactionExecutorError,
+    },
+}
+
+impl TransactionSimulation {
+    /// Returns whether the transaction was executed successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self, TransactionSimulation::Executed(_))
+    }
+}
+
+/// Effects of a successfully simulated transaction.
+#[derive(Debug, Clone)]
+pub struct SimulatedTransaction {
+    executed_transaction: ExecutedTransaction,
+    /// Fee paid by the transaction under the client's [`FeePolicy`].
+    fee: Option<FungibleAsset>,
+}
+
+impl SimulatedTransaction {
+    fn new(executed_transaction: ExecutedTransaction, fee_policy: &FeePolicy) -> Self {
+        let fee = fee_policy.transaction_fee(&executed_transaction);
+        Self { executed_transaction, fee }
+    }
+
+    /// Returns the ID of the account that executed the transaction.
+    pub fn account_id(&self) -> AccountId {
+        self.executed_transaction.account_id()
+    }
+
+    /// Returns the block against which the transaction was executed.
+    pub fn block_num(&self) -> BlockNumber {
+        self.executed_transaction.block_header().block_num()
+    }
+
+    /// Returns the changes to the account's vault, storage and nonce.
+    pub fn account_delta(&self) -> &AccountDelta {
+        self.executed_transaction.account_delta()
+    }
+
+    /// Returns the notes that the transaction consumes.
+    pub fn consumed_notes(&self) -> &InputNotes<InputNote> {
+        self.executed_transaction.input_notes()
+    }
+
+    /// Returns the notes that the transaction creates. The recipients of full and partial notes
+    /// are included.
+    pub fn created_notes(&self) -> &OutputNotes {
+        self.executed_transaction.output_notes()
+    }
+
+    /// Returns the fee that the transaction pays under the client's [`FeePolicy`], or `None` if it
+    /// doesn't pay one.
+    pub fn fee(&self) -> Option<FungibleAsset> {
+        self.fee
+    }
+
+    /// Returns the number of cycles spent in each step of the transaction.
+    pub fn measurements(&self) -> &TransactionMeasurements {
+        self.executed_transaction.measurements()
+    }
+
+    /// Returns the total number of cycles spent executing the transaction.
+    pub fn total_cycles(&self) -> usize {
+        self.measurements().total_cycles()
+    }
+
+    /// Returns the underlying [`ExecutedTransaction`].
+    pub fn executed_transaction(&self) -> &ExecutedTransaction {
+        &self.executed_transaction
+    }
+}
@@ -0,0 +1,162 @@
+//! Light-client verification of the data received from the node.
+//!
+//! By default, the client trusts the account and note data returned by the node. In verified
+//! mode (see [`Client::set_verified_mode`]), that data is checked against the chain tracked by the
+//! client before it's stored:
+//!
+//! - Public accounts imported with `import_account_by_id` are checked against an account proof
+//!   whose witness must match the account root of a block header authenticated against the client's
+//!   partial blockchain.
+//! - Notes imported with an inclusion proof, or by ID, must match the note root of a block header
+//!   authenticated against the client's partial blockchain.
+//! - Syncs fail instead of storing notes whose inclusion proofs don't match their block, or public
+//!   accounts whose state doesn't match the commitment reported for them in the synced blocks.
+//!
+//! Verification failures are returned as [`VerificationError`]s.
+
+use alloc::collections::BTreeSet;
+
+use miden_objects::{
+    account::Account,
+    block::{AccountWitness, BlockHeader},
+    note::{NoteId, NoteInclusionProof, NoteMetadata, compute_note_commitment},
+};
+
+use crate::{
+    Client, ClientError, VerificationError, rpc::domain::account::AccountStorageRequirements,
+    store::InputNoteState, sync::StateSyncUpdate, transaction::ForeignAccount,
+};
+
+/// Verified mode methods.
+impl Client {
+    /// Returns whether the data received from the node is verified before being stored.
+    pub fn is_in_verified_mode(&self) -> bool {
+        self.verified_mode
+    }
+
+    /// Enables or disables verified mode.
+    ///
+    /// In verified mode, account and note data received from the node is checked against account
+    /// proofs and inclusion proofs anchored in block headers tracked by the client, and rejected
+    /// with a [`VerificationError`] if it doesn't match. Verification requires extra requests, and
+    /// data anchored in a block the client hasn't synced to yet is rejected with
+    /// [`VerificationError::BlockNotSynced`] until the client is synced.
+    pub fn set_verified_mode(&mut self, verified_mode: bool) {
+        self.verified_mode = verified_mode;
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks the provided public account against the account proof returned by the node.
+    ///
+    /// The proof's witness is checked against the account root of its block, which is
+    /// authenticated against the client's partial blockchain, and the account's commitment must
+    /// match the proven one. If the account changed on-chain after it was fetched, the commitments
+    /// won't match and the account should be fetched again.
+    pub(crate) async fn verify_public_account(
+        &mut self,
+        account: &Account,
+    ) -> Result<(), ClientError> {
+        let account_id = account.id();
+        let foreign_account =
+            ForeignAccount::public(account_id, AccountStorageRequirements::default())?;
+
+        let (block_num, account_proofs) = self
+            .rpc_api
+            .get_account_proofs(&BTreeSet::from([foreign_account]), vec![])
+            .await?;
+        let account_proof = account_proofs
+            .into_iter()
+            .find(|proof| proof.account_id() == account_id)
+            .ok_or(VerificationError::MissingAccountProof(account_id))?;
+
+        let block_header = self.get_verified_block_header(block_num).await?;
+        verify_account_witness(account_proof.account_witness(), &block_header)?;
+
+        if account_proof.account_commitment() != account.commitment() {
+            return Err(VerificationError::AccountStateMismatch(account_id).into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks the provided note inclusion proof against the note root of its block, which is
+    /// authenticated against the client's partial blockchain.
+    pub(crate) async fn verify_note_inclusion(
+        &mut self,
+        note_id: NoteId,
+        metadata: &NoteMetadata,
+        inclusion_proof: &NoteInclusionProof,
+    ) -> Result<(), ClientError> {
+        let block_header =
+            self.get_verified_block_header(inclusion_proof.location().block_num()).await?;
+
+        verify_note_inclusion_proof(note_id, metadata, inclusion_proof, &block_header)
+            .map_err(Into::into)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Checks that the account witness matches the account root of the provided block.
+fn verify_account_witness(
+    account_witness: &AccountWitness,
+    block_header: &BlockHeader,
+) -> Result<(), VerificationError> {
+    if account_witness.clone().into_proof().compute_root() != block_header.account_root() {
+        return Err(VerificationError::AccountWitnessMismatch {
+            account_id: account_witness.id(),
+            block_num: block_header.block_num(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the note inclusion proof matches the note root of the provided block.
+fn verify_note_inclusion_proof(
+    note_id: NoteId,
+    metadata: &NoteMetadata,
+    inclusion_proof: &NoteInclusionProof,
+    block_header: &BlockHeader,
+) -> Result<(), VerificationError> {
+    inclusion_proof
+        .note_path()
+        .verify(
+            inclusion_proof.location().node_index_in_block().into(),
+            compute_note_commitment(note_id, metadata),
+            &block_header.note_root(),
+        )
+        .map_err(|_| VerificationError::NoteInclusionMismatch {
+            note_id,
+            block_num: block_header.block_num(),
+        })
+}
+
+/// Checks that none of the notes in the sync update failed the verification of their inclusion
+/// proofs against the synced blocks, and that the fetched public accounts match the latest
+/// commitments received for them.
+pub(crate) fn verify_state_sync_update(
+    state_sync_update: &StateSyncUpdate,
+) -> Result<(), VerificationError> {
+    let account_updates = &state_sync_update.account_updates;
+    for account in account_updates.updated_public_accounts() {
+        let expected_commitment = account_updates.public_account_commitments().get(&account.id());
+        if expected_commitment != Some(&account.commitment()) {
+            return Err(VerificationError::AccountStateMismatch(account.id()));
+        }
+    }
+
+    for note_update in state_sync_update.note_updates.updated_input_notes() {
+        if let InputNoteState::Invalid(state) = note_update.inner().state() {
+            return Err(VerificationError::NoteInclusionMismatch {
+                note_id: note_update.inner().id(),
+                block_num: state.invalid_inclusion_proof.location().block_num(),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use miden_client::auth::AuthSecretKey;
+use miden_client::{auth::AuthSecretKey, keystore::KeyStore};
 use miden_objects::{
     account::{AccountFile, AccountId as NativeAccountId},
     note::NoteFile,
@@ -37,6 +37,11 @@ impl SyncSummary {
         self.0.committed_transactions.iter().map(Into::into).collect()
     }
 
+    #[wasm_bindgen(js_name = "expiredTransactions")]
+    pub fn expired_transactions(&self) -> Vec<TransactionId> {
+        self.0.expired_transactions.iter().map(Into::into).collect()
+    }
+
     pub fn serialize(&self) -> Uint8Array {
         serialize_to_uint8array(&self.0)
     }
@@ -3,6 +3,7 @@ use miden_client::{
     account::{AccountBuilder, AccountType},
     auth::AuthSecretKey,
     crypto::SecretKey,
+    keystore::KeyStore,
 };
 use miden_lib::account::{auth::RpoFalcon512, faucets::BasicFungibleFaucet};
 use miden_objects::{AccountIdError, asset::TokenSymbol};
@@ -3,6 +3,7 @@ use miden_client::{
     account::{Account, StorageSlot},
     auth::AuthSecretKey,
     block::BlockHeader,
+    keystore::KeyStore,
     rpc::domain::account::{AccountStorageRequirements, StorageMapKey},
     testing::common::*,
     transaction::{ForeignAccount, TransactionKernel, TransactionRequestBuilder},
@@ -385,7 +386,7 @@ async fn deploy_foreign_account(
         foreign_account_with_code(storage_mode, &anchor_block, code);
     let foreign_account_id = foreign_account.id();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
     client.add_account(&foreign_account, Some(foreign_seed), false).await.unwrap();
 
     let deployment_tx_script = TransactionScript::compile(